@@ -8,8 +8,9 @@ use axum::{
     response::{IntoResponse, Response},
 };
 use serde::Serialize;
+use sha2::{Digest, Sha256};
 use std::collections::HashSet;
-use std::sync::Arc;
+use std::sync::{Arc, RwLock};
 use subtle::ConstantTimeEq;
 use tracing::warn;
 
@@ -54,12 +55,66 @@ impl Default for AuthConfig {
     }
 }
 
+/// Authentication configuration that can be replaced while the server runs.
+///
+/// Each request checks the configuration current when it arrives; a
+/// replacement takes effect for the next request, atomically.
+#[derive(Debug, Clone)]
+pub struct SharedAuthConfig(Arc<RwLock<Arc<AuthConfig>>>);
+
+impl SharedAuthConfig {
+    pub fn new(config: AuthConfig) -> Self {
+        Self(Arc::new(RwLock::new(Arc::new(config))))
+    }
+
+    /// The configuration in effect.
+    pub fn current(&self) -> Arc<AuthConfig> {
+        self.0.read().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    /// Replace the configuration for subsequent requests.
+    pub fn replace(&self, config: AuthConfig) {
+        *self.0.write().unwrap_or_else(|e| e.into_inner()) = Arc::new(config);
+    }
+}
+
+impl Default for SharedAuthConfig {
+    fn default() -> Self {
+        Self::new(AuthConfig::default())
+    }
+}
+
+/// Identity of an authenticated HTTP client.
+///
+/// Inserted into the request extensions by [`auth_middleware`]. Holds a
+/// fingerprint of the bearer token, never the token itself.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuthIdentity(String);
+
+impl AuthIdentity {
+    fn from_token(token: &str) -> Self {
+        let digest = Sha256::digest(token.as_bytes());
+        Self(digest[..8].iter().map(|b| format!("{:02x}", b)).collect())
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
 /// Authentication middleware for HTTP requests.
+///
+/// Passes every request through while the current configuration has no tokens.
 pub async fn auth_middleware(
-    State(auth_config): State<Arc<AuthConfig>>,
-    request: Request<Body>,
+    State(shared): State<SharedAuthConfig>,
+    mut request: Request<Body>,
     next: Next,
 ) -> Response {
+    let auth_config = shared.current();
+    if !auth_config.is_enabled() {
+        return next.run(request).await;
+    }
+
     let token = match extract_bearer_token(&request) {
         Ok(Some(token)) => token,
         Ok(None) => {
@@ -76,6 +131,8 @@ pub async fn auth_middleware(
     };
 
     if verify_token(&auth_config, token) {
+        let identity = AuthIdentity::from_token(token);
+        request.extensions_mut().insert(identity);
         next.run(request).await
     } else {
         warn!(token_prefix = %mask_token(token), "Authentication failed: invalid token");
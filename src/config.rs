@@ -2,8 +2,10 @@
 //!
 //! This module provides configuration management via CLI arguments and environment variables.
 
+use crate::models::{ConnectionConfig, ConnectionConfigError};
 use clap::{Parser, ValueEnum};
 use std::collections::HashMap;
+use std::path::PathBuf;
 use std::time::Duration;
 use url::Url;
 
@@ -13,6 +15,7 @@ pub const DEFAULT_MCP_ENDPOINT: &str = "/";
 pub const DEFAULT_QUERY_TIMEOUT_SECS: u64 = 30;
 pub const DEFAULT_CONNECT_TIMEOUT_SECS: u64 = 10;
 pub const DEFAULT_TRANSACTION_TIMEOUT_SECS: u64 = 60;
+pub const DEFAULT_SCHEMA_CACHE_TTL_SECS: u64 = 60;
 
 // Pool configuration defaults
 pub const DEFAULT_MAX_CONNECTIONS: u32 = 10;
@@ -26,7 +29,7 @@ pub const DEFAULT_DATABASE_POOL_IDLE_TIMEOUT_SECS: u64 = 600;
 pub const DEFAULT_DATABASE_POOL_CLEANUP_INTERVAL_SECS: u64 = 60;
 
 /// Connection pool configuration options parsed from database URL.
-#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
 pub struct PoolOptions {
     /// Maximum connections in pool (default: 10 for MySQL/PostgreSQL, 1 for SQLite)
     pub max_connections: Option<u32>,
@@ -234,6 +237,18 @@ impl DatabaseConfig {
         })
     }
 
+    /// Build the connection configuration for this database.
+    pub fn to_connection_config(&self) -> Result<ConnectionConfig, ConnectionConfigError> {
+        ConnectionConfig::new(
+            &self.id,
+            &self.connection_string,
+            self.writable,
+            self.server_level,
+            self.database.clone(),
+            self.pool_options.clone(),
+        )
+    }
+
     /// Parse pool options from extracted URL query parameters.
     fn parse_pool_options(opts: &mut HashMap<String, String>) -> PoolOptions {
         PoolOptions {
@@ -376,6 +391,14 @@ pub struct Config {
     )]
     pub transaction_timeout: u64,
 
+    /// Schema metadata cache TTL in seconds (0 disables caching)
+    #[arg(
+        long,
+        default_value_t = DEFAULT_SCHEMA_CACHE_TTL_SECS,
+        env = "MCP_SCHEMA_CACHE_TTL"
+    )]
+    pub schema_cache_ttl: u64,
+
     /// Log level (trace, debug, info, warn, error)
     #[arg(long, default_value = "info", env = "MCP_LOG_LEVEL")]
     pub log_level: String,
@@ -398,6 +421,57 @@ pub struct Config {
         value_delimiter = ','
     )]
     pub auth_tokens: Vec<String>,
+
+    /// Directory containing versioned migration files (`<version>_<name>.sql`).
+    /// Enables the migration_status, migration_apply and migration_rollback tools.
+    #[arg(long, value_name = "DIR", env = "MCP_MIGRATIONS_DIR")]
+    pub migrations_dir: Option<PathBuf>,
+
+    /// Directory of prompt templates (`<name>.md`) served alongside the
+    /// built-in MCP prompts. Files named like a built-in prompt replace it.
+    #[arg(long, value_name = "DIR", env = "MCP_PROMPTS_DIR")]
+    pub prompts_dir: Option<PathBuf>,
+
+    /// JSON file with additional `databases` and `auth_tokens`.
+    /// Re-read on SIGHUP and whenever it changes, updating connections and
+    /// tokens without a restart.
+    #[arg(long, value_name = "FILE", env = "MCP_CONFIG_FILE")]
+    pub config_file: Option<PathBuf>,
+
+    /// Enable the add_connection, remove_connection and test_connection tools.
+    /// New connections are restricted to --admin-allowed-host and
+    /// --admin-allowed-path.
+    #[arg(long, env = "MCP_ENABLE_ADMIN_TOOLS")]
+    pub enable_admin_tools: bool,
+
+    /// Hosts that add_connection may connect to (MySQL/PostgreSQL).
+    /// Can be specified multiple times or as comma-separated values.
+    #[arg(
+        long = "admin-allowed-host",
+        value_name = "HOST",
+        env = "MCP_ADMIN_ALLOWED_HOSTS",
+        value_delimiter = ','
+    )]
+    pub admin_allowed_hosts: Vec<String>,
+
+    /// Directories whose SQLite files add_connection may open.
+    /// Can be specified multiple times or as comma-separated values.
+    #[arg(
+        long = "admin-allowed-path",
+        value_name = "DIR",
+        env = "MCP_ADMIN_ALLOWED_PATHS",
+        value_delimiter = ','
+    )]
+    pub admin_allowed_paths: Vec<PathBuf>,
+
+    /// Serve Prometheus metrics at /metrics on the HTTP transport
+    #[arg(long, env = "MCP_ENABLE_METRICS")]
+    pub enable_metrics: bool,
+
+    /// Serve Prometheus metrics on this port of the HTTP host instead.
+    /// Also enables metrics in stdio mode.
+    #[arg(long, env = "MCP_METRICS_PORT")]
+    pub metrics_port: Option<u16>,
 }
 
 impl Config {
@@ -417,10 +491,19 @@ impl Config {
             query_timeout: DEFAULT_QUERY_TIMEOUT_SECS,
             connect_timeout: DEFAULT_CONNECT_TIMEOUT_SECS,
             transaction_timeout: DEFAULT_TRANSACTION_TIMEOUT_SECS,
+            schema_cache_ttl: DEFAULT_SCHEMA_CACHE_TTL_SECS,
             log_level: "info".to_string(),
             json_logs: false,
             enable_logs: false,
             auth_tokens: Vec::new(),
+            migrations_dir: None,
+            prompts_dir: None,
+            config_file: None,
+            enable_admin_tools: false,
+            admin_allowed_hosts: Vec::new(),
+            admin_allowed_paths: Vec::new(),
+            enable_metrics: false,
+            metrics_port: None,
         }
     }
 
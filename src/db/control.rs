@@ -0,0 +1,507 @@
+//! Cancellation, timeouts and progress reporting for running statements.
+//!
+//! A [`QueryControl`] carries a request's cancellation token and an optional
+//! progress callback. Statements run under a control get a pooled connection
+//! to themselves, so a statement that is cancelled or times out is stopped on
+//! the database server instead of only abandoning the result stream:
+//! PostgreSQL gets `pg_cancel_backend`, MySQL gets `KILL QUERY`, and SQLite is
+//! interrupted from its progress handler. A MySQL or PostgreSQL connection
+//! whose statement was stopped, or whose request was dropped mid-statement, is
+//! closed rather than returned to the pool.
+//!
+//! Every controlled statement runs under a timeout, so its MySQL or
+//! PostgreSQL backend is always looked up before it starts (once per registry
+//! transaction). That costs a round trip per statement, but a statement that
+//! times out never keeps running, and holding locks, on the server.
+
+use crate::db::pool::DbPool;
+use crate::error::{DbError, DbResult};
+use sqlx::pool::PoolConnection;
+use sqlx::{
+    Connection, MySql, MySqlConnection, MySqlPool, PgConnection, PgPool, Postgres, Sqlite,
+    SqliteConnection,
+};
+use std::future::Future;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, warn};
+
+/// Interval between progress reports while a statement runs.
+pub const PROGRESS_INTERVAL: Duration = Duration::from_secs(1);
+
+/// SQLite virtual machine instructions between checks for an interrupt.
+const SQLITE_INTERRUPT_CHECK_OPS: i32 = 1000;
+
+/// Progress of a running statement.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QueryProgress {
+    /// Time since the statement started
+    pub elapsed: Duration,
+    /// Rows received from the database so far
+    pub rows: u64,
+}
+
+impl QueryProgress {
+    /// Human-readable summary, e.g. "12s elapsed, 4000 rows fetched".
+    pub fn message(&self) -> String {
+        format!(
+            "{}s elapsed, {} rows fetched",
+            self.elapsed.as_secs(),
+            self.rows
+        )
+    }
+}
+
+type ProgressCallback = Arc<dyn Fn(QueryProgress) + Send + Sync>;
+
+/// Cancellation and progress reporting for one statement.
+///
+/// The default control is never cancelled and reports nothing; its
+/// statements are still stopped on the server when they time out.
+#[derive(Clone, Default)]
+pub struct QueryControl {
+    cancel: Option<CancellationToken>,
+    on_progress: Option<ProgressCallback>,
+    rows: Arc<AtomicU64>,
+}
+
+impl QueryControl {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cancel the statement when `token` is cancelled.
+    pub fn with_cancellation(mut self, token: CancellationToken) -> Self {
+        self.cancel = Some(token);
+        self
+    }
+
+    /// Call `on_progress` every [`PROGRESS_INTERVAL`] while the statement runs.
+    pub fn with_progress(
+        mut self,
+        on_progress: impl Fn(QueryProgress) + Send + Sync + 'static,
+    ) -> Self {
+        self.on_progress = Some(Arc::new(on_progress));
+        self
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancel.as_ref().is_some_and(|t| t.is_cancelled())
+    }
+
+    /// Rows received from the database so far.
+    pub fn rows_fetched(&self) -> u64 {
+        self.rows.load(Ordering::Relaxed)
+    }
+
+    pub(crate) fn row_counter(&self) -> &AtomicU64 {
+        &self.rows
+    }
+
+    /// Check out a connection for one statement, with a [`Canceller`] that
+    /// can stop the statement from outside.
+    pub(crate) async fn acquire(&self, pool: &DbPool) -> DbResult<Pinned> {
+        let (conn, target) = match pool {
+            DbPool::MySql(p) => {
+                let mut conn = p.acquire().await?;
+                let target = CancelTarget::mysql(&mut conn, p).await?;
+                (PinnedConnection::MySql(conn), target)
+            }
+            DbPool::Postgres(p) => {
+                let mut conn = p.acquire().await?;
+                let target = CancelTarget::postgres(&mut conn, p).await?;
+                (PinnedConnection::Postgres(conn), target)
+            }
+            DbPool::SQLite(p) => {
+                let mut conn = p.acquire().await?;
+                let target = CancelTarget::sqlite(&mut conn).await?;
+                (PinnedConnection::SQLite(conn), target)
+            }
+        };
+        Ok(Pinned {
+            conn,
+            canceller: Canceller::new(target),
+            released: false,
+        })
+    }
+
+    /// Drive `work` to completion, reporting progress. The statement is
+    /// stopped on the server if the control is cancelled or `limit` passes
+    /// first.
+    pub(crate) async fn supervise<T>(
+        &self,
+        canceller: &Canceller,
+        operation: &str,
+        limit: Duration,
+        work: impl Future<Output = DbResult<T>>,
+    ) -> DbResult<T> {
+        let start = Instant::now();
+        let mut ticks = tokio::time::interval_at(
+            tokio::time::Instant::now() + PROGRESS_INTERVAL,
+            PROGRESS_INTERVAL,
+        );
+        let cancelled = async {
+            match &self.cancel {
+                Some(token) => token.cancelled().await,
+                None => std::future::pending().await,
+            }
+        };
+        let deadline = tokio::time::sleep(limit);
+        tokio::pin!(work, cancelled, deadline);
+
+        loop {
+            tokio::select! {
+                result = &mut work => {
+                    canceller.finished.store(true, Ordering::Relaxed);
+                    return result;
+                }
+                _ = &mut cancelled => {
+                    debug!(operation = operation, "Cancelling statement");
+                    canceller.cancel().await;
+                    return Err(DbError::cancelled(operation));
+                }
+                _ = &mut deadline => {
+                    warn!(
+                        operation = operation,
+                        timeout_secs = limit.as_secs(),
+                        "Statement timed out, stopping it"
+                    );
+                    canceller.cancel().await;
+                    return Err(DbError::timeout(operation, limit.as_secs() as u32));
+                }
+                _ = ticks.tick(), if self.on_progress.is_some() => {
+                    if let Some(on_progress) = &self.on_progress {
+                        on_progress(QueryProgress {
+                            elapsed: start.elapsed(),
+                            rows: self.rows_fetched(),
+                        });
+                    }
+                }
+            }
+        }
+    }
+
+    /// Return the connection to the pool once its statement has finished.
+    /// SQLite connections drop their interrupt check first.
+    pub(crate) async fn release(&self, mut pinned: Pinned) {
+        if let PinnedConnection::SQLite(conn) = &mut pinned.conn {
+            // Waits for an interrupted statement to stop
+            if let Err(e) = remove_sqlite_interrupt(conn).await {
+                warn!(error = %e, "Failed to remove SQLite progress handler");
+                return;
+            }
+        }
+        pinned.released = true;
+    }
+}
+
+/// A pooled connection checked out for a single controlled statement.
+pub(crate) enum PinnedConnection {
+    MySql(PoolConnection<MySql>),
+    Postgres(PoolConnection<Postgres>),
+    SQLite(PoolConnection<Sqlite>),
+}
+
+/// A [`PinnedConnection`] with the [`Canceller`] for its statement.
+///
+/// Unless [`QueryControl::release`] ran, dropping it closes the connection:
+/// the statement may still be running, or was stopped, on it. A statement
+/// abandoned mid-flight is also cancelled on the server.
+pub(crate) struct Pinned {
+    conn: PinnedConnection,
+    canceller: Canceller,
+    released: bool,
+}
+
+impl Pinned {
+    pub(crate) fn parts(&mut self) -> (&mut PinnedConnection, &Canceller) {
+        (&mut self.conn, &self.canceller)
+    }
+}
+
+impl Drop for Pinned {
+    fn drop(&mut self) {
+        let reusable = match self.conn {
+            PinnedConnection::MySql(_) | PinnedConnection::Postgres(_) => {
+                self.canceller.finished() && !self.canceller.fired()
+            }
+            PinnedConnection::SQLite(_) => self.released,
+        };
+        if reusable {
+            return;
+        }
+        match &mut self.conn {
+            PinnedConnection::MySql(conn) => conn.close_on_drop(),
+            PinnedConnection::Postgres(conn) => conn.close_on_drop(),
+            PinnedConnection::SQLite(conn) => conn.close_on_drop(),
+        }
+    }
+}
+
+/// Stops a statement from outside the connection running it.
+///
+/// Dropping a canceller whose statement neither finished nor was cancelled
+/// cancels the statement in the background.
+pub(crate) struct Canceller {
+    target: CancelTarget,
+    fired: AtomicBool,
+    finished: AtomicBool,
+}
+
+/// Where to send a cancel for the statement running on one connection.
+#[derive(Clone)]
+pub(crate) enum CancelTarget {
+    MySql { pool: MySqlPool, id: u64 },
+    Postgres { pool: PgPool, pid: i32 },
+    SQLite { interrupted: Arc<AtomicBool> },
+}
+
+impl CancelTarget {
+    /// Target the MySQL connection `conn` of `pool`.
+    pub(crate) async fn mysql(conn: &mut MySqlConnection, pool: &MySqlPool) -> DbResult<Self> {
+        let id: u64 = sqlx::query_scalar("SELECT CONNECTION_ID()")
+            .fetch_one(conn)
+            .await?;
+        Ok(Self::MySql {
+            pool: pool.clone(),
+            id,
+        })
+    }
+
+    /// Target the PostgreSQL connection `conn` of `pool`.
+    pub(crate) async fn postgres(conn: &mut PgConnection, pool: &PgPool) -> DbResult<Self> {
+        let pid: i32 = sqlx::query_scalar("SELECT pg_backend_pid()")
+            .fetch_one(conn)
+            .await?;
+        Ok(Self::Postgres {
+            pool: pool.clone(),
+            pid,
+        })
+    }
+
+    /// Install an interrupt check on `conn`. Each cancel interrupts the
+    /// statement running at the time; remove the check with
+    /// [`remove_sqlite_interrupt`] before the connection goes back to the
+    /// pool.
+    pub(crate) async fn sqlite(conn: &mut SqliteConnection) -> DbResult<Self> {
+        let interrupted = Arc::new(AtomicBool::new(false));
+        let flag = interrupted.clone();
+        conn.lock_handle()
+            .await?
+            .set_progress_handler(SQLITE_INTERRUPT_CHECK_OPS, move || {
+                !flag.swap(false, Ordering::Relaxed)
+            });
+        Ok(Self::SQLite { interrupted })
+    }
+
+    /// Forget a cancel that arrived after its statement had finished, so it
+    /// does not interrupt the next one.
+    pub(crate) fn rearm(&self) {
+        if let Self::SQLite { interrupted } = self {
+            interrupted.store(false, Ordering::Relaxed);
+        }
+    }
+
+    async fn cancel(&self) {
+        let result = match self {
+            Self::MySql { pool, id } => kill_mysql_query(pool, *id).await,
+            Self::Postgres { pool, pid } => cancel_postgres_backend(pool, *pid).await,
+            Self::SQLite { interrupted } => {
+                interrupted.store(true, Ordering::Relaxed);
+                Ok(())
+            }
+        };
+        if let Err(e) = result {
+            warn!(error = %e, "Failed to cancel statement on the server");
+        }
+    }
+}
+
+impl Canceller {
+    pub(crate) fn new(target: CancelTarget) -> Self {
+        Self {
+            target,
+            fired: AtomicBool::new(false),
+            finished: AtomicBool::new(false),
+        }
+    }
+
+    /// See [`CancelTarget::rearm`].
+    pub(crate) fn rearm(&self) {
+        self.target.rearm();
+    }
+
+    /// Whether [`Canceller::cancel`] was called.
+    pub(crate) fn fired(&self) -> bool {
+        self.fired.load(Ordering::Relaxed)
+    }
+
+    /// Whether the supervised statement ran to completion.
+    pub(crate) fn finished(&self) -> bool {
+        self.finished.load(Ordering::Relaxed)
+    }
+
+    /// Ask the server to stop the statement. MySQL and PostgreSQL are asked on
+    /// a separate connection opened for the purpose, so a saturated pool
+    /// cannot delay the cancel. Failures are logged; the statement's result
+    /// is dropped either way.
+    pub(crate) async fn cancel(&self) {
+        self.fired.store(true, Ordering::Relaxed);
+        self.target.cancel().await;
+    }
+}
+
+impl Drop for Canceller {
+    fn drop(&mut self) {
+        if self.finished() || self.fired() {
+            return;
+        }
+        match &self.target {
+            CancelTarget::SQLite { interrupted } => interrupted.store(true, Ordering::Relaxed),
+            target => {
+                let Ok(runtime) = tokio::runtime::Handle::try_current() else {
+                    return;
+                };
+                debug!("Cancelling statement abandoned mid-flight");
+                let target = target.clone();
+                runtime.spawn(async move { target.cancel().await });
+            }
+        }
+    }
+}
+
+/// Remove the interrupt check installed by [`CancelTarget::sqlite`]. Waits
+/// for a running statement to stop.
+pub(crate) async fn remove_sqlite_interrupt(conn: &mut SqliteConnection) -> DbResult<()> {
+    conn.lock_handle().await?.remove_progress_handler();
+    Ok(())
+}
+
+async fn kill_mysql_query(pool: &MySqlPool, id: u64) -> Result<(), sqlx::Error> {
+    let mut conn = MySqlConnection::connect_with(&pool.connect_options()).await?;
+    sqlx::query(&format!("KILL QUERY {}", id))
+        .execute(&mut conn)
+        .await?;
+    conn.close().await
+}
+
+async fn cancel_postgres_backend(pool: &PgPool, pid: i32) -> Result<(), sqlx::Error> {
+    let mut conn = PgConnection::connect_with(&pool.connect_options()).await?;
+    sqlx::query("SELECT pg_cancel_backend($1)")
+        .bind(pid)
+        .execute(&mut conn)
+        .await?;
+    conn.close().await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sqlite_target() -> CancelTarget {
+        CancelTarget::SQLite {
+            interrupted: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    #[test]
+    fn test_progress_message() {
+        let progress = QueryProgress {
+            elapsed: Duration::from_millis(12_400),
+            rows: 4000,
+        };
+        assert_eq!(progress.message(), "12s elapsed, 4000 rows fetched");
+    }
+
+    #[test]
+    fn test_default_control_is_not_cancellable() {
+        let control = QueryControl::new();
+        assert!(!control.is_cancelled());
+        assert_eq!(control.rows_fetched(), 0);
+
+        let token = CancellationToken::new();
+        let control = QueryControl::new().with_cancellation(token.clone());
+        token.cancel();
+        assert!(control.is_cancelled());
+    }
+
+    #[tokio::test]
+    async fn test_supervise_reports_progress_and_cancels() {
+        let reports = Arc::new(AtomicU64::new(0));
+        let counter = reports.clone();
+        let token = CancellationToken::new();
+        let control = QueryControl::new()
+            .with_cancellation(token.clone())
+            .with_progress(move |_| {
+                counter.fetch_add(1, Ordering::Relaxed);
+            });
+
+        tokio::time::pause();
+        let cancel_later = async {
+            tokio::time::sleep(PROGRESS_INTERVAL * 3 + PROGRESS_INTERVAL / 2).await;
+            token.cancel();
+        };
+        let canceller = Canceller::new(sqlite_target());
+        let work = std::future::pending::<DbResult<()>>();
+        let (result, _) = tokio::join!(
+            control.supervise(&canceller, "query execution", Duration::from_secs(60), work),
+            cancel_later
+        );
+
+        assert!(matches!(result, Err(DbError::Cancelled { .. })));
+        assert_eq!(reports.load(Ordering::Relaxed), 3);
+        assert!(canceller.fired());
+    }
+
+    #[tokio::test]
+    async fn test_supervise_cancels_on_timeout() {
+        tokio::time::pause();
+        let control = QueryControl::new();
+        let canceller = Canceller::new(sqlite_target());
+
+        let finished = control
+            .supervise(
+                &canceller,
+                "query execution",
+                Duration::from_secs(5),
+                async { Ok(1) },
+            )
+            .await;
+        assert_eq!(finished.unwrap(), 1);
+        assert!(!canceller.fired());
+
+        let work = std::future::pending::<DbResult<()>>();
+        let result = control
+            .supervise(&canceller, "query execution", Duration::from_secs(5), work)
+            .await;
+        assert!(matches!(
+            result,
+            Err(DbError::Timeout {
+                elapsed_secs: 5,
+                ..
+            })
+        ));
+        assert!(canceller.fired());
+    }
+
+    #[test]
+    fn test_dropping_unfinished_statement_interrupts_it() {
+        let interrupted = Arc::new(AtomicBool::new(false));
+        let target = CancelTarget::SQLite {
+            interrupted: interrupted.clone(),
+        };
+
+        let finished = Canceller::new(target.clone());
+        finished.finished.store(true, Ordering::Relaxed);
+        drop(finished);
+        assert!(!interrupted.load(Ordering::Relaxed));
+
+        drop(Canceller::new(target.clone()));
+        assert!(interrupted.load(Ordering::Relaxed));
+
+        target.rearm();
+        assert!(!interrupted.load(Ordering::Relaxed));
+    }
+}
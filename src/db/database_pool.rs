@@ -294,6 +294,16 @@ impl DatabasePoolManager {
         pools.values().filter(|cell| cell.get().is_some()).count()
     }
 
+    /// Get the number of borrows not yet released, summed over all pools.
+    pub async fn active_count(&self) -> usize {
+        let pools = self.pools.read().await;
+        pools
+            .values()
+            .filter_map(|cell| cell.get())
+            .map(DatabasePoolEntry::active_count)
+            .sum()
+    }
+
     /// Close all database pools and cancel the cleanup task.
     pub async fn close_all(&self) {
         // Cancel cleanup task using synchronous mutex
@@ -0,0 +1,987 @@
+//! DDL rendering from schema metadata.
+//!
+//! This module turns `TableSchema`/`ColumnDefinition` values into SQL DDL for
+//! any supported dialect. Column types and default values are parsed from the
+//! dialect they were introspected from and rendered for the target dialect,
+//! so the same renderer handles both same-dialect output and cross-dialect
+//! translation (e.g. a MySQL table as PostgreSQL DDL).
+//!
+//! Type translation is best-effort: common SQL types map cleanly, while
+//! vendor-specific types (enums, arrays, geometry, ...) are passed through
+//! unchanged.
+
+use crate::models::{ColumnDefinition, DatabaseType, ForeignKey, IndexInfo, TableSchema};
+
+// =============================================================================
+// Portable Types
+// =============================================================================
+
+/// A column type normalized across dialects.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PortableType {
+    SmallInt,
+    Integer,
+    BigInt,
+    Boolean,
+    Real,
+    Double,
+    Decimal(Option<String>),
+    Varchar(Option<String>),
+    Char(Option<String>),
+    Text,
+    Binary,
+    Date,
+    Time,
+    Timestamp,
+    TimestampTz,
+    Json,
+    Uuid,
+    /// Unrecognized type, kept verbatim
+    Other(String),
+}
+
+impl PortableType {
+    /// Parse a type name as reported by `describe_table`.
+    pub fn parse(data_type: &str) -> Self {
+        let lower = data_type.trim().to_lowercase();
+        let (base, args) = match (lower.find('('), lower.rfind(')')) {
+            (Some(open), Some(close)) if close > open => {
+                let args = lower[open + 1..close].replace(' ', "");
+                let base = format!("{} {}", lower[..open].trim(), lower[close + 1..].trim());
+                (
+                    base.trim().to_string(),
+                    Some(args).filter(|a| !a.is_empty()),
+                )
+            }
+            _ => (lower.clone(), None),
+        };
+        let base = base
+            .trim_end_matches(" zerofill")
+            .trim_end_matches(" unsigned");
+
+        match base {
+            "tinyint" if args.as_deref() == Some("1") => Self::Boolean,
+            "bool" | "boolean" => Self::Boolean,
+            "smallint" | "int2" | "tinyint" | "smallserial" => Self::SmallInt,
+            "int" | "integer" | "int4" | "mediumint" | "serial" => Self::Integer,
+            "bigint" | "int8" | "bigserial" => Self::BigInt,
+            "real" | "float4" | "float" => Self::Real,
+            "double" | "double precision" | "float8" => Self::Double,
+            "decimal" | "numeric" => Self::Decimal(args),
+            "varchar" | "character varying" | "nvarchar" | "varchar2" => Self::Varchar(args),
+            "char" | "character" | "bpchar" | "nchar" => Self::Char(args),
+            "text" | "tinytext" | "mediumtext" | "longtext" | "clob" => Self::Text,
+            "blob" | "tinyblob" | "mediumblob" | "longblob" | "bytea" | "binary" | "varbinary" => {
+                Self::Binary
+            }
+            "date" => Self::Date,
+            "time" | "time without time zone" => Self::Time,
+            "datetime" | "timestamp" | "timestamp without time zone" => Self::Timestamp,
+            "timestamptz" | "timestamp with time zone" => Self::TimestampTz,
+            "json" | "jsonb" => Self::Json,
+            "uuid" => Self::Uuid,
+            _ => Self::Other(data_type.trim().to_string()),
+        }
+    }
+
+    /// Canonical, dialect-independent spelling used for comparisons.
+    pub fn canonical(&self) -> String {
+        match self {
+            Self::SmallInt => "smallint".to_string(),
+            Self::Integer => "integer".to_string(),
+            Self::BigInt => "bigint".to_string(),
+            Self::Boolean => "boolean".to_string(),
+            Self::Real => "real".to_string(),
+            Self::Double => "double".to_string(),
+            Self::Decimal(args) => with_args("decimal", args),
+            Self::Varchar(args) => with_args("varchar", args),
+            Self::Char(args) => with_args("char", args),
+            Self::Text => "text".to_string(),
+            Self::Binary => "binary".to_string(),
+            Self::Date => "date".to_string(),
+            Self::Time => "time".to_string(),
+            Self::Timestamp => "timestamp".to_string(),
+            Self::TimestampTz => "timestamptz".to_string(),
+            Self::Json => "json".to_string(),
+            Self::Uuid => "uuid".to_string(),
+            Self::Other(raw) => raw.to_lowercase(),
+        }
+    }
+
+    /// Render the type for a target dialect.
+    pub fn render(&self, target: DatabaseType) -> String {
+        use DatabaseType::*;
+        match (self, target) {
+            (Self::SmallInt, SQLite) | (Self::Integer, SQLite) | (Self::BigInt, SQLite) => {
+                "INTEGER".to_string()
+            }
+            (Self::SmallInt, _) => "SMALLINT".to_string(),
+            (Self::Integer, MySQL) => "INT".to_string(),
+            (Self::Integer, _) => "INTEGER".to_string(),
+            (Self::BigInt, _) => "BIGINT".to_string(),
+            (Self::Boolean, MySQL) => "TINYINT(1)".to_string(),
+            (Self::Boolean, _) => "BOOLEAN".to_string(),
+            (Self::Real, MySQL) => "FLOAT".to_string(),
+            (Self::Real, _) => "REAL".to_string(),
+            (Self::Double, PostgreSQL) => "DOUBLE PRECISION".to_string(),
+            (Self::Double, MySQL) => "DOUBLE".to_string(),
+            (Self::Double, SQLite) => "REAL".to_string(),
+            (Self::Decimal(args), PostgreSQL) => with_args("NUMERIC", args),
+            (Self::Decimal(args), _) => with_args("DECIMAL", args),
+            (Self::Varchar(None), MySQL) => "VARCHAR(255)".to_string(),
+            (Self::Varchar(args), _) => with_args("VARCHAR", args),
+            (Self::Char(args), _) => with_args("CHAR", args),
+            (Self::Text, _) => "TEXT".to_string(),
+            (Self::Binary, PostgreSQL) => "BYTEA".to_string(),
+            (Self::Binary, MySQL) => "LONGBLOB".to_string(),
+            (Self::Binary, SQLite) => "BLOB".to_string(),
+            (Self::Date, _) => "DATE".to_string(),
+            (Self::Time, _) => "TIME".to_string(),
+            (Self::Timestamp, PostgreSQL) => "TIMESTAMP".to_string(),
+            (Self::Timestamp, _) => "DATETIME".to_string(),
+            (Self::TimestampTz, PostgreSQL) => "TIMESTAMPTZ".to_string(),
+            (Self::TimestampTz, _) => "DATETIME".to_string(),
+            (Self::Json, PostgreSQL) => "JSONB".to_string(),
+            (Self::Json, MySQL) => "JSON".to_string(),
+            (Self::Json, SQLite) => "TEXT".to_string(),
+            (Self::Uuid, PostgreSQL) => "UUID".to_string(),
+            (Self::Uuid, MySQL) => "CHAR(36)".to_string(),
+            (Self::Uuid, SQLite) => "TEXT".to_string(),
+            (Self::Other(raw), _) => raw.clone(),
+        }
+    }
+
+    fn is_integer(&self) -> bool {
+        matches!(self, Self::SmallInt | Self::Integer | Self::BigInt)
+    }
+
+    fn is_numeric(&self) -> bool {
+        self.is_integer() || matches!(self, Self::Real | Self::Double | Self::Decimal(_))
+    }
+}
+
+fn with_args(name: &str, args: &Option<String>) -> String {
+    match args {
+        Some(a) => format!("{}({})", name, a),
+        None => name.to_string(),
+    }
+}
+
+// =============================================================================
+// Default Values
+// =============================================================================
+
+/// A column default normalized across dialects.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DefaultValue {
+    /// String literal (unquoted content)
+    Literal(String),
+    /// Numeric literal
+    Number(String),
+    Boolean(bool),
+    /// SQL expression such as `CURRENT_TIMESTAMP`
+    Expression(String),
+}
+
+impl DefaultValue {
+    /// Parse a column's default as introspected from `source`.
+    ///
+    /// Returns `None` when the column has no default or when the default only
+    /// implements auto-increment (e.g. PostgreSQL `nextval(...)`).
+    pub fn from_column(column: &ColumnDefinition, source: DatabaseType) -> Option<Self> {
+        let ty = PortableType::parse(&column.data_type);
+        match column.default_value.as_ref()? {
+            serde_json::Value::Null => None,
+            serde_json::Value::Bool(b) => Some(Self::Boolean(*b)),
+            serde_json::Value::Number(n) if ty == PortableType::Boolean => {
+                Some(Self::Boolean(n.as_i64() != Some(0)))
+            }
+            serde_json::Value::Number(n) => Some(Self::Number(n.to_string())),
+            serde_json::Value::String(s) => Self::parse_str(s, column, &ty, source),
+            other => Some(Self::Literal(other.to_string())),
+        }
+    }
+
+    fn parse_str(
+        raw: &str,
+        column: &ColumnDefinition,
+        ty: &PortableType,
+        source: DatabaseType,
+    ) -> Option<Self> {
+        let s = raw.trim();
+        if s.eq_ignore_ascii_case("null") {
+            return None;
+        }
+
+        if source == DatabaseType::MySQL {
+            // MySQL reports literal defaults unquoted; expressions are flagged
+            // with DEFAULT_GENERATED in EXTRA (8.0+) or are timestamp keywords.
+            let generated = column
+                .extra
+                .as_deref()
+                .is_some_and(|e| e.to_uppercase().contains("DEFAULT_GENERATED"));
+            if generated || is_timestamp_keyword(s) {
+                return Some(Self::Expression(s.to_string()));
+            }
+            if *ty == PortableType::Boolean {
+                return Some(Self::Boolean(s != "0" && !s.eq_ignore_ascii_case("false")));
+            }
+            if ty.is_numeric() && s.parse::<f64>().is_ok() {
+                return Some(Self::Number(s.to_string()));
+            }
+            return Some(Self::Literal(s.to_string()));
+        }
+
+        if s.to_lowercase().starts_with("nextval(") {
+            return None;
+        }
+
+        let s = strip_pg_cast(s);
+        let s = strip_parens(s);
+        if let Some(inner) = s.strip_prefix('\'').and_then(|r| r.strip_suffix('\'')) {
+            return Some(Self::Literal(inner.replace("''", "'")));
+        }
+        match s.to_lowercase().as_str() {
+            "true" => return Some(Self::Boolean(true)),
+            "false" => return Some(Self::Boolean(false)),
+            _ => {}
+        }
+        if s.parse::<f64>().is_ok() {
+            if *ty == PortableType::Boolean {
+                return Some(Self::Boolean(s != "0"));
+            }
+            return Some(Self::Number(s.to_string()));
+        }
+        Some(Self::Expression(s.to_string()))
+    }
+
+    /// Canonical, dialect-independent spelling used for comparisons.
+    pub fn canonical(&self) -> String {
+        match self {
+            Self::Literal(s) => format!("'{}'", s),
+            Self::Number(n) => n.clone(),
+            Self::Boolean(b) => b.to_string(),
+            Self::Expression(e) if is_timestamp_keyword(e) => "current_timestamp".to_string(),
+            Self::Expression(e) => e.to_lowercase(),
+        }
+    }
+
+    /// Render the default for a target dialect.
+    pub fn render(&self, source: DatabaseType, target: DatabaseType) -> String {
+        match self {
+            Self::Literal(s) => format!("'{}'", s.replace('\'', "''")),
+            Self::Number(n) => n.clone(),
+            Self::Boolean(b) => match target {
+                DatabaseType::PostgreSQL => if *b { "TRUE" } else { "FALSE" }.to_string(),
+                _ => if *b { "1" } else { "0" }.to_string(),
+            },
+            Self::Expression(e) if is_timestamp_keyword(e) => "CURRENT_TIMESTAMP".to_string(),
+            Self::Expression(e) if source == target => e.clone(),
+            // MySQL 8.0.13+ requires parentheses around expression defaults
+            Self::Expression(e) if target == DatabaseType::MySQL => format!("({})", e),
+            Self::Expression(e) => e.clone(),
+        }
+    }
+}
+
+fn is_timestamp_keyword(s: &str) -> bool {
+    let lower = s.trim().to_lowercase();
+    lower.starts_with("current_timestamp")
+        || lower == "now()"
+        || lower.starts_with("localtimestamp")
+        || lower == "datetime('now')"
+        || lower == "(datetime('now'))"
+}
+
+/// Strip a trailing PostgreSQL cast, e.g. `'abc'::character varying` -> `'abc'`.
+fn strip_pg_cast(s: &str) -> &str {
+    if s.starts_with('\'') {
+        // Find the closing quote, skipping escaped ''
+        let bytes = s.as_bytes();
+        let mut i = 1;
+        while i < bytes.len() {
+            if bytes[i] == b'\'' {
+                if bytes.get(i + 1) == Some(&b'\'') {
+                    i += 2;
+                    continue;
+                }
+                return if s[i + 1..].starts_with("::") {
+                    &s[..=i]
+                } else {
+                    s
+                };
+            }
+            i += 1;
+        }
+        return s;
+    }
+    match s.find("::") {
+        Some(pos) if !s[..pos].contains('(') => &s[..pos],
+        _ => s,
+    }
+}
+
+/// Strip one layer of wrapping parentheses, e.g. SQLite's `(0)`.
+fn strip_parens(s: &str) -> &str {
+    match s.strip_prefix('(').and_then(|r| r.strip_suffix(')')) {
+        Some(inner) if !inner.contains('(') && !inner.contains(')') => inner.trim(),
+        _ => s,
+    }
+}
+
+/// Whether a column is auto-incrementing in its source dialect.
+pub fn is_auto_increment(column: &ColumnDefinition) -> bool {
+    let extra = column
+        .extra
+        .as_deref()
+        .is_some_and(|e| e.to_lowercase().contains("auto_increment"));
+    let nextval = matches!(
+        &column.default_value,
+        Some(serde_json::Value::String(s)) if s.to_lowercase().starts_with("nextval(")
+    );
+    let serial = column.data_type.to_lowercase().contains("serial");
+    extra || nextval || serial
+}
+
+// =============================================================================
+// Renderer
+// =============================================================================
+
+/// Renders DDL statements for schema metadata introspected from `source`,
+/// in the dialect of `target`.
+#[derive(Debug, Clone, Copy)]
+pub struct DdlRenderer {
+    source: DatabaseType,
+    target: DatabaseType,
+}
+
+impl DdlRenderer {
+    /// Create a renderer translating from `source` to `target` dialect.
+    pub fn new(source: DatabaseType, target: DatabaseType) -> Self {
+        Self { source, target }
+    }
+
+    /// The dialect the schema metadata was introspected from.
+    pub fn source(&self) -> DatabaseType {
+        self.source
+    }
+
+    /// The dialect statements are rendered in.
+    pub fn target(&self) -> DatabaseType {
+        self.target
+    }
+
+    fn quote(&self, identifier: &str) -> String {
+        self.target.quote_identifier(identifier)
+    }
+
+    /// Quoted table name, schema-qualified for non-default PostgreSQL schemas.
+    pub fn table_name(&self, table: &TableSchema) -> String {
+        match table.schema_name.as_deref() {
+            Some(schema)
+                if self.source == self.target
+                    && self.target == DatabaseType::PostgreSQL
+                    && schema != "public" =>
+            {
+                format!("{}.{}", self.quote(schema), self.quote(&table.table_name))
+            }
+            _ => self.quote(&table.table_name),
+        }
+    }
+
+    /// Render a column type for the target dialect.
+    pub fn column_type(&self, column: &ColumnDefinition) -> String {
+        if self.source == self.target {
+            return column.data_type.clone();
+        }
+        PortableType::parse(&column.data_type).render(self.target)
+    }
+
+    /// Render a column definition (name, type, nullability, default, ...).
+    ///
+    /// `inline_primary_key` renders `PRIMARY KEY` on the column itself, which
+    /// SQLite requires for auto-incrementing rowid aliases.
+    pub fn column_definition(&self, column: &ColumnDefinition, inline_primary_key: bool) -> String {
+        self.render_column(column, inline_primary_key, is_auto_increment(column))
+    }
+
+    fn render_column(
+        &self,
+        column: &ColumnDefinition,
+        inline_primary_key: bool,
+        auto_increment: bool,
+    ) -> String {
+        let mut ty = self.column_type(column);
+        if inline_primary_key && self.target == DatabaseType::SQLite {
+            // Only an exact INTEGER PRIMARY KEY becomes a rowid alias
+            if PortableType::parse(&column.data_type).is_integer() {
+                ty = "INTEGER".to_string();
+            }
+        }
+
+        let mut def = format!("{} {}", self.quote(&column.name), ty);
+
+        if self.source == self.target && self.target == DatabaseType::MySQL {
+            if let Some(charset) = &column.character_set {
+                def.push_str(&format!(" CHARACTER SET {}", charset));
+            }
+            if let Some(collation) = &column.collation {
+                def.push_str(&format!(" COLLATE {}", collation));
+            }
+        }
+
+        if inline_primary_key {
+            def.push_str(" PRIMARY KEY");
+        } else if !column.nullable || column.is_primary_key {
+            def.push_str(" NOT NULL");
+        }
+
+        // PostgreSQL serial columns keep their nextval() default when not translated
+        let keeps_serial_default =
+            self.source == DatabaseType::PostgreSQL && self.target == DatabaseType::PostgreSQL;
+        if auto_increment && !keeps_serial_default {
+            match self.target {
+                DatabaseType::PostgreSQL => def.push_str(" GENERATED BY DEFAULT AS IDENTITY"),
+                DatabaseType::MySQL => def.push_str(" AUTO_INCREMENT"),
+                DatabaseType::SQLite if inline_primary_key => def.push_str(" AUTOINCREMENT"),
+                DatabaseType::SQLite => {}
+            }
+        } else if let Some(default) = self.default_clause(column) {
+            def.push_str(&default);
+        }
+
+        if self.target == DatabaseType::MySQL {
+            if let Some(comment) = &column.comment {
+                def.push_str(&format!(" COMMENT {}", quote_literal(comment)));
+            }
+        }
+
+        def
+    }
+
+    fn default_clause(&self, column: &ColumnDefinition) -> Option<String> {
+        // Same-dialect PostgreSQL serial defaults are preserved verbatim
+        if self.source == self.target && self.target == DatabaseType::PostgreSQL {
+            if let Some(serde_json::Value::String(s)) = &column.default_value {
+                if s.to_lowercase().starts_with("nextval(") {
+                    return Some(format!(" DEFAULT {}", s));
+                }
+            }
+        }
+        DefaultValue::from_column(column, self.source)
+            .map(|d| format!(" DEFAULT {}", d.render(self.source, self.target)))
+    }
+
+    /// Render `CREATE TABLE` plus index and comment statements.
+    ///
+    /// When `inline_foreign_keys` is false, foreign keys are left out so they
+    /// can be added with [`DdlRenderer::add_foreign_key`] once all tables exist.
+    pub fn create_table(&self, table: &TableSchema, inline_foreign_keys: bool) -> Vec<String> {
+        let pk_columns: Vec<&str> = if table.primary_key.is_empty() {
+            table
+                .columns
+                .iter()
+                .filter(|c| c.is_primary_key)
+                .map(|c| c.name.as_str())
+                .collect()
+        } else {
+            table.primary_key.iter().map(String::as_str).collect()
+        };
+
+        // SQLite needs INTEGER PRIMARY KEY inline for rowid/autoincrement
+        let inline_pk = pk_columns.len() == 1
+            && self.target == DatabaseType::SQLite
+            && table
+                .columns
+                .iter()
+                .find(|c| c.name == pk_columns[0])
+                .is_some_and(|c| PortableType::parse(&c.data_type).is_integer());
+
+        // A SQLite INTEGER PRIMARY KEY is an alias for the auto-assigned rowid
+        let rowid_alias = self.source == DatabaseType::SQLite
+            && self.target != DatabaseType::SQLite
+            && pk_columns.len() == 1
+            && table
+                .columns
+                .iter()
+                .find(|c| c.name == pk_columns[0])
+                .is_some_and(|c| c.data_type.eq_ignore_ascii_case("integer"));
+
+        let mut lines: Vec<String> = table
+            .columns
+            .iter()
+            .map(|c| {
+                let inline = inline_pk && c.name == pk_columns[0];
+                let auto_increment =
+                    is_auto_increment(c) || (rowid_alias && c.name == pk_columns[0]);
+                format!("  {}", self.render_column(c, inline, auto_increment))
+            })
+            .collect();
+
+        if !pk_columns.is_empty() && !inline_pk {
+            lines.push(format!("  PRIMARY KEY ({})", self.column_list(&pk_columns)));
+        }
+
+        if inline_foreign_keys || self.target == DatabaseType::SQLite {
+            for fk in &table.foreign_keys {
+                lines.push(format!("  {}", self.foreign_key_clause(fk)));
+            }
+        }
+
+        let mut create = format!(
+            "CREATE TABLE {} (\n{}\n)",
+            self.table_name(table),
+            lines.join(",\n")
+        );
+        if self.target == DatabaseType::MySQL {
+            if let Some(comment) = &table.comment {
+                create.push_str(&format!(" COMMENT={}", quote_literal(comment)));
+            }
+        }
+        let mut statements = vec![create];
+
+        for index in self.secondary_indexes(table) {
+            statements.push(self.create_index(table, index));
+        }
+
+        if self.target == DatabaseType::PostgreSQL {
+            if let Some(comment) = &table.comment {
+                statements.push(format!(
+                    "COMMENT ON TABLE {} IS {}",
+                    self.table_name(table),
+                    quote_literal(comment)
+                ));
+            }
+            for column in &table.columns {
+                if let Some(comment) = &column.comment {
+                    statements.push(format!(
+                        "COMMENT ON COLUMN {}.{} IS {}",
+                        self.table_name(table),
+                        self.quote(&column.name),
+                        quote_literal(comment)
+                    ));
+                }
+            }
+        }
+
+        statements
+    }
+
+    /// Indexes that need an explicit `CREATE INDEX` (not the primary key).
+    pub fn secondary_indexes<'a>(&self, table: &'a TableSchema) -> Vec<&'a IndexInfo> {
+        table.indexes.iter().filter(|i| !i.is_primary).collect()
+    }
+
+    fn column_list<S: AsRef<str>>(&self, columns: &[S]) -> String {
+        columns
+            .iter()
+            .map(|c| self.quote(c.as_ref()))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    fn foreign_key_clause(&self, fk: &ForeignKey) -> String {
+        let mut clause = String::new();
+        if let Some(name) = &fk.name {
+            clause.push_str(&format!("CONSTRAINT {} ", self.quote(name)));
+        }
+        clause.push_str(&format!(
+            "FOREIGN KEY ({}) REFERENCES {} ({})",
+            self.quote(&fk.column),
+            self.quote(&fk.references_table),
+            self.quote(&fk.references_column)
+        ));
+        if fk.on_delete != Default::default() {
+            clause.push_str(&format!(" ON DELETE {}", fk.on_delete));
+        }
+        if fk.on_update != Default::default() {
+            clause.push_str(&format!(" ON UPDATE {}", fk.on_update));
+        }
+        clause
+    }
+
+    /// Render `CREATE [UNIQUE] INDEX`.
+    pub fn create_index(&self, table: &TableSchema, index: &IndexInfo) -> String {
+        let name = if index.name.starts_with("sqlite_autoindex") {
+            // Implicit SQLite indexes for UNIQUE constraints cannot be recreated by name
+            format!("uq_{}_{}", table.table_name, index.columns.join("_"))
+        } else {
+            index.name.clone()
+        };
+        let unique = if index.is_unique { "UNIQUE " } else { "" };
+        let using = match (&index.index_algorithm, self.target) {
+            (Some(algo), DatabaseType::PostgreSQL)
+                if self.source == self.target && !algo.eq_ignore_ascii_case("btree") =>
+            {
+                format!(" USING {}", algo)
+            }
+            _ => String::new(),
+        };
+        format!(
+            "CREATE {}INDEX {} ON {}{} ({})",
+            unique,
+            self.quote(&name),
+            self.table_name(table),
+            using,
+            self.column_list(&index.columns)
+        )
+    }
+
+    /// Render `DROP INDEX`.
+    pub fn drop_index(&self, table: &TableSchema, index_name: &str) -> String {
+        match self.target {
+            DatabaseType::MySQL => format!(
+                "DROP INDEX {} ON {}",
+                self.quote(index_name),
+                self.table_name(table)
+            ),
+            _ => format!("DROP INDEX {}", self.quote(index_name)),
+        }
+    }
+
+    /// Render `DROP TABLE`.
+    pub fn drop_table(&self, table: &TableSchema) -> String {
+        format!("DROP TABLE {}", self.table_name(table))
+    }
+
+    /// Render `ALTER TABLE ... ADD COLUMN`.
+    pub fn add_column(&self, table: &TableSchema, column: &ColumnDefinition) -> String {
+        format!(
+            "ALTER TABLE {} ADD COLUMN {}",
+            self.table_name(table),
+            self.column_definition(column, false)
+        )
+    }
+
+    /// Render `ALTER TABLE ... DROP COLUMN`.
+    pub fn drop_column(&self, table: &TableSchema, column_name: &str) -> String {
+        format!(
+            "ALTER TABLE {} DROP COLUMN {}",
+            self.table_name(table),
+            self.quote(column_name)
+        )
+    }
+
+    /// Render statements that change an existing column to match `column`.
+    ///
+    /// Returns `None` for SQLite, which cannot alter columns in place.
+    pub fn alter_column(
+        &self,
+        table: &TableSchema,
+        column: &ColumnDefinition,
+    ) -> Option<Vec<String>> {
+        let table_name = self.table_name(table);
+        match self.target {
+            DatabaseType::SQLite => None,
+            DatabaseType::MySQL => Some(vec![format!(
+                "ALTER TABLE {} MODIFY COLUMN {}",
+                table_name,
+                self.column_definition(column, false)
+            )]),
+            DatabaseType::PostgreSQL => {
+                let col = self.quote(&column.name);
+                let ty = self.column_type(column);
+                let mut statements = vec![format!(
+                    "ALTER TABLE {} ALTER COLUMN {} TYPE {}",
+                    table_name, col, ty
+                )];
+                statements.push(format!(
+                    "ALTER TABLE {} ALTER COLUMN {} {} NOT NULL",
+                    table_name,
+                    col,
+                    if column.nullable { "DROP" } else { "SET" }
+                ));
+                statements.push(match self.default_clause(column) {
+                    Some(default) => format!(
+                        "ALTER TABLE {} ALTER COLUMN {} SET{}",
+                        table_name, col, default
+                    ),
+                    None => format!(
+                        "ALTER TABLE {} ALTER COLUMN {} DROP DEFAULT",
+                        table_name, col
+                    ),
+                });
+                Some(statements)
+            }
+        }
+    }
+
+    /// Render `ALTER TABLE ... ADD [CONSTRAINT] FOREIGN KEY`.
+    ///
+    /// Returns `None` for SQLite, which only supports foreign keys in `CREATE TABLE`.
+    pub fn add_foreign_key(&self, table: &TableSchema, fk: &ForeignKey) -> Option<String> {
+        if self.target == DatabaseType::SQLite {
+            return None;
+        }
+        Some(format!(
+            "ALTER TABLE {} ADD {}",
+            self.table_name(table),
+            self.foreign_key_clause(fk)
+        ))
+    }
+
+    /// Render the statement dropping a named foreign key.
+    ///
+    /// Returns `None` for SQLite or when the constraint name is unknown.
+    pub fn drop_foreign_key(&self, table: &TableSchema, fk: &ForeignKey) -> Option<String> {
+        let name = fk.name.as_deref()?;
+        match self.target {
+            DatabaseType::SQLite => None,
+            DatabaseType::MySQL => Some(format!(
+                "ALTER TABLE {} DROP FOREIGN KEY {}",
+                self.table_name(table),
+                self.quote(name)
+            )),
+            DatabaseType::PostgreSQL => Some(format!(
+                "ALTER TABLE {} DROP CONSTRAINT {}",
+                self.table_name(table),
+                self.quote(name)
+            )),
+        }
+    }
+}
+
+/// Quote a string literal, escaping embedded single quotes.
+pub fn quote_literal(s: &str) -> String {
+    format!("'{}'", s.replace('\'', "''"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn users_table() -> TableSchema {
+        let mut table = TableSchema::new("users").with_primary_key(vec!["id".to_string()]);
+        table.columns = vec![
+            ColumnDefinition::new("id", "int", false)
+                .with_primary_key(true)
+                .with_extra("auto_increment"),
+            ColumnDefinition::new("email", "varchar(255)", false),
+            ColumnDefinition::new("active", "tinyint(1)", false).with_default(json!(1)),
+            ColumnDefinition::new("created_at", "datetime", true)
+                .with_default(json!("CURRENT_TIMESTAMP"))
+                .with_extra("DEFAULT_GENERATED"),
+        ];
+        table.indexes = vec![
+            IndexInfo::new("PRIMARY", vec!["id".to_string()]).with_primary(true),
+            IndexInfo::new("idx_email", vec!["email".to_string()]).with_unique(true),
+        ];
+        table
+    }
+
+    #[test]
+    fn test_portable_type_parse() {
+        assert_eq!(PortableType::parse("INTEGER"), PortableType::Integer);
+        assert_eq!(PortableType::parse("int(11)"), PortableType::Integer);
+        assert_eq!(PortableType::parse("bigint unsigned"), PortableType::BigInt);
+        assert_eq!(PortableType::parse("tinyint(1)"), PortableType::Boolean);
+        assert_eq!(
+            PortableType::parse("character varying(30)"),
+            PortableType::Varchar(Some("30".to_string()))
+        );
+        assert_eq!(
+            PortableType::parse("numeric(10, 2)"),
+            PortableType::Decimal(Some("10,2".to_string()))
+        );
+        assert_eq!(
+            PortableType::parse("timestamp without time zone"),
+            PortableType::Timestamp
+        );
+        assert_eq!(
+            PortableType::parse("enum('a','b')"),
+            PortableType::Other("enum('a','b')".to_string())
+        );
+    }
+
+    #[test]
+    fn test_portable_type_canonical_matches_across_dialects() {
+        assert_eq!(
+            PortableType::parse("varchar(255)").canonical(),
+            PortableType::parse("character varying(255)").canonical()
+        );
+        assert_eq!(
+            PortableType::parse("int4").canonical(),
+            PortableType::parse("INTEGER").canonical()
+        );
+        assert_ne!(
+            PortableType::parse("varchar(30)").canonical(),
+            PortableType::parse("varchar(50)").canonical()
+        );
+    }
+
+    #[test]
+    fn test_portable_type_render() {
+        assert_eq!(
+            PortableType::Boolean.render(DatabaseType::MySQL),
+            "TINYINT(1)"
+        );
+        assert_eq!(
+            PortableType::Binary.render(DatabaseType::PostgreSQL),
+            "BYTEA"
+        );
+        assert_eq!(PortableType::Json.render(DatabaseType::SQLite), "TEXT");
+        assert_eq!(
+            PortableType::Varchar(None).render(DatabaseType::MySQL),
+            "VARCHAR(255)"
+        );
+    }
+
+    #[test]
+    fn test_default_value_postgres_cast_stripped() {
+        let col = ColumnDefinition::new("status", "character varying(20)", false)
+            .with_default_str("'active'::character varying");
+        assert_eq!(
+            DefaultValue::from_column(&col, DatabaseType::PostgreSQL),
+            Some(DefaultValue::Literal("active".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_default_value_mysql_unquoted_literal() {
+        let col = ColumnDefinition::new("status", "varchar(20)", false).with_default_str("active");
+        assert_eq!(
+            DefaultValue::from_column(&col, DatabaseType::MySQL),
+            Some(DefaultValue::Literal("active".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_default_value_nextval_is_not_a_default() {
+        let col = ColumnDefinition::new("id", "integer", false)
+            .with_default_str("nextval('users_id_seq'::regclass)");
+        assert_eq!(
+            DefaultValue::from_column(&col, DatabaseType::PostgreSQL),
+            None
+        );
+        assert!(is_auto_increment(&col));
+    }
+
+    #[test]
+    fn test_default_value_canonical_bool() {
+        let mysql = ColumnDefinition::new("active", "tinyint(1)", false).with_default_str("0");
+        let pg = ColumnDefinition::new("active", "boolean", false).with_default_str("false");
+        assert_eq!(
+            DefaultValue::from_column(&mysql, DatabaseType::MySQL).map(|d| d.canonical()),
+            DefaultValue::from_column(&pg, DatabaseType::PostgreSQL).map(|d| d.canonical())
+        );
+    }
+
+    #[test]
+    fn test_create_table_mysql_to_postgres() {
+        let renderer = DdlRenderer::new(DatabaseType::MySQL, DatabaseType::PostgreSQL);
+        let statements = renderer.create_table(&users_table(), true);
+        let create = &statements[0];
+        assert!(create.starts_with("CREATE TABLE \"users\""));
+        assert!(create.contains("\"id\" INTEGER NOT NULL GENERATED BY DEFAULT AS IDENTITY"));
+        assert!(create.contains("\"active\" BOOLEAN NOT NULL DEFAULT TRUE"));
+        assert!(create.contains("\"created_at\" TIMESTAMP DEFAULT CURRENT_TIMESTAMP"));
+        assert!(create.contains("PRIMARY KEY (\"id\")"));
+        assert_eq!(
+            statements[1],
+            "CREATE UNIQUE INDEX \"idx_email\" ON \"users\" (\"email\")"
+        );
+    }
+
+    #[test]
+    fn test_create_table_to_sqlite_inlines_integer_pk() {
+        let renderer = DdlRenderer::new(DatabaseType::MySQL, DatabaseType::SQLite);
+        let create = &renderer.create_table(&users_table(), true)[0];
+        assert!(create.contains("\"id\" INTEGER PRIMARY KEY AUTOINCREMENT"));
+        assert!(!create.contains("  PRIMARY KEY ("));
+    }
+
+    #[test]
+    fn test_foreign_keys_inline_and_deferred() {
+        let mut table = TableSchema::new("orders").with_primary_key(vec!["id".to_string()]);
+        table.columns = vec![
+            ColumnDefinition::new("id", "integer", false).with_primary_key(true),
+            ColumnDefinition::new("user_id", "integer", false),
+        ];
+        table.foreign_keys = vec![
+            ForeignKey::new("user_id", "users", "id")
+                .with_name("fk_orders_user")
+                .with_on_delete(crate::models::ForeignKeyAction::Cascade),
+        ];
+
+        let pg = DdlRenderer::new(DatabaseType::PostgreSQL, DatabaseType::PostgreSQL);
+        let inline = pg.create_table(&table, true);
+        assert!(inline[0].contains(
+            "CONSTRAINT \"fk_orders_user\" FOREIGN KEY (\"user_id\") REFERENCES \"users\" (\"id\") ON DELETE CASCADE"
+        ));
+        let deferred = pg.create_table(&table, false);
+        assert!(!deferred[0].contains("FOREIGN KEY"));
+        assert!(pg.add_foreign_key(&table, &table.foreign_keys[0]).is_some());
+
+        let sqlite = DdlRenderer::new(DatabaseType::PostgreSQL, DatabaseType::SQLite);
+        assert!(sqlite.create_table(&table, false)[0].contains("FOREIGN KEY"));
+        assert!(
+            sqlite
+                .add_foreign_key(&table, &table.foreign_keys[0])
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_alter_column_per_dialect() {
+        let table = TableSchema::new("users");
+        let col = ColumnDefinition::new("email", "varchar(320)", true);
+
+        let pg = DdlRenderer::new(DatabaseType::PostgreSQL, DatabaseType::PostgreSQL);
+        let statements = pg.alter_column(&table, &col).unwrap();
+        assert_eq!(
+            statements[0],
+            "ALTER TABLE \"users\" ALTER COLUMN \"email\" TYPE varchar(320)"
+        );
+        assert!(statements[1].ends_with("DROP NOT NULL"));
+        assert!(statements[2].ends_with("DROP DEFAULT"));
+
+        let mysql = DdlRenderer::new(DatabaseType::MySQL, DatabaseType::MySQL);
+        assert_eq!(
+            mysql.alter_column(&table, &col).unwrap(),
+            vec!["ALTER TABLE `users` MODIFY COLUMN `email` varchar(320)".to_string()]
+        );
+
+        let sqlite = DdlRenderer::new(DatabaseType::SQLite, DatabaseType::SQLite);
+        assert!(sqlite.alter_column(&table, &col).is_none());
+    }
+
+    #[test]
+    fn test_drop_index_mysql_needs_table() {
+        let table = TableSchema::new("users");
+        let mysql = DdlRenderer::new(DatabaseType::MySQL, DatabaseType::MySQL);
+        assert_eq!(
+            mysql.drop_index(&table, "idx"),
+            "DROP INDEX `idx` ON `users`"
+        );
+        let pg = DdlRenderer::new(DatabaseType::PostgreSQL, DatabaseType::PostgreSQL);
+        assert_eq!(pg.drop_index(&table, "idx"), "DROP INDEX \"idx\"");
+    }
+
+    #[test]
+    fn test_comments_per_dialect() {
+        let mut table = TableSchema::new("users").with_comment("Registered users");
+        table.columns =
+            vec![ColumnDefinition::new("name", "text", true).with_comment("It's a name")];
+
+        let pg = DdlRenderer::new(DatabaseType::MySQL, DatabaseType::PostgreSQL);
+        let statements = pg.create_table(&table, true);
+        assert_eq!(
+            statements[1],
+            "COMMENT ON TABLE \"users\" IS 'Registered users'"
+        );
+        assert_eq!(
+            statements[2],
+            "COMMENT ON COLUMN \"users\".\"name\" IS 'It''s a name'"
+        );
+
+        let mysql = DdlRenderer::new(DatabaseType::PostgreSQL, DatabaseType::MySQL);
+        let statements = mysql.create_table(&table, true);
+        assert_eq!(statements.len(), 1);
+        assert!(statements[0].contains("`name` TEXT COMMENT 'It''s a name'"));
+        assert!(statements[0].ends_with(") COMMENT='Registered users'"));
+
+        let sqlite = DdlRenderer::new(DatabaseType::PostgreSQL, DatabaseType::SQLite);
+        assert_eq!(sqlite.create_table(&table, true).len(), 1);
+    }
+}
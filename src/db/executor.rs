@@ -3,8 +3,12 @@
 //! This module provides query execution functionality with support for:
 //! - Parameterized queries
 //! - Row limits (enforced via streaming - only fetches needed rows)
-//! - Query timeouts
-//! - Result streaming
+//! - Query timeouts, enforced on the server: a statement that runs past its
+//!   timeout is cancelled on its connection, which is then closed rather than
+//!   returned to the pool
+//! - Cancellation and progress reporting through a [`QueryControl`]
+//! - Result streaming, including row-at-a-time consumption via
+//!   [`QueryExecutor::for_each_row`] for results too large to collect
 //!
 //! # Architecture
 //!
@@ -15,6 +19,7 @@
 //!
 //! Each submodule provides identical functionality adapted to the database's type system.
 
+use crate::db::control::{PinnedConnection, QueryControl};
 use crate::db::pool::DbPool;
 use crate::db::types::RowToJson;
 use crate::error::{DbError, DbResult};
@@ -22,9 +27,9 @@ use crate::models::{
     DEFAULT_QUERY_TIMEOUT_SECS, DEFAULT_ROW_LIMIT, MAX_ROW_LIMIT, QueryParam, QueryRequest,
     QueryResult,
 };
-use futures_util::StreamExt;
+use futures_util::{Stream, StreamExt};
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::time::{Duration, Instant};
-use tokio::time::timeout;
 use tracing::{debug, warn};
 
 /// Query executor that handles database query execution.
@@ -50,11 +55,28 @@ impl QueryExecutor {
         }
     }
 
+    /// Timeout for statements that do not set their own.
+    pub fn default_timeout(&self) -> Duration {
+        self.default_timeout
+    }
+
     /// Execute a SELECT query and return results.
     pub async fn execute_query(
         &self,
         pool: &DbPool,
         request: &QueryRequest,
+    ) -> DbResult<QueryResult> {
+        self.execute_query_controlled(pool, request, &QueryControl::default())
+            .await
+    }
+
+    /// Execute a SELECT query under `control`, which can cancel it and is
+    /// told how many rows have been fetched while it runs.
+    pub async fn execute_query_controlled(
+        &self,
+        pool: &DbPool,
+        request: &QueryRequest,
+        control: &QueryControl,
     ) -> DbResult<QueryResult> {
         let start = Instant::now();
         // Clamp limit to [1, MAX_ROW_LIMIT] to avoid edge case where limit=0 marks all results as "truncated"
@@ -75,31 +97,31 @@ impl QueryExecutor {
             "Executing query"
         );
 
-        match pool {
-            DbPool::MySql(p) => {
-                let rows =
-                    mysql::fetch_rows(p, &request.sql, &request.params, row_limit, query_timeout)
-                        .await?;
-                process_rows(rows, row_limit, start, request.decode_binary)
-            }
-            DbPool::Postgres(p) => {
-                let rows = postgres::fetch_rows(
-                    p,
-                    &request.sql,
-                    &request.params,
-                    row_limit,
-                    query_timeout,
-                )
-                .await?;
-                process_rows(rows, row_limit, start, request.decode_binary)
-            }
-            DbPool::SQLite(p) => {
-                let rows =
-                    sqlite::fetch_rows(p, &request.sql, &request.params, row_limit, query_timeout)
-                        .await?;
-                process_rows(rows, row_limit, start, request.decode_binary)
-            }
-        }
+        let mut pinned = control.acquire(pool).await?;
+        let (conn, canceller) = pinned.parts();
+        let counter = control.row_counter();
+        let sql = request.sql.as_str();
+        let params = request.params.as_slice();
+        let result = control
+            .supervise(canceller, "query execution", query_timeout, async {
+                match conn {
+                    PinnedConnection::MySql(c) => {
+                        let rows = mysql::fetch_rows(c, sql, params, row_limit, counter).await?;
+                        process_rows(rows, row_limit, start, request.decode_binary)
+                    }
+                    PinnedConnection::Postgres(c) => {
+                        let rows = postgres::fetch_rows(c, sql, params, row_limit, counter).await?;
+                        process_rows(rows, row_limit, start, request.decode_binary)
+                    }
+                    PinnedConnection::SQLite(c) => {
+                        let rows = sqlite::fetch_rows(c, sql, params, row_limit, counter).await?;
+                        process_rows(rows, row_limit, start, request.decode_binary)
+                    }
+                }
+            })
+            .await;
+        control.release(pinned).await;
+        result
     }
 
     /// Execute a write operation (INSERT, UPDATE, DELETE) and return affected rows.
@@ -109,6 +131,19 @@ impl QueryExecutor {
         sql: &str,
         params: &[QueryParam],
         query_timeout: Option<Duration>,
+    ) -> DbResult<(u64, u64)> {
+        self.execute_write_controlled(pool, sql, params, query_timeout, &QueryControl::default())
+            .await
+    }
+
+    /// Execute a write operation under `control`, which can cancel it.
+    pub async fn execute_write_controlled(
+        &self,
+        pool: &DbPool,
+        sql: &str,
+        params: &[QueryParam],
+        query_timeout: Option<Duration>,
+        control: &QueryControl,
     ) -> DbResult<(u64, u64)> {
         let start = Instant::now();
         let query_timeout = query_timeout.unwrap_or(self.default_timeout);
@@ -120,15 +155,68 @@ impl QueryExecutor {
             "Executing write operation"
         );
 
-        let rows_affected = match pool {
-            DbPool::MySql(p) => mysql::execute_write(p, sql, params, query_timeout).await?,
-            DbPool::Postgres(p) => postgres::execute_write(p, sql, params, query_timeout).await?,
-            DbPool::SQLite(p) => sqlite::execute_write(p, sql, params, query_timeout).await?,
-        };
-
+        let mut pinned = control.acquire(pool).await?;
+        let (conn, canceller) = pinned.parts();
+        let result = control
+            .supervise(canceller, "write operation", query_timeout, async {
+                match conn {
+                    PinnedConnection::MySql(c) => mysql::execute_write(c, sql, params).await,
+                    PinnedConnection::Postgres(c) => postgres::execute_write(c, sql, params).await,
+                    PinnedConnection::SQLite(c) => sqlite::execute_write(c, sql, params).await,
+                }
+            })
+            .await;
+        control.release(pinned).await;
+
+        let rows_affected = result?;
         let execution_time_ms = start.elapsed().as_millis() as u64;
         Ok((rows_affected, execution_time_ms))
     }
+
+    /// Stream the rows of a query to `on_row` one at a time as JSON, reading at
+    /// most `max_rows` rows. Rows are not collected, so memory use is up to the
+    /// caller. Returns the number of rows passed to `on_row` and whether the
+    /// result had more rows.
+    pub async fn for_each_row(
+        &self,
+        pool: &DbPool,
+        sql: &str,
+        params: &[QueryParam],
+        max_rows: u64,
+        query_timeout: Option<Duration>,
+        mut on_row: impl FnMut(serde_json::Map<String, serde_json::Value>) -> DbResult<()>,
+    ) -> DbResult<(u64, bool)> {
+        let query_timeout = query_timeout.unwrap_or(self.default_timeout);
+
+        debug!(
+            sql = %sql,
+            params = ?params.len(),
+            max_rows = max_rows,
+            timeout_secs = ?query_timeout.as_secs(),
+            "Streaming query rows"
+        );
+
+        let control = QueryControl::default();
+        let mut pinned = control.acquire(pool).await?;
+        let (conn, canceller) = pinned.parts();
+        let result = control
+            .supervise(canceller, "query execution", query_timeout, async {
+                match conn {
+                    PinnedConnection::MySql(c) => {
+                        mysql::for_each_row(c, sql, params, max_rows, &mut on_row).await
+                    }
+                    PinnedConnection::Postgres(c) => {
+                        postgres::for_each_row(c, sql, params, max_rows, &mut on_row).await
+                    }
+                    PinnedConnection::SQLite(c) => {
+                        sqlite::for_each_row(c, sql, params, max_rows, &mut on_row).await
+                    }
+                }
+            })
+            .await;
+        control.release(pinned).await;
+        result
+    }
 }
 
 impl Default for QueryExecutor {
@@ -198,8 +286,26 @@ fn collect_rows<R>(results: Vec<Result<R, sqlx::Error>>) -> DbResult<Vec<R>> {
     Ok(rows)
 }
 
-fn timeout_error(operation: &str, timeout: Duration) -> DbError {
-    DbError::timeout(operation, timeout.as_secs() as u32)
+/// Convert streamed rows to JSON one at a time, stopping after `max_rows`.
+async fn drain_rows<R, S>(
+    mut stream: S,
+    max_rows: u64,
+    on_row: &mut impl FnMut(serde_json::Map<String, serde_json::Value>) -> DbResult<()>,
+) -> DbResult<(u64, bool)>
+where
+    R: RowToJson,
+    S: Stream<Item = Result<R, sqlx::Error>> + Unpin,
+{
+    let mut count = 0;
+    while let Some(row) = stream.next().await {
+        let row = row.map_err(DbError::from)?;
+        if count == max_rows {
+            return Ok((count, true));
+        }
+        on_row(row.to_json_map())?;
+        count += 1;
+    }
+    Ok((count, false))
 }
 
 // =============================================================================
@@ -211,61 +317,81 @@ fn timeout_error(operation: &str, timeout: Duration) -> DbError {
 
 mod mysql {
     use super::*;
-    use sqlx::MySqlPool;
+    use sqlx::MySqlConnection;
     use sqlx::mysql::{MySqlArguments, MySqlRow};
 
     pub async fn fetch_rows(
-        pool: &MySqlPool,
+        conn: &mut MySqlConnection,
         sql: &str,
         params: &[QueryParam],
         row_limit: u32,
-        query_timeout: Duration,
+        rows: &AtomicU64,
     ) -> DbResult<Vec<MySqlRow>> {
         // When params is empty, use raw SQL to avoid prepared statement issues
         let fetch_limit = row_limit as usize + 1;
-        let rows_future = if params.is_empty() {
+        let count_row = |_: &Result<_, sqlx::Error>| {
+            rows.fetch_add(1, Ordering::Relaxed);
+        };
+        let results = if params.is_empty() {
             use sqlx::Executor;
-            let stream = pool.fetch(sql);
-            stream.take(fetch_limit).collect::<Vec<_>>()
+            let stream = (&mut *conn).fetch(sql);
+            stream
+                .take(fetch_limit)
+                .inspect(count_row)
+                .collect::<Vec<_>>()
+                .await
         } else {
             let mut query = sqlx::query(sql);
             for param in params {
                 query = bind_param(query, param);
             }
-            let stream = query.fetch(pool);
-            stream.take(fetch_limit).collect::<Vec<_>>()
+            let stream = query.fetch(&mut *conn);
+            stream
+                .take(fetch_limit)
+                .inspect(count_row)
+                .collect::<Vec<_>>()
+                .await
         };
+        collect_rows(results)
+    }
 
-        match timeout(query_timeout, rows_future).await {
-            Ok(results) => collect_rows(results),
-            Err(_) => Err(timeout_error("query execution", query_timeout)),
+    pub async fn for_each_row(
+        conn: &mut MySqlConnection,
+        sql: &str,
+        params: &[QueryParam],
+        max_rows: u64,
+        on_row: &mut impl FnMut(serde_json::Map<String, serde_json::Value>) -> DbResult<()>,
+    ) -> DbResult<(u64, bool)> {
+        if params.is_empty() {
+            use sqlx::Executor;
+            drain_rows((&mut *conn).fetch(sql), max_rows, on_row).await
+        } else {
+            let mut query = sqlx::query(sql);
+            for param in params {
+                query = bind_param(query, param);
+            }
+            drain_rows(query.fetch(&mut *conn), max_rows, on_row).await
         }
     }
 
     pub async fn execute_write(
-        pool: &MySqlPool,
+        conn: &mut MySqlConnection,
         sql: &str,
         params: &[QueryParam],
-        query_timeout: Duration,
     ) -> DbResult<u64> {
         // When params is empty, execute raw SQL directly to avoid prepared statement issues
         // (some SQL like CREATE PROCEDURE doesn't support prepared statements)
         let result = if params.is_empty() {
             use sqlx::Executor;
-            timeout(query_timeout, pool.execute(sql)).await
+            (&mut *conn).execute(sql).await
         } else {
             let mut query = sqlx::query(sql);
             for param in params {
                 query = bind_param(query, param);
             }
-            timeout(query_timeout, query.execute(pool)).await
+            query.execute(&mut *conn).await
         };
-
-        match result {
-            Ok(Ok(r)) => Ok(r.rows_affected()),
-            Ok(Err(e)) => Err(DbError::from(e)),
-            Err(_) => Err(timeout_error("write operation", query_timeout)),
-        }
+        Ok(result?.rows_affected())
     }
 
     fn bind_param<'q>(
@@ -285,58 +411,78 @@ mod mysql {
 
 mod postgres {
     use super::*;
-    use sqlx::PgPool;
+    use sqlx::PgConnection;
     use sqlx::postgres::{PgArguments, PgRow};
 
     pub async fn fetch_rows(
-        pool: &PgPool,
+        conn: &mut PgConnection,
         sql: &str,
         params: &[QueryParam],
         row_limit: u32,
-        query_timeout: Duration,
+        rows: &AtomicU64,
     ) -> DbResult<Vec<PgRow>> {
         let fetch_limit = row_limit as usize + 1;
-        let rows_future = if params.is_empty() {
+        let count_row = |_: &Result<_, sqlx::Error>| {
+            rows.fetch_add(1, Ordering::Relaxed);
+        };
+        let results = if params.is_empty() {
             use sqlx::Executor;
-            let stream = pool.fetch(sql);
-            stream.take(fetch_limit).collect::<Vec<_>>()
+            let stream = (&mut *conn).fetch(sql);
+            stream
+                .take(fetch_limit)
+                .inspect(count_row)
+                .collect::<Vec<_>>()
+                .await
         } else {
             let mut query = sqlx::query(sql);
             for param in params {
                 query = bind_param(query, param);
             }
-            let stream = query.fetch(pool);
-            stream.take(fetch_limit).collect::<Vec<_>>()
+            let stream = query.fetch(&mut *conn);
+            stream
+                .take(fetch_limit)
+                .inspect(count_row)
+                .collect::<Vec<_>>()
+                .await
         };
+        collect_rows(results)
+    }
 
-        match timeout(query_timeout, rows_future).await {
-            Ok(results) => collect_rows(results),
-            Err(_) => Err(timeout_error("query execution", query_timeout)),
+    pub async fn for_each_row(
+        conn: &mut PgConnection,
+        sql: &str,
+        params: &[QueryParam],
+        max_rows: u64,
+        on_row: &mut impl FnMut(serde_json::Map<String, serde_json::Value>) -> DbResult<()>,
+    ) -> DbResult<(u64, bool)> {
+        if params.is_empty() {
+            use sqlx::Executor;
+            drain_rows((&mut *conn).fetch(sql), max_rows, on_row).await
+        } else {
+            let mut query = sqlx::query(sql);
+            for param in params {
+                query = bind_param(query, param);
+            }
+            drain_rows(query.fetch(&mut *conn), max_rows, on_row).await
         }
     }
 
     pub async fn execute_write(
-        pool: &PgPool,
+        conn: &mut PgConnection,
         sql: &str,
         params: &[QueryParam],
-        query_timeout: Duration,
     ) -> DbResult<u64> {
         let result = if params.is_empty() {
             use sqlx::Executor;
-            timeout(query_timeout, pool.execute(sql)).await
+            (&mut *conn).execute(sql).await
         } else {
             let mut query = sqlx::query(sql);
             for param in params {
                 query = bind_param(query, param);
             }
-            timeout(query_timeout, query.execute(pool)).await
+            query.execute(&mut *conn).await
         };
-
-        match result {
-            Ok(Ok(r)) => Ok(r.rows_affected()),
-            Ok(Err(e)) => Err(DbError::from(e)),
-            Err(_) => Err(timeout_error("write operation", query_timeout)),
-        }
+        Ok(result?.rows_affected())
     }
 
     fn bind_param<'q>(
@@ -356,58 +502,78 @@ mod postgres {
 
 mod sqlite {
     use super::*;
-    use sqlx::SqlitePool;
+    use sqlx::SqliteConnection;
     use sqlx::sqlite::{SqliteArguments, SqliteRow};
 
     pub async fn fetch_rows(
-        pool: &SqlitePool,
+        conn: &mut SqliteConnection,
         sql: &str,
         params: &[QueryParam],
         row_limit: u32,
-        query_timeout: Duration,
+        rows: &AtomicU64,
     ) -> DbResult<Vec<SqliteRow>> {
         let fetch_limit = row_limit as usize + 1;
-        let rows_future = if params.is_empty() {
+        let count_row = |_: &Result<_, sqlx::Error>| {
+            rows.fetch_add(1, Ordering::Relaxed);
+        };
+        let results = if params.is_empty() {
             use sqlx::Executor;
-            let stream = pool.fetch(sql);
-            stream.take(fetch_limit).collect::<Vec<_>>()
+            let stream = (&mut *conn).fetch(sql);
+            stream
+                .take(fetch_limit)
+                .inspect(count_row)
+                .collect::<Vec<_>>()
+                .await
         } else {
             let mut query = sqlx::query(sql);
             for param in params {
                 query = bind_param(query, param);
             }
-            let stream = query.fetch(pool);
-            stream.take(fetch_limit).collect::<Vec<_>>()
+            let stream = query.fetch(&mut *conn);
+            stream
+                .take(fetch_limit)
+                .inspect(count_row)
+                .collect::<Vec<_>>()
+                .await
         };
+        collect_rows(results)
+    }
 
-        match timeout(query_timeout, rows_future).await {
-            Ok(results) => collect_rows(results),
-            Err(_) => Err(timeout_error("query execution", query_timeout)),
+    pub async fn for_each_row(
+        conn: &mut SqliteConnection,
+        sql: &str,
+        params: &[QueryParam],
+        max_rows: u64,
+        on_row: &mut impl FnMut(serde_json::Map<String, serde_json::Value>) -> DbResult<()>,
+    ) -> DbResult<(u64, bool)> {
+        if params.is_empty() {
+            use sqlx::Executor;
+            drain_rows((&mut *conn).fetch(sql), max_rows, on_row).await
+        } else {
+            let mut query = sqlx::query(sql);
+            for param in params {
+                query = bind_param(query, param);
+            }
+            drain_rows(query.fetch(&mut *conn), max_rows, on_row).await
         }
     }
 
     pub async fn execute_write(
-        pool: &SqlitePool,
+        conn: &mut SqliteConnection,
         sql: &str,
         params: &[QueryParam],
-        query_timeout: Duration,
     ) -> DbResult<u64> {
         let result = if params.is_empty() {
             use sqlx::Executor;
-            timeout(query_timeout, pool.execute(sql)).await
+            (&mut *conn).execute(sql).await
         } else {
             let mut query = sqlx::query(sql);
             for param in params {
                 query = bind_param(query, param);
             }
-            timeout(query_timeout, query.execute(pool)).await
+            query.execute(&mut *conn).await
         };
-
-        match result {
-            Ok(Ok(r)) => Ok(r.rows_affected()),
-            Ok(Err(e)) => Err(DbError::from(e)),
-            Err(_) => Err(timeout_error("write operation", query_timeout)),
-        }
+        Ok(result?.rows_affected())
     }
 
     fn bind_param<'q>(
@@ -0,0 +1,522 @@
+//! Versioned schema migrations.
+//!
+//! Migrations are plain `.sql` files in a configured directory, named
+//! `<version>_<name>.sql` (or `<version>_<name>.up.sql`). An optional
+//! `<version>_<name>.down.sql` file provides the statements used by rollback.
+//!
+//! Applied versions are recorded in the `_mcp_migrations` history table along
+//! with a SHA-256 checksum of the up script, so edits to already-applied files
+//! can be detected before anything else is run.
+//!
+//! PostgreSQL and SQLite support transactional DDL: each migration and its
+//! history row are committed atomically. MySQL implicitly commits DDL, so a
+//! failing migration there may leave partial changes behind.
+
+use crate::db::pool::DbPool;
+use crate::error::{DbError, DbResult};
+use crate::models::DatabaseType;
+use sha2::{Digest, Sha256};
+use sqlx::{Executor, Row};
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::time::Instant;
+use tracing::{debug, info};
+
+/// Name of the migration history table.
+pub const MIGRATIONS_TABLE: &str = "_mcp_migrations";
+
+const CREATE_HISTORY_TABLE: &str = r#"
+    CREATE TABLE IF NOT EXISTS _mcp_migrations (
+        version BIGINT PRIMARY KEY,
+        name VARCHAR(255) NOT NULL,
+        checksum VARCHAR(64) NOT NULL,
+        applied_at VARCHAR(64) NOT NULL,
+        execution_time_ms BIGINT NOT NULL
+    )
+"#;
+
+const SELECT_HISTORY: &str = r#"
+    SELECT version, name, checksum, applied_at, execution_time_ms
+    FROM _mcp_migrations
+    ORDER BY version
+"#;
+
+/// A migration script loaded from the migrations directory.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MigrationFile {
+    /// Migration version (numeric file name prefix)
+    pub version: i64,
+    /// Descriptive name (file name after the version prefix)
+    pub name: String,
+    /// Statements applied by `migration_apply`
+    pub up_sql: String,
+    /// Statements applied by `migration_rollback`, if a `.down.sql` file exists
+    pub down_sql: Option<String>,
+    /// SHA-256 checksum of `up_sql` (lowercase hex)
+    pub checksum: String,
+}
+
+/// A row of the `_mcp_migrations` history table.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AppliedMigration {
+    pub version: i64,
+    pub name: String,
+    pub checksum: String,
+    pub applied_at: String,
+    pub execution_time_ms: i64,
+}
+
+/// Which script of a migration file a name refers to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ScriptKind {
+    Up,
+    Down,
+}
+
+/// Compute the SHA-256 checksum of a migration script as lowercase hex.
+pub fn checksum(sql: &str) -> String {
+    Sha256::digest(sql.as_bytes())
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+/// Whether the backend can roll back DDL statements as part of a transaction.
+pub fn supports_transactional_ddl(db_type: DatabaseType) -> bool {
+    match db_type {
+        DatabaseType::PostgreSQL | DatabaseType::SQLite => true,
+        DatabaseType::MySQL => false,
+    }
+}
+
+/// Parse a migration file name into (version, name, kind).
+///
+/// Returns `None` for files that are not `.sql` files.
+fn parse_file_name(file_name: &str) -> Option<DbResult<(i64, String, ScriptKind)>> {
+    let (stem, kind) = if let Some(stem) = file_name.strip_suffix(".down.sql") {
+        (stem, ScriptKind::Down)
+    } else if let Some(stem) = file_name.strip_suffix(".up.sql") {
+        (stem, ScriptKind::Up)
+    } else if let Some(stem) = file_name.strip_suffix(".sql") {
+        (stem, ScriptKind::Up)
+    } else {
+        return None;
+    };
+
+    let (version_part, name) = match stem.split_once('_') {
+        Some((version, name)) => (version, name),
+        None => (stem, ""),
+    };
+
+    let version = match version_part.parse::<i64>() {
+        Ok(v) if v >= 0 && !version_part.is_empty() => v,
+        _ => {
+            return Some(Err(DbError::invalid_input(format!(
+                "Invalid migration file name '{}': expected '<version>_<name>.sql' with a numeric version",
+                file_name
+            ))));
+        }
+    };
+
+    Some(Ok((version, name.to_string(), kind)))
+}
+
+/// Load all migrations from a directory, sorted by version.
+pub fn load_migrations(dir: &Path) -> DbResult<Vec<MigrationFile>> {
+    let entries = std::fs::read_dir(dir).map_err(|e| {
+        DbError::invalid_input(format!(
+            "Cannot read migrations directory '{}': {}",
+            dir.display(),
+            e
+        ))
+    })?;
+
+    let mut ups: BTreeMap<i64, (String, String)> = BTreeMap::new();
+    let mut downs: BTreeMap<i64, String> = BTreeMap::new();
+
+    for entry in entries {
+        let entry = entry.map_err(|e| DbError::internal(e.to_string()))?;
+        if !entry.path().is_file() {
+            continue;
+        }
+        let file_name = entry.file_name().to_string_lossy().into_owned();
+        let Some(parsed) = parse_file_name(&file_name) else {
+            continue;
+        };
+        let (version, name, kind) = parsed?;
+
+        let sql = std::fs::read_to_string(entry.path()).map_err(|e| {
+            DbError::invalid_input(format!("Cannot read migration file '{}': {}", file_name, e))
+        })?;
+
+        let duplicate = match kind {
+            ScriptKind::Up => ups.insert(version, (name, sql)).is_some(),
+            ScriptKind::Down => downs.insert(version, sql).is_some(),
+        };
+        if duplicate {
+            return Err(DbError::invalid_input(format!(
+                "Duplicate migration version {} in '{}'",
+                version,
+                dir.display()
+            )));
+        }
+    }
+
+    if let Some(version) = downs.keys().find(|v| !ups.contains_key(v)) {
+        return Err(DbError::invalid_input(format!(
+            "Migration version {} has a .down.sql file but no up script",
+            version
+        )));
+    }
+
+    let migrations = ups
+        .into_iter()
+        .map(|(version, (name, up_sql))| MigrationFile {
+            version,
+            checksum: checksum(&up_sql),
+            down_sql: downs.remove(&version),
+            name,
+            up_sql,
+        })
+        .collect::<Vec<_>>();
+
+    debug!(
+        dir = %dir.display(),
+        count = migrations.len(),
+        "Loaded migration files"
+    );
+
+    Ok(migrations)
+}
+
+/// Runs migrations against a pool and maintains the history table.
+pub struct MigrationRunner;
+
+impl MigrationRunner {
+    /// Read the migration history, returning an empty list if the history table
+    /// has not been created yet.
+    pub async fn applied(pool: &DbPool) -> DbResult<Vec<AppliedMigration>> {
+        if !Self::history_table_exists(pool).await? {
+            return Ok(Vec::new());
+        }
+
+        match pool {
+            DbPool::Postgres(p) => {
+                let rows = sqlx::query(SELECT_HISTORY).fetch_all(p).await?;
+                rows.iter().map(history_row).collect()
+            }
+            DbPool::MySql(p) => {
+                let rows = sqlx::query(SELECT_HISTORY).fetch_all(p).await?;
+                rows.iter().map(history_row).collect()
+            }
+            DbPool::SQLite(p) => {
+                let rows = sqlx::query(SELECT_HISTORY).fetch_all(p).await?;
+                rows.iter().map(history_row).collect()
+            }
+        }
+    }
+
+    /// Create the history table if it does not exist.
+    pub async fn ensure_history_table(pool: &DbPool) -> DbResult<()> {
+        match pool {
+            DbPool::Postgres(p) => {
+                p.execute(CREATE_HISTORY_TABLE).await?;
+            }
+            DbPool::MySql(p) => {
+                p.execute(CREATE_HISTORY_TABLE).await?;
+            }
+            DbPool::SQLite(p) => {
+                p.execute(CREATE_HISTORY_TABLE).await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Apply a migration's up script and record it in the history table.
+    ///
+    /// Returns the execution time in milliseconds.
+    pub async fn apply(pool: &DbPool, migration: &MigrationFile) -> DbResult<u64> {
+        let start = Instant::now();
+        let applied_at = chrono::Utc::now().to_rfc3339();
+        let sql = migration.up_sql.as_str();
+
+        match pool {
+            DbPool::Postgres(p) => {
+                let mut tx = p.begin().await?;
+                (&mut *tx)
+                    .execute(sql)
+                    .await
+                    .map_err(|e| migration_error(migration, e))?;
+                let elapsed = start.elapsed().as_millis() as i64;
+                sqlx::query(
+                    "INSERT INTO _mcp_migrations (version, name, checksum, applied_at, execution_time_ms) VALUES ($1, $2, $3, $4, $5)",
+                )
+                .bind(migration.version)
+                .bind(&migration.name)
+                .bind(&migration.checksum)
+                .bind(&applied_at)
+                .bind(elapsed)
+                .execute(&mut *tx)
+                .await?;
+                tx.commit().await?;
+            }
+            DbPool::SQLite(p) => {
+                let mut tx = p.begin().await?;
+                (&mut *tx)
+                    .execute(sql)
+                    .await
+                    .map_err(|e| migration_error(migration, e))?;
+                let elapsed = start.elapsed().as_millis() as i64;
+                sqlx::query(
+                    "INSERT INTO _mcp_migrations (version, name, checksum, applied_at, execution_time_ms) VALUES (?, ?, ?, ?, ?)",
+                )
+                .bind(migration.version)
+                .bind(&migration.name)
+                .bind(&migration.checksum)
+                .bind(&applied_at)
+                .bind(elapsed)
+                .execute(&mut *tx)
+                .await?;
+                tx.commit().await?;
+            }
+            DbPool::MySql(p) => {
+                // DDL causes an implicit commit in MySQL, so a transaction would
+                // not protect anything here. Run on a single connection instead.
+                let mut conn = p.acquire().await?;
+                (&mut *conn)
+                    .execute(sql)
+                    .await
+                    .map_err(|e| migration_error(migration, e))?;
+                let elapsed = start.elapsed().as_millis() as i64;
+                sqlx::query(
+                    "INSERT INTO _mcp_migrations (version, name, checksum, applied_at, execution_time_ms) VALUES (?, ?, ?, ?, ?)",
+                )
+                .bind(migration.version)
+                .bind(&migration.name)
+                .bind(&migration.checksum)
+                .bind(&applied_at)
+                .bind(elapsed)
+                .execute(&mut *conn)
+                .await?;
+            }
+        }
+
+        let execution_time_ms = start.elapsed().as_millis() as u64;
+        info!(
+            version = migration.version,
+            name = %migration.name,
+            execution_time_ms = execution_time_ms,
+            "Migration applied"
+        );
+        Ok(execution_time_ms)
+    }
+
+    /// Run a migration's down script and remove it from the history table.
+    ///
+    /// Returns the execution time in milliseconds.
+    pub async fn revert(pool: &DbPool, migration: &MigrationFile) -> DbResult<u64> {
+        let Some(sql) = migration.down_sql.as_deref() else {
+            return Err(DbError::invalid_input(format!(
+                "Migration {} ({}) has no .down.sql file and cannot be rolled back",
+                migration.version, migration.name
+            )));
+        };
+        let start = Instant::now();
+
+        match pool {
+            DbPool::Postgres(p) => {
+                let mut tx = p.begin().await?;
+                (&mut *tx)
+                    .execute(sql)
+                    .await
+                    .map_err(|e| migration_error(migration, e))?;
+                sqlx::query("DELETE FROM _mcp_migrations WHERE version = $1")
+                    .bind(migration.version)
+                    .execute(&mut *tx)
+                    .await?;
+                tx.commit().await?;
+            }
+            DbPool::SQLite(p) => {
+                let mut tx = p.begin().await?;
+                (&mut *tx)
+                    .execute(sql)
+                    .await
+                    .map_err(|e| migration_error(migration, e))?;
+                sqlx::query("DELETE FROM _mcp_migrations WHERE version = ?")
+                    .bind(migration.version)
+                    .execute(&mut *tx)
+                    .await?;
+                tx.commit().await?;
+            }
+            DbPool::MySql(p) => {
+                let mut conn = p.acquire().await?;
+                (&mut *conn)
+                    .execute(sql)
+                    .await
+                    .map_err(|e| migration_error(migration, e))?;
+                sqlx::query("DELETE FROM _mcp_migrations WHERE version = ?")
+                    .bind(migration.version)
+                    .execute(&mut *conn)
+                    .await?;
+            }
+        }
+
+        let execution_time_ms = start.elapsed().as_millis() as u64;
+        info!(
+            version = migration.version,
+            name = %migration.name,
+            execution_time_ms = execution_time_ms,
+            "Migration rolled back"
+        );
+        Ok(execution_time_ms)
+    }
+
+    async fn history_table_exists(pool: &DbPool) -> DbResult<bool> {
+        let exists = match pool {
+            DbPool::Postgres(p) => {
+                sqlx::query_scalar::<_, bool>("SELECT to_regclass($1) IS NOT NULL")
+                    .bind(MIGRATIONS_TABLE)
+                    .fetch_one(p)
+                    .await?
+            }
+            DbPool::MySql(p) => {
+                sqlx::query_scalar::<_, i64>(
+                    "SELECT COUNT(*) FROM information_schema.TABLES WHERE TABLE_SCHEMA = DATABASE() AND TABLE_NAME = ?",
+                )
+                .bind(MIGRATIONS_TABLE)
+                .fetch_one(p)
+                .await?
+                    > 0
+            }
+            DbPool::SQLite(p) => {
+                sqlx::query_scalar::<_, i64>(
+                    "SELECT COUNT(*) FROM sqlite_master WHERE type = 'table' AND name = ?",
+                )
+                .bind(MIGRATIONS_TABLE)
+                .fetch_one(p)
+                .await?
+                    > 0
+            }
+        };
+        Ok(exists)
+    }
+}
+
+fn history_row<'r, R>(row: &'r R) -> DbResult<AppliedMigration>
+where
+    R: Row,
+    usize: sqlx::ColumnIndex<R>,
+    i64: sqlx::Decode<'r, R::Database> + sqlx::Type<R::Database>,
+    String: sqlx::Decode<'r, R::Database> + sqlx::Type<R::Database>,
+{
+    Ok(AppliedMigration {
+        version: row.try_get(0)?,
+        name: row.try_get(1)?,
+        checksum: row.try_get(2)?,
+        applied_at: row.try_get(3)?,
+        execution_time_ms: row.try_get(4)?,
+    })
+}
+
+fn migration_error(migration: &MigrationFile, err: sqlx::Error) -> DbError {
+    let context = format!(
+        "Migration {} ({}) failed",
+        migration.version, migration.name
+    );
+    match DbError::from(err) {
+        DbError::Database {
+            message,
+            sql_state,
+            suggestion,
+        } => DbError::database(format!("{}: {}", context, message), sql_state, suggestion),
+        other => DbError::database(
+            format!("{}: {}", context, other),
+            None,
+            "Fix the migration script and run migration_apply again",
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_file_name_variants() {
+        let (v, name, kind) = parse_file_name("0001_create_users.sql").unwrap().unwrap();
+        assert_eq!(
+            (v, name.as_str(), kind),
+            (1, "create_users", ScriptKind::Up)
+        );
+
+        let (v, name, kind) = parse_file_name("2_add_index.up.sql").unwrap().unwrap();
+        assert_eq!((v, name.as_str(), kind), (2, "add_index", ScriptKind::Up));
+
+        let (v, name, kind) = parse_file_name("2_add_index.down.sql").unwrap().unwrap();
+        assert_eq!((v, name.as_str(), kind), (2, "add_index", ScriptKind::Down));
+
+        let (v, name, _) = parse_file_name("20240101.sql").unwrap().unwrap();
+        assert_eq!((v, name.as_str()), (20240101, ""));
+    }
+
+    #[test]
+    fn test_parse_file_name_ignores_non_sql() {
+        assert!(parse_file_name("README.md").is_none());
+    }
+
+    #[test]
+    fn test_parse_file_name_rejects_non_numeric_version() {
+        assert!(parse_file_name("init_users.sql").unwrap().is_err());
+    }
+
+    #[test]
+    fn test_checksum_is_stable_hex() {
+        let a = checksum("CREATE TABLE t (id INTEGER)");
+        assert_eq!(a.len(), 64);
+        assert_eq!(a, checksum("CREATE TABLE t (id INTEGER)"));
+        assert_ne!(a, checksum("CREATE TABLE t (id BIGINT)"));
+    }
+
+    #[test]
+    fn test_supports_transactional_ddl() {
+        assert!(supports_transactional_ddl(DatabaseType::PostgreSQL));
+        assert!(supports_transactional_ddl(DatabaseType::SQLite));
+        assert!(!supports_transactional_ddl(DatabaseType::MySQL));
+    }
+
+    #[test]
+    fn test_load_migrations_pairs_up_and_down() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("0002_b.sql"), "CREATE TABLE b (id INT);").unwrap();
+        std::fs::write(dir.path().join("0001_a.up.sql"), "CREATE TABLE a (id INT);").unwrap();
+        std::fs::write(dir.path().join("0001_a.down.sql"), "DROP TABLE a;").unwrap();
+        std::fs::write(dir.path().join("notes.txt"), "ignored").unwrap();
+
+        let migrations = load_migrations(dir.path()).unwrap();
+        assert_eq!(migrations.len(), 2);
+        assert_eq!(migrations[0].version, 1);
+        assert_eq!(migrations[0].down_sql.as_deref(), Some("DROP TABLE a;"));
+        assert_eq!(migrations[1].version, 2);
+        assert!(migrations[1].down_sql.is_none());
+    }
+
+    #[test]
+    fn test_load_migrations_rejects_duplicate_versions() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("1_a.sql"), "SELECT 1;").unwrap();
+        std::fs::write(dir.path().join("1_b.sql"), "SELECT 2;").unwrap();
+        assert!(load_migrations(dir.path()).is_err());
+    }
+
+    #[test]
+    fn test_load_migrations_rejects_orphan_down() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("1_a.down.sql"), "DROP TABLE a;").unwrap();
+        assert!(load_migrations(dir.path()).is_err());
+    }
+
+    #[test]
+    fn test_load_migrations_missing_dir() {
+        assert!(load_migrations(Path::new("/nonexistent/migrations/dir")).is_err());
+    }
+}
@@ -3,23 +3,36 @@
 //! This module provides database access functionality:
 //! - Connection pool management
 //! - Query execution
+//! - Cancellation and progress reporting for running statements
 //! - Schema introspection
 //! - Type mappings
 //! - Transaction registry for stateful transaction management
 //! - Database-specific connection pools for server-level connections
+//! - Versioned schema migrations with a checksummed history table
+//! - Cross-dialect DDL rendering from introspected schema metadata
+//! - TTL cache of schema metadata with DDL invalidation
 
+pub mod control;
 pub mod database_pool;
+pub mod ddl;
 pub mod executor;
+pub mod migration;
 pub mod params;
 pub mod pool;
 pub mod schema;
+pub mod schema_cache;
 pub mod transaction_registry;
 pub mod types;
 
+pub use control::{QueryControl, QueryProgress};
 pub use database_pool::{
     DatabasePoolConfig, DatabasePoolEntry, DatabasePoolManager, DatabaseTarget,
 };
 pub use executor::QueryExecutor;
-pub use pool::{ConnectionManager, ConnectionSummary, DbPool, PoolGuard};
+pub use migration::{AppliedMigration, MigrationFile, MigrationRunner};
+pub use pool::{
+    ConnectionManager, ConnectionSummary, DbPool, PoolGuard, PoolStats, RemovedConnection,
+};
 pub use schema::{DatabaseInfoRow, SchemaInspector};
-pub use transaction_registry::TransactionRegistry;
+pub use schema_cache::{SchemaCache, SchemaCacheStats};
+pub use transaction_registry::{SessionGuard, TransactionRegistry};
@@ -4,6 +4,7 @@
 //! pools (MySqlPool, PgPool, SqlitePool) to ensure full type support.
 
 use crate::db::database_pool::{DatabasePoolConfig, DatabasePoolManager, DatabaseTarget};
+use crate::db::schema_cache::SchemaCache;
 use crate::error::{DbError, DbResult};
 use crate::models::{ConnectionConfig, ConnectionInfo, DatabaseType};
 use sqlx::{
@@ -11,6 +12,7 @@ use sqlx::{
     postgres::PgPoolOptions, sqlite::SqliteConnectOptions, sqlite::SqlitePoolOptions,
 };
 use std::collections::HashMap;
+use std::collections::hash_map::Entry;
 use std::str::FromStr;
 use std::sync::Arc;
 use std::time::Duration;
@@ -42,6 +44,21 @@ pub struct ConnectionSummary {
     pub user: Option<String>,
 }
 
+impl From<&ConnectionConfig> for ConnectionSummary {
+    fn from(config: &ConnectionConfig) -> Self {
+        Self {
+            id: config.id.clone(),
+            db_type: config.db_type,
+            writable: config.writable,
+            server_level: config.server_level,
+            database: config.database.clone(),
+            host: config.host.clone(),
+            port: config.port,
+            user: config.user.clone(),
+        }
+    }
+}
+
 /// Database-specific connection pool (avoids AnyPool limitations).
 #[derive(Debug, Clone)]
 pub enum DbPool {
@@ -60,6 +77,15 @@ impl DbPool {
         }
     }
 
+    /// Open connections and how many of them are idle.
+    pub fn connection_counts(&self) -> (u32, usize) {
+        match self {
+            DbPool::MySql(pool) => (pool.size(), pool.num_idle()),
+            DbPool::Postgres(pool) => (pool.size(), pool.num_idle()),
+            DbPool::SQLite(pool) => (pool.size(), pool.num_idle()),
+        }
+    }
+
     /// Get the database type for this pool.
     pub fn db_type(&self) -> DatabaseType {
         match self {
@@ -124,15 +150,33 @@ impl std::fmt::Debug for ConnectionPool {
     }
 }
 
+/// Pool usage of one connection, as exported by the metrics endpoint.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PoolStats {
+    pub connection_id: String,
+    /// Open connections of the connection's own pool (none for server-level
+    /// connections)
+    pub connections: u32,
+    /// Idle connections of the connection's own pool
+    pub idle_connections: usize,
+    /// Per-database pools created for the `database` parameter
+    pub database_pools: usize,
+    /// Per-database pool borrows not yet released
+    pub active_database_pools: usize,
+}
+
 #[derive(Debug)]
 struct PoolEntry {
     connection: ConnectionPool,
     config: ConnectionConfig,
+    /// Added with the admin tools rather than from the server configuration
+    added_at_runtime: bool,
 }
 
 #[derive(Debug, Clone)]
 pub struct ConnectionManager {
     pools: Arc<RwLock<HashMap<String, PoolEntry>>>,
+    schema_cache: Arc<SchemaCache>,
 }
 
 impl ConnectionManager {
@@ -140,11 +184,38 @@ impl ConnectionManager {
     pub fn new() -> Self {
         Self {
             pools: Arc::new(RwLock::new(HashMap::new())),
+            schema_cache: Arc::new(SchemaCache::default()),
         }
     }
 
+    /// Set the schema metadata cache TTL. A zero TTL disables caching.
+    pub fn with_schema_cache_ttl(mut self, ttl: Duration) -> Self {
+        self.schema_cache = Arc::new(SchemaCache::new(ttl));
+        self
+    }
+
+    /// Schema metadata cache shared by all connections.
+    pub fn schema_cache(&self) -> &SchemaCache {
+        &self.schema_cache
+    }
+
     /// Connect to a database and register the pool.
     pub async fn connect(&self, config: ConnectionConfig) -> DbResult<ConnectionInfo> {
+        self.register(config, false).await
+    }
+
+    /// Connect like [`connect`](Self::connect), marking the connection as
+    /// added at runtime so [`remove_added_at_runtime`](Self::remove_added_at_runtime)
+    /// may remove it.
+    pub async fn connect_at_runtime(&self, config: ConnectionConfig) -> DbResult<ConnectionInfo> {
+        self.register(config, true).await
+    }
+
+    async fn register(
+        &self,
+        config: ConnectionConfig,
+        added_at_runtime: bool,
+    ) -> DbResult<ConnectionInfo> {
         let connection_id = config.id.clone();
         let db_type = config.db_type;
 
@@ -159,35 +230,7 @@ impl ConnectionManager {
             }
         }
 
-        info!(
-            connection_id = %connection_id,
-            db_type = %db_type,
-            server_level = %config.server_level,
-            "Connecting to database"
-        );
-
-        let (connection, server_version) = if config.server_level {
-            // Server-level connection: create DatabasePoolManager
-            let db_pool_config = Self::create_database_pool_config(&config);
-            let manager = DatabasePoolManager::new(db_pool_config);
-            info!(
-                connection_id = %connection_id,
-                "Created database pool manager for server-level connection"
-            );
-            (ConnectionPool::ServerLevel(manager), None)
-        } else {
-            // Regular connection: create pool directly
-            let pool = self.create_pool(&config).await?;
-            let server_version = self.get_server_version(&pool).await;
-            (
-                ConnectionPool::Database {
-                    pool,
-                    server_version: server_version.clone(),
-                    override_manager: None,
-                },
-                server_version,
-            )
-        };
+        let (connection, server_version) = self.open(&config).await?;
 
         // Re-check after async work to prevent TOCTOU race
         // If duplicate detected, return the connection so we can close it outside the lock
@@ -199,6 +242,7 @@ impl ConnectionManager {
                 let entry = PoolEntry {
                     connection,
                     config: config.clone(),
+                    added_at_runtime,
                 };
                 pools.insert(connection_id.clone(), entry);
                 None
@@ -230,6 +274,101 @@ impl ConnectionManager {
         })
     }
 
+    /// Swap in a new configuration (URL, access or pool options) for an
+    /// existing connection.
+    ///
+    /// The new pools are created before the swap. Operations and transactions
+    /// already running keep the old pools, which are returned so the caller
+    /// can close them once drained. The connection counts as configured from
+    /// then on, even if it was added at runtime.
+    pub async fn replace(
+        &self,
+        config: ConnectionConfig,
+    ) -> DbResult<(ConnectionInfo, RemovedConnection)> {
+        let connection_id = config.id.clone();
+        if !self.exists(&connection_id).await {
+            return Err(DbError::connection_not_found(&connection_id));
+        }
+
+        let (connection, server_version) = self.open(&config).await?;
+        let entry = PoolEntry {
+            connection,
+            config: config.clone(),
+            added_at_runtime: false,
+        };
+        let old = {
+            let mut pools = self.pools.write().await;
+            match pools.get_mut(&connection_id) {
+                Some(current) => Ok(std::mem::replace(current, entry)),
+                // Removed while the new pools were being created
+                None => Err(entry),
+            }
+        }; // Lock released here
+
+        let old = match old {
+            Ok(old) => old,
+            Err(entry) => {
+                entry.connection.close().await;
+                return Err(DbError::connection_not_found(&connection_id));
+            }
+        };
+        self.schema_cache.invalidate(&connection_id, None);
+        info!(
+            connection_id = %connection_id,
+            server_version = ?server_version,
+            "Connection reconfigured"
+        );
+
+        Ok((
+            ConnectionInfo {
+                connection_id,
+                database_type: config.db_type,
+                server_version,
+                writable: config.writable,
+                server_level: config.server_level,
+                database: config.database,
+            },
+            RemovedConnection {
+                connection: old.connection,
+                config: old.config,
+            },
+        ))
+    }
+
+    /// Create the pools for a configuration, returning the server version
+    /// for database connections.
+    async fn open(&self, config: &ConnectionConfig) -> DbResult<(ConnectionPool, Option<String>)> {
+        info!(
+            connection_id = %config.id,
+            db_type = %config.db_type,
+            server_level = %config.server_level,
+            "Connecting to database"
+        );
+
+        if config.server_level {
+            // Server-level connection: create DatabasePoolManager
+            let db_pool_config = Self::create_database_pool_config(config);
+            let manager = DatabasePoolManager::new(db_pool_config);
+            info!(
+                connection_id = %config.id,
+                "Created database pool manager for server-level connection"
+            );
+            Ok((ConnectionPool::ServerLevel(manager), None))
+        } else {
+            // Regular connection: create pool directly
+            let pool = self.create_pool(config).await?;
+            let server_version = self.get_server_version(&pool).await;
+            Ok((
+                ConnectionPool::Database {
+                    pool,
+                    server_version: server_version.clone(),
+                    override_manager: None,
+                },
+                server_version,
+            ))
+        }
+    }
+
     /// Get a connection pool by ID.
     /// For server-level connections, this returns an error - use get_pool_for_database instead.
     pub async fn get_pool(&self, connection_id: &str) -> DbResult<DbPool> {
@@ -391,16 +530,7 @@ impl ConnectionManager {
         let pools = self.pools.read().await;
         pools
             .values()
-            .map(|entry| ConnectionSummary {
-                id: entry.config.id.clone(),
-                db_type: entry.config.db_type,
-                writable: entry.config.writable,
-                server_level: entry.config.server_level,
-                database: entry.config.database.clone(),
-                host: entry.config.host.clone(),
-                port: entry.config.port,
-                user: entry.config.user.clone(),
-            })
+            .map(|entry| ConnectionSummary::from(&entry.config))
             .collect()
     }
 
@@ -410,6 +540,88 @@ impl ConnectionManager {
         pools.len()
     }
 
+    /// Pool usage of every connection.
+    pub async fn pool_stats(&self) -> Vec<PoolStats> {
+        let connections: Vec<(String, Option<DbPool>, Option<Arc<DatabasePoolManager>>)> = {
+            let pools = self.pools.read().await;
+            pools
+                .iter()
+                .map(|(id, entry)| match &entry.connection {
+                    ConnectionPool::Database {
+                        pool,
+                        override_manager,
+                        ..
+                    } => (id.clone(), Some(pool.clone()), override_manager.clone()),
+                    ConnectionPool::ServerLevel(manager) => {
+                        (id.clone(), None, Some(manager.clone()))
+                    }
+                })
+                .collect()
+        };
+
+        let mut stats = Vec::with_capacity(connections.len());
+        for (connection_id, pool, manager) in connections {
+            let (connections, idle_connections) =
+                pool.map(|p| p.connection_counts()).unwrap_or_default();
+            let (database_pools, active_database_pools) = match manager {
+                Some(manager) => (manager.pool_count().await, manager.active_count().await),
+                None => (0, 0),
+            };
+            stats.push(PoolStats {
+                connection_id,
+                connections,
+                idle_connections,
+                database_pools,
+                active_database_pools,
+            });
+        }
+        stats
+    }
+
+    /// Unregister a connection.
+    ///
+    /// New operations on the connection fail from now on; its pools stay open
+    /// until [`RemovedConnection::close`] so the caller can first roll back
+    /// transactions still holding pooled connections.
+    pub async fn remove(&self, connection_id: &str) -> DbResult<RemovedConnection> {
+        self.unregister(connection_id, false).await
+    }
+
+    /// Unregister a connection like [`remove`](Self::remove), but only one
+    /// added with [`connect_at_runtime`](Self::connect_at_runtime).
+    pub async fn remove_added_at_runtime(
+        &self,
+        connection_id: &str,
+    ) -> DbResult<RemovedConnection> {
+        self.unregister(connection_id, true).await
+    }
+
+    async fn unregister(
+        &self,
+        connection_id: &str,
+        only_added_at_runtime: bool,
+    ) -> DbResult<RemovedConnection> {
+        let entry = match self.pools.write().await.entry(connection_id.to_string()) {
+            Entry::Occupied(entry) if only_added_at_runtime && !entry.get().added_at_runtime => {
+                return Err(DbError::permission(
+                    "remove_connection",
+                    format!(
+                        "Connection '{}' comes from the server configuration. Only connections added with add_connection can be removed",
+                        connection_id
+                    ),
+                ));
+            }
+            Entry::Occupied(entry) => entry.remove(),
+            Entry::Vacant(_) => return Err(DbError::connection_not_found(connection_id)),
+        };
+        self.schema_cache.invalidate(connection_id, None);
+        info!(connection_id = %connection_id, "Connection removed");
+        Ok(RemovedConnection {
+            connection: entry.connection,
+            config: entry.config,
+        })
+    }
+
     /// Close all connections and clear the pool.
     pub async fn close_all(&self) {
         let mut pools = self.pools.write().await;
@@ -610,6 +822,28 @@ impl ConnectionManager {
     }
 }
 
+/// A connection unregistered by [`ConnectionManager::remove`] whose pools
+/// are not closed yet.
+#[derive(Debug)]
+pub struct RemovedConnection {
+    connection: ConnectionPool,
+    config: ConnectionConfig,
+}
+
+impl RemovedConnection {
+    /// Configuration the connection was registered with.
+    pub fn config(&self) -> &ConnectionConfig {
+        &self.config
+    }
+
+    /// Close the connection's pools, waiting for checked-out connections to
+    /// be returned.
+    pub async fn close(self) {
+        info!(connection_id = %self.config.id, "Closing connection");
+        self.connection.close().await;
+    }
+}
+
 impl Default for ConnectionManager {
     fn default() -> Self {
         Self::new()
@@ -768,4 +1002,50 @@ mod tests {
         let connections = manager.list_connections().await;
         assert!(connections.is_empty());
     }
+
+    #[tokio::test]
+    async fn test_remove_connection() {
+        let manager = ConnectionManager::new();
+        let config = ConnectionConfig::new(
+            "mem",
+            "sqlite::memory:",
+            false,
+            false,
+            None,
+            Default::default(),
+        )
+        .unwrap();
+        manager.connect(config).await.unwrap();
+
+        let removed = manager.remove("mem").await.unwrap();
+        assert_eq!(removed.config().id, "mem");
+        assert!(!manager.exists("mem").await);
+        assert!(matches!(
+            manager.remove("mem").await,
+            Err(DbError::ConnectionNotFound { .. })
+        ));
+        removed.close().await;
+    }
+
+    #[tokio::test]
+    async fn test_pool_stats() {
+        let manager = ConnectionManager::new();
+        let config = ConnectionConfig::new(
+            "mem",
+            "sqlite::memory:",
+            false,
+            false,
+            None,
+            Default::default(),
+        )
+        .unwrap();
+        manager.connect(config).await.unwrap();
+
+        let stats = manager.pool_stats().await;
+        assert_eq!(stats.len(), 1);
+        assert_eq!(stats[0].connection_id, "mem");
+        assert!(stats[0].connections >= 1);
+        assert_eq!(stats[0].database_pools, 0);
+        assert_eq!(stats[0].active_database_pools, 0);
+    }
 }
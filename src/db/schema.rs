@@ -12,7 +12,9 @@
 use crate::db::pool::DbPool;
 use crate::error::{DbError, DbResult};
 use crate::models::{
-    ColumnDefinition, ForeignKey, ForeignKeyAction, IndexInfo, TableInfo, TableSchema, TableType,
+    ColumnDefinition, ColumnStatistics, DatabaseType, ForeignKey, ForeignKeyAction, IndexInfo,
+    RoutineInfo, RoutineType, SequenceInfo, TableInfo, TableSchema, TableStatistics, TableType,
+    TriggerInfo, ViewSchema,
 };
 use tracing::debug;
 
@@ -55,6 +57,125 @@ impl SchemaInspector {
         }
     }
 
+    /// Describe a view: its defining query and output columns.
+    pub async fn describe_view(
+        pool: &DbPool,
+        view_name: &str,
+        schema: Option<&str>,
+    ) -> DbResult<ViewSchema> {
+        match pool {
+            DbPool::Postgres(p) => postgres::describe_view(p, view_name, schema).await,
+            DbPool::MySql(p) => mysql::describe_view(p, view_name, schema).await,
+            DbPool::SQLite(p) => sqlite::describe_view(p, view_name).await,
+        }
+    }
+
+    /// List stored functions and procedures (without their bodies).
+    /// Supported for MySQL and PostgreSQL. SQLite has no stored routines.
+    pub async fn list_routines(pool: &DbPool, schema: Option<&str>) -> DbResult<Vec<RoutineInfo>> {
+        match pool {
+            DbPool::Postgres(p) => postgres::list_routines(p, schema).await,
+            DbPool::MySql(p) => mysql::list_routines(p, schema).await,
+            DbPool::SQLite(_) => Err(sqlite_no_routines()),
+        }
+    }
+
+    /// Describe a stored routine including its definition.
+    ///
+    /// Returns every overload with the given name (PostgreSQL allows several).
+    pub async fn describe_routine(
+        pool: &DbPool,
+        routine_name: &str,
+        schema: Option<&str>,
+    ) -> DbResult<Vec<RoutineInfo>> {
+        let routines = match pool {
+            DbPool::Postgres(p) => postgres::describe_routine(p, routine_name, schema).await?,
+            DbPool::MySql(p) => mysql::describe_routine(p, routine_name, schema).await?,
+            DbPool::SQLite(_) => return Err(sqlite_no_routines()),
+        };
+        if routines.is_empty() {
+            return Err(DbError::schema(
+                format!("Routine '{}' not found", routine_name),
+                routine_name.to_string(),
+            ));
+        }
+        Ok(routines)
+    }
+
+    /// List triggers, optionally only those on one table.
+    pub async fn list_triggers(
+        pool: &DbPool,
+        schema: Option<&str>,
+        table_name: Option<&str>,
+    ) -> DbResult<Vec<TriggerInfo>> {
+        match pool {
+            DbPool::Postgres(p) => postgres::list_triggers(p, schema, table_name).await,
+            DbPool::MySql(p) => mysql::list_triggers(p, schema, table_name).await,
+            DbPool::SQLite(p) => sqlite::list_triggers(p, table_name).await,
+        }
+    }
+
+    /// List sequences with their current values.
+    ///
+    /// MySQL and SQLite report per-table auto-increment counters instead.
+    pub async fn list_sequences(
+        pool: &DbPool,
+        schema: Option<&str>,
+    ) -> DbResult<Vec<SequenceInfo>> {
+        match pool {
+            DbPool::Postgres(p) => postgres::list_sequences(p, schema).await,
+            DbPool::MySql(p) => mysql::list_sequences(p, schema).await,
+            DbPool::SQLite(p) => sqlite::list_sequences(p).await,
+        }
+    }
+
+    /// Read planner statistics for a table from the catalog.
+    ///
+    /// Sources: `pg_stats` (PostgreSQL), index cardinality in
+    /// `information_schema.STATISTICS` (MySQL) and `sqlite_stat1` (SQLite).
+    /// Columns without statistics are omitted; nothing is computed.
+    pub async fn table_statistics(
+        pool: &DbPool,
+        table_name: &str,
+        schema: Option<&str>,
+    ) -> DbResult<TableStatistics> {
+        match pool {
+            DbPool::Postgres(p) => postgres::table_statistics(p, table_name, schema).await,
+            DbPool::MySql(p) => mysql::table_statistics(p, table_name, schema).await,
+            DbPool::SQLite(p) => sqlite::table_statistics(p, table_name).await,
+        }
+    }
+
+    /// Get the native DDL the database stores or reports for a table.
+    ///
+    /// SQLite returns the statements recorded in `sqlite_master` (table, indexes
+    /// and triggers), MySQL the output of `SHOW CREATE TABLE`. PostgreSQL has no
+    /// native equivalent and returns `None`; callers synthesize DDL from
+    /// [`SchemaInspector::describe_table`] instead.
+    pub async fn show_create(
+        pool: &DbPool,
+        table_name: &str,
+        schema: Option<&str>,
+    ) -> DbResult<Option<Vec<String>>> {
+        match pool {
+            DbPool::Postgres(_) => Ok(None),
+            DbPool::MySql(p) => mysql::show_create(p, table_name, schema).await.map(Some),
+            DbPool::SQLite(p) => sqlite::show_create(p, table_name).await.map(Some),
+        }
+    }
+
+    /// Schemas that unqualified names resolve through, in order.
+    ///
+    /// Only PostgreSQL has a search path; MySQL and SQLite return an error.
+    pub async fn search_path(pool: &DbPool) -> DbResult<Vec<String>> {
+        match pool {
+            DbPool::Postgres(p) => postgres::search_path(p).await,
+            DbPool::MySql(_) | DbPool::SQLite(_) => Err(DbError::invalid_input(
+                "Only PostgreSQL resolves table names through a schema search path",
+            )),
+        }
+    }
+
     /// List all databases on the server.
     /// Supported for MySQL and PostgreSQL. SQLite returns an error (file-based).
     pub async fn list_databases(pool: &DbPool) -> DbResult<Vec<DatabaseInfoRow>> {
@@ -68,6 +189,12 @@ impl SchemaInspector {
     }
 }
 
+fn sqlite_no_routines() -> DbError {
+    DbError::invalid_input(
+        "SQLite does not support stored functions or procedures. Use list_triggers to inspect trigger logic.",
+    )
+}
+
 // =============================================================================
 // SQL Query Templates
 // =============================================================================
@@ -163,6 +290,7 @@ mod queries {
 
         pub const DESCRIBE_FOREIGN_KEYS: &str = r#"
         SELECT
+            tc.constraint_name,
             kcu.column_name,
             ccu.table_name AS foreign_table_name,
             ccu.column_name AS foreign_column_name,
@@ -199,6 +327,144 @@ mod queries {
         WHERE t.relname = $1 AND n.nspname = $2
         GROUP BY i.relname, ix.indisunique, ix.indisprimary, am.amname
         "#;
+
+        pub const TABLE_COMMENT: &str = r#"
+        SELECT obj_description(c.oid, 'pg_class') as comment
+        FROM pg_class c
+        JOIN pg_namespace n ON n.oid = c.relnamespace
+        WHERE c.relname = $1 AND n.nspname = $2
+        "#;
+
+        pub const ROW_ESTIMATE: &str = r#"
+        SELECT c.reltuples::float8 as reltuples
+        FROM pg_class c
+        JOIN pg_namespace n ON n.oid = c.relnamespace
+        WHERE c.relname = $1 AND n.nspname = $2
+        "#;
+
+        pub const COLUMN_STATS: &str = r#"
+        SELECT
+            s.attname::text as column_name,
+            s.null_frac::float8 as null_frac,
+            s.n_distinct::float8 as n_distinct,
+            s.most_common_vals::text::text[] as most_common_vals,
+            s.most_common_freqs::float8[] as most_common_freqs
+        FROM pg_stats s
+        WHERE s.tablename = $1 AND s.schemaname = $2
+        "#;
+
+        pub const VIEW_DEFINITION: &str = r#"
+        SELECT
+            pg_get_viewdef(c.oid, true) as definition,
+            c.relkind = 'm' as materialized
+        FROM pg_class c
+        JOIN pg_namespace n ON n.oid = c.relnamespace
+        WHERE c.relname = $1 AND n.nspname = $2
+        AND c.relkind IN ('v', 'm')
+        "#;
+
+        // information_schema.columns omits materialized views, so read pg_attribute
+        pub const VIEW_COLUMNS: &str = r#"
+        SELECT
+            a.attname as column_name,
+            format_type(a.atttypid, a.atttypmod) as column_type,
+            NOT a.attnotnull as nullable,
+            col_description(c.oid, a.attnum) as column_comment
+        FROM pg_attribute a
+        JOIN pg_class c ON c.oid = a.attrelid
+        JOIN pg_namespace n ON n.oid = c.relnamespace
+        WHERE c.relname = $1 AND n.nspname = $2
+        AND a.attnum > 0 AND NOT a.attisdropped
+        ORDER BY a.attnum
+        "#;
+
+        pub const LIST_ROUTINES: &str = r#"
+        SELECT
+            p.proname as routine_name,
+            p.prokind::text as routine_kind,
+            pg_get_function_identity_arguments(p.oid) as arguments,
+            pg_get_function_result(p.oid) as return_type,
+            l.lanname as language
+        FROM pg_proc p
+        JOIN pg_namespace n ON n.oid = p.pronamespace
+        JOIN pg_language l ON l.oid = p.prolang
+        WHERE n.nspname = $1
+        AND NOT EXISTS (
+            SELECT 1 FROM pg_depend d WHERE d.objid = p.oid AND d.deptype = 'e'
+        )
+        ORDER BY p.proname, arguments
+        "#;
+
+        pub const DESCRIBE_ROUTINE: &str = r#"
+        SELECT
+            p.proname as routine_name,
+            p.prokind::text as routine_kind,
+            pg_get_function_identity_arguments(p.oid) as arguments,
+            pg_get_function_result(p.oid) as return_type,
+            l.lanname as language,
+            CASE WHEN p.prokind IN ('f', 'p') THEN pg_get_functiondef(p.oid) END as definition
+        FROM pg_proc p
+        JOIN pg_namespace n ON n.oid = p.pronamespace
+        JOIN pg_language l ON l.oid = p.prolang
+        WHERE p.proname = $1 AND n.nspname = $2
+        ORDER BY arguments
+        "#;
+
+        // tgtype bits: 2 = BEFORE, 64 = INSTEAD OF, 4/8/16/32 = INSERT/DELETE/UPDATE/TRUNCATE
+        pub const LIST_TRIGGERS: &str = r#"
+        SELECT
+            t.tgname as trigger_name,
+            c.relname as table_name,
+            CASE
+                WHEN t.tgtype & 2 = 2 THEN 'BEFORE'
+                WHEN t.tgtype & 64 = 64 THEN 'INSTEAD OF'
+                ELSE 'AFTER'
+            END as timing,
+            concat_ws(' OR ',
+                CASE WHEN t.tgtype & 4 = 4 THEN 'INSERT' END,
+                CASE WHEN t.tgtype & 16 = 16 THEN 'UPDATE' END,
+                CASE WHEN t.tgtype & 8 = 8 THEN 'DELETE' END,
+                CASE WHEN t.tgtype & 32 = 32 THEN 'TRUNCATE' END
+            ) as event,
+            pg_get_triggerdef(t.oid, true) as definition,
+            p.prosrc as body
+        FROM pg_trigger t
+        JOIN pg_class c ON c.oid = t.tgrelid
+        JOIN pg_namespace n ON n.oid = c.relnamespace
+        JOIN pg_proc p ON p.oid = t.tgfoid
+        WHERE NOT t.tgisinternal
+        AND n.nspname = $1
+        AND ($2::text IS NULL OR c.relname = $2)
+        ORDER BY c.relname, t.tgname
+        "#;
+
+        pub const LIST_SEQUENCES: &str = r#"
+        SELECT
+            s.sequencename as sequence_name,
+            s.data_type::text as data_type,
+            s.start_value,
+            s.increment_by,
+            s.min_value,
+            s.max_value,
+            s.last_value,
+            seq_owner.table_name,
+            seq_owner.column_name
+        FROM pg_sequences s
+        LEFT JOIN LATERAL (
+            SELECT c.relname as table_name, a.attname as column_name
+            FROM pg_depend d
+            JOIN pg_class c ON c.oid = d.refobjid
+            JOIN pg_attribute a ON a.attrelid = d.refobjid AND a.attnum = d.refobjsubid
+            WHERE d.classid = 'pg_class'::regclass
+            AND d.objid = (quote_ident(s.schemaname) || '.' || quote_ident(s.sequencename))::regclass
+            AND d.deptype IN ('a', 'i')
+            LIMIT 1
+        ) seq_owner ON true
+        WHERE s.schemaname = $1
+        ORDER BY s.sequencename
+        "#;
+
+        pub const SEARCH_PATH: &str = "SELECT unnest(current_schemas(false))::text";
     }
 
     pub mod mysql {
@@ -260,13 +526,20 @@ mod queries {
 
         pub const DESCRIBE_FOREIGN_KEYS: &str = r#"
         SELECT
-            CONVERT(COLUMN_NAME USING utf8) AS COLUMN_NAME,
-            CONVERT(REFERENCED_TABLE_NAME USING utf8) AS REFERENCED_TABLE_NAME,
-            CONVERT(REFERENCED_COLUMN_NAME USING utf8) AS REFERENCED_COLUMN_NAME
-        FROM information_schema.KEY_COLUMN_USAGE
-        WHERE TABLE_NAME = ?
-        AND TABLE_SCHEMA = COALESCE(?, DATABASE())
-        AND REFERENCED_TABLE_NAME IS NOT NULL
+            CONVERT(kcu.CONSTRAINT_NAME USING utf8) AS CONSTRAINT_NAME,
+            CONVERT(kcu.COLUMN_NAME USING utf8) AS COLUMN_NAME,
+            CONVERT(kcu.REFERENCED_TABLE_NAME USING utf8) AS REFERENCED_TABLE_NAME,
+            CONVERT(kcu.REFERENCED_COLUMN_NAME USING utf8) AS REFERENCED_COLUMN_NAME,
+            CONVERT(rc.DELETE_RULE USING utf8) AS DELETE_RULE,
+            CONVERT(rc.UPDATE_RULE USING utf8) AS UPDATE_RULE
+        FROM information_schema.KEY_COLUMN_USAGE kcu
+        LEFT JOIN information_schema.REFERENTIAL_CONSTRAINTS rc
+            ON rc.CONSTRAINT_SCHEMA = kcu.TABLE_SCHEMA
+            AND rc.CONSTRAINT_NAME = kcu.CONSTRAINT_NAME
+        WHERE kcu.TABLE_NAME = ?
+        AND kcu.TABLE_SCHEMA = COALESCE(?, DATABASE())
+        AND kcu.REFERENCED_TABLE_NAME IS NOT NULL
+        ORDER BY kcu.CONSTRAINT_NAME, kcu.ORDINAL_POSITION
         "#;
 
         pub const DESCRIBE_INDEXES: &str = r#"
@@ -279,6 +552,114 @@ mod queries {
         WHERE TABLE_NAME = ? AND TABLE_SCHEMA = COALESCE(?, DATABASE())
         GROUP BY INDEX_NAME, NON_UNIQUE, INDEX_TYPE
         "#;
+
+        pub const TABLE_COMMENT: &str = r#"
+        SELECT CONVERT(TABLE_COMMENT USING utf8) AS TABLE_COMMENT
+        FROM information_schema.TABLES
+        WHERE TABLE_NAME = ? AND TABLE_SCHEMA = COALESCE(?, DATABASE())
+        "#;
+
+        pub const ROW_ESTIMATE: &str = r#"
+        SELECT TABLE_ROWS AS ROW_COUNT
+        FROM information_schema.TABLES
+        WHERE TABLE_NAME = ? AND TABLE_SCHEMA = COALESCE(?, DATABASE())
+        "#;
+
+        // Cardinality of single-column indexes estimates the column's distinct count
+        pub const COLUMN_CARDINALITY: &str = r#"
+        SELECT
+            CONVERT(s.COLUMN_NAME USING utf8) AS COLUMN_NAME,
+            MAX(s.CARDINALITY) AS CARDINALITY
+        FROM information_schema.STATISTICS s
+        WHERE s.TABLE_NAME = ? AND s.TABLE_SCHEMA = COALESCE(?, DATABASE())
+        AND s.SEQ_IN_INDEX = 1
+        AND s.CARDINALITY IS NOT NULL
+        AND NOT EXISTS (
+            SELECT 1 FROM information_schema.STATISTICS s2
+            WHERE s2.TABLE_SCHEMA = s.TABLE_SCHEMA
+            AND s2.TABLE_NAME = s.TABLE_NAME
+            AND s2.INDEX_NAME = s.INDEX_NAME
+            AND s2.SEQ_IN_INDEX = 2
+        )
+        GROUP BY s.COLUMN_NAME
+        "#;
+
+        pub const VIEW_DEFINITION: &str = r#"
+        SELECT CONVERT(VIEW_DEFINITION USING utf8) AS VIEW_DEFINITION
+        FROM information_schema.VIEWS
+        WHERE TABLE_NAME = ? AND TABLE_SCHEMA = COALESCE(?, DATABASE())
+        "#;
+
+        pub const LIST_ROUTINES: &str = r#"
+        SELECT
+            CONVERT(r.ROUTINE_NAME USING utf8) AS ROUTINE_NAME,
+            CONVERT(r.ROUTINE_TYPE USING utf8) AS ROUTINE_TYPE,
+            CONVERT(r.DTD_IDENTIFIER USING utf8) AS RETURN_TYPE,
+            CONVERT(r.ROUTINE_BODY USING utf8) AS LANGUAGE,
+            CONVERT((
+                SELECT GROUP_CONCAT(
+                    CONCAT_WS(' ', p.PARAMETER_MODE, p.PARAMETER_NAME, p.DTD_IDENTIFIER)
+                    ORDER BY p.ORDINAL_POSITION SEPARATOR ', ')
+                FROM information_schema.PARAMETERS p
+                WHERE p.SPECIFIC_SCHEMA = r.ROUTINE_SCHEMA
+                AND p.SPECIFIC_NAME = r.SPECIFIC_NAME
+                AND p.ORDINAL_POSITION > 0
+            ) USING utf8) AS ARGUMENTS
+        FROM information_schema.ROUTINES r
+        WHERE r.ROUTINE_SCHEMA = COALESCE(?, DATABASE())
+        ORDER BY r.ROUTINE_NAME
+        "#;
+
+        pub const DESCRIBE_ROUTINE: &str = r#"
+        SELECT
+            CONVERT(r.ROUTINE_NAME USING utf8) AS ROUTINE_NAME,
+            CONVERT(r.ROUTINE_TYPE USING utf8) AS ROUTINE_TYPE,
+            CONVERT(r.DTD_IDENTIFIER USING utf8) AS RETURN_TYPE,
+            CONVERT(r.ROUTINE_BODY USING utf8) AS LANGUAGE,
+            CONVERT((
+                SELECT GROUP_CONCAT(
+                    CONCAT_WS(' ', p.PARAMETER_MODE, p.PARAMETER_NAME, p.DTD_IDENTIFIER)
+                    ORDER BY p.ORDINAL_POSITION SEPARATOR ', ')
+                FROM information_schema.PARAMETERS p
+                WHERE p.SPECIFIC_SCHEMA = r.ROUTINE_SCHEMA
+                AND p.SPECIFIC_NAME = r.SPECIFIC_NAME
+                AND p.ORDINAL_POSITION > 0
+            ) USING utf8) AS ARGUMENTS,
+            CONVERT(r.ROUTINE_DEFINITION USING utf8) AS ROUTINE_DEFINITION
+        FROM information_schema.ROUTINES r
+        WHERE r.ROUTINE_NAME = ? AND r.ROUTINE_SCHEMA = COALESCE(?, DATABASE())
+        "#;
+
+        pub const LIST_TRIGGERS: &str = r#"
+        SELECT
+            CONVERT(TRIGGER_NAME USING utf8) AS TRIGGER_NAME,
+            CONVERT(EVENT_OBJECT_TABLE USING utf8) AS TABLE_NAME,
+            CONVERT(ACTION_TIMING USING utf8) AS ACTION_TIMING,
+            CONVERT(EVENT_MANIPULATION USING utf8) AS EVENT_MANIPULATION,
+            CONVERT(ACTION_STATEMENT USING utf8) AS ACTION_STATEMENT
+        FROM information_schema.TRIGGERS
+        WHERE TRIGGER_SCHEMA = COALESCE(?, DATABASE())
+        AND (? IS NULL OR EVENT_OBJECT_TABLE = ?)
+        ORDER BY EVENT_OBJECT_TABLE, ACTION_ORDER
+        "#;
+
+        // AUTO_INCREMENT is the next value; MySQL 8 may cache it
+        // (information_schema_stats_expiry)
+        pub const LIST_SEQUENCES: &str = r#"
+        SELECT
+            CONVERT(t.TABLE_NAME USING utf8) AS TABLE_NAME,
+            CONVERT(c.COLUMN_NAME USING utf8) AS COLUMN_NAME,
+            CONVERT(c.COLUMN_TYPE USING utf8) AS COLUMN_TYPE,
+            t.AUTO_INCREMENT AS NEXT_VALUE
+        FROM information_schema.TABLES t
+        LEFT JOIN information_schema.COLUMNS c
+            ON c.TABLE_SCHEMA = t.TABLE_SCHEMA
+            AND c.TABLE_NAME = t.TABLE_NAME
+            AND c.EXTRA LIKE '%auto_increment%'
+        WHERE t.TABLE_SCHEMA = COALESCE(?, DATABASE())
+        AND t.AUTO_INCREMENT IS NOT NULL
+        ORDER BY t.TABLE_NAME
+        "#;
     }
 
     pub mod sqlite {
@@ -297,6 +678,35 @@ mod queries {
             "#;
 
         pub const TABLE_SIZE: &str = "SELECT SUM(pgsize) as size_bytes FROM dbstat WHERE name = ?";
+
+        pub const SHOW_CREATE: &str = r#"
+            SELECT sql FROM sqlite_master
+            WHERE tbl_name = ? COLLATE NOCASE
+            AND sql IS NOT NULL
+            ORDER BY CASE type WHEN 'table' THEN 0 WHEN 'view' THEN 0 WHEN 'index' THEN 1 ELSE 2 END, name
+            "#;
+
+        pub const VIEW_DEFINITION: &str = r#"
+            SELECT sql FROM sqlite_master
+            WHERE type = 'view' AND name = ? COLLATE NOCASE
+            "#;
+
+        pub const LIST_TRIGGERS: &str = r#"
+            SELECT name, tbl_name, sql FROM sqlite_master
+            WHERE type = 'trigger'
+            AND (?1 IS NULL OR tbl_name = ?1 COLLATE NOCASE)
+            ORDER BY tbl_name, name
+            "#;
+
+        pub const HAS_SEQUENCES: &str =
+            "SELECT COUNT(*) FROM sqlite_master WHERE type = 'table' AND name = 'sqlite_sequence'";
+
+        pub const LIST_SEQUENCES: &str = "SELECT name, seq FROM sqlite_sequence ORDER BY name";
+
+        pub const HAS_STAT1: &str =
+            "SELECT COUNT(*) FROM sqlite_master WHERE type = 'table' AND name = 'sqlite_stat1'";
+
+        pub const STAT1: &str = "SELECT idx, stat FROM sqlite_stat1 WHERE tbl = ? COLLATE NOCASE";
     }
 }
 
@@ -400,6 +810,13 @@ mod postgres {
         Ok(databases)
     }
 
+    pub async fn search_path(pool: &PgPool) -> DbResult<Vec<String>> {
+        let schemas = sqlx::query_scalar::<_, String>(queries::postgres::SEARCH_PATH)
+            .fetch_all(pool)
+            .await?;
+        Ok(schemas)
+    }
+
     pub async fn describe_table(
         pool: &PgPool,
         table_name: &str,
@@ -423,6 +840,7 @@ mod postgres {
 
         let foreign_keys = fetch_foreign_keys(pool, table_name, schema_name).await?;
         let indexes = fetch_indexes(pool, table_name, schema_name).await;
+        let comment = fetch_table_comment(pool, table_name, schema_name).await;
 
         Ok(TableSchema {
             table_name: table_name.to_string(),
@@ -431,9 +849,26 @@ mod postgres {
             primary_key,
             foreign_keys,
             indexes,
+            comment,
         })
     }
 
+    async fn fetch_table_comment(
+        pool: &PgPool,
+        table_name: &str,
+        schema_name: &str,
+    ) -> Option<String> {
+        sqlx::query(queries::postgres::TABLE_COMMENT)
+            .bind(table_name)
+            .bind(schema_name)
+            .fetch_optional(pool)
+            .await
+            .ok()
+            .flatten()
+            .and_then(|row| row.try_get::<Option<String>, _>("comment").ok().flatten())
+            .filter(|c| !c.is_empty())
+    }
+
     async fn fetch_columns(
         pool: &PgPool,
         table_name: &str,
@@ -491,6 +926,7 @@ mod postgres {
         Ok(rows
             .iter()
             .map(|row| {
+                let name: String = row.get("constraint_name");
                 let column: String = row.get("column_name");
                 let ref_table: String = row.get("foreign_table_name");
                 let ref_column: String = row.get("foreign_column_name");
@@ -498,6 +934,7 @@ mod postgres {
                 let update_rule: String = row.get("update_rule");
 
                 ForeignKey::new(column, ref_table, ref_column)
+                    .with_name(name)
                     .with_on_delete(ForeignKeyAction::parse(&delete_rule))
                     .with_on_update(ForeignKeyAction::parse(&update_rule))
             })
@@ -537,6 +974,243 @@ mod postgres {
             })
             .collect()
     }
+
+    pub async fn describe_view(
+        pool: &PgPool,
+        view_name: &str,
+        schema: Option<&str>,
+    ) -> DbResult<ViewSchema> {
+        let schema_name = schema.unwrap_or("public");
+
+        let row = sqlx::query(queries::postgres::VIEW_DEFINITION)
+            .bind(view_name)
+            .bind(schema_name)
+            .fetch_optional(pool)
+            .await?
+            .ok_or_else(|| {
+                DbError::schema(
+                    format!("View '{}' not found", view_name),
+                    view_name.to_string(),
+                )
+            })?;
+        let definition: Option<String> = row.try_get("definition").ok().flatten();
+        let materialized: bool = row.get("materialized");
+
+        let rows = sqlx::query(queries::postgres::VIEW_COLUMNS)
+            .bind(view_name)
+            .bind(schema_name)
+            .fetch_all(pool)
+            .await?;
+
+        let mut view = ViewSchema::new(view_name)
+            .with_schema(schema_name)
+            .with_materialized(materialized);
+        if let Some(def) = definition {
+            view = view.with_definition(def.trim());
+        }
+        view.columns = rows
+            .iter()
+            .map(|row| {
+                let name: String = row.get("column_name");
+                let column_type: String = row.get("column_type");
+                let nullable: bool = row.get("nullable");
+                let comment: Option<String> = row.get("column_comment");
+
+                let mut col = ColumnDefinition::new(&name, &column_type, nullable);
+                if let Some(c) = comment.filter(|c| !c.is_empty()) {
+                    col = col.with_comment(c);
+                }
+                col
+            })
+            .collect();
+
+        Ok(view)
+    }
+
+    fn routine_from_row(row: &sqlx::postgres::PgRow, schema_name: &str) -> RoutineInfo {
+        let name: String = row.get("routine_name");
+        let kind: String = row.get("routine_kind");
+        let arguments: Option<String> = row.try_get("arguments").ok().flatten();
+        let return_type: Option<String> = row.try_get("return_type").ok().flatten();
+        let language: String = row.get("language");
+
+        let mut routine = RoutineInfo::new(name, RoutineType::parse(&kind))
+            .with_schema(schema_name)
+            .with_language(language);
+        if let Some(args) = arguments {
+            routine = routine.with_arguments(args);
+        }
+        if let Some(ret) = return_type {
+            routine = routine.with_return_type(ret);
+        }
+        routine
+    }
+
+    pub async fn list_routines(pool: &PgPool, schema: Option<&str>) -> DbResult<Vec<RoutineInfo>> {
+        let schema_name = schema.unwrap_or("public");
+        let rows = sqlx::query(queries::postgres::LIST_ROUTINES)
+            .bind(schema_name)
+            .fetch_all(pool)
+            .await?;
+
+        let routines = rows
+            .iter()
+            .map(|row| routine_from_row(row, schema_name))
+            .collect::<Vec<_>>();
+
+        debug!(count = routines.len(), "Listed PostgreSQL routines");
+        Ok(routines)
+    }
+
+    pub async fn describe_routine(
+        pool: &PgPool,
+        routine_name: &str,
+        schema: Option<&str>,
+    ) -> DbResult<Vec<RoutineInfo>> {
+        let schema_name = schema.unwrap_or("public");
+        let rows = sqlx::query(queries::postgres::DESCRIBE_ROUTINE)
+            .bind(routine_name)
+            .bind(schema_name)
+            .fetch_all(pool)
+            .await?;
+
+        Ok(rows
+            .iter()
+            .map(|row| {
+                let routine = routine_from_row(row, schema_name);
+                match row
+                    .try_get::<Option<String>, _>("definition")
+                    .ok()
+                    .flatten()
+                {
+                    Some(def) => routine.with_definition(def),
+                    None => routine,
+                }
+            })
+            .collect())
+    }
+
+    pub async fn list_triggers(
+        pool: &PgPool,
+        schema: Option<&str>,
+        table_name: Option<&str>,
+    ) -> DbResult<Vec<TriggerInfo>> {
+        let schema_name = schema.unwrap_or("public");
+        let rows = sqlx::query(queries::postgres::LIST_TRIGGERS)
+            .bind(schema_name)
+            .bind(table_name)
+            .fetch_all(pool)
+            .await?;
+
+        let triggers = rows
+            .iter()
+            .map(|row| {
+                let name: String = row.get("trigger_name");
+                let table: String = row.get("table_name");
+                let timing: String = row.get("timing");
+                let event: String = row.get("event");
+                let definition: String = row.get("definition");
+                let body: Option<String> = row.try_get("body").ok().flatten();
+
+                let trigger =
+                    TriggerInfo::new(name, table, timing, event).with_definition(definition);
+                match body {
+                    Some(body) => trigger.with_body(body.trim()),
+                    None => trigger,
+                }
+            })
+            .collect::<Vec<_>>();
+
+        debug!(count = triggers.len(), "Listed PostgreSQL triggers");
+        Ok(triggers)
+    }
+
+    pub async fn list_sequences(
+        pool: &PgPool,
+        schema: Option<&str>,
+    ) -> DbResult<Vec<SequenceInfo>> {
+        let schema_name = schema.unwrap_or("public");
+        let rows = sqlx::query(queries::postgres::LIST_SEQUENCES)
+            .bind(schema_name)
+            .fetch_all(pool)
+            .await?;
+
+        let sequences = rows
+            .iter()
+            .map(|row| {
+                let name: String = row.get("sequence_name");
+                let table: Option<String> = row.get("table_name");
+                let column: Option<String> = row.get("column_name");
+
+                let mut seq = SequenceInfo::new(name)
+                    .with_current_value(row.try_get("last_value").ok().flatten());
+                if let Some(table) = table {
+                    seq = seq.with_owner(table, column);
+                }
+                seq.data_type = row.try_get("data_type").ok();
+                seq.start_value = row.try_get("start_value").ok();
+                seq.increment = row.try_get("increment_by").ok();
+                seq.min_value = row.try_get("min_value").ok();
+                seq.max_value = row.try_get("max_value").ok();
+                seq
+            })
+            .collect::<Vec<_>>();
+
+        debug!(count = sequences.len(), "Listed PostgreSQL sequences");
+        Ok(sequences)
+    }
+
+    pub async fn table_statistics(
+        pool: &PgPool,
+        table_name: &str,
+        schema: Option<&str>,
+    ) -> DbResult<TableStatistics> {
+        let schema_name = schema.unwrap_or("public");
+
+        // reltuples is -1 (PostgreSQL 14+) or 0 until the table is analyzed
+        let reltuples: Option<f64> = sqlx::query_scalar(queries::postgres::ROW_ESTIMATE)
+            .bind(table_name)
+            .bind(schema_name)
+            .fetch_optional(pool)
+            .await?;
+        let row_count_estimate = reltuples.filter(|r| *r > 0.0).map(|r| r as u64);
+
+        let rows = sqlx::query(queries::postgres::COLUMN_STATS)
+            .bind(table_name)
+            .bind(schema_name)
+            .fetch_all(pool)
+            .await?;
+
+        let columns = rows
+            .iter()
+            .map(|row| {
+                let mut stats = ColumnStatistics::new(row.get::<String, _>("column_name"));
+                stats.null_fraction = row.try_get("null_frac").ok();
+
+                // Negative n_distinct is a fraction of the row count
+                let n_distinct: f64 = row.try_get("n_distinct").unwrap_or(0.0);
+                stats.distinct_count = if n_distinct > 0.0 {
+                    Some(n_distinct as u64)
+                } else if n_distinct < 0.0 {
+                    row_count_estimate.map(|rows| (-n_distinct * rows as f64).round() as u64)
+                } else {
+                    None
+                };
+
+                let values: Option<Vec<String>> = row.try_get("most_common_vals").ok().flatten();
+                let freqs: Option<Vec<f64>> = row.try_get("most_common_freqs").ok().flatten();
+                if let (Some(values), Some(freqs)) = (values, freqs) {
+                    stats.most_common_values = values.into_iter().zip(freqs).collect();
+                }
+                stats
+            })
+            .collect();
+
+        Ok(TableStatistics {
+            row_count_estimate,
+            columns,
+        })
+    }
 }
 
 mod mysql {
@@ -727,6 +1401,7 @@ mod mysql {
 
         let foreign_keys = fetch_foreign_keys(pool, table_name, schema).await?;
         let indexes = fetch_indexes(pool, table_name, schema).await;
+        let comment = fetch_table_comment(pool, table_name, schema).await;
 
         Ok(TableSchema {
             table_name: table_name.to_string(),
@@ -735,9 +1410,50 @@ mod mysql {
             primary_key,
             foreign_keys,
             indexes,
+            comment,
         })
     }
 
+    async fn fetch_table_comment(
+        pool: &MySqlPool,
+        table_name: &str,
+        schema: Option<&str>,
+    ) -> Option<String> {
+        sqlx::query(queries::mysql::TABLE_COMMENT)
+            .bind(table_name)
+            .bind(schema)
+            .fetch_optional(pool)
+            .await
+            .ok()
+            .flatten()
+            .and_then(|row| get_optional_string(&row, "TABLE_COMMENT"))
+            .filter(|c| !c.is_empty())
+    }
+
+    pub async fn show_create(
+        pool: &MySqlPool,
+        table_name: &str,
+        schema: Option<&str>,
+    ) -> DbResult<Vec<String>> {
+        let quote = |s: &str| DatabaseType::MySQL.quote_identifier(s);
+        let qualified = match schema {
+            Some(schema) => format!("{}.{}", quote(schema), quote(table_name)),
+            None => quote(table_name),
+        };
+        let row = sqlx::query(&format!("SHOW CREATE TABLE {}", qualified))
+            .fetch_one(pool)
+            .await?;
+
+        // Columns are (Table, Create Table) or (View, Create View, ...)
+        let ddl = get_string_by_index(&row, 1).ok_or_else(|| {
+            DbError::schema(
+                format!("SHOW CREATE TABLE returned no DDL for '{}'", table_name),
+                table_name.to_string(),
+            )
+        })?;
+        Ok(vec![ddl])
+    }
+
     async fn fetch_columns(
         pool: &MySqlPool,
         table_name: &str,
@@ -808,10 +1524,16 @@ mod mysql {
         Ok(rows
             .iter()
             .map(|row| {
+                let name = get_string(row, "CONSTRAINT_NAME");
                 let column = get_string(row, "COLUMN_NAME");
                 let ref_table = get_string(row, "REFERENCED_TABLE_NAME");
                 let ref_column = get_string(row, "REFERENCED_COLUMN_NAME");
+                let delete_rule = get_optional_string(row, "DELETE_RULE").unwrap_or_default();
+                let update_rule = get_optional_string(row, "UPDATE_RULE").unwrap_or_default();
                 ForeignKey::new(column, ref_table, ref_column)
+                    .with_name(name)
+                    .with_on_delete(ForeignKeyAction::parse(&delete_rule))
+                    .with_on_update(ForeignKeyAction::parse(&update_rule))
             })
             .collect())
     }
@@ -850,6 +1572,193 @@ mod mysql {
             })
             .collect()
     }
+
+    pub async fn describe_view(
+        pool: &MySqlPool,
+        view_name: &str,
+        schema: Option<&str>,
+    ) -> DbResult<ViewSchema> {
+        let row = sqlx::query(queries::mysql::VIEW_DEFINITION)
+            .bind(view_name)
+            .bind(schema)
+            .fetch_optional(pool)
+            .await?
+            .ok_or_else(|| {
+                DbError::schema(
+                    format!("View '{}' not found", view_name),
+                    view_name.to_string(),
+                )
+            })?;
+
+        let mut view = ViewSchema::new(view_name);
+        if let Some(s) = schema {
+            view = view.with_schema(s);
+        }
+        if let Some(def) = get_optional_string(&row, "VIEW_DEFINITION") {
+            view = view.with_definition(def);
+        }
+        view.columns = fetch_columns(pool, view_name, schema).await?;
+        Ok(view)
+    }
+
+    fn routine_from_row(row: &sqlx::mysql::MySqlRow, schema: Option<&str>) -> RoutineInfo {
+        let name = get_string(row, "ROUTINE_NAME");
+        let routine_type = get_string(row, "ROUTINE_TYPE");
+
+        let mut routine = RoutineInfo::new(name, RoutineType::parse(&routine_type));
+        if let Some(s) = schema {
+            routine = routine.with_schema(s);
+        }
+        if let Some(args) = get_optional_string(row, "ARGUMENTS") {
+            routine = routine.with_arguments(args);
+        }
+        if let Some(ret) = get_optional_string(row, "RETURN_TYPE") {
+            routine = routine.with_return_type(ret);
+        }
+        if let Some(lang) = get_optional_string(row, "LANGUAGE") {
+            routine = routine.with_language(lang);
+        }
+        routine
+    }
+
+    pub async fn list_routines(
+        pool: &MySqlPool,
+        schema: Option<&str>,
+    ) -> DbResult<Vec<RoutineInfo>> {
+        let rows = sqlx::query(queries::mysql::LIST_ROUTINES)
+            .bind(schema)
+            .fetch_all(pool)
+            .await?;
+
+        let routines = rows
+            .iter()
+            .map(|row| routine_from_row(row, schema))
+            .collect::<Vec<_>>();
+
+        debug!(count = routines.len(), "Listed MySQL routines");
+        Ok(routines)
+    }
+
+    pub async fn describe_routine(
+        pool: &MySqlPool,
+        routine_name: &str,
+        schema: Option<&str>,
+    ) -> DbResult<Vec<RoutineInfo>> {
+        let rows = sqlx::query(queries::mysql::DESCRIBE_ROUTINE)
+            .bind(routine_name)
+            .bind(schema)
+            .fetch_all(pool)
+            .await?;
+
+        Ok(rows
+            .iter()
+            .map(|row| {
+                let routine = routine_from_row(row, schema);
+                match get_optional_string(row, "ROUTINE_DEFINITION") {
+                    Some(def) => routine.with_definition(def),
+                    None => routine,
+                }
+            })
+            .collect())
+    }
+
+    pub async fn list_triggers(
+        pool: &MySqlPool,
+        schema: Option<&str>,
+        table_name: Option<&str>,
+    ) -> DbResult<Vec<TriggerInfo>> {
+        let rows = sqlx::query(queries::mysql::LIST_TRIGGERS)
+            .bind(schema)
+            .bind(table_name)
+            .bind(table_name)
+            .fetch_all(pool)
+            .await?;
+
+        let triggers = rows
+            .iter()
+            .map(|row| {
+                let trigger = TriggerInfo::new(
+                    get_string(row, "TRIGGER_NAME"),
+                    get_string(row, "TABLE_NAME"),
+                    get_string(row, "ACTION_TIMING"),
+                    get_string(row, "EVENT_MANIPULATION"),
+                );
+                match get_optional_string(row, "ACTION_STATEMENT") {
+                    Some(body) => trigger.with_body(body),
+                    None => trigger,
+                }
+            })
+            .collect::<Vec<_>>();
+
+        debug!(count = triggers.len(), "Listed MySQL triggers");
+        Ok(triggers)
+    }
+
+    pub async fn list_sequences(
+        pool: &MySqlPool,
+        schema: Option<&str>,
+    ) -> DbResult<Vec<SequenceInfo>> {
+        let rows = sqlx::query(queries::mysql::LIST_SEQUENCES)
+            .bind(schema)
+            .fetch_all(pool)
+            .await?;
+
+        let sequences = rows
+            .iter()
+            .map(|row| {
+                let table = get_string(row, "TABLE_NAME");
+                // AUTO_INCREMENT holds the next value to be assigned
+                let current = try_get_u64(row, "NEXT_VALUE")
+                    .filter(|next| *next > 1)
+                    .map(|next| (next - 1) as i64);
+
+                let mut seq = SequenceInfo::new(&table)
+                    .with_owner(&table, get_optional_string(row, "COLUMN_NAME"))
+                    .with_current_value(current);
+                seq.data_type = get_optional_string(row, "COLUMN_TYPE");
+                seq
+            })
+            .collect::<Vec<_>>();
+
+        debug!(
+            count = sequences.len(),
+            "Listed MySQL auto-increment counters"
+        );
+        Ok(sequences)
+    }
+
+    pub async fn table_statistics(
+        pool: &MySqlPool,
+        table_name: &str,
+        schema: Option<&str>,
+    ) -> DbResult<TableStatistics> {
+        let row_count_estimate = sqlx::query(queries::mysql::ROW_ESTIMATE)
+            .bind(table_name)
+            .bind(schema)
+            .fetch_optional(pool)
+            .await?
+            .and_then(|row| try_get_u64(&row, "ROW_COUNT"));
+
+        let rows = sqlx::query(queries::mysql::COLUMN_CARDINALITY)
+            .bind(table_name)
+            .bind(schema)
+            .fetch_all(pool)
+            .await?;
+
+        let columns = rows
+            .iter()
+            .map(|row| {
+                let mut stats = ColumnStatistics::new(get_string(row, "COLUMN_NAME"));
+                stats.distinct_count = try_get_u64(row, "CARDINALITY");
+                stats
+            })
+            .collect();
+
+        Ok(TableStatistics {
+            row_count_estimate,
+            columns,
+        })
+    }
 }
 
 mod sqlite {
@@ -919,9 +1828,25 @@ mod sqlite {
             primary_key,
             foreign_keys,
             indexes,
+            comment: None,
         })
     }
 
+    pub async fn show_create(pool: &SqlitePool, table_name: &str) -> DbResult<Vec<String>> {
+        let statements: Vec<String> = sqlx::query_scalar(queries::sqlite::SHOW_CREATE)
+            .bind(table_name)
+            .fetch_all(pool)
+            .await?;
+
+        if statements.is_empty() {
+            return Err(DbError::schema(
+                format!("Table '{}' not found", table_name),
+                table_name.to_string(),
+            ));
+        }
+        Ok(statements)
+    }
+
     async fn fetch_columns(pool: &SqlitePool, table_name: &str) -> DbResult<Vec<ColumnDefinition>> {
         let pragma_query = format!("PRAGMA table_info('{}')", table_name);
         let rows = sqlx::query(&pragma_query).fetch_all(pool).await?;
@@ -1005,12 +1930,209 @@ mod sqlite {
             .map(|row| row.get("name"))
             .collect()
     }
+
+    pub async fn describe_view(pool: &SqlitePool, view_name: &str) -> DbResult<ViewSchema> {
+        let definition: Option<String> = sqlx::query_scalar(queries::sqlite::VIEW_DEFINITION)
+            .bind(view_name)
+            .fetch_optional(pool)
+            .await?
+            .ok_or_else(|| {
+                DbError::schema(
+                    format!("View '{}' not found", view_name),
+                    view_name.to_string(),
+                )
+            })?;
+
+        let mut view = ViewSchema::new(view_name);
+        if let Some(def) = definition {
+            view = view.with_definition(def);
+        }
+        view.columns = fetch_columns(pool, view_name).await?;
+        Ok(view)
+    }
+
+    pub async fn list_triggers(
+        pool: &SqlitePool,
+        table_name: Option<&str>,
+    ) -> DbResult<Vec<TriggerInfo>> {
+        let rows = sqlx::query(queries::sqlite::LIST_TRIGGERS)
+            .bind(table_name)
+            .fetch_all(pool)
+            .await?;
+
+        let triggers = rows
+            .iter()
+            .map(|row| {
+                let name: String = row.get("name");
+                let table: String = row.get("tbl_name");
+                let sql: String = row.try_get("sql").unwrap_or_default();
+                let (timing, event, body) = parse_trigger(&sql);
+
+                let trigger = TriggerInfo::new(name, table, timing, event).with_definition(&sql);
+                match body {
+                    Some(body) => trigger.with_body(body),
+                    None => trigger,
+                }
+            })
+            .collect::<Vec<_>>();
+
+        debug!(count = triggers.len(), "Listed SQLite triggers");
+        Ok(triggers)
+    }
+
+    /// Extract timing, event and body from a `CREATE TRIGGER` statement.
+    ///
+    /// SQLite only stores the statement text; timing defaults to BEFORE when
+    /// omitted.
+    pub fn parse_trigger(sql: &str) -> (&'static str, String, Option<String>) {
+        // ASCII uppercasing keeps byte offsets aligned with `sql`
+        let upper = sql.to_ascii_uppercase();
+        let begin = find_keyword(&upper, "BEGIN");
+        let header = &upper[..begin.unwrap_or(upper.len())];
+        let words: Vec<&str> = header
+            .split(|c: char| c.is_whitespace() || c == '(' || c == ')' || c == ',')
+            .filter(|w| !w.is_empty())
+            .collect();
+
+        let timing = if words.windows(2).any(|w| w == ["INSTEAD", "OF"]) {
+            "INSTEAD OF"
+        } else if words.contains(&"AFTER") {
+            "AFTER"
+        } else {
+            "BEFORE"
+        };
+        let event = words
+            .iter()
+            .find(|w| matches!(**w, "INSERT" | "UPDATE" | "DELETE"))
+            .map(|w| w.to_string())
+            .unwrap_or_default();
+
+        let body = begin.and_then(|start| {
+            let start = start + "BEGIN".len();
+            let end = upper.rfind("END").filter(|end| *end >= start)?;
+            Some(sql[start..end].trim().to_string())
+        });
+
+        (timing, event, body)
+    }
+
+    /// Find the first occurrence of `keyword` as a whole word.
+    fn find_keyword(haystack: &str, keyword: &str) -> Option<usize> {
+        let is_ident = |c: char| c.is_ascii_alphanumeric() || c == '_';
+        haystack.match_indices(keyword).map(|(i, _)| i).find(|&i| {
+            let before = haystack[..i].chars().next_back();
+            let after = haystack[i + keyword.len()..].chars().next();
+            !before.is_some_and(is_ident) && !after.is_some_and(is_ident)
+        })
+    }
+
+    pub async fn list_sequences(pool: &SqlitePool) -> DbResult<Vec<SequenceInfo>> {
+        // sqlite_sequence only exists once an AUTOINCREMENT table has been created
+        let exists: i64 = sqlx::query_scalar(queries::sqlite::HAS_SEQUENCES)
+            .fetch_one(pool)
+            .await?;
+        if exists == 0 {
+            return Ok(Vec::new());
+        }
+
+        let rows = sqlx::query(queries::sqlite::LIST_SEQUENCES)
+            .fetch_all(pool)
+            .await?;
+
+        let mut sequences = Vec::with_capacity(rows.len());
+        for row in &rows {
+            let table: String = row.get("name");
+            let seq: Option<i64> = row.try_get("seq").ok();
+
+            let pk_column = fetch_columns(pool, &table)
+                .await
+                .unwrap_or_default()
+                .into_iter()
+                .find(|c| c.is_primary_key);
+            let mut info = SequenceInfo::new(&table)
+                .with_owner(&table, pk_column.as_ref().map(|c| c.name.clone()))
+                .with_current_value(seq.filter(|v| *v > 0));
+            info.data_type = pk_column.map(|c| c.data_type);
+            sequences.push(info);
+        }
+
+        debug!(
+            count = sequences.len(),
+            "Listed SQLite auto-increment counters"
+        );
+        Ok(sequences)
+    }
+
+    pub async fn table_statistics(
+        pool: &SqlitePool,
+        table_name: &str,
+    ) -> DbResult<TableStatistics> {
+        // sqlite_stat1 only exists after ANALYZE
+        let exists: i64 = sqlx::query_scalar(queries::sqlite::HAS_STAT1)
+            .fetch_one(pool)
+            .await?;
+        if exists == 0 {
+            return Ok(TableStatistics::default());
+        }
+
+        let rows = sqlx::query(queries::sqlite::STAT1)
+            .bind(table_name)
+            .fetch_all(pool)
+            .await?;
+        let indexes = fetch_indexes(pool, table_name).await;
+
+        let mut stats = TableStatistics::default();
+        for row in &rows {
+            let idx: Option<String> = row.try_get("idx").ok().flatten();
+            let stat: String = row.try_get("stat").unwrap_or_default();
+            // "<rows> <avg rows per distinct prefix of 1 column> <... 2 columns> ..."
+            let mut numbers = stat.split_whitespace().map(|n| n.parse::<u64>().ok());
+            let Some(Some(nrows)) = numbers.next() else {
+                continue;
+            };
+            stats.row_count_estimate = Some(nrows);
+
+            let first_column = idx
+                .as_deref()
+                .and_then(|idx| indexes.iter().find(|i| i.name == idx))
+                .and_then(|i| i.columns.first());
+            if let (Some(column), Some(Some(per_key))) = (first_column, numbers.next()) {
+                if per_key > 0 && stats.column(column).is_none() {
+                    let mut col = ColumnStatistics::new(column);
+                    col.distinct_count = Some(nrows.div_ceil(per_key));
+                    stats.columns.push(col);
+                }
+            }
+        }
+        Ok(stats)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_parse_sqlite_trigger() {
+        let sql = "CREATE TRIGGER audit_users AFTER UPDATE OF email ON users\n\
+                   BEGIN\n  INSERT INTO audit VALUES (old.id);\nEND";
+        let (timing, event, body) = sqlite::parse_trigger(sql);
+        assert_eq!(timing, "AFTER");
+        assert_eq!(event, "UPDATE");
+        assert_eq!(body.as_deref(), Some("INSERT INTO audit VALUES (old.id);"));
+
+        let sql = "create trigger after_insert_fix insert on t begin select 1; end";
+        let (timing, event, _) = sqlite::parse_trigger(sql);
+        assert_eq!(timing, "BEFORE");
+        assert_eq!(event, "INSERT");
+
+        let (timing, event, _) = sqlite::parse_trigger(
+            "CREATE TRIGGER v_del INSTEAD OF DELETE ON v BEGIN SELECT 1; END",
+        );
+        assert_eq!(timing, "INSTEAD OF");
+        assert_eq!(event, "DELETE");
+    }
+
     #[test]
     fn test_table_type_parsing() {
         assert_eq!(TableType::parse("BASE TABLE"), TableType::Table);
@@ -0,0 +1,422 @@
+//! Schema metadata cache.
+//!
+//! `list_tables`, `describe_table` and `list_databases` results, and the
+//! PostgreSQL search path, are cached per (connection, database) for a
+//! configurable TTL, so repeated introspection
+//! does not hit `information_schema` on every call. Entries are invalidated
+//! when the `execute` tool runs DDL, when migrations are applied, and on
+//! demand via the `refresh_schema` tool. Each invalidation is also broadcast
+//! to [`SchemaCache::subscribe`]rs, which MCP sessions use to send
+//! `resources/list_changed` notifications. [`SchemaCache::refresh`] drops
+//! entries suspected to be stale without notifying anyone.
+
+use crate::db::DbPool;
+use crate::db::schema::{DatabaseInfoRow, SchemaInspector};
+use crate::error::DbResult;
+use crate::models::{TableInfo, TableSchema};
+use schemars::JsonSchema;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, RwLock};
+use std::time::{Duration, Instant};
+use tokio::sync::broadcast;
+use tracing::debug;
+
+/// Default time-to-live for cached schema metadata in seconds.
+pub const DEFAULT_SCHEMA_CACHE_TTL_SECS: u64 = 60;
+
+/// Invalidations buffered per subscriber before older ones are dropped.
+const CHANGE_CHANNEL_CAPACITY: usize = 64;
+
+/// Minimum time between two [`SchemaCache::refresh`]es of one connection and
+/// database.
+const MIN_REFRESH_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Cached introspection call.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum CachedObject {
+    Tables {
+        schema: Option<String>,
+        include_views: bool,
+    },
+    Table {
+        schema: Option<String>,
+        name: String,
+    },
+    Databases,
+    SearchPath,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct CacheKey {
+    connection_id: String,
+    database: Option<String>,
+    object: CachedObject,
+}
+
+#[derive(Debug, Clone)]
+enum CachedValue {
+    Tables(Vec<TableInfo>),
+    Table(Box<TableSchema>),
+    Databases(Vec<DatabaseInfoRow>),
+    SearchPath(Vec<String>),
+}
+
+#[derive(Debug)]
+struct CacheEntry {
+    value: CachedValue,
+    stored_at: Instant,
+}
+
+/// Schema cache counters.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, JsonSchema)]
+pub struct SchemaCacheStats {
+    pub hits: u64,
+    pub misses: u64,
+    /// Entries dropped by DDL, migrations, refresh_schema or stale-entry
+    /// refreshes
+    pub invalidations: u64,
+    /// Entries currently cached (including expired ones not yet evicted)
+    pub entries: usize,
+    /// Time-to-live in seconds (0 = caching disabled)
+    pub ttl_secs: u64,
+}
+
+/// TTL cache of schema metadata keyed by (connection, database).
+#[derive(Debug)]
+pub struct SchemaCache {
+    ttl: Duration,
+    entries: RwLock<HashMap<CacheKey, CacheEntry>>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+    invalidations: AtomicU64,
+    /// Connection IDs whose schema was invalidated
+    changes: broadcast::Sender<String>,
+    /// Last refresh per (connection, database)
+    refreshed: Mutex<HashMap<(String, Option<String>), Instant>>,
+}
+
+impl SchemaCache {
+    /// Create a cache with the given TTL. A zero TTL disables caching.
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            entries: RwLock::new(HashMap::new()),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+            invalidations: AtomicU64::new(0),
+            changes: broadcast::channel(CHANGE_CHANNEL_CAPACITY).0,
+            refreshed: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Time-to-live of cached entries.
+    pub fn ttl(&self) -> Duration {
+        self.ttl
+    }
+
+    /// List tables, serving from the cache when a fresh entry exists.
+    pub async fn list_tables(
+        &self,
+        connection_id: &str,
+        database: Option<&str>,
+        pool: &DbPool,
+        schema: Option<&str>,
+        include_views: bool,
+    ) -> DbResult<Vec<TableInfo>> {
+        let key = CacheKey {
+            connection_id: connection_id.to_string(),
+            database: database.map(str::to_string),
+            object: CachedObject::Tables {
+                schema: schema.map(str::to_string),
+                include_views,
+            },
+        };
+        if let Some(CachedValue::Tables(tables)) = self.get(&key) {
+            return Ok(tables);
+        }
+
+        let tables = SchemaInspector::list_tables(pool, schema, include_views).await?;
+        self.put(key, CachedValue::Tables(tables.clone()));
+        Ok(tables)
+    }
+
+    /// Describe a table, serving from the cache when a fresh entry exists.
+    pub async fn describe_table(
+        &self,
+        connection_id: &str,
+        database: Option<&str>,
+        pool: &DbPool,
+        table_name: &str,
+        schema: Option<&str>,
+    ) -> DbResult<TableSchema> {
+        let key = CacheKey {
+            connection_id: connection_id.to_string(),
+            database: database.map(str::to_string),
+            object: CachedObject::Table {
+                schema: schema.map(str::to_string),
+                name: table_name.to_string(),
+            },
+        };
+        if let Some(CachedValue::Table(table)) = self.get(&key) {
+            return Ok(*table);
+        }
+
+        let table = SchemaInspector::describe_table(pool, table_name, schema).await?;
+        self.put(key, CachedValue::Table(Box::new(table.clone())));
+        Ok(table)
+    }
+
+    /// List databases, serving from the cache when a fresh entry exists.
+    ///
+    /// Cached under the connection itself, so only a connection-wide
+    /// invalidation drops it.
+    pub async fn list_databases(
+        &self,
+        connection_id: &str,
+        pool: &DbPool,
+    ) -> DbResult<Vec<DatabaseInfoRow>> {
+        let key = CacheKey {
+            connection_id: connection_id.to_string(),
+            database: None,
+            object: CachedObject::Databases,
+        };
+        if let Some(CachedValue::Databases(databases)) = self.get(&key) {
+            return Ok(databases);
+        }
+
+        let databases = SchemaInspector::list_databases(pool).await?;
+        self.put(key, CachedValue::Databases(databases.clone()));
+        Ok(databases)
+    }
+
+    /// Get the PostgreSQL search path, serving from the cache when a fresh
+    /// entry exists.
+    pub async fn search_path(
+        &self,
+        connection_id: &str,
+        database: Option<&str>,
+        pool: &DbPool,
+    ) -> DbResult<Vec<String>> {
+        let key = CacheKey {
+            connection_id: connection_id.to_string(),
+            database: database.map(str::to_string),
+            object: CachedObject::SearchPath,
+        };
+        if let Some(CachedValue::SearchPath(schemas)) = self.get(&key) {
+            return Ok(schemas);
+        }
+
+        let schemas = SchemaInspector::search_path(pool).await?;
+        self.put(key, CachedValue::SearchPath(schemas.clone()));
+        Ok(schemas)
+    }
+
+    /// Subscribe to invalidations. Receives the connection ID on every
+    /// [`invalidate`](Self::invalidate) call, whether or not entries were cached.
+    pub fn subscribe(&self) -> broadcast::Receiver<String> {
+        self.changes.subscribe()
+    }
+
+    /// Drop cached entries for a connection, or only one of its databases.
+    ///
+    /// Returns the number of entries removed.
+    pub fn invalidate(&self, connection_id: &str, database: Option<&str>) -> usize {
+        let removed = self.remove(connection_id, database);
+        // Sending only fails when nobody is subscribed
+        let _ = self.changes.send(connection_id.to_string());
+        removed
+    }
+
+    /// Drop cached entries for a connection (or one of its databases) that
+    /// may be stale, without notifying subscribers.
+    ///
+    /// For retries after metadata did not match the database; nothing is
+    /// known to have changed. Does nothing and returns false when the same
+    /// connection and database were refreshed within the last few seconds.
+    pub fn refresh(&self, connection_id: &str, database: Option<&str>) -> bool {
+        {
+            let mut refreshed = self.refreshed.lock().unwrap_or_else(|e| e.into_inner());
+            let key = (connection_id.to_string(), database.map(str::to_string));
+            if refreshed
+                .get(&key)
+                .is_some_and(|at| at.elapsed() < MIN_REFRESH_INTERVAL)
+            {
+                return false;
+            }
+            refreshed.insert(key, Instant::now());
+        }
+        self.remove(connection_id, database);
+        true
+    }
+
+    fn remove(&self, connection_id: &str, database: Option<&str>) -> usize {
+        let mut entries = self.entries.write().unwrap_or_else(|e| e.into_inner());
+        let before = entries.len();
+        entries.retain(|key, _| {
+            key.connection_id != connection_id
+                || database.is_some_and(|db| key.database.as_deref() != Some(db))
+        });
+        let removed = before - entries.len();
+        drop(entries);
+
+        self.invalidations
+            .fetch_add(removed as u64, Ordering::Relaxed);
+        if removed > 0 {
+            debug!(
+                connection_id = %connection_id,
+                database = ?database,
+                removed,
+                "Invalidated schema cache"
+            );
+        }
+        removed
+    }
+
+    /// Current counters.
+    pub fn stats(&self) -> SchemaCacheStats {
+        SchemaCacheStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            invalidations: self.invalidations.load(Ordering::Relaxed),
+            entries: self.entries.read().unwrap_or_else(|e| e.into_inner()).len(),
+            ttl_secs: self.ttl.as_secs(),
+        }
+    }
+
+    fn get(&self, key: &CacheKey) -> Option<CachedValue> {
+        let value = if self.ttl.is_zero() {
+            None
+        } else {
+            let entries = self.entries.read().unwrap_or_else(|e| e.into_inner());
+            entries
+                .get(key)
+                .filter(|entry| entry.stored_at.elapsed() < self.ttl)
+                .map(|entry| entry.value.clone())
+        };
+
+        let counter = if value.is_some() {
+            &self.hits
+        } else {
+            &self.misses
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+        value
+    }
+
+    fn put(&self, key: CacheKey, value: CachedValue) {
+        if self.ttl.is_zero() {
+            return;
+        }
+        let mut entries = self.entries.write().unwrap_or_else(|e| e.into_inner());
+        // Evict expired entries so the map does not grow with stale tables
+        entries.retain(|_, entry| entry.stored_at.elapsed() < self.ttl);
+        entries.insert(
+            key,
+            CacheEntry {
+                value,
+                stored_at: Instant::now(),
+            },
+        );
+    }
+}
+
+impl Default for SchemaCache {
+    fn default() -> Self {
+        Self::new(Duration::from_secs(DEFAULT_SCHEMA_CACHE_TTL_SECS))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::TableType;
+
+    fn key(connection_id: &str, database: Option<&str>, table: &str) -> CacheKey {
+        CacheKey {
+            connection_id: connection_id.to_string(),
+            database: database.map(str::to_string),
+            object: CachedObject::Table {
+                schema: None,
+                name: table.to_string(),
+            },
+        }
+    }
+
+    fn tables() -> CachedValue {
+        CachedValue::Tables(vec![TableInfo::new("users", TableType::Table)])
+    }
+
+    #[test]
+    fn test_hit_and_miss_counts() {
+        let cache = SchemaCache::default();
+        assert!(cache.get(&key("a", None, "users")).is_none());
+        cache.put(key("a", None, "users"), tables());
+        assert!(cache.get(&key("a", None, "users")).is_some());
+
+        let stats = cache.stats();
+        assert_eq!((stats.hits, stats.misses, stats.entries), (1, 1, 1));
+    }
+
+    #[test]
+    fn test_expired_entries_miss() {
+        let cache = SchemaCache::new(Duration::from_millis(1));
+        cache.put(key("a", None, "users"), tables());
+        std::thread::sleep(Duration::from_millis(5));
+        assert!(cache.get(&key("a", None, "users")).is_none());
+    }
+
+    #[test]
+    fn test_zero_ttl_disables_cache() {
+        let cache = SchemaCache::new(Duration::ZERO);
+        cache.put(key("a", None, "users"), tables());
+        assert!(cache.get(&key("a", None, "users")).is_none());
+        assert_eq!(cache.stats().entries, 0);
+    }
+
+    #[test]
+    fn test_invalidate_scopes() {
+        let cache = SchemaCache::default();
+        cache.put(key("a", Some("db1"), "users"), tables());
+        cache.put(key("a", Some("db2"), "users"), tables());
+        cache.put(key("b", None, "users"), tables());
+
+        assert_eq!(cache.invalidate("a", Some("db1")), 1);
+        assert!(cache.get(&key("a", Some("db2"), "users")).is_some());
+
+        assert_eq!(cache.invalidate("a", None), 1);
+        assert!(cache.get(&key("b", None, "users")).is_some());
+        assert_eq!(cache.stats().invalidations, 2);
+    }
+
+    #[test]
+    fn test_invalidate_notifies_subscribers() {
+        let cache = SchemaCache::default();
+        let mut changes = cache.subscribe();
+
+        // Notified even when nothing was cached
+        cache.invalidate("a", None);
+        cache.invalidate("b", Some("db1"));
+        assert_eq!(changes.try_recv().unwrap(), "a");
+        assert_eq!(changes.try_recv().unwrap(), "b");
+        assert!(changes.try_recv().is_err());
+    }
+
+    #[test]
+    fn test_refresh_is_silent_and_rate_limited() {
+        let cache = SchemaCache::default();
+        let mut changes = cache.subscribe();
+        cache.put(key("a", None, "users"), tables());
+
+        assert!(cache.refresh("a", None));
+        assert!(cache.get(&key("a", None, "users")).is_none());
+        assert!(changes.try_recv().is_err());
+
+        // A second refresh right away is skipped, leaving new entries cached
+        cache.put(key("a", None, "users"), tables());
+        assert!(!cache.refresh("a", None));
+        assert!(cache.get(&key("a", None, "users")).is_some());
+        assert!(cache.refresh("b", None));
+    }
+}
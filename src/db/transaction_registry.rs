@@ -3,7 +3,18 @@
 //! This module provides stateful transaction management, enabling transactions
 //! to persist across multiple tool invocations. Each transaction holds a dedicated
 //! database connection until committed or rolled back.
+//!
+//! Transactions belong to the MCP session (and authenticated client) that
+//! started them. Each [`DbService`](crate::mcp::DbService) works on a view of
+//! the shared registry created with [`TransactionRegistry::new_session`]; a
+//! session cannot see or use another session's transactions, and its open
+//! transactions are rolled back when the session ends (see [`SessionGuard`]).
+//!
+//! The backend of each transaction's connection is recorded when it begins,
+//! so a statement in the transaction that is cancelled or times out is stopped
+//! on the server like any other controlled statement.
 
+use crate::db::control::{CancelTarget, Canceller, QueryControl, remove_sqlite_interrupt};
 use crate::db::params::{bind_mysql_param, bind_postgres_param, bind_sqlite_param};
 use crate::error::{DbError, DbResult};
 use crate::models::{DatabaseType, QueryParam, QueryResult};
@@ -11,6 +22,7 @@ use chrono::{DateTime, Utc};
 use sqlx::{MySql, MySqlPool, PgPool, Postgres, Sqlite, SqlitePool, Transaction};
 use std::collections::HashMap;
 use std::sync::Arc;
+use std::sync::atomic::Ordering;
 use std::time::{Duration, Instant};
 use tokio::sync::{Mutex, RwLock};
 use tracing::{debug, info, warn};
@@ -46,7 +58,10 @@ impl DbTransaction {
         match self {
             DbTransaction::MySql(tx) => tx.commit().await.map_err(DbError::from),
             DbTransaction::Postgres(tx) => tx.commit().await.map_err(DbError::from),
-            DbTransaction::SQLite(tx) => tx.commit().await.map_err(DbError::from),
+            DbTransaction::SQLite(mut tx) => {
+                Self::remove_interrupt(&mut tx).await;
+                tx.commit().await.map_err(DbError::from)
+            }
         }
     }
 
@@ -55,7 +70,17 @@ impl DbTransaction {
         match self {
             DbTransaction::MySql(tx) => tx.rollback().await.map_err(DbError::from),
             DbTransaction::Postgres(tx) => tx.rollback().await.map_err(DbError::from),
-            DbTransaction::SQLite(tx) => tx.rollback().await.map_err(DbError::from),
+            DbTransaction::SQLite(mut tx) => {
+                Self::remove_interrupt(&mut tx).await;
+                tx.rollback().await.map_err(DbError::from)
+            }
+        }
+    }
+
+    /// The pooled connection must not keep the transaction's interrupt check.
+    async fn remove_interrupt(tx: &mut Transaction<'static, Sqlite>) {
+        if let Err(e) = remove_sqlite_interrupt(tx).await {
+            warn!(error = %e, "Failed to remove SQLite progress handler");
         }
     }
 }
@@ -70,6 +95,12 @@ struct TxEntry {
     created_at: Instant,
     /// Configured timeout for this transaction
     timeout_secs: u32,
+    /// Session that started the transaction (None for unscoped registries)
+    owner: Option<TxOwner>,
+    /// Stops the statement running on the transaction's connection
+    cancel_target: CancelTarget,
+    /// Whether a statement in the transaction ran DDL
+    schema_changed: bool,
 }
 
 impl TxEntry {
@@ -79,6 +110,14 @@ impl TxEntry {
     }
 }
 
+/// The MCP session and authenticated client a transaction belongs to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct TxOwner {
+    session_id: String,
+    /// Authenticated client identity (HTTP transport with auth enabled)
+    identity: Option<String>,
+}
+
 /// Metadata about an active transaction (for listing without consuming).
 #[derive(Debug, Clone)]
 pub struct TransactionMetadata {
@@ -99,6 +138,8 @@ pub struct TransactionRegistry {
     // Used to convert Instant to DateTime<Utc>
     system_start_instant: Instant,
     system_start_datetime: DateTime<Utc>,
+    /// Session this view is scoped to. Unscoped views see every transaction.
+    owner: Option<TxOwner>,
 }
 
 impl TransactionRegistry {
@@ -109,6 +150,7 @@ impl TransactionRegistry {
             default_timeout_secs: DEFAULT_TRANSACTION_TIMEOUT_SECS,
             system_start_instant: Instant::now(),
             system_start_datetime: Utc::now(),
+            owner: None,
         }
     }
 
@@ -119,16 +161,68 @@ impl TransactionRegistry {
             default_timeout_secs: default_timeout_secs.min(MAX_TRANSACTION_TIMEOUT_SECS),
             system_start_instant: Instant::now(),
             system_start_datetime: Utc::now(),
+            owner: None,
+        }
+    }
+
+    /// Create a view of this registry scoped to a new session.
+    ///
+    /// Transactions started through the view belong to the session; other
+    /// sessions' transactions are neither listed nor accessible.
+    pub fn new_session(&self) -> Self {
+        Self {
+            owner: Some(TxOwner {
+                session_id: generate_session_id(),
+                identity: None,
+            }),
+            ..self.clone()
+        }
+    }
+
+    /// Narrow a session view to an authenticated client identity.
+    ///
+    /// Has no effect on unscoped registries.
+    pub fn with_identity(&self, identity: impl Into<String>) -> Self {
+        Self {
+            owner: self.owner.as_ref().map(|owner| TxOwner {
+                session_id: owner.session_id.clone(),
+                identity: Some(identity.into()),
+            }),
+            ..self.clone()
+        }
+    }
+
+    /// Session ID of this view, if scoped.
+    pub fn session_id(&self) -> Option<&str> {
+        self.owner.as_ref().map(|owner| owner.session_id.as_str())
+    }
+
+    /// Whether this view may access a transaction owned by `owner`.
+    fn owns(&self, owner: &Option<TxOwner>) -> bool {
+        self.owner.is_none() || self.owner == *owner
+    }
+
+    fn check_owner(&self, entry: &TxEntry, transaction_id: &str) -> DbResult<()> {
+        if self.owns(&entry.owner) {
+            Ok(())
+        } else {
+            Err(DbError::transaction(
+                "Transaction belongs to a different session",
+                transaction_id,
+            ))
         }
     }
 
-    /// List all active transactions with their metadata.
+    /// List all active transactions visible to this view with their metadata.
     pub async fn list_all(&self) -> Vec<TransactionMetadata> {
         let txs = self.transactions.read().await;
         let mut result = Vec::with_capacity(txs.len());
 
         for (id, entry_arc) in txs.iter() {
             let entry = entry_arc.lock().await;
+            if !self.owns(&entry.owner) {
+                continue;
+            }
             let duration = entry.created_at.elapsed();
             let duration_secs = duration.as_secs();
             let offset_from_start = entry.created_at.duration_since(self.system_start_instant);
@@ -168,7 +262,8 @@ impl TransactionRegistry {
             .map(|t| t.min(MAX_TRANSACTION_TIMEOUT_SECS))
             .unwrap_or(self.default_timeout_secs);
 
-        let tx = pool.begin().await.map_err(DbError::from)?;
+        let mut tx = pool.begin().await.map_err(DbError::from)?;
+        let cancel_target = CancelTarget::mysql(&mut tx, pool).await?;
         let transaction_id = generate_transaction_id();
 
         info!(
@@ -183,6 +278,9 @@ impl TransactionRegistry {
             connection_id,
             created_at: Instant::now(),
             timeout_secs,
+            owner: self.owner.clone(),
+            cancel_target,
+            schema_changed: false,
         };
 
         {
@@ -204,7 +302,8 @@ impl TransactionRegistry {
             .map(|t| t.min(MAX_TRANSACTION_TIMEOUT_SECS))
             .unwrap_or(self.default_timeout_secs);
 
-        let tx = pool.begin().await.map_err(DbError::from)?;
+        let mut tx = pool.begin().await.map_err(DbError::from)?;
+        let cancel_target = CancelTarget::postgres(&mut tx, pool).await?;
         let transaction_id = generate_transaction_id();
 
         info!(
@@ -219,6 +318,9 @@ impl TransactionRegistry {
             connection_id,
             created_at: Instant::now(),
             timeout_secs,
+            owner: self.owner.clone(),
+            cancel_target,
+            schema_changed: false,
         };
 
         {
@@ -240,7 +342,8 @@ impl TransactionRegistry {
             .map(|t| t.min(MAX_TRANSACTION_TIMEOUT_SECS))
             .unwrap_or(self.default_timeout_secs);
 
-        let tx = pool.begin().await.map_err(DbError::from)?;
+        let mut tx = pool.begin().await.map_err(DbError::from)?;
+        let cancel_target = CancelTarget::sqlite(&mut tx).await?;
         let transaction_id = generate_transaction_id();
 
         info!(
@@ -255,6 +358,9 @@ impl TransactionRegistry {
             connection_id,
             created_at: Instant::now(),
             timeout_secs,
+            owner: self.owner.clone(),
+            cancel_target,
+            schema_changed: false,
         };
 
         {
@@ -277,10 +383,42 @@ impl TransactionRegistry {
 
         // Phase 2: Lock individual transaction (map lock already released)
         let entry = entry_arc.lock().await;
+        self.check_owner(&entry, transaction_id)?;
         let expired = entry.is_expired();
         Ok((entry.connection_id.clone(), entry.timeout_secs, expired))
     }
 
+    /// Record that a statement in the transaction ran DDL, so the schema
+    /// cache can be invalidated when the transaction ends.
+    pub async fn mark_schema_changed(&self, transaction_id: &str) -> DbResult<()> {
+        let entry_arc = {
+            let txs = self.transactions.read().await;
+            txs.get(transaction_id).cloned()
+        };
+        if let Some(entry_arc) = entry_arc {
+            let mut entry = entry_arc.lock().await;
+            self.check_owner(&entry, transaction_id)?;
+            entry.schema_changed = true;
+        }
+        Ok(())
+    }
+
+    /// Whether a statement in the transaction ran DDL.
+    pub async fn schema_changed(&self, transaction_id: &str) -> DbResult<bool> {
+        let entry_arc = {
+            let txs = self.transactions.read().await;
+            txs.get(transaction_id).cloned()
+        };
+        match entry_arc {
+            Some(entry_arc) => {
+                let entry = entry_arc.lock().await;
+                self.check_owner(&entry, transaction_id)?;
+                Ok(entry.schema_changed)
+            }
+            None => Ok(false),
+        }
+    }
+
     /// Check if a transaction exists and is valid (not expired).
     /// Uses two-phase locking: short map lock to get Arc, then lock entry.
     pub async fn is_valid(&self, transaction_id: &str, connection_id: &str) -> DbResult<()> {
@@ -293,10 +431,16 @@ impl TransactionRegistry {
 
         // Phase 2: Lock individual transaction (map lock already released)
         let entry = entry_arc.lock().await;
-        Self::validate_entry(&entry, connection_id, transaction_id)
+        self.validate_entry(&entry, connection_id, transaction_id)
     }
 
-    fn validate_entry(entry: &TxEntry, connection_id: &str, transaction_id: &str) -> DbResult<()> {
+    fn validate_entry(
+        &self,
+        entry: &TxEntry,
+        connection_id: &str,
+        transaction_id: &str,
+    ) -> DbResult<()> {
+        self.check_owner(entry, transaction_id)?;
         if entry.connection_id != connection_id {
             return Err(DbError::transaction(
                 "Transaction belongs to a different connection",
@@ -318,7 +462,8 @@ impl TransactionRegistry {
         Ok(())
     }
 
-    /// Execute a write operation within a transaction.
+    /// Execute a write operation within a transaction, stopping it on the
+    /// server if `control` is cancelled or `timeout` passes first.
     /// Uses two-phase locking: short map lock to get Arc, then lock entry.
     pub async fn execute_in_transaction(
         &self,
@@ -326,6 +471,8 @@ impl TransactionRegistry {
         connection_id: &str,
         sql: &str,
         params: &[QueryParam],
+        timeout: Duration,
+        control: &QueryControl,
     ) -> DbResult<u64> {
         // Phase 1: Short-lived map lock to get Arc reference
         let entry_arc = {
@@ -336,47 +483,54 @@ impl TransactionRegistry {
 
         // Phase 2: Lock individual transaction (map lock already released)
         let mut entry = entry_arc.lock().await;
-        Self::validate_entry(&entry, connection_id, transaction_id)?;
+        self.validate_entry(&entry, connection_id, transaction_id)?;
 
+        let canceller = Canceller::new(entry.cancel_target.clone());
         let tx = entry.transaction.as_mut().ok_or_else(|| {
             DbError::transaction("Transaction is no longer active", transaction_id)
         })?;
-
-        let rows_affected = match tx {
-            DbTransaction::MySql(tx) => {
-                let mut query = sqlx::query(sql);
-                for param in params {
-                    query = bind_mysql_param(query, param);
+        Self::prepare_statement(tx, &canceller).await?;
+
+        let work = async {
+            Ok::<_, DbError>(match tx {
+                DbTransaction::MySql(tx) => {
+                    let mut query = sqlx::query(sql);
+                    for param in params {
+                        query = bind_mysql_param(query, param);
+                    }
+                    query
+                        .execute(&mut **tx)
+                        .await
+                        .map_err(DbError::from)?
+                        .rows_affected()
                 }
-                query
-                    .execute(&mut **tx)
-                    .await
-                    .map_err(DbError::from)?
-                    .rows_affected()
-            }
-            DbTransaction::Postgres(tx) => {
-                let mut query = sqlx::query(sql);
-                for param in params {
-                    query = bind_postgres_param(query, param);
+                DbTransaction::Postgres(tx) => {
+                    let mut query = sqlx::query(sql);
+                    for param in params {
+                        query = bind_postgres_param(query, param);
+                    }
+                    query
+                        .execute(&mut **tx)
+                        .await
+                        .map_err(DbError::from)?
+                        .rows_affected()
                 }
-                query
-                    .execute(&mut **tx)
-                    .await
-                    .map_err(DbError::from)?
-                    .rows_affected()
-            }
-            DbTransaction::SQLite(tx) => {
-                let mut query = sqlx::query(sql);
-                for param in params {
-                    query = bind_sqlite_param(query, param);
+                DbTransaction::SQLite(tx) => {
+                    let mut query = sqlx::query(sql);
+                    for param in params {
+                        query = bind_sqlite_param(query, param);
+                    }
+                    query
+                        .execute(&mut **tx)
+                        .await
+                        .map_err(DbError::from)?
+                        .rows_affected()
                 }
-                query
-                    .execute(&mut **tx)
-                    .await
-                    .map_err(DbError::from)?
-                    .rows_affected()
-            }
+            })
         };
+        let rows_affected = control
+            .supervise(&canceller, "write operation", timeout, work)
+            .await?;
 
         debug!(
             transaction_id = %transaction_id,
@@ -388,9 +542,11 @@ impl TransactionRegistry {
         Ok(rows_affected)
     }
 
-    /// Execute a query within a transaction.
+    /// Execute a query within a transaction, stopping it on the server if
+    /// `control` is cancelled or `timeout` passes first.
     /// Uses two-phase locking: short map lock to get Arc, then lock entry.
     /// Uses streaming with take(limit+1) for memory efficiency.
+    #[allow(clippy::too_many_arguments)]
     pub async fn query_in_transaction(
         &self,
         transaction_id: &str,
@@ -399,6 +555,8 @@ impl TransactionRegistry {
         params: &[QueryParam],
         limit: u32,
         decode_binary: bool,
+        timeout: Duration,
+        control: &QueryControl,
     ) -> DbResult<QueryResult> {
         use crate::db::types::RowToJson;
         use futures_util::StreamExt;
@@ -415,91 +573,114 @@ impl TransactionRegistry {
 
         // Phase 2: Lock individual transaction (map lock already released)
         let mut entry = entry_arc.lock().await;
-        Self::validate_entry(&entry, connection_id, transaction_id)?;
+        self.validate_entry(&entry, connection_id, transaction_id)?;
 
+        let canceller = Canceller::new(entry.cancel_target.clone());
         let tx = entry.transaction.as_mut().ok_or_else(|| {
             DbError::transaction("Transaction is no longer active", transaction_id)
         })?;
+        Self::prepare_statement(tx, &canceller).await?;
 
         let fetch_limit = limit as usize + 1;
-
-        let (columns, rows) = match tx {
-            DbTransaction::MySql(tx) => {
-                let mut query = sqlx::query(sql);
-                for param in params {
-                    query = bind_mysql_param(query, param);
+        let counter = control.row_counter();
+
+        let work = async {
+            Ok::<_, DbError>(match tx {
+                DbTransaction::MySql(tx) => {
+                    let mut query = sqlx::query(sql);
+                    for param in params {
+                        query = bind_mysql_param(query, param);
+                    }
+                    let stream = query.fetch(&mut **tx);
+                    let results: Vec<Result<sqlx::mysql::MySqlRow, sqlx::Error>> = stream
+                        .take(fetch_limit)
+                        .inspect(|_| {
+                            counter.fetch_add(1, Ordering::Relaxed);
+                        })
+                        .collect()
+                        .await;
+
+                    let mut rows = Vec::with_capacity(results.len());
+                    for result in results {
+                        rows.push(result.map_err(DbError::from)?);
+                    }
+
+                    let columns = if rows.is_empty() {
+                        Vec::new()
+                    } else {
+                        rows[0].get_column_names()
+                    };
+                    let json_rows: Vec<_> = rows
+                        .iter()
+                        .map(|r| r.to_json_map_with_options(decode_binary))
+                        .collect();
+                    (columns, json_rows)
                 }
-                let stream = query.fetch(&mut **tx);
-                let results: Vec<Result<sqlx::mysql::MySqlRow, sqlx::Error>> =
-                    stream.take(fetch_limit).collect().await;
-
-                let mut rows = Vec::with_capacity(results.len());
-                for result in results {
-                    rows.push(result.map_err(DbError::from)?);
+                DbTransaction::Postgres(tx) => {
+                    let mut query = sqlx::query(sql);
+                    for param in params {
+                        query = bind_postgres_param(query, param);
+                    }
+                    let stream = query.fetch(&mut **tx);
+                    let results: Vec<Result<sqlx::postgres::PgRow, sqlx::Error>> = stream
+                        .take(fetch_limit)
+                        .inspect(|_| {
+                            counter.fetch_add(1, Ordering::Relaxed);
+                        })
+                        .collect()
+                        .await;
+
+                    let mut rows = Vec::with_capacity(results.len());
+                    for result in results {
+                        rows.push(result.map_err(DbError::from)?);
+                    }
+
+                    let columns = if rows.is_empty() {
+                        Vec::new()
+                    } else {
+                        rows[0].get_column_names()
+                    };
+                    let json_rows: Vec<_> = rows
+                        .iter()
+                        .map(|r| r.to_json_map_with_options(decode_binary))
+                        .collect();
+                    (columns, json_rows)
                 }
-
-                let columns = if rows.is_empty() {
-                    Vec::new()
-                } else {
-                    rows[0].get_column_names()
-                };
-                let json_rows: Vec<_> = rows
-                    .iter()
-                    .map(|r| r.to_json_map_with_options(decode_binary))
-                    .collect();
-                (columns, json_rows)
-            }
-            DbTransaction::Postgres(tx) => {
-                let mut query = sqlx::query(sql);
-                for param in params {
-                    query = bind_postgres_param(query, param);
+                DbTransaction::SQLite(tx) => {
+                    let mut query = sqlx::query(sql);
+                    for param in params {
+                        query = bind_sqlite_param(query, param);
+                    }
+                    let stream = query.fetch(&mut **tx);
+                    let results: Vec<Result<sqlx::sqlite::SqliteRow, sqlx::Error>> = stream
+                        .take(fetch_limit)
+                        .inspect(|_| {
+                            counter.fetch_add(1, Ordering::Relaxed);
+                        })
+                        .collect()
+                        .await;
+
+                    let mut rows = Vec::with_capacity(results.len());
+                    for result in results {
+                        rows.push(result.map_err(DbError::from)?);
+                    }
+
+                    let columns = if rows.is_empty() {
+                        Vec::new()
+                    } else {
+                        rows[0].get_column_names()
+                    };
+                    let json_rows: Vec<_> = rows
+                        .iter()
+                        .map(|r| r.to_json_map_with_options(decode_binary))
+                        .collect();
+                    (columns, json_rows)
                 }
-                let stream = query.fetch(&mut **tx);
-                let results: Vec<Result<sqlx::postgres::PgRow, sqlx::Error>> =
-                    stream.take(fetch_limit).collect().await;
-
-                let mut rows = Vec::with_capacity(results.len());
-                for result in results {
-                    rows.push(result.map_err(DbError::from)?);
-                }
-
-                let columns = if rows.is_empty() {
-                    Vec::new()
-                } else {
-                    rows[0].get_column_names()
-                };
-                let json_rows: Vec<_> = rows
-                    .iter()
-                    .map(|r| r.to_json_map_with_options(decode_binary))
-                    .collect();
-                (columns, json_rows)
-            }
-            DbTransaction::SQLite(tx) => {
-                let mut query = sqlx::query(sql);
-                for param in params {
-                    query = bind_sqlite_param(query, param);
-                }
-                let stream = query.fetch(&mut **tx);
-                let results: Vec<Result<sqlx::sqlite::SqliteRow, sqlx::Error>> =
-                    stream.take(fetch_limit).collect().await;
-
-                let mut rows = Vec::with_capacity(results.len());
-                for result in results {
-                    rows.push(result.map_err(DbError::from)?);
-                }
-
-                let columns = if rows.is_empty() {
-                    Vec::new()
-                } else {
-                    rows[0].get_column_names()
-                };
-                let json_rows: Vec<_> = rows
-                    .iter()
-                    .map(|r| r.to_json_map_with_options(decode_binary))
-                    .collect();
-                (columns, json_rows)
-            }
+            })
         };
+        let (columns, rows) = control
+            .supervise(&canceller, "query execution", timeout, work)
+            .await?;
 
         let execution_time_ms = start.elapsed().as_millis() as u64;
         let has_more = rows.len() > limit as usize;
@@ -523,6 +704,16 @@ impl TransactionRegistry {
         })
     }
 
+    /// Get the transaction's connection ready for a statement under `canceller`.
+    async fn prepare_statement(tx: &mut DbTransaction, canceller: &Canceller) -> DbResult<()> {
+        if let DbTransaction::SQLite(tx) = tx {
+            // Waits for a statement abandoned earlier to stop
+            tx.lock_handle().await?;
+        }
+        canceller.rearm();
+        Ok(())
+    }
+
     /// Commit a transaction.
     /// Uses two-phase locking: lock entry to validate and take transaction,
     /// then remove from map and commit outside all locks.
@@ -537,6 +728,7 @@ impl TransactionRegistry {
         // Phase 2: Lock entry, validate, and take the transaction
         let tx = {
             let mut entry = entry_arc.lock().await;
+            self.check_owner(&entry, transaction_id)?;
 
             if entry.connection_id != connection_id {
                 return Err(DbError::transaction(
@@ -583,6 +775,7 @@ impl TransactionRegistry {
         // Phase 2: Lock entry, validate, and take the transaction
         let tx = {
             let mut entry = entry_arc.lock().await;
+            self.check_owner(&entry, transaction_id)?;
 
             if entry.connection_id != connection_id {
                 return Err(DbError::transaction(
@@ -616,8 +809,50 @@ impl TransactionRegistry {
     }
 
     /// Clean up expired transactions.
-    /// Collects expired IDs, removes from map, then rolls back outside locks.
     async fn cleanup_expired(&self) {
+        self.rollback_matching(TxEntry::is_expired, "Rolling back expired transaction")
+            .await;
+    }
+
+    /// Roll back every open transaction of this view's session.
+    ///
+    /// Called when the session ends. Returns the number of transactions
+    /// rolled back; unscoped registries roll back nothing.
+    pub async fn rollback_session(&self) -> usize {
+        let Some(session_id) = self.session_id() else {
+            return 0;
+        };
+        self.rollback_matching(
+            |entry| {
+                entry
+                    .owner
+                    .as_ref()
+                    .is_some_and(|owner| owner.session_id == session_id)
+            },
+            "Rolling back transaction of closed session",
+        )
+        .await
+    }
+
+    /// Roll back every open transaction on a connection, in all sessions.
+    ///
+    /// Called when the connection is removed. Returns the number of
+    /// transactions rolled back.
+    pub async fn rollback_connection(&self, connection_id: &str) -> usize {
+        self.rollback_matching(
+            |entry| entry.connection_id == connection_id,
+            "Rolling back transaction of removed connection",
+        )
+        .await
+    }
+
+    /// Roll back the transactions matching `predicate`.
+    /// Collects matching IDs, removes from map, then rolls back outside locks.
+    async fn rollback_matching(
+        &self,
+        predicate: impl Fn(&TxEntry) -> bool,
+        message: &str,
+    ) -> usize {
         // Phase 1: Collect Arc references with read lock (don't wait for entry locks)
         let candidates: Vec<(String, Arc<Mutex<TxEntry>>)> = {
             let txs = self.transactions.read().await;
@@ -626,23 +861,23 @@ impl TransactionRegistry {
                 .collect()
         };
 
-        // Phase 1.5: Check expiration outside read lock to reduce lock contention
-        let mut expired_entries = Vec::new();
+        // Phase 1.5: Check entries outside read lock to reduce lock contention
+        let mut matching_entries = Vec::new();
         for (id, entry_arc) in candidates {
             let entry = entry_arc.lock().await;
-            if entry.is_expired() && entry.transaction.is_some() {
-                expired_entries.push((id, entry.connection_id.clone(), Arc::clone(&entry_arc)));
+            if predicate(&entry) && entry.transaction.is_some() {
+                matching_entries.push((id, entry.connection_id.clone(), Arc::clone(&entry_arc)));
             }
         }
 
-        if expired_entries.is_empty() {
-            return;
+        if matching_entries.is_empty() {
+            return 0;
         }
 
-        // Phase 2: For each expired entry, take the transaction and remove from map
+        // Phase 2: For each matching entry, take the transaction and remove from map
         let mut transactions_to_rollback = Vec::new();
 
-        for (id, connection_id, entry_arc) in expired_entries {
+        for (id, connection_id, entry_arc) in matching_entries {
             // Lock entry and take transaction
             let tx = {
                 let mut entry = entry_arc.lock().await;
@@ -659,15 +894,17 @@ impl TransactionRegistry {
             }
         }
 
-        // Phase 3: Rollback all expired transactions outside all locks
+        // Phase 3: Rollback all matching transactions outside all locks
+        let count = transactions_to_rollback.len();
         for (id, connection_id, tx) in transactions_to_rollback {
             warn!(
                 transaction_id = %id,
                 connection_id = %connection_id,
-                "Rolling back expired transaction"
+                "{}", message
             );
             let _ = tx.rollback().await;
         }
+        count
     }
 
     /// Get the number of active transactions.
@@ -688,6 +925,39 @@ fn generate_transaction_id() -> String {
     format!("tx_{}", uuid::Uuid::new_v4().simple())
 }
 
+/// Generate a unique session ID.
+fn generate_session_id() -> String {
+    format!("session_{}", uuid::Uuid::new_v4().simple())
+}
+
+/// Rolls back a session's open transactions when the session ends.
+///
+/// Held by each [`DbService`](crate::mcp::DbService); the last clone of the
+/// service is dropped when its MCP session closes or the client disconnects.
+pub struct SessionGuard {
+    registry: TransactionRegistry,
+}
+
+impl SessionGuard {
+    /// Create a guard for the session of a scoped registry view.
+    pub fn new(registry: TransactionRegistry) -> Self {
+        Self { registry }
+    }
+}
+
+impl Drop for SessionGuard {
+    fn drop(&mut self) {
+        // Without a runtime (e.g. at process exit) the dropped transactions
+        // are rolled back by the database when their connections close.
+        if let Ok(handle) = tokio::runtime::Handle::try_current() {
+            let registry = self.registry.clone();
+            handle.spawn(async move {
+                registry.rollback_session().await;
+            });
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -833,4 +1103,81 @@ mod tests {
             assert_eq!(count, 0);
         }
     }
+
+    async fn sqlite_pool() -> SqlitePool {
+        SqlitePool::connect("sqlite::memory:")
+            .await
+            .expect("Failed to open SQLite")
+    }
+
+    #[tokio::test]
+    async fn test_sessions_are_isolated() {
+        let pool = sqlite_pool().await;
+        let registry = TransactionRegistry::new();
+        let alice = registry.new_session();
+        let bob = registry.new_session();
+        assert_ne!(alice.session_id(), bob.session_id());
+
+        let tx_id = alice
+            .begin_sqlite(&pool, "db".to_string(), None)
+            .await
+            .unwrap();
+
+        let err = bob.get_info(&tx_id).await.unwrap_err();
+        assert!(err.to_string().contains("different session"));
+        assert!(bob.commit(&tx_id, "db").await.is_err());
+        assert!(bob.rollback(&tx_id, "db").await.is_err());
+        assert!(bob.is_valid(&tx_id, "db").await.is_err());
+        assert!(bob.mark_schema_changed(&tx_id).await.is_err());
+        assert!(bob.schema_changed(&tx_id).await.is_err());
+        assert!(!alice.schema_changed(&tx_id).await.unwrap());
+        assert!(bob.list_all().await.is_empty());
+
+        // Same session, different authenticated client
+        let other_client = alice.with_identity("other");
+        assert!(other_client.is_valid(&tx_id, "db").await.is_err());
+        assert!(other_client.list_all().await.is_empty());
+
+        // The unscoped registry still sees every transaction
+        assert_eq!(registry.list_all().await.len(), 1);
+        assert_eq!(alice.list_all().await.len(), 1);
+        alice.commit(&tx_id, "db").await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_rollback_session() {
+        let pool = sqlite_pool().await;
+        let registry = TransactionRegistry::new();
+        let alice = registry.new_session();
+        let bob = registry.new_session();
+
+        alice
+            .begin_sqlite(&pool, "db".to_string(), None)
+            .await
+            .unwrap();
+        alice
+            .with_identity("token")
+            .begin_sqlite(&pool, "db".to_string(), None)
+            .await
+            .unwrap();
+        let bob_tx = bob
+            .begin_sqlite(&pool, "db".to_string(), None)
+            .await
+            .unwrap();
+
+        assert_eq!(alice.rollback_session().await, 2);
+        assert_eq!(registry.count().await, 1);
+        assert!(bob.is_valid(&bob_tx, "db").await.is_ok());
+        assert_eq!(registry.rollback_session().await, 0);
+
+        // Dropping the guard rolls back in the background
+        drop(SessionGuard::new(bob.clone()));
+        for _ in 0..50 {
+            if registry.count().await == 0 {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        assert_eq!(registry.count().await, 0);
+    }
 }
@@ -519,16 +519,20 @@ mod sqlite {
             }
             return JsonValue::String(v);
         }
+        // Expression columns (e.g. aggregates over a subquery) have no declared
+        // type; fall back to the value's storage class
+        if let Ok(Some(v)) = row.try_get::<Option<i64>, _>(idx) {
+            return JsonValue::Number(v.into());
+        }
+        if let Ok(Some(v)) = row.try_get::<Option<f64>, _>(idx) {
+            return serde_json::Number::from_f64(v)
+                .map(JsonValue::Number)
+                .unwrap_or(JsonValue::Null);
+        }
         JsonValue::Null
     }
 }
 
-// =============================================================================
-// Type Normalization
-// =============================================================================
-
-/// Normalize a database type name to a more consistent format.
-
 #[cfg(test)]
 mod tests {
     use super::*;
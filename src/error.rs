@@ -37,6 +37,9 @@ pub enum DbError {
         elapsed_secs: u32,
     },
 
+    #[error("Cancelled: {operation} was cancelled by the client")]
+    Cancelled { operation: String },
+
     #[error("Connection not found: {connection_id}")]
     ConnectionNotFound { connection_id: String },
 
@@ -120,6 +123,13 @@ impl DbError {
         }
     }
 
+    /// Create a cancellation error.
+    pub fn cancelled(operation: impl Into<String>) -> Self {
+        Self::Cancelled {
+            operation: operation.into(),
+        }
+    }
+
     /// Create a connection not found error.
     pub fn connection_not_found(connection_id: impl Into<String>) -> Self {
         Self::ConnectionNotFound {
@@ -198,6 +208,27 @@ impl DbError {
     pub fn is_retryable(&self) -> bool {
         matches!(self, Self::Connection { .. } | Self::Timeout { .. })
     }
+
+    /// Short name of the error variant, used as a metrics label.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::Connection { .. } => "connection",
+            Self::Database { .. } => "database",
+            Self::Permission { .. } => "permission",
+            Self::Schema { .. } => "schema",
+            Self::Transaction { .. } => "transaction",
+            Self::Timeout { .. } => "timeout",
+            Self::Cancelled { .. } => "cancelled",
+            Self::ConnectionNotFound { .. } => "connection_not_found",
+            Self::InvalidInput { .. } => "invalid_input",
+            Self::Internal { .. } => "internal",
+            Self::DangerousOperationBlocked { .. } => "dangerous_operation_blocked",
+            Self::DatabaseNotFound { .. } => "database_not_found",
+            Self::DatabaseRequired { .. } => "database_required",
+            Self::Unauthorized { .. } => "unauthorized",
+            Self::InvalidAuthHeader { .. } => "invalid_auth_header",
+        }
+    }
 }
 
 /// Convert sqlx errors to DbError.
@@ -273,8 +304,10 @@ fn suggestion_data(suggestion: Option<&str>) -> Option<serde_json::Value> {
 
 /// Convert DbError to MCP ErrorData for semantic error categorization.
 /// Includes the suggestion field in the `data` object when available.
+/// Every conversion is counted in the `db_mcp_errors_total` metric.
 impl From<DbError> for rmcp::ErrorData {
     fn from(err: DbError) -> Self {
+        crate::metrics::record_error(&err);
         match &err {
             // InvalidInput, Permission, DangerousOperationBlocked, Schema -> invalid_params
             DbError::InvalidInput { .. } => {
@@ -311,7 +344,7 @@ impl From<DbError> for rmcp::ErrorData {
                 )),
             ),
 
-            // Connection, Timeout -> internal_error (with implicit retryable flag)
+            // Connection, Timeout, Cancelled -> internal_error (the first two retryable)
             DbError::Connection { suggestion, .. } => {
                 rmcp::ErrorData::internal_error(err.to_string(), suggestion_data(Some(suggestion)))
             }
@@ -321,6 +354,7 @@ impl From<DbError> for rmcp::ErrorData {
                     "Consider increasing the timeout or optimizing the operation",
                 )),
             ),
+            DbError::Cancelled { .. } => rmcp::ErrorData::internal_error(err.to_string(), None),
 
             // Database errors -> invalid_params with sql_state in message
             DbError::Database {
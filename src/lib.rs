@@ -8,7 +8,9 @@ pub mod config;
 pub mod db;
 pub mod error;
 pub mod mcp;
+pub mod metrics;
 pub mod models;
+pub mod reload;
 pub mod tools;
 pub mod transport;
 
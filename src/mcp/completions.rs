@@ -0,0 +1,189 @@
+//! MCP argument completions.
+//!
+//! Suggests connection IDs, database names, table names and column names for
+//! prompt arguments and resource template variables, filtered by prefix.
+//! Values already chosen for other arguments (the request context) narrow the
+//! lookup. Databases, tables and columns are read through the schema cache,
+//! so completing as the user types does not query the database every time.
+
+use crate::db::ConnectionManager;
+use crate::error::DbResult;
+use crate::mcp::resources::DEFAULT_DATABASE;
+use crate::models::DatabaseType;
+use crate::tools::schema::{
+    DescribeTableInput, ListTablesInput, SchemaToolHandler, listing_database,
+};
+use rmcp::model::{CompleteRequestParam, CompletionInfo, Reference};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tracing::debug;
+
+/// Suggests argument values from the configured connections and their schemas.
+pub struct CompletionProvider {
+    connection_manager: Arc<ConnectionManager>,
+}
+
+impl CompletionProvider {
+    pub fn new(connection_manager: Arc<ConnectionManager>) -> Self {
+        Self { connection_manager }
+    }
+
+    /// Complete one argument. Lookup failures yield no suggestions.
+    pub async fn complete(&self, request: &CompleteRequestParam) -> CompletionInfo {
+        let context = request
+            .context
+            .as_ref()
+            .and_then(|c| c.arguments.clone())
+            .unwrap_or_default();
+        let for_resource = matches!(request.r#ref, Reference::Resource(_));
+
+        let candidates = match request.argument.name.as_str() {
+            "connection_id" => Ok(self.connection_manager.list_connections().await),
+            "database" => self.databases(&context, for_resource).await,
+            "table" | "table_name" => self.tables(&context).await,
+            "column" | "column_name" | "key_column" => self.columns(&context).await,
+            _ => Ok(Vec::new()),
+        };
+        let candidates = candidates.unwrap_or_else(|e| {
+            debug!(argument = %request.argument.name, error = %e, "Completion lookup failed");
+            Vec::new()
+        });
+        filter_candidates(candidates, &request.argument.value)
+    }
+
+    async fn databases(
+        &self,
+        context: &HashMap<String, String>,
+        for_resource: bool,
+    ) -> DbResult<Vec<String>> {
+        let Some(connection_id) = context.get("connection_id") else {
+            return Ok(Vec::new());
+        };
+        let config = self.connection_manager.get_config(connection_id).await?;
+
+        // Resource URIs name the connection's own database "default"
+        let mut names = Vec::new();
+        if for_resource && !config.server_level {
+            names.push(DEFAULT_DATABASE.to_string());
+        }
+        if config.db_type == DatabaseType::SQLite {
+            return Ok(names);
+        }
+
+        let system_db = listing_database(&config);
+        let pool = self
+            .connection_manager
+            .get_pool_for_database(connection_id, system_db)
+            .await?;
+        let result = self
+            .connection_manager
+            .schema_cache()
+            .list_databases(connection_id, &pool)
+            .await;
+        self.connection_manager
+            .release_pool_for_database(connection_id, system_db)
+            .await;
+
+        names.extend(result?.into_iter().map(|db| db.name));
+        Ok(names)
+    }
+
+    async fn tables(&self, context: &HashMap<String, String>) -> DbResult<Vec<String>> {
+        let Some(connection_id) = context.get("connection_id") else {
+            return Ok(Vec::new());
+        };
+        let handler = SchemaToolHandler::new(self.connection_manager.clone());
+        let output = handler
+            .list_tables(ListTablesInput {
+                connection_id: connection_id.clone(),
+                database: context_database(context),
+                include_views: true,
+            })
+            .await?;
+        Ok(output.tables.into_iter().map(|t| t.name).collect())
+    }
+
+    async fn columns(&self, context: &HashMap<String, String>) -> DbResult<Vec<String>> {
+        let (Some(connection_id), Some(table)) = (
+            context.get("connection_id"),
+            context.get("table").or_else(|| context.get("table_name")),
+        ) else {
+            return Ok(Vec::new());
+        };
+        let handler = SchemaToolHandler::new(self.connection_manager.clone());
+        let output = handler
+            .describe_table(DescribeTableInput {
+                connection_id: connection_id.clone(),
+                table_name: table.clone(),
+                database: context_database(context),
+            })
+            .await?;
+        Ok(output.columns.into_iter().map(|c| c.name).collect())
+    }
+}
+
+/// The database chosen in the context; empty and `default` mean none.
+fn context_database(context: &HashMap<String, String>) -> Option<String> {
+    context
+        .get("database")
+        .filter(|db| !db.is_empty() && db.as_str() != DEFAULT_DATABASE)
+        .cloned()
+}
+
+/// Sorted, deduplicated candidates starting with `prefix` (case-insensitive),
+/// capped at the protocol's maximum number of values.
+fn filter_candidates(mut candidates: Vec<String>, prefix: &str) -> CompletionInfo {
+    let prefix = prefix.to_lowercase();
+    candidates.retain(|c| c.to_lowercase().starts_with(&prefix));
+    candidates.sort();
+    candidates.dedup();
+
+    let total = candidates.len();
+    candidates.truncate(CompletionInfo::MAX_VALUES);
+    CompletionInfo {
+        has_more: Some(total > candidates.len()),
+        total: Some(total as u32),
+        values: candidates,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(values: &[&str]) -> Vec<String> {
+        values.iter().map(|v| v.to_string()).collect()
+    }
+
+    #[test]
+    fn test_filter_candidates_by_prefix() {
+        let info = filter_candidates(names(&["orders", "Users", "order_items", "users"]), "OR");
+        assert_eq!(info.values, vec!["order_items", "orders"]);
+        assert_eq!(info.total, Some(2));
+        assert_eq!(info.has_more, Some(false));
+
+        let info = filter_candidates(names(&["b", "a", "a"]), "");
+        assert_eq!(info.values, vec!["a", "b"]);
+    }
+
+    #[test]
+    fn test_filter_candidates_caps_values() {
+        let many: Vec<String> = (0..150).map(|i| format!("t{i:03}")).collect();
+        let info = filter_candidates(many, "t");
+        assert_eq!(info.values.len(), CompletionInfo::MAX_VALUES);
+        assert_eq!(info.total, Some(150));
+        assert_eq!(info.has_more, Some(true));
+    }
+
+    #[test]
+    fn test_context_database() {
+        let context = |db: &str| HashMap::from([("database".to_string(), db.to_string())]);
+        assert_eq!(
+            context_database(&context("sales")),
+            Some("sales".to_string())
+        );
+        assert_eq!(context_database(&context(DEFAULT_DATABASE)), None);
+        assert_eq!(context_database(&context("")), None);
+        assert_eq!(context_database(&HashMap::new()), None);
+    }
+}
@@ -0,0 +1,340 @@
+//! MCP logging capability.
+//!
+//! [`LogForwarder::layer`] is a `tracing_subscriber` layer, installed by
+//! `init_tracing` next to the stderr formatter, that turns the server's own
+//! events and sqlx's slow statement warnings into `notifications/message`
+//! payloads and broadcasts them. Each MCP session forwards the messages at or
+//! above the level its client chose with `logging/setLevel`. Nothing is
+//! written to stdout, so forwarding is safe on the stdio transport and works
+//! even when stderr logging is disabled.
+//!
+//! Requests are handled inside a [`session_span`], and events emitted there
+//! are forwarded only to that session. Events from outside any session, such
+//! as the transaction reaper, reach every session with the fields that could
+//! reveal another client's work ([`REDACTED_FIELDS`]) removed.
+
+use rmcp::model::{JsonObject, LoggingLevel, LoggingMessageNotificationParam};
+use serde_json::Value;
+use tokio::sync::broadcast;
+use tokio::sync::broadcast::error::{RecvError, TryRecvError};
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id};
+use tracing::{Event, Level, Span, Subscriber};
+use tracing_subscriber::filter::Targets;
+use tracing_subscriber::layer::{Context, Layer};
+use tracing_subscriber::registry::LookupSpan;
+
+/// Level forwarded to a client that has not called `logging/setLevel`.
+pub const DEFAULT_LOG_LEVEL: LoggingLevel = LoggingLevel::Info;
+
+/// Messages buffered per session; a session that falls further behind skips
+/// the oldest ones.
+const LOG_CHANNEL_CAPACITY: usize = 256;
+
+/// Name of the span that ties events to the MCP session emitting them.
+const SESSION_SPAN: &str = "mcp_session";
+
+/// Fields dropped from events that are not tied to a session, since every
+/// session receives those.
+pub const REDACTED_FIELDS: &[&str] = &["sql", "transaction_id", "params"];
+
+/// Span for handling a request of the MCP session `session_id`.
+///
+/// Log messages for events emitted inside it are forwarded to that session
+/// only.
+pub fn session_span(session_id: &str) -> Span {
+    tracing::info_span!(SESSION_SPAN, session = session_id)
+}
+
+/// A published log message and the session it belongs to, if any.
+#[derive(Clone)]
+struct LogRecord {
+    session: Option<String>,
+    message: LoggingMessageNotificationParam,
+}
+
+/// Broadcasts log messages from the tracing layer to the MCP sessions.
+#[derive(Clone)]
+pub struct LogForwarder {
+    sender: broadcast::Sender<LogRecord>,
+}
+
+impl LogForwarder {
+    pub fn new() -> Self {
+        Self {
+            sender: broadcast::channel(LOG_CHANNEL_CAPACITY).0,
+        }
+    }
+
+    /// Tracing layer publishing the events worth sending to clients.
+    pub fn layer<S>(&self) -> impl Layer<S> + use<S>
+    where
+        S: Subscriber + for<'a> LookupSpan<'a>,
+    {
+        McpLogLayer {
+            sender: self.sender.clone(),
+        }
+        .with_filter(forwarded_targets())
+    }
+
+    /// Receive the messages for session `session_id` published from now on.
+    pub fn subscribe(&self, session_id: &str) -> SessionLog {
+        SessionLog {
+            session_id: session_id.to_string(),
+            receiver: self.sender.subscribe(),
+        }
+    }
+}
+
+/// The log messages one MCP session may see: its own and those not tied to
+/// any session.
+pub struct SessionLog {
+    session_id: String,
+    receiver: broadcast::Receiver<LogRecord>,
+}
+
+impl SessionLog {
+    /// Wait for the next message for this session.
+    pub async fn recv(&mut self) -> Result<LoggingMessageNotificationParam, RecvError> {
+        loop {
+            let record = self.receiver.recv().await?;
+            if self.is_visible(&record) {
+                return Ok(record.message);
+            }
+        }
+    }
+
+    /// Take the next message for this session without waiting.
+    pub fn try_recv(&mut self) -> Result<LoggingMessageNotificationParam, TryRecvError> {
+        loop {
+            let record = self.receiver.try_recv()?;
+            if self.is_visible(&record) {
+                return Ok(record.message);
+            }
+        }
+    }
+
+    fn is_visible(&self, record: &LogRecord) -> bool {
+        record
+            .session
+            .as_deref()
+            .is_none_or(|session| session == self.session_id)
+    }
+}
+
+impl Default for LogForwarder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The server's own events and sqlx's slow statement warnings. Other
+/// libraries stay on stderr; rmcp in particular logs every message it sends,
+/// which would feed back into the forwarding.
+fn forwarded_targets() -> Targets {
+    Targets::new()
+        .with_target("db_mcp_server", Level::DEBUG)
+        .with_target("sqlx::query", Level::WARN)
+}
+
+struct McpLogLayer {
+    sender: broadcast::Sender<LogRecord>,
+}
+
+/// Session id recorded on a [`session_span`], kept in the span's extensions.
+struct SessionId(String);
+
+impl<S> Layer<S> for McpLogLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        if attrs.metadata().name() != SESSION_SPAN {
+            return;
+        }
+        let mut fields = FieldVisitor(JsonObject::new());
+        attrs.record(&mut fields);
+        let (Some(Value::String(session)), Some(span)) = (fields.0.remove("session"), ctx.span(id))
+        else {
+            return;
+        };
+        span.extensions_mut().insert(SessionId(session));
+    }
+
+    fn on_event(&self, event: &Event<'_>, ctx: Context<'_, S>) {
+        // No session is listening, e.g. before the first client connects
+        if self.sender.receiver_count() == 0 {
+            return;
+        }
+        let session = ctx.event_scope(event).and_then(|scope| {
+            scope
+                .into_iter()
+                .find_map(|span| span.extensions().get::<SessionId>().map(|id| id.0.clone()))
+        });
+        let message = log_message(event, session.is_none());
+        let _ = self.sender.send(LogRecord { session, message });
+    }
+}
+
+/// Notification payload for an event: its fields as a JSON object, with the
+/// event target as the logger name. With `redact`, the [`REDACTED_FIELDS`]
+/// are left out.
+fn log_message(event: &Event<'_>, redact: bool) -> LoggingMessageNotificationParam {
+    let mut fields = FieldVisitor(JsonObject::new());
+    event.record(&mut fields);
+    if redact {
+        fields
+            .0
+            .retain(|name, _| !REDACTED_FIELDS.contains(&name.as_str()));
+    }
+    let metadata = event.metadata();
+    LoggingMessageNotificationParam {
+        level: logging_level(*metadata.level()),
+        logger: Some(metadata.target().to_string()),
+        data: Value::Object(fields.0),
+    }
+}
+
+/// MCP level for a tracing level.
+pub fn logging_level(level: Level) -> LoggingLevel {
+    match level {
+        Level::ERROR => LoggingLevel::Error,
+        Level::WARN => LoggingLevel::Warning,
+        Level::INFO => LoggingLevel::Info,
+        _ => LoggingLevel::Debug,
+    }
+}
+
+/// Whether a message at `level` passes a client's minimum level.
+pub fn is_enabled(level: LoggingLevel, minimum: LoggingLevel) -> bool {
+    severity(level) >= severity(minimum)
+}
+
+fn severity(level: LoggingLevel) -> u8 {
+    match level {
+        LoggingLevel::Debug => 0,
+        LoggingLevel::Info => 1,
+        LoggingLevel::Notice => 2,
+        LoggingLevel::Warning => 3,
+        LoggingLevel::Error => 4,
+        LoggingLevel::Critical => 5,
+        LoggingLevel::Alert => 6,
+        LoggingLevel::Emergency => 7,
+    }
+}
+
+struct FieldVisitor(JsonObject);
+
+impl Visit for FieldVisitor {
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        self.0.insert(field.name().to_string(), value.into());
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.0.insert(field.name().to_string(), value.into());
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.0.insert(field.name().to_string(), value.into());
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.0.insert(field.name().to_string(), value.into());
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.insert(field.name().to_string(), value.into());
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        self.0
+            .insert(field.name().to_string(), format!("{:?}", value).into());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    #[tokio::test]
+    async fn test_events_become_log_messages() {
+        let forwarder = LogForwarder::new();
+        let mut messages = forwarder.subscribe("session_a");
+        let subscriber = tracing_subscriber::registry().with(forwarder.layer());
+
+        tracing::subscriber::with_default(subscriber, || {
+            tracing::warn!(total_rows = 120, limit = 100, "Query result truncated");
+            tracing::info!(target: "rmcp::service", "sending notification");
+            tracing::debug!(target: "sqlx::query", "SELECT 1");
+            tracing::warn!(target: "sqlx::query", elapsed_secs = 2.5, "slow statement");
+            tracing::info!("done");
+        });
+
+        let message = messages.recv().await.unwrap();
+        assert_eq!(message.level, LoggingLevel::Warning);
+        assert_eq!(
+            message.logger.as_deref(),
+            Some("db_mcp_server::mcp::logging::tests")
+        );
+        assert_eq!(
+            message.data,
+            serde_json::json!({
+                "message": "Query result truncated",
+                "total_rows": 120,
+                "limit": 100,
+            })
+        );
+
+        let message = messages.recv().await.unwrap();
+        assert_eq!(message.logger.as_deref(), Some("sqlx::query"));
+        assert_eq!(message.data["elapsed_secs"], 2.5);
+        let message = messages.recv().await.unwrap();
+        assert_eq!(message.data["message"], "done");
+    }
+
+    #[tokio::test]
+    async fn test_sessions_only_see_their_own_events() {
+        let forwarder = LogForwarder::new();
+        let mut session_a = forwarder.subscribe("session_a");
+        let mut session_b = forwarder.subscribe("session_b");
+        let subscriber = tracing_subscriber::registry().with(forwarder.layer());
+
+        tracing::subscriber::with_default(subscriber, || {
+            session_span("session_a").in_scope(|| {
+                tracing::debug!(sql = "SELECT secret FROM a", "Streaming query rows");
+            });
+            session_span("session_b").in_scope(|| {
+                tracing::debug!(sql = "SELECT 1", "Streaming query rows");
+            });
+            tracing::info!(
+                transaction_id = "tx_1",
+                sql = "UPDATE a SET secret = 1",
+                "Transaction timed out"
+            );
+        });
+
+        let message = session_a.recv().await.unwrap();
+        assert_eq!(message.data["sql"], "SELECT secret FROM a");
+        let message = session_a.recv().await.unwrap();
+        assert_eq!(message.data["message"], "Transaction timed out");
+
+        let message = session_b.recv().await.unwrap();
+        assert_eq!(message.data["sql"], "SELECT 1");
+        // Not tied to a session: delivered to both, without the SQL
+        let message = session_b.recv().await.unwrap();
+        assert_eq!(
+            message.data,
+            serde_json::json!({ "message": "Transaction timed out" })
+        );
+    }
+
+    #[test]
+    fn test_level_threshold() {
+        assert!(is_enabled(LoggingLevel::Warning, LoggingLevel::Info));
+        assert!(is_enabled(LoggingLevel::Info, LoggingLevel::Info));
+        assert!(!is_enabled(LoggingLevel::Debug, DEFAULT_LOG_LEVEL));
+        assert!(!is_enabled(LoggingLevel::Error, LoggingLevel::Critical));
+        assert_eq!(logging_level(Level::TRACE), LoggingLevel::Debug);
+    }
+}
@@ -3,6 +3,10 @@
 //! This module provides the integration between the MCP protocol and
 //! the database tool handlers using the rmcp framework.
 
+pub mod completions;
+pub mod logging;
+pub mod prompts;
+pub mod resources;
 pub mod service;
 
 pub use service::DbService;
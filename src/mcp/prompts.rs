@@ -0,0 +1,551 @@
+//! MCP prompts for common database workflows.
+//!
+//! Prompts are templates with `{{name}}` placeholders, prefilled with schema
+//! context when rendered. Built-in prompts cover exploring a database,
+//! explaining a slow query, writing a migration and summarizing a table.
+//! Additional prompts are loaded from `<name>.md` files in the prompts
+//! directory; a file named like a built-in prompt replaces it.
+//!
+//! A template file starts with an optional header:
+//!
+//! ```text
+//! ---
+//! description: Review a table for personal data
+//! arguments: connection_id, table, database?
+//! ---
+//! Review `{{table}}` on {{db_type}}:
+//!
+//! {{schema}}
+//! ```
+//!
+//! Arguments ending in `?` are optional. Besides the arguments, templates can
+//! use `{{db_type}}` and `{{schema}}`, which expands to the schema of `table`
+//! when given, of the tables named in `sql` when given, and otherwise to an
+//! overview of every table. Both require a `connection_id` argument.
+
+use crate::db::ConnectionManager;
+use crate::error::{DbError, DbResult};
+use crate::tools::schema::{
+    DescribeTableInput, DescribeTableOutput, ListTablesInput, SchemaToolHandler,
+};
+use rmcp::model::{
+    GetPromptResult, JsonObject, Prompt, PromptArgument, PromptMessage, PromptMessageRole,
+};
+use serde_json::Value as JsonValue;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::Path;
+use std::sync::Arc;
+use tracing::{info, warn};
+
+/// File extension of prompt template files.
+const TEMPLATE_EXTENSION: &str = "md";
+/// Header delimiter line of template files.
+const HEADER_DELIMITER: &str = "---";
+/// Tables described in full in a database overview; the rest are only named.
+const MAX_DESCRIBED_TABLES: usize = 50;
+
+const EXPLORE_DATABASE: &str = "---
+description: Get oriented in a database: what it stores and how its tables relate
+arguments: connection_id, database?
+---
+Help me understand the database behind connection `{{connection_id}}` ({{db_type}}).
+
+Schema:
+
+{{schema}}
+
+Describe what the database appears to be for, group the tables by domain and explain the key \
+relationships between them. Use `relationship_graph` for the full foreign-key picture and \
+`sample_table` or `profile_table` to check assumptions against real data. Only run read-only queries.
+";
+
+const EXPLAIN_SLOW_QUERY: &str = "---
+description: Find out why a query is slow and how to speed it up
+arguments: connection_id, sql, database?
+---
+This query on connection `{{connection_id}}` ({{db_type}}) is slow:
+
+```sql
+{{sql}}
+```
+
+Schema of the tables it references:
+
+{{schema}}
+
+Find out why. Run `explain` on it (with `analyze: true` only if the statement is safe to execute), \
+look for full scans, poor join orders and missing indexes, and run `suggest_indexes` for index \
+recommendations. Propose a rewritten query and/or `CREATE INDEX` statements and explain the \
+expected improvement.
+";
+
+const SAFE_MIGRATION: &str = "---
+description: Write a reversible migration for a schema change
+arguments: connection_id, change, database?
+---
+Write a migration for connection `{{connection_id}}` ({{db_type}}) that makes this change:
+
+{{change}}
+
+Current schema:
+
+{{schema}}
+
+Requirements:
+- Provide an up migration (`<version>_<name>.sql`) and a down migration (`<version>_<name>.down.sql`) that reverts it
+- Use {{db_type}} syntax and avoid long locks on large tables where an online alternative exists
+- Never drop or rewrite data without an explicit backfill step, and flag every destructive statement
+- Check `migration_status` first, and only run `migration_apply` after I confirm
+";
+
+const SUMMARIZE_TABLE: &str = "---
+description: Summarize a table's contents, relationships and data quality
+arguments: connection_id, table, database?
+---
+Summarize the table `{{table}}` on connection `{{connection_id}}` ({{db_type}}).
+
+Schema:
+
+{{schema}}
+
+Use `profile_table` for value distributions and `sample_table` for example rows. Explain what each \
+column holds, how the table relates to others and any data quality issues (unexpected nulls, \
+outliers, inconsistent values).
+";
+
+const BUILTIN_TEMPLATES: &[(&str, &str)] = &[
+    ("explore_database", EXPLORE_DATABASE),
+    ("explain_slow_query", EXPLAIN_SLOW_QUERY),
+    ("safe_migration", SAFE_MIGRATION),
+    ("summarize_table", SUMMARIZE_TABLE),
+];
+
+/// Descriptions of well-known argument names.
+fn argument_description(name: &str) -> Option<&'static str> {
+    Some(match name {
+        "connection_id" => "Database connection ID from list_connections",
+        "database" => "Target database name (required for server-level connections)",
+        "table" => "Table name",
+        "sql" => "SQL statement",
+        "change" => "Description of the schema change",
+        _ => return None,
+    })
+}
+
+/// A declared template argument.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TemplateArgument {
+    pub name: String,
+    pub required: bool,
+}
+
+/// A parsed prompt template.
+#[derive(Debug, Clone)]
+pub struct PromptTemplate {
+    pub name: String,
+    pub description: Option<String>,
+    pub arguments: Vec<TemplateArgument>,
+    pub body: String,
+}
+
+impl PromptTemplate {
+    /// Parse a template, with or without a header.
+    pub fn parse(name: &str, text: &str) -> DbResult<Self> {
+        let mut template = Self {
+            name: name.to_string(),
+            description: None,
+            arguments: Vec::new(),
+            body: text.to_string(),
+        };
+
+        let Some(rest) = text
+            .strip_prefix(HEADER_DELIMITER)
+            .and_then(|r| r.strip_prefix('\n'))
+        else {
+            return Ok(template);
+        };
+        let Some((header, body)) = rest.split_once(&format!("\n{HEADER_DELIMITER}\n")) else {
+            return Err(DbError::invalid_input(format!(
+                "Prompt '{name}': header is not closed with '{HEADER_DELIMITER}'"
+            )));
+        };
+
+        for line in header.lines().map(str::trim).filter(|l| !l.is_empty()) {
+            let Some((key, value)) = line.split_once(':') else {
+                return Err(DbError::invalid_input(format!(
+                    "Prompt '{name}': expected 'key: value' in header, got '{line}'"
+                )));
+            };
+            match key.trim() {
+                "description" => template.description = Some(value.trim().to_string()),
+                "arguments" => {
+                    template.arguments = value
+                        .split(',')
+                        .map(str::trim)
+                        .filter(|a| !a.is_empty())
+                        .map(|a| TemplateArgument {
+                            name: a.trim_end_matches('?').to_string(),
+                            required: !a.ends_with('?'),
+                        })
+                        .collect();
+                }
+                other => {
+                    return Err(DbError::invalid_input(format!(
+                        "Prompt '{name}': unknown header key '{other}'"
+                    )));
+                }
+            }
+        }
+        template.body = body.to_string();
+        Ok(template)
+    }
+
+    fn has_argument(&self, name: &str) -> bool {
+        self.arguments.iter().any(|a| a.name == name)
+    }
+
+    fn uses(&self, placeholder: &str) -> bool {
+        self.body.contains(&format!("{{{{{placeholder}}}}}"))
+    }
+
+    /// The MCP prompt listing entry.
+    pub fn to_prompt(&self) -> Prompt {
+        let arguments = self
+            .arguments
+            .iter()
+            .map(|a| PromptArgument {
+                name: a.name.clone(),
+                title: None,
+                description: argument_description(&a.name).map(str::to_string),
+                required: Some(a.required),
+            })
+            .collect();
+        Prompt::new(&self.name, self.description.clone(), Some(arguments))
+    }
+
+    /// Substitute placeholders; unknown `{{...}}` sequences are kept as-is.
+    pub fn render(&self, values: &HashMap<String, String>) -> String {
+        let mut text = self.body.clone();
+        for (name, value) in values {
+            text = text.replace(&format!("{{{{{name}}}}}"), value);
+        }
+        text.trim_end().to_string()
+    }
+}
+
+/// Built-in prompts plus those found in the prompts directory.
+#[derive(Debug, Clone)]
+pub struct PromptLibrary {
+    templates: BTreeMap<String, PromptTemplate>,
+}
+
+impl PromptLibrary {
+    /// Only the built-in prompts.
+    pub fn builtin() -> Self {
+        let templates = BUILTIN_TEMPLATES
+            .iter()
+            .map(|(name, text)| {
+                let template = PromptTemplate::parse(name, text).expect("valid built-in prompt");
+                (name.to_string(), template)
+            })
+            .collect();
+        Self { templates }
+    }
+
+    /// Built-in prompts overlaid with the `*.md` templates of `dir`.
+    ///
+    /// Unreadable or malformed files are skipped with a warning.
+    pub fn load(dir: Option<&Path>) -> Self {
+        let mut library = Self::builtin();
+        let Some(dir) = dir else {
+            return library;
+        };
+
+        let entries = match std::fs::read_dir(dir) {
+            Ok(entries) => entries,
+            Err(e) => {
+                warn!(dir = %dir.display(), error = %e, "Failed to read prompts directory");
+                return library;
+            }
+        };
+        for path in entries.filter_map(|e| e.ok()).map(|e| e.path()) {
+            if path.extension().and_then(|e| e.to_str()) != Some(TEMPLATE_EXTENSION) {
+                continue;
+            }
+            let Some(name) = path.file_stem().and_then(|s| s.to_str()) else {
+                continue;
+            };
+            let template = std::fs::read_to_string(&path)
+                .map_err(|e| DbError::internal(format!("Failed to read {}: {e}", path.display())))
+                .and_then(|text| PromptTemplate::parse(name, &text));
+            match template {
+                Ok(template) => {
+                    library.templates.insert(name.to_string(), template);
+                }
+                Err(e) => warn!(path = %path.display(), error = %e, "Skipping prompt template"),
+            }
+        }
+        library
+    }
+
+    /// Prompts sorted by name.
+    pub fn list(&self) -> Vec<Prompt> {
+        self.templates
+            .values()
+            .map(PromptTemplate::to_prompt)
+            .collect()
+    }
+
+    pub fn get(&self, name: &str) -> Option<&PromptTemplate> {
+        self.templates.get(name)
+    }
+}
+
+/// Renders prompts, gathering schema context for the `{{schema}}` placeholder.
+pub struct PromptRenderer {
+    connection_manager: Arc<ConnectionManager>,
+}
+
+impl PromptRenderer {
+    pub fn new(connection_manager: Arc<ConnectionManager>) -> Self {
+        Self { connection_manager }
+    }
+
+    /// Render a template with the client's arguments.
+    pub async fn render(
+        &self,
+        template: &PromptTemplate,
+        arguments: Option<JsonObject>,
+    ) -> DbResult<GetPromptResult> {
+        let mut values: HashMap<String, String> = arguments
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|(name, _)| template.has_argument(name))
+            .map(|(name, value)| {
+                let value = match value {
+                    JsonValue::String(s) => s,
+                    other => other.to_string(),
+                };
+                (name, value.trim().to_string())
+            })
+            .filter(|(_, value)| !value.is_empty())
+            .collect();
+
+        if let Some(missing) = template
+            .arguments
+            .iter()
+            .find(|a| a.required && !values.contains_key(&a.name))
+        {
+            return Err(DbError::invalid_input(format!(
+                "Prompt '{}' requires the '{}' argument",
+                template.name, missing.name
+            )));
+        }
+
+        if template.uses("db_type") || template.uses("schema") {
+            let Some(connection_id) = values.get("connection_id").cloned() else {
+                return Err(DbError::invalid_input(format!(
+                    "Prompt '{}' needs a 'connection_id' argument to gather schema context",
+                    template.name
+                )));
+            };
+            let config = self.connection_manager.get_config(&connection_id).await?;
+            values.insert("db_type".to_string(), config.db_type.to_string());
+            if template.uses("schema") {
+                let schema = self.schema_context(&values).await?;
+                values.insert("schema".to_string(), schema);
+            }
+        }
+        // Unset optional arguments render as empty text
+        for argument in &template.arguments {
+            values.entry(argument.name.clone()).or_default();
+        }
+
+        info!(prompt = %template.name, "Rendered prompt");
+
+        Ok(GetPromptResult {
+            description: template.description.clone(),
+            messages: vec![PromptMessage::new_text(
+                PromptMessageRole::User,
+                template.render(&values),
+            )],
+        })
+    }
+
+    async fn schema_context(&self, values: &HashMap<String, String>) -> DbResult<String> {
+        let connection_id = &values["connection_id"];
+        let database = values.get("database").cloned();
+        let handler = SchemaToolHandler::new(self.connection_manager.clone());
+
+        let describe = |table: String| {
+            handler.describe_table(DescribeTableInput {
+                connection_id: connection_id.clone(),
+                table_name: table,
+                database: database.clone(),
+            })
+        };
+
+        if let Some(table) = values.get("table") {
+            return Ok(format_table(&describe(table.clone()).await?));
+        }
+
+        let tables: Vec<String> = handler
+            .list_tables(ListTablesInput {
+                connection_id: connection_id.clone(),
+                database: database.clone(),
+                include_views: true,
+            })
+            .await?
+            .tables
+            .into_iter()
+            .map(|t| t.name)
+            .collect();
+        let tables = match values.get("sql") {
+            Some(sql) => referenced_tables(sql, tables),
+            None => tables,
+        };
+        if tables.is_empty() {
+            return Ok("(no tables found)".to_string());
+        }
+
+        let mut sections = Vec::new();
+        for table in tables.iter().take(MAX_DESCRIBED_TABLES) {
+            sections.push(format_table(&describe(table.clone()).await?));
+        }
+        if tables.len() > MAX_DESCRIBED_TABLES {
+            sections.push(format!(
+                "Other tables: {}",
+                tables[MAX_DESCRIBED_TABLES..].join(", ")
+            ));
+        }
+        Ok(sections.join("\n\n"))
+    }
+}
+
+/// Tables whose names appear as identifiers in the SQL, in catalog order.
+fn referenced_tables(sql: &str, tables: Vec<String>) -> Vec<String> {
+    let words: HashSet<String> = sql
+        .split(|c: char| !(c.is_alphanumeric() || c == '_' || c == '$'))
+        .filter(|w| !w.is_empty())
+        .map(str::to_lowercase)
+        .collect();
+    tables
+        .into_iter()
+        .filter(|t| words.contains(&t.to_lowercase()))
+        .collect()
+}
+
+/// Compact markdown description of a table.
+fn format_table(table: &DescribeTableOutput) -> String {
+    let mut lines = vec![format!("### {}", table.table_name)];
+    if let Some(comment) = &table.comment {
+        lines.push(comment.clone());
+    }
+    for column in &table.columns {
+        let mut line = format!("- {} {}", column.name, column.data_type);
+        if column.is_primary_key {
+            line.push_str(" PRIMARY KEY");
+        } else if !column.nullable {
+            line.push_str(" NOT NULL");
+        }
+        for fk in table
+            .foreign_keys
+            .iter()
+            .filter(|fk| fk.column == column.name)
+        {
+            line.push_str(&format!(
+                " REFERENCES {}({})",
+                fk.references_table, fk.references_column
+            ));
+        }
+        lines.push(line);
+    }
+    for index in table.indexes.iter().filter(|i| !i.is_primary) {
+        lines.push(format!(
+            "- {}INDEX {} ({})",
+            if index.is_unique { "UNIQUE " } else { "" },
+            index.name,
+            index.columns.join(", ")
+        ));
+    }
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builtin_prompts_parse() {
+        let library = PromptLibrary::builtin();
+        let names: Vec<String> = library.list().into_iter().map(|p| p.name).collect();
+        assert_eq!(
+            names,
+            vec![
+                "explain_slow_query",
+                "explore_database",
+                "safe_migration",
+                "summarize_table"
+            ]
+        );
+
+        let summarize = library.get("summarize_table").unwrap();
+        assert!(summarize.description.is_some());
+        assert_eq!(
+            summarize.arguments,
+            vec![
+                TemplateArgument {
+                    name: "connection_id".to_string(),
+                    required: true
+                },
+                TemplateArgument {
+                    name: "table".to_string(),
+                    required: true
+                },
+                TemplateArgument {
+                    name: "database".to_string(),
+                    required: false
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_without_header() {
+        let template = PromptTemplate::parse("plain", "Just text {{x}}").unwrap();
+        assert!(template.arguments.is_empty());
+        assert_eq!(template.body, "Just text {{x}}");
+    }
+
+    #[test]
+    fn test_parse_rejects_bad_headers() {
+        assert!(PromptTemplate::parse("a", "---\ndescription: x\nbody").is_err());
+        assert!(PromptTemplate::parse("a", "---\nowner: me\n---\nbody").is_err());
+        assert!(PromptTemplate::parse("a", "---\nno colon\n---\nbody").is_err());
+    }
+
+    #[test]
+    fn test_render_keeps_unknown_placeholders() {
+        let template =
+            PromptTemplate::parse("a", "---\narguments: table\n---\n{{table}} {{other}}\n")
+                .unwrap();
+        let values = HashMap::from([("table".to_string(), "users".to_string())]);
+        assert_eq!(template.render(&values), "users {{other}}");
+    }
+
+    #[test]
+    fn test_referenced_tables() {
+        let tables = vec![
+            "users".to_string(),
+            "orders".to_string(),
+            "order_items".to_string(),
+        ];
+        assert_eq!(
+            referenced_tables(
+                r#"SELECT * FROM "Users" u JOIN order_items oi ON oi.user_id = u.id"#,
+                tables
+            ),
+            vec!["users", "order_items"]
+        );
+    }
+}
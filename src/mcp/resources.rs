@@ -0,0 +1,340 @@
+//! MCP resources exposing table schemas and samples.
+//!
+//! Every table of a connection is published as `db://{connection_id}/{database}/{table}`,
+//! whose content is the `describe_table` output, and `db://.../{table}/sample`,
+//! a small random sample of its rows. The `default` database segment refers to
+//! the connection's own database. Server-level connections are not listed but
+//! can be read through the resource templates with an explicit database name.
+
+use crate::db::ConnectionManager;
+use crate::error::{DbError, DbResult};
+use crate::tools::format::OutputFormat;
+use crate::tools::sample::{SampleTableInput, SampleToolHandler};
+use crate::tools::schema::{DescribeTableInput, ListTablesInput, SchemaToolHandler};
+use rmcp::model::{
+    AnnotateAble, RawResource, RawResourceTemplate, Resource, ResourceContents, ResourceTemplate,
+};
+use serde::Serialize;
+use std::sync::Arc;
+use tracing::{info, warn};
+
+/// URI scheme prefix of table resources.
+pub const URI_PREFIX: &str = "db://";
+/// Database segment referring to the connection's own database.
+pub const DEFAULT_DATABASE: &str = "default";
+/// Trailing segment of sample resources.
+const SAMPLE_SEGMENT: &str = "sample";
+/// Rows returned by sample resources.
+const SAMPLE_ROWS: u32 = 10;
+/// MIME type of all resource contents.
+const JSON_MIME_TYPE: &str = "application/json";
+
+/// A parsed table resource URI.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TableResource {
+    pub connection_id: String,
+    /// Database name, or [`DEFAULT_DATABASE`]
+    pub database: String,
+    pub table: String,
+    /// True for the `/sample` resource, false for the schema
+    pub sample: bool,
+}
+
+impl TableResource {
+    /// Parse a `db://{connection_id}/{database}/{table}[/sample]` URI.
+    ///
+    /// Segments are percent-decoded.
+    pub fn parse(uri: &str) -> DbResult<Self> {
+        let invalid = || {
+            DbError::invalid_input(format!(
+                "Invalid resource URI '{uri}'. Expected {URI_PREFIX}{{connection_id}}/{{database}}/{{table}}[/sample]"
+            ))
+        };
+
+        let path = uri.strip_prefix(URI_PREFIX).ok_or_else(invalid)?;
+        let segments = path
+            .split('/')
+            .map(percent_decode)
+            .collect::<Option<Vec<_>>>()
+            .ok_or_else(invalid)?;
+        let (segments, sample) = match segments.as_slice() {
+            [_, _, _] => (segments.as_slice(), false),
+            [rest @ .., last] if rest.len() == 3 && last == SAMPLE_SEGMENT => (rest, true),
+            _ => return Err(invalid()),
+        };
+        if segments.iter().any(String::is_empty) {
+            return Err(invalid());
+        }
+
+        Ok(Self {
+            connection_id: segments[0].clone(),
+            database: segments[1].clone(),
+            table: segments[2].clone(),
+            sample,
+        })
+    }
+
+    /// Format the resource as a URI, percent-encoding each segment.
+    pub fn uri(&self) -> String {
+        let mut uri = format!(
+            "{URI_PREFIX}{}/{}/{}",
+            percent_encode(&self.connection_id),
+            percent_encode(&self.database),
+            percent_encode(&self.table)
+        );
+        if self.sample {
+            uri.push('/');
+            uri.push_str(SAMPLE_SEGMENT);
+        }
+        uri
+    }
+
+    fn to_resource(&self) -> Resource {
+        let (name, description) = if self.sample {
+            (
+                format!("{}.{} sample", self.connection_id, self.table),
+                format!("Random sample of up to {SAMPLE_ROWS} rows"),
+            )
+        } else {
+            (
+                format!("{}.{}", self.connection_id, self.table),
+                "Columns, primary key, foreign keys and indexes".to_string(),
+            )
+        };
+        let mut resource = RawResource::new(self.uri(), name);
+        resource.description = Some(description);
+        resource.mime_type = Some(JSON_MIME_TYPE.to_string());
+        resource.no_annotation()
+    }
+}
+
+/// Templates for reading any table, including those of server-level connections.
+pub fn resource_templates() -> Vec<ResourceTemplate> {
+    let template = |uri_template: &str, name: &str, description: &str| {
+        RawResourceTemplate {
+            uri_template: uri_template.to_string(),
+            name: name.to_string(),
+            title: None,
+            description: Some(description.to_string()),
+            mime_type: Some(JSON_MIME_TYPE.to_string()),
+        }
+        .no_annotation()
+    };
+
+    vec![
+        template(
+            "db://{connection_id}/{database}/{table}",
+            "table_schema",
+            "Table schema. Use 'default' as database for the connection's own database",
+        ),
+        template(
+            "db://{connection_id}/{database}/{table}/sample",
+            "table_sample",
+            "Random sample of table rows. Use 'default' as database for the connection's own database",
+        ),
+    ]
+}
+
+/// Lists and reads table resources.
+pub struct ResourceProvider {
+    connection_manager: Arc<ConnectionManager>,
+    default_timeout_secs: u64,
+}
+
+impl ResourceProvider {
+    pub fn new(connection_manager: Arc<ConnectionManager>, default_timeout_secs: u64) -> Self {
+        Self {
+            connection_manager,
+            default_timeout_secs,
+        }
+    }
+
+    /// List schema and sample resources for the tables of every connection
+    /// that targets a specific database.
+    ///
+    /// Connections whose tables cannot be listed are skipped.
+    pub async fn list(&self) -> Vec<Resource> {
+        let mut connections = self.connection_manager.list_connections_detail().await;
+        connections.sort_by(|a, b| a.id.cmp(&b.id));
+
+        let handler = SchemaToolHandler::new(self.connection_manager.clone());
+        let mut resources = Vec::new();
+        for connection in connections.iter().filter(|c| !c.server_level) {
+            let input = ListTablesInput {
+                connection_id: connection.id.clone(),
+                database: None,
+                include_views: false,
+            };
+            let tables = match handler.list_tables(input).await {
+                Ok(output) => output.tables,
+                Err(e) => {
+                    warn!(connection_id = %connection.id, error = %e, "Skipping connection in resource list");
+                    continue;
+                }
+            };
+
+            for table in tables {
+                for sample in [false, true] {
+                    let resource = TableResource {
+                        connection_id: connection.id.clone(),
+                        database: DEFAULT_DATABASE.to_string(),
+                        table: table.name.clone(),
+                        sample,
+                    };
+                    resources.push(resource.to_resource());
+                }
+            }
+        }
+        resources
+    }
+
+    /// Read a table resource as JSON.
+    pub async fn read(&self, uri: &str) -> DbResult<ResourceContents> {
+        let resource = TableResource::parse(uri)?;
+        let database = self.resolve_database(&resource).await?;
+
+        let text = if resource.sample {
+            let handler =
+                SampleToolHandler::new(self.connection_manager.clone(), self.default_timeout_secs);
+            let input = SampleTableInput {
+                connection_id: resource.connection_id.clone(),
+                table_name: resource.table.clone(),
+                database,
+                sample_size: Some(SAMPLE_ROWS),
+                filter: None,
+                stratify_by: None,
+                max_strata: None,
+                timeout_secs: None,
+                format: OutputFormat::Json,
+                decode_binary: true,
+            };
+            to_json(&handler.sample_table(input).await?)?
+        } else {
+            let handler = SchemaToolHandler::new(self.connection_manager.clone());
+            let input = DescribeTableInput {
+                connection_id: resource.connection_id.clone(),
+                table_name: resource.table.clone(),
+                database,
+            };
+            to_json(&handler.describe_table(input).await?)?
+        };
+
+        info!(
+            connection_id = %resource.connection_id,
+            table = %resource.table,
+            sample = resource.sample,
+            "Read table resource"
+        );
+
+        Ok(ResourceContents::TextResourceContents {
+            uri: uri.to_string(),
+            mime_type: Some(JSON_MIME_TYPE.to_string()),
+            text,
+            meta: None,
+        })
+    }
+
+    /// Map the database segment to the `database` parameter of the tools.
+    async fn resolve_database(&self, resource: &TableResource) -> DbResult<Option<String>> {
+        let config = self
+            .connection_manager
+            .get_config(&resource.connection_id)
+            .await?;
+        let is_default = resource.database == DEFAULT_DATABASE;
+
+        if config.server_level {
+            if is_default {
+                return Err(DbError::database_required(&resource.connection_id));
+            }
+            return Ok(Some(resource.database.clone()));
+        }
+        if is_default || config.database.as_deref() == Some(resource.database.as_str()) {
+            return Ok(None);
+        }
+        Err(DbError::invalid_input(format!(
+            "Connection '{}' is bound to a single database; use '{DEFAULT_DATABASE}' as the database segment",
+            resource.connection_id
+        )))
+    }
+}
+
+fn to_json<T: Serialize>(value: &T) -> DbResult<String> {
+    serde_json::to_string_pretty(value)
+        .map_err(|e| DbError::internal(format!("Failed to serialize resource: {e}")))
+}
+
+fn percent_encode(segment: &str) -> String {
+    let mut encoded = String::with_capacity(segment.len());
+    for byte in segment.bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'.' | b'_' | b'~') {
+            encoded.push(byte as char);
+        } else {
+            encoded.push_str(&format!("%{byte:02X}"));
+        }
+    }
+    encoded
+}
+
+/// Decode `%XX` escapes; None on malformed escapes or invalid UTF-8.
+fn percent_decode(segment: &str) -> Option<String> {
+    let bytes = segment.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' {
+            let hex = segment.get(i + 1..i + 3)?;
+            decoded.push(u8::from_str_radix(hex, 16).ok()?);
+            i += 3;
+        } else {
+            decoded.push(bytes[i]);
+            i += 1;
+        }
+    }
+    String::from_utf8(decoded).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn resource(table: &str, sample: bool) -> TableResource {
+        TableResource {
+            connection_id: "app".to_string(),
+            database: DEFAULT_DATABASE.to_string(),
+            table: table.to_string(),
+            sample,
+        }
+    }
+
+    #[test]
+    fn test_uri_round_trip() {
+        let schema = resource("users", false);
+        assert_eq!(schema.uri(), "db://app/default/users");
+        assert_eq!(TableResource::parse(&schema.uri()).unwrap(), schema);
+
+        let sample = resource("users", true);
+        assert_eq!(sample.uri(), "db://app/default/users/sample");
+        assert_eq!(TableResource::parse(&sample.uri()).unwrap(), sample);
+    }
+
+    #[test]
+    fn test_uri_percent_encoding() {
+        let odd = resource("order items/2024", false);
+        assert_eq!(odd.uri(), "db://app/default/order%20items%2F2024");
+        assert_eq!(TableResource::parse(&odd.uri()).unwrap(), odd);
+    }
+
+    #[test]
+    fn test_parse_rejects_malformed_uris() {
+        for uri in [
+            "file:///tmp/x",
+            "db://app/default",
+            "db://app//users",
+            "db://app/default/users/rows",
+            "db://app/default/users/sample/extra",
+            "db://app/default/bad%2",
+        ] {
+            assert!(TableResource::parse(uri).is_err(), "{uri}");
+        }
+    }
+}
@@ -4,13 +4,45 @@
 //! exposed via the MCP protocol using the rmcp framework's macros.
 //! Tool names use simplified format without `db_` prefix.
 
-use crate::db::{ConnectionManager, ConnectionSummary, TransactionRegistry};
+use crate::auth::AuthIdentity;
+use crate::db::{
+    ConnectionManager, ConnectionSummary, QueryControl, SessionGuard, TransactionRegistry,
+};
+use crate::mcp::completions::CompletionProvider;
+use crate::mcp::logging::{self, DEFAULT_LOG_LEVEL, LogForwarder};
+use crate::mcp::prompts::{PromptLibrary, PromptRenderer};
+use crate::mcp::resources::{self, ResourceProvider};
+use crate::metrics;
+use crate::tools::admin::{
+    AddConnectionInput, AddConnectionOutput, AdminPolicy, AdminToolHandler, RemoveConnectionInput,
+    RemoveConnectionOutput, TestConnectionInput, TestConnectionOutput,
+};
+use crate::tools::compare::{CompareQueriesInput, CompareQueriesOutput, CompareToolHandler};
+use crate::tools::data_diff::{DataDiffToolHandler, DiffTableDataInput, DiffTableDataOutput};
 use crate::tools::explain::{ExplainInput, ExplainOutput, ExplainToolHandler};
+use crate::tools::index_advisor::{
+    IndexAdvisorToolHandler, SuggestIndexesInput, SuggestIndexesOutput,
+};
+use crate::tools::migration::{
+    MigrationApplyInput, MigrationRollbackInput, MigrationRunOutput, MigrationStatusInput,
+    MigrationStatusOutput, MigrationToolHandler,
+};
+use crate::tools::profile::{ProfileTableInput, ProfileTableOutput, ProfileToolHandler};
 use crate::tools::query::{QueryInput, QueryOutput, QueryToolHandler};
+use crate::tools::relationships::{
+    FindJoinPathInput, FindJoinPathOutput, RelationshipGraphInput, RelationshipGraphOutput,
+    RelationshipToolHandler,
+};
+use crate::tools::sample::{SampleTableInput, SampleTableOutput, SampleToolHandler};
 use crate::tools::schema::{
-    DescribeTableInput, DescribeTableOutput, ListDatabasesInput, ListDatabasesOutput,
-    ListTablesInput, ListTablesOutput, SchemaToolHandler,
+    DescribeRoutineInput, DescribeRoutineOutput, DescribeTableInput, DescribeTableOutput,
+    DescribeViewInput, DescribeViewOutput, ListDatabasesInput, ListDatabasesOutput,
+    ListRoutinesInput, ListRoutinesOutput, ListSequencesInput, ListSequencesOutput,
+    ListTablesInput, ListTablesOutput, ListTriggersInput, ListTriggersOutput, RefreshSchemaInput,
+    RefreshSchemaOutput, SchemaToolHandler, ShowCreateInput, ShowCreateOutput,
 };
+use crate::tools::schema_diff::{DiffSchemaInput, DiffSchemaOutput, SchemaDiffToolHandler};
+use crate::tools::search::{SearchSchemaInput, SearchSchemaOutput, SearchToolHandler};
 use crate::tools::transaction::{
     BeginTransactionInput, BeginTransactionOutput, CommitInput, CommitOutput,
     ListTransactionsInput, ListTransactionsOutput, RollbackInput, RollbackOutput,
@@ -19,14 +51,27 @@ use crate::tools::transaction::{
 use crate::tools::write::{ExecuteInput, ExecuteOutput, WriteToolHandler};
 use rmcp::{
     ErrorData as McpError, Json, ServerHandler,
-    handler::server::tool::ToolRouter,
+    handler::server::tool::{ToolCallContext, ToolRouter},
     handler::server::wrapper::Parameters,
-    model::{Implementation, ProtocolVersion, ServerCapabilities, ServerInfo},
+    model::{
+        CallToolRequestParam, CallToolResult, CompleteRequestParam, CompleteResult, Extensions,
+        GetPromptRequestParam, GetPromptResult, Implementation, ListPromptsResult,
+        ListResourceTemplatesResult, ListResourcesResult, ListToolsResult, LoggingLevel,
+        PaginatedRequestParam, ProgressNotificationParam, ProtocolVersion,
+        ReadResourceRequestParam, ReadResourceResult, ServerCapabilities, ServerInfo,
+        SetLevelRequestParam,
+    },
     schemars::JsonSchema,
-    tool, tool_handler, tool_router,
+    service::{NotificationContext, RequestContext, RoleServer},
+    tool, tool_router,
 };
 use serde::Serialize;
+use std::path::PathBuf;
 use std::sync::Arc;
+use std::time::Instant;
+use tokio::sync::broadcast::error::RecvError;
+use tokio::sync::watch;
+use tracing::{Instrument, debug};
 
 /// Output for the list_connections tool.
 #[derive(Debug, Serialize, JsonSchema)]
@@ -41,12 +86,26 @@ pub struct ListConnectionsOutput {
 pub struct DbService {
     /// Shared connection manager for all database operations
     connection_manager: Arc<ConnectionManager>,
-    /// Shared transaction registry for transaction management
+    /// Transaction registry scoped to this MCP session
     transaction_registry: Arc<TransactionRegistry>,
+    /// Rolls back the session's open transactions when the last clone is dropped
+    _session: Arc<SessionGuard>,
+    /// Dropped with the last clone, stopping the session's notification task
+    session_closed: Arc<watch::Sender<()>>,
     /// Default query timeout in seconds (from config)
     default_query_timeout_secs: u64,
     /// Default row limit for queries (from config)
     default_row_limit: u32,
+    /// Directory containing migration files (from config)
+    migrations_dir: Option<PathBuf>,
+    /// Directory of additional prompt templates (from config)
+    prompts_dir: Option<PathBuf>,
+    /// Allow-lists for the admin tools, which are only routed when set
+    admin_policy: Option<AdminPolicy>,
+    /// Source of the log messages forwarded to the client
+    log_forwarder: Option<LogForwarder>,
+    /// Minimum level of forwarded log messages, set by `logging/setLevel`
+    log_level: Arc<watch::Sender<LoggingLevel>>,
     /// Tool router for MCP tool dispatch (auto-generated)
     tool_router: ToolRouter<Self>,
 }
@@ -67,13 +126,12 @@ impl DbService {
         connection_manager: Arc<ConnectionManager>,
         transaction_registry: Arc<TransactionRegistry>,
     ) -> Self {
-        Self {
+        Self::with_config(
             connection_manager,
             transaction_registry,
-            default_query_timeout_secs: DEFAULT_QUERY_TIMEOUT_SECS,
-            default_row_limit: DEFAULT_ROW_LIMIT,
-            tool_router: Self::tool_router(),
-        }
+            DEFAULT_QUERY_TIMEOUT_SECS,
+            DEFAULT_ROW_LIMIT,
+        )
     }
 
     /// Create a new DbService instance with custom timeout configuration.
@@ -90,15 +148,123 @@ impl DbService {
         query_timeout_secs: u64,
         row_limit: u32,
     ) -> Self {
+        // Each service instance serves one MCP session
+        let transaction_registry = Arc::new(transaction_registry.new_session());
         Self {
             connection_manager,
+            _session: Arc::new(SessionGuard::new(transaction_registry.as_ref().clone())),
+            session_closed: Arc::new(watch::channel(()).0),
             transaction_registry,
             default_query_timeout_secs: query_timeout_secs,
             default_row_limit: row_limit,
+            migrations_dir: None,
+            prompts_dir: None,
+            admin_policy: None,
+            log_forwarder: None,
+            log_level: Arc::new(watch::channel(DEFAULT_LOG_LEVEL).0),
             tool_router: Self::tool_router(),
         }
     }
 
+    /// Set the directory used by the migration tools.
+    pub fn with_migrations_dir(mut self, migrations_dir: Option<PathBuf>) -> Self {
+        self.migrations_dir = migrations_dir;
+        self
+    }
+
+    /// Set the directory of additional prompt templates.
+    pub fn with_prompts_dir(mut self, prompts_dir: Option<PathBuf>) -> Self {
+        self.prompts_dir = prompts_dir;
+        self
+    }
+
+    /// Enable the connection administration tools, restricted by `policy`.
+    ///
+    /// Without a policy the tools are not listed and cannot be called.
+    pub fn with_admin_policy(mut self, policy: Option<AdminPolicy>) -> Self {
+        self.tool_router = Self::tool_router();
+        if policy.is_some() {
+            self.tool_router.merge(Self::admin_tool_router());
+        }
+        self.admin_policy = policy;
+        self
+    }
+
+    /// Forward log messages from `forwarder` to the client as
+    /// `notifications/message`.
+    pub fn with_log_forwarder(mut self, forwarder: Option<LogForwarder>) -> Self {
+        self.log_forwarder = forwarder;
+        self
+    }
+
+    /// Id of this service's MCP session.
+    fn session_id(&self) -> &str {
+        self.transaction_registry.session_id().unwrap_or_default()
+    }
+
+    /// Roll back the open transactions of this service's session.
+    ///
+    /// Runs automatically when the last clone of the service is dropped;
+    /// transports call it directly when the client disconnects.
+    pub async fn close_session(&self) -> usize {
+        self.transaction_registry.rollback_session().await
+    }
+
+    /// Transaction registry for one request: this session, narrowed to the
+    /// authenticated HTTP client if any.
+    fn transactions(&self, extensions: &Extensions) -> Arc<TransactionRegistry> {
+        let identity = extensions
+            .get::<axum::http::request::Parts>()
+            .and_then(|parts| parts.extensions.get::<AuthIdentity>());
+        match identity {
+            Some(identity) => Arc::new(self.transaction_registry.with_identity(identity.as_str())),
+            None => self.transaction_registry.clone(),
+        }
+    }
+
+    /// Handler for the admin tools; fails when they are not enabled.
+    fn admin_handler(
+        &self,
+        context: &RequestContext<RoleServer>,
+    ) -> Result<AdminToolHandler, McpError> {
+        let policy = self.admin_policy.clone().ok_or_else(|| {
+            McpError::invalid_request(
+                "Admin tools are disabled. Start the server with --enable-admin-tools.",
+                None,
+            )
+        })?;
+        Ok(AdminToolHandler::new(
+            self.connection_manager.clone(),
+            self.transactions(&context.extensions),
+            policy,
+        ))
+    }
+
+    /// Control for a statement run by one request: it is cancelled with the
+    /// request (including when the client disconnects) and reports progress
+    /// when the client sent a progress token.
+    fn query_control(context: &RequestContext<RoleServer>) -> QueryControl {
+        let control = QueryControl::new().with_cancellation(context.ct.clone());
+        let Some(progress_token) = context.meta.get_progress_token() else {
+            return control;
+        };
+        let peer = context.peer.clone();
+        control.with_progress(move |progress| {
+            let peer = peer.clone();
+            let param = ProgressNotificationParam {
+                progress_token: progress_token.clone(),
+                progress: progress.elapsed.as_secs_f64(),
+                total: None,
+                message: Some(progress.message()),
+            };
+            tokio::spawn(async move {
+                if let Err(e) = peer.notify_progress(param).await {
+                    debug!(error = %e, "Failed to send progress notification");
+                }
+            });
+        })
+    }
+
     /// Validate connection ID - ensure it is provided and non-empty.
     ///
     /// Returns the trimmed connection ID if valid, otherwise returns an error
@@ -134,15 +300,17 @@ impl DbService {
     async fn query(
         &self,
         Parameters(input): Parameters<QueryInput>,
+        context: RequestContext<RoleServer>,
     ) -> Result<Json<QueryOutput>, McpError> {
         let mut input = input;
         input.connection_id = self.validate_connection_id(&input.connection_id)?;
         let handler = QueryToolHandler::with_defaults(
             self.connection_manager.clone(),
-            self.transaction_registry.clone(),
+            self.transactions(&context.extensions),
             self.default_query_timeout_secs,
             self.default_row_limit,
-        );
+        )
+        .with_control(Self::query_control(&context));
         handler.query(input).await.map(Json).map_err(Into::into)
     }
 
@@ -163,6 +331,19 @@ impl DbService {
             .map_err(Into::into)
     }
 
+    #[tool(
+        description = "Clear cached schema metadata for a connection so the next list_tables/describe_table reads fresh catalog data.\nUse after schema changes made outside this server."
+    )]
+    async fn refresh_schema(
+        &self,
+        Parameters(input): Parameters<RefreshSchemaInput>,
+    ) -> Result<Json<RefreshSchemaOutput>, McpError> {
+        let mut input = input;
+        input.connection_id = self.validate_connection_id(&input.connection_id)?;
+        let handler = SchemaToolHandler::new(self.connection_manager.clone());
+        handler.refresh_schema(input).map(Json).map_err(Into::into)
+    }
+
     #[tool(description = "List all tables and views in the database.")]
     async fn list_tables(
         &self,
@@ -195,6 +376,290 @@ impl DbService {
             .map_err(Into::into)
     }
 
+    #[tool(
+        description = "Get the definition (SQL) and output columns of a view.\nIncludes PostgreSQL materialized views."
+    )]
+    async fn describe_view(
+        &self,
+        Parameters(input): Parameters<DescribeViewInput>,
+    ) -> Result<Json<DescribeViewOutput>, McpError> {
+        let mut input = input;
+        input.connection_id = self.validate_connection_id(&input.connection_id)?;
+        let handler = SchemaToolHandler::new(self.connection_manager.clone());
+        handler
+            .describe_view(input)
+            .await
+            .map(Json)
+            .map_err(Into::into)
+    }
+
+    #[tool(
+        description = "List stored functions and procedures with their signatures.\nMySQL and PostgreSQL only. Use describe_routine to see a body."
+    )]
+    async fn list_routines(
+        &self,
+        Parameters(input): Parameters<ListRoutinesInput>,
+    ) -> Result<Json<ListRoutinesOutput>, McpError> {
+        let mut input = input;
+        input.connection_id = self.validate_connection_id(&input.connection_id)?;
+        let handler = SchemaToolHandler::new(self.connection_manager.clone());
+        handler
+            .list_routines(input)
+            .await
+            .map(Json)
+            .map_err(Into::into)
+    }
+
+    #[tool(
+        description = "Get the full definition of a stored function or procedure, including its body.\nReturns all overloads with the given name (PostgreSQL)."
+    )]
+    async fn describe_routine(
+        &self,
+        Parameters(input): Parameters<DescribeRoutineInput>,
+    ) -> Result<Json<DescribeRoutineOutput>, McpError> {
+        let mut input = input;
+        input.connection_id = self.validate_connection_id(&input.connection_id)?;
+        let handler = SchemaToolHandler::new(self.connection_manager.clone());
+        handler
+            .describe_routine(input)
+            .await
+            .map(Json)
+            .map_err(Into::into)
+    }
+
+    #[tool(
+        description = "List triggers with their timing (BEFORE/AFTER/INSTEAD OF), event and body.\nOptionally filter by table_name."
+    )]
+    async fn list_triggers(
+        &self,
+        Parameters(input): Parameters<ListTriggersInput>,
+    ) -> Result<Json<ListTriggersOutput>, McpError> {
+        let mut input = input;
+        input.connection_id = self.validate_connection_id(&input.connection_id)?;
+        let handler = SchemaToolHandler::new(self.connection_manager.clone());
+        handler
+            .list_triggers(input)
+            .await
+            .map(Json)
+            .map_err(Into::into)
+    }
+
+    #[tool(
+        description = "List sequences with their current values.\nMySQL and SQLite report per-table auto-increment counters instead."
+    )]
+    async fn list_sequences(
+        &self,
+        Parameters(input): Parameters<ListSequencesInput>,
+    ) -> Result<Json<ListSequencesOutput>, McpError> {
+        let mut input = input;
+        input.connection_id = self.validate_connection_id(&input.connection_id)?;
+        let handler = SchemaToolHandler::new(self.connection_manager.clone());
+        handler
+            .list_sequences(input)
+            .await
+            .map(Json)
+            .map_err(Into::into)
+    }
+
+    #[tool(
+        description = "Get CREATE TABLE DDL for a table, including indexes, foreign keys and comments.\n\
+        Returns the database's native DDL for SQLite (sqlite_master) and MySQL (SHOW CREATE TABLE); synthesized for PostgreSQL.\n\
+        Set dialect to render the table for another database type (e.g. a MySQL table as PostgreSQL DDL)."
+    )]
+    async fn show_create(
+        &self,
+        Parameters(input): Parameters<ShowCreateInput>,
+    ) -> Result<Json<ShowCreateOutput>, McpError> {
+        let mut input = input;
+        input.connection_id = self.validate_connection_id(&input.connection_id)?;
+        let handler = SchemaToolHandler::new(self.connection_manager.clone());
+        handler
+            .show_create(input)
+            .await
+            .map(Json)
+            .map_err(Into::into)
+    }
+
+    #[tool(
+        description = "Profile a table's columns: null fraction, distinct count, min/max, most frequent values and text lengths.\n\
+        Uses catalog statistics (pg_stats, MySQL index cardinality, SQLite sqlite_stat1) where available and fills the rest from a bounded sample.\n\
+        Use before writing queries to learn value distributions. Set use_catalog: false to profile the sample only."
+    )]
+    async fn profile_table(
+        &self,
+        Parameters(input): Parameters<ProfileTableInput>,
+    ) -> Result<Json<ProfileTableOutput>, McpError> {
+        let mut input = input;
+        input.connection_id = self.validate_connection_id(&input.connection_id)?;
+        let handler = ProfileToolHandler::new(
+            self.connection_manager.clone(),
+            self.default_query_timeout_secs,
+        );
+        handler
+            .profile_table(input)
+            .await
+            .map(Json)
+            .map_err(Into::into)
+    }
+
+    #[tool(
+        description = "Return a random sample of rows from a table, instead of the storage-order rows LIMIT gives.\n\
+        Uses TABLESAMPLE on PostgreSQL and random key ranges on SQLite (rowid) and MySQL (integer primary key), falling back to ORDER BY random.\n\
+        Optional filter (WHERE condition) restricts the sampled rows. Set stratify_by to get sample_size rows for each distinct value of a column."
+    )]
+    async fn sample_table(
+        &self,
+        Parameters(input): Parameters<SampleTableInput>,
+    ) -> Result<Json<SampleTableOutput>, McpError> {
+        let mut input = input;
+        input.connection_id = self.validate_connection_id(&input.connection_id)?;
+        let handler = SampleToolHandler::new(
+            self.connection_manager.clone(),
+            self.default_query_timeout_secs,
+        );
+        handler
+            .sample_table(input)
+            .await
+            .map(Json)
+            .map_err(Into::into)
+    }
+
+    #[tool(
+        description = "Get the foreign-key relationship graph of a database.\n\
+        format \"json\" returns per-table adjacency lists (references / referenced_by) and every relationship with its columns.\n\
+        format \"mermaid\" returns a Mermaid erDiagram."
+    )]
+    async fn relationship_graph(
+        &self,
+        Parameters(input): Parameters<RelationshipGraphInput>,
+    ) -> Result<Json<RelationshipGraphOutput>, McpError> {
+        let mut input = input;
+        input.connection_id = self.validate_connection_id(&input.connection_id)?;
+        let handler = RelationshipToolHandler::new(self.connection_manager.clone());
+        handler
+            .relationship_graph(input)
+            .await
+            .map(Json)
+            .map_err(Into::into)
+    }
+
+    #[tool(
+        description = "Find the shortest foreign-key path between two tables.\n\
+        Returns each hop with its join columns and a ready-made FROM ... JOIN ... ON ... clause.\n\
+        Use when you need to join tables that are not directly related."
+    )]
+    async fn find_join_path(
+        &self,
+        Parameters(input): Parameters<FindJoinPathInput>,
+    ) -> Result<Json<FindJoinPathOutput>, McpError> {
+        let mut input = input;
+        input.connection_id = self.validate_connection_id(&input.connection_id)?;
+        let handler = RelationshipToolHandler::new(self.connection_manager.clone());
+        handler
+            .find_join_path(input)
+            .await
+            .map(Json)
+            .map_err(Into::into)
+    }
+
+    #[tool(
+        description = "Search tables, views and columns by name and comment.\n\
+        Matches query words against name tokens (snake_case and camelCase are split) by exact, prefix, substring and fuzzy matching.\n\
+        Returns ranked hits with their table context. Use instead of list_tables on databases with many tables."
+    )]
+    async fn search_schema(
+        &self,
+        Parameters(input): Parameters<SearchSchemaInput>,
+    ) -> Result<Json<SearchSchemaOutput>, McpError> {
+        let mut input = input;
+        input.connection_id = self.validate_connection_id(&input.connection_id)?;
+        let handler = SearchToolHandler::new(self.connection_manager.clone());
+        handler
+            .search_schema(input)
+            .await
+            .map(Json)
+            .map_err(Into::into)
+    }
+
+    #[tool(
+        description = "Suggest indexes for one or more queries. Advisory only: nothing is created.\n\
+        Collects WHERE, JOIN, ORDER BY and GROUP BY columns per table, skips what existing indexes already serve and ranks the rest using EXPLAIN.\n\
+        Returns CREATE INDEX statements with a rationale, and flags duplicate or redundant existing indexes."
+    )]
+    async fn suggest_indexes(
+        &self,
+        Parameters(input): Parameters<SuggestIndexesInput>,
+    ) -> Result<Json<SuggestIndexesOutput>, McpError> {
+        let mut input = input;
+        input.connection_id = self.validate_connection_id(&input.connection_id)?;
+        let handler = IndexAdvisorToolHandler::new(self.connection_manager.clone());
+        handler
+            .suggest_indexes(input)
+            .await
+            .map(Json)
+            .map_err(Into::into)
+    }
+
+    #[tool(
+        description = "Compare the results of two SELECT queries, possibly on different connections/databases.\n\
+        Rows are matched on key_columns and reported as added (only in target), removed (only in source) or changed, with column-level differences.\n\
+        Both sides are streamed and capped at max_rows; order both queries by the key columns when results may exceed it."
+    )]
+    async fn compare_queries(
+        &self,
+        Parameters(input): Parameters<CompareQueriesInput>,
+    ) -> Result<Json<CompareQueriesOutput>, McpError> {
+        let mut input = input;
+        input.source.connection_id = self.validate_connection_id(&input.source.connection_id)?;
+        input.target.connection_id = self.validate_connection_id(&input.target.connection_id)?;
+        let handler = CompareToolHandler::new(self.connection_manager.clone());
+        handler
+            .compare_queries(input)
+            .await
+            .map(Json)
+            .map_err(Into::into)
+    }
+
+    #[tool(
+        description = "Compare the schemas of two connections/databases (source vs target).\n\
+        Reports added/removed tables, column type/nullability/default changes, and primary key, foreign key and index differences.\n\
+        Works across database types. With include_ddl, returns DDL in the target's dialect that makes the target match the source (not executed)."
+    )]
+    async fn diff_schema(
+        &self,
+        Parameters(input): Parameters<DiffSchemaInput>,
+    ) -> Result<Json<DiffSchemaOutput>, McpError> {
+        let mut input = input;
+        input.source.connection_id = self.validate_connection_id(&input.source.connection_id)?;
+        input.target.connection_id = self.validate_connection_id(&input.target.connection_id)?;
+        let handler = SchemaDiffToolHandler::new(self.connection_manager.clone());
+        handler
+            .diff_schema(input)
+            .await
+            .map(Json)
+            .map_err(Into::into)
+    }
+
+    #[tool(
+        description = "Compare the rows of a table on two connections/databases (source vs target), e.g. to verify replication, ETL loads or migrations.\n\
+        Splits the table into key ranges, compares per-range row counts and checksums, and narrows differing ranges down to row-level diffs.\n\
+        Reports added (only in target), removed (only in source) and changed rows. Uses the single-column primary key unless key_column is given."
+    )]
+    async fn diff_table_data(
+        &self,
+        Parameters(input): Parameters<DiffTableDataInput>,
+    ) -> Result<Json<DiffTableDataOutput>, McpError> {
+        let mut input = input;
+        input.source.connection_id = self.validate_connection_id(&input.source.connection_id)?;
+        input.target.connection_id = self.validate_connection_id(&input.target.connection_id)?;
+        let handler = DataDiffToolHandler::new(self.connection_manager.clone());
+        handler
+            .diff_table_data(input)
+            .await
+            .map(Json)
+            .map_err(Into::into)
+    }
+
     #[tool(
         description = "Execute a write operation (INSERT, UPDATE, DELETE, DDL).\n\
         Requires writable connection (writable: true).\n\
@@ -204,14 +669,16 @@ impl DbService {
     async fn execute(
         &self,
         Parameters(input): Parameters<ExecuteInput>,
+        context: RequestContext<RoleServer>,
     ) -> Result<Json<ExecuteOutput>, McpError> {
         let mut input = input;
         input.connection_id = self.validate_connection_id(&input.connection_id)?;
         let handler = WriteToolHandler::with_defaults(
             self.connection_manager.clone(),
-            self.transaction_registry.clone(),
+            self.transactions(&context.extensions),
             self.default_query_timeout_secs,
-        );
+        )
+        .with_control(Self::query_control(&context));
         handler.execute(input).await.map(Json).map_err(Into::into)
     }
 
@@ -221,12 +688,13 @@ impl DbService {
     async fn begin_transaction(
         &self,
         Parameters(input): Parameters<BeginTransactionInput>,
+        extensions: Extensions,
     ) -> Result<Json<BeginTransactionOutput>, McpError> {
         let mut input = input;
         input.connection_id = self.validate_connection_id(&input.connection_id)?;
         let handler = TransactionToolHandler::new(
             self.connection_manager.clone(),
-            self.transaction_registry.clone(),
+            self.transactions(&extensions),
         );
         handler
             .begin_transaction(input)
@@ -239,10 +707,11 @@ impl DbService {
     async fn commit(
         &self,
         Parameters(input): Parameters<CommitInput>,
+        extensions: Extensions,
     ) -> Result<Json<CommitOutput>, McpError> {
         let handler = TransactionToolHandler::new(
             self.connection_manager.clone(),
-            self.transaction_registry.clone(),
+            self.transactions(&extensions),
         );
         handler.commit(input).await.map(Json).map_err(Into::into)
     }
@@ -251,24 +720,26 @@ impl DbService {
     async fn rollback(
         &self,
         Parameters(input): Parameters<RollbackInput>,
+        extensions: Extensions,
     ) -> Result<Json<RollbackOutput>, McpError> {
         let handler = TransactionToolHandler::new(
             self.connection_manager.clone(),
-            self.transaction_registry.clone(),
+            self.transactions(&extensions),
         );
         handler.rollback(input).await.map(Json).map_err(Into::into)
     }
 
     #[tool(
-        description = "List all active database transactions.\nReturns transaction IDs, connection IDs, start times, and duration.\nLong-running transactions (>5 minutes) are flagged."
+        description = "List the active database transactions of this session.\nReturns transaction IDs, connection IDs, start times, and duration.\nLong-running transactions (>5 minutes) are flagged."
     )]
     async fn list_transactions(
         &self,
         Parameters(input): Parameters<ListTransactionsInput>,
+        extensions: Extensions,
     ) -> Result<Json<ListTransactionsOutput>, McpError> {
         let handler = TransactionToolHandler::new(
             self.connection_manager.clone(),
-            self.transaction_registry.clone(),
+            self.transactions(&extensions),
         );
         handler
             .list_transactions(input)
@@ -278,28 +749,161 @@ impl DbService {
     }
 
     #[tool(
-        description = "Show query execution plan without executing the query.\nSupports SELECT, INSERT, UPDATE, and DELETE statements.\nUseful for understanding query performance and index usage."
+        description = "Show query execution plan without executing the query.\nSupports SELECT, INSERT, UPDATE, and DELETE statements.\nReturns a normalized plan tree (operation, table, index, estimated rows/cost), a cost summary and findings such as full table scans and missing indexes.\nSet analyze=true to execute the statement (EXPLAIN ANALYZE) and compare actual with estimated rows; write statements run in a transaction that is always rolled back."
     )]
     async fn explain(
         &self,
         Parameters(input): Parameters<ExplainInput>,
+        context: RequestContext<RoleServer>,
     ) -> Result<Json<ExplainOutput>, McpError> {
         let mut input = input;
         input.connection_id = self.validate_connection_id(&input.connection_id)?;
         let handler = ExplainToolHandler::new(
             self.connection_manager.clone(),
-            self.transaction_registry.clone(),
-        );
+            self.transactions(&context.extensions),
+        )
+        .with_control(Self::query_control(&context));
         handler.explain(input).await.map(Json).map_err(Into::into)
     }
+
+    #[tool(
+        description = "Show migration status for a connection.\nCompares versioned .sql files in the migrations directory with the _mcp_migrations history table.\nReports applied, pending, drifted (checksum changed) and missing migrations."
+    )]
+    async fn migration_status(
+        &self,
+        Parameters(input): Parameters<MigrationStatusInput>,
+    ) -> Result<Json<MigrationStatusOutput>, McpError> {
+        let mut input = input;
+        input.connection_id = self.validate_connection_id(&input.connection_id)?;
+        let handler =
+            MigrationToolHandler::new(self.connection_manager.clone(), self.migrations_dir.clone());
+        handler.status(input).await.map(Json).map_err(Into::into)
+    }
+
+    #[tool(description = "Apply pending migrations in version order.\n\
+        Requires writable connection (writable: true).\n\
+        Each migration runs in its own transaction on PostgreSQL and SQLite.\n\
+        Refuses to run if an applied migration's checksum has drifted, or if a pending migration is older than the latest applied one (unless allow_out_of_order is set). Use dry_run to preview.")]
+    async fn migration_apply(
+        &self,
+        Parameters(input): Parameters<MigrationApplyInput>,
+    ) -> Result<Json<MigrationRunOutput>, McpError> {
+        let mut input = input;
+        input.connection_id = self.validate_connection_id(&input.connection_id)?;
+        let handler =
+            MigrationToolHandler::new(self.connection_manager.clone(), self.migrations_dir.clone());
+        handler.apply(input).await.map(Json).map_err(Into::into)
+    }
+
+    #[tool(
+        description = "Roll back the most recently applied migrations using their .down.sql files.\n\
+        Requires writable connection (writable: true). Default: 1 step. Use dry_run to preview."
+    )]
+    async fn migration_rollback(
+        &self,
+        Parameters(input): Parameters<MigrationRollbackInput>,
+    ) -> Result<Json<MigrationRunOutput>, McpError> {
+        let mut input = input;
+        input.connection_id = self.validate_connection_id(&input.connection_id)?;
+        let handler =
+            MigrationToolHandler::new(self.connection_manager.clone(), self.migrations_dir.clone());
+        handler.rollback(input).await.map(Json).map_err(Into::into)
+    }
+}
+
+/// Connection administration tools, routed only with an admin policy.
+#[tool_router(router = admin_tool_router)]
+impl DbService {
+    #[tool(
+        description = "Connect to a database and register it under a new connection ID.\n\
+        Takes a connection URL in the same format as --database (add ?writable=true to allow writes).\n\
+        The host or SQLite file must be on the server's admin allow-list."
+    )]
+    async fn add_connection(
+        &self,
+        Parameters(input): Parameters<AddConnectionInput>,
+        context: RequestContext<RoleServer>,
+    ) -> Result<Json<AddConnectionOutput>, McpError> {
+        let mut input = input;
+        input.connection_id = self.validate_connection_id(&input.connection_id)?;
+        self.admin_handler(&context)?
+            .add_connection(input)
+            .await
+            .map(Json)
+            .map_err(Into::into)
+    }
+
+    #[tool(description = "Remove a connection added with add_connection.\n\
+        Open transactions on it are rolled back (in every session) and its pools are closed. Connections from the server configuration cannot be removed.")]
+    async fn remove_connection(
+        &self,
+        Parameters(input): Parameters<RemoveConnectionInput>,
+        context: RequestContext<RoleServer>,
+    ) -> Result<Json<RemoveConnectionOutput>, McpError> {
+        let mut input = input;
+        input.connection_id = self.validate_connection_id(&input.connection_id)?;
+        self.admin_handler(&context)?
+            .remove_connection(input)
+            .await
+            .map(Json)
+            .map_err(Into::into)
+    }
+
+    #[tool(
+        description = "Check that a connection reaches its database.\nReturns the server version and the round-trip time in milliseconds."
+    )]
+    async fn test_connection(
+        &self,
+        Parameters(input): Parameters<TestConnectionInput>,
+        context: RequestContext<RoleServer>,
+    ) -> Result<Json<TestConnectionOutput>, McpError> {
+        let mut input = input;
+        input.connection_id = self.validate_connection_id(&input.connection_id)?;
+        self.admin_handler(&context)?
+            .test_connection(input)
+            .await
+            .map(Json)
+            .map_err(Into::into)
+    }
 }
 
-#[tool_handler]
 impl ServerHandler for DbService {
+    async fn call_tool(
+        &self,
+        request: CallToolRequestParam,
+        context: RequestContext<RoleServer>,
+    ) -> Result<CallToolResult, McpError> {
+        let tool = request.name.clone();
+        let started = Instant::now();
+        let result = self
+            .tool_router
+            .call(ToolCallContext::new(self, request, context))
+            .instrument(logging::session_span(self.session_id()))
+            .await;
+        let success = matches!(&result, Ok(r) if r.is_error != Some(true));
+        metrics::record_tool_call(&tool, started.elapsed(), success);
+        result
+    }
+
+    async fn list_tools(
+        &self,
+        _request: Option<PaginatedRequestParam>,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ListToolsResult, McpError> {
+        Ok(ListToolsResult::with_all_items(self.tool_router.list_all()))
+    }
+
     fn get_info(&self) -> ServerInfo {
         ServerInfo {
             protocol_version: ProtocolVersion::V_2025_03_26,
-            capabilities: ServerCapabilities::builder().enable_tools().build(),
+            capabilities: ServerCapabilities::builder()
+                .enable_tools()
+                .enable_prompts()
+                .enable_completions()
+                .enable_logging()
+                .enable_resources()
+                .enable_resources_list_changed()
+                .build(),
             server_info: Implementation {
                 name: "db-mcp-server".to_owned(),
                 title: Some("DB MCP Server".to_owned()),
@@ -320,20 +924,177 @@ impl ServerHandler for DbService {
                 2. `query`/`execute` with transaction_id → operations within transaction\n\
                 3. `commit` or `rollback` with transaction_id → finalize\n\
                 \n\
+                ## Long-Running Statements\n\
+                query, execute and explain send progress notifications (elapsed time, rows fetched) when the request carries a progress token. \
+                Cancelling the request stops the statement on the database server unless it runs in a transaction.\n\
+                \n\
+                ## Logging\n\
+                Server log messages (truncated results, slow statements, connection events) are sent as notifications at info level and above; \
+                use logging/setLevel to change the minimum level.\n\
+                \n\
                 ## Tools by Category\n\
-                - **Read-only**: query, list_tables, describe_table, list_databases, explain\n\
+                - **Read-only**: query, list_tables, describe_table, show_create, describe_view, list_routines, describe_routine, list_triggers, list_sequences, list_databases, explain, profile_table, sample_table, relationship_graph, find_join_path, search_schema, suggest_indexes, compare_queries, diff_schema, diff_table_data\n\
                 - **Write** (requires writable: true): execute\n\
                 - **Transaction**: begin_transaction, commit, rollback\n\
-                - **Utility**: list_connections, list_transactions\n\
+                - **Migration**: migration_status, migration_apply, migration_rollback (prefer these over ad-hoc DDL via execute)\n\
+                - **Admin** (only when enabled on the server): add_connection, remove_connection, test_connection\n\
+                - **Utility**: list_connections, list_transactions, refresh_schema\n\
+                \n\
+                ## Resources\n\
+                Table schemas are available as `db://{connection_id}/{database}/{table}` resources and row samples as `.../sample` \
+                (`default` as database means the connection's own database).\n\
+                \n\
+                ## Prompts\n\
+                explore_database, explain_slow_query, safe_migration and summarize_table are prefilled with schema context.\n\
                 \n\
                 ## Database-Specific Notes\n\
                 - MySQL: Cross-database queries supported (use `db.table` syntax or `database` parameter)\n\
                 - PostgreSQL: Queries cannot span databases (use `database` parameter to switch)\n\
-                - SQLite: list_databases not supported (file-based)"
+                - SQLite: list_databases, list_routines and describe_routine not supported"
                     .to_string(),
             ),
         }
     }
+
+    async fn list_prompts(
+        &self,
+        _request: Option<PaginatedRequestParam>,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ListPromptsResult, McpError> {
+        let library = PromptLibrary::load(self.prompts_dir.as_deref());
+        Ok(ListPromptsResult::with_all_items(library.list()))
+    }
+
+    async fn get_prompt(
+        &self,
+        request: GetPromptRequestParam,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<GetPromptResult, McpError> {
+        let library = PromptLibrary::load(self.prompts_dir.as_deref());
+        let Some(template) = library.get(&request.name) else {
+            return Err(McpError::invalid_params(
+                format!("Unknown prompt '{}'", request.name),
+                None,
+            ));
+        };
+        let renderer = PromptRenderer::new(self.connection_manager.clone());
+        renderer
+            .render(template, request.arguments)
+            .await
+            .map_err(Into::into)
+    }
+
+    async fn complete(
+        &self,
+        request: CompleteRequestParam,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<CompleteResult, McpError> {
+        let provider = CompletionProvider::new(self.connection_manager.clone());
+        Ok(CompleteResult {
+            completion: provider.complete(&request).await,
+        })
+    }
+
+    async fn list_resources(
+        &self,
+        _request: Option<PaginatedRequestParam>,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ListResourcesResult, McpError> {
+        let provider = ResourceProvider::new(
+            self.connection_manager.clone(),
+            self.default_query_timeout_secs,
+        );
+        Ok(ListResourcesResult::with_all_items(provider.list().await))
+    }
+
+    async fn list_resource_templates(
+        &self,
+        _request: Option<PaginatedRequestParam>,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ListResourceTemplatesResult, McpError> {
+        Ok(ListResourceTemplatesResult::with_all_items(
+            resources::resource_templates(),
+        ))
+    }
+
+    async fn read_resource(
+        &self,
+        request: ReadResourceRequestParam,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ReadResourceResult, McpError> {
+        let provider = ResourceProvider::new(
+            self.connection_manager.clone(),
+            self.default_query_timeout_secs,
+        );
+        let contents = provider
+            .read(&request.uri)
+            .instrument(logging::session_span(self.session_id()))
+            .await?;
+        Ok(ReadResourceResult {
+            contents: vec![contents],
+        })
+    }
+
+    async fn set_level(
+        &self,
+        request: SetLevelRequestParam,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<(), McpError> {
+        self.log_level.send_replace(request.level);
+        Ok(())
+    }
+
+    async fn on_initialized(&self, context: NotificationContext<RoleServer>) {
+        let peer = context.peer;
+
+        // Forward log messages at or above the client's level until the
+        // session ends. Nothing is logged here: it would be forwarded too.
+        if let Some(forwarder) = &self.log_forwarder {
+            let mut messages = forwarder.subscribe(self.session_id());
+            let level = self.log_level.subscribe();
+            let mut closed = self.session_closed.subscribe();
+            let peer = peer.clone();
+            tokio::spawn(async move {
+                loop {
+                    tokio::select! {
+                        message = messages.recv() => match message {
+                            Ok(message) => {
+                                if !logging::is_enabled(message.level, *level.borrow()) {
+                                    continue;
+                                }
+                                if peer.notify_logging_message(message).await.is_err() {
+                                    break;
+                                }
+                            }
+                            Err(RecvError::Lagged(_)) => continue,
+                            Err(RecvError::Closed) => break,
+                        },
+                        _ = closed.changed() => break,
+                    }
+                }
+            });
+        }
+
+        // Forward schema invalidations (DDL, migrations, refresh_schema) to
+        // the client until the session ends
+        let mut changes = self.connection_manager.schema_cache().subscribe();
+        let mut closed = self.session_closed.subscribe();
+        tokio::spawn(async move {
+            loop {
+                tokio::select! {
+                    change = changes.recv() => match change {
+                        Ok(_) | Err(RecvError::Lagged(_)) => {
+                            if peer.notify_resource_list_changed().await.is_err() {
+                                break;
+                            }
+                        }
+                        Err(RecvError::Closed) => break,
+                    },
+                    _ = closed.changed() => break,
+                }
+            }
+        });
+    }
 }
 
 #[cfg(test)]
@@ -351,6 +1112,21 @@ mod tests {
         let _service = create_test_service();
     }
 
+    #[test]
+    fn test_admin_tools_require_policy() {
+        let service = create_test_service();
+        assert!(!service.tool_router.has_route("add_connection"));
+        assert!(service.tool_router.has_route("query"));
+
+        let service = service.with_admin_policy(Some(AdminPolicy::default()));
+        for tool in ["add_connection", "remove_connection", "test_connection"] {
+            assert!(service.tool_router.has_route(tool), "{tool}");
+        }
+
+        let service = service.with_admin_policy(None);
+        assert!(!service.tool_router.has_route("remove_connection"));
+    }
+
     #[test]
     fn test_validate_connection_id_with_valid() {
         let service = create_test_service();
@@ -384,5 +1160,9 @@ mod tests {
         // from_build_env() uses rmcp's own package name, which is expected
         assert!(!info.server_info.name.is_empty());
         assert!(info.capabilities.tools.is_some());
+        assert!(info.capabilities.prompts.is_some());
+        assert!(info.capabilities.completions.is_some());
+        let resources = info.capabilities.resources.expect("resources capability");
+        assert_eq!(resources.list_changed, Some(true));
     }
 }
@@ -0,0 +1,187 @@
+//! Prometheus metrics.
+//!
+//! Tool calls and errors are recorded through the `metrics` facade, which
+//! discards them until [`install`] sets up the Prometheus recorder, so nothing
+//! is collected unless metrics are enabled. Pool and transaction gauges are
+//! sampled when `/metrics` is scraped.
+//!
+//! | Metric | Type | Labels |
+//! |--------|------|--------|
+//! | `db_mcp_tool_calls_total` | counter | `tool`, `outcome` (`success`/`error`) |
+//! | `db_mcp_tool_call_duration_seconds` | histogram | `tool` |
+//! | `db_mcp_errors_total` | counter | `kind` ([`DbError::kind`]) |
+//! | `db_mcp_pool_connections` | gauge | `connection_id` |
+//! | `db_mcp_pool_idle_connections` | gauge | `connection_id` |
+//! | `db_mcp_database_pools` | gauge | `connection_id` |
+//! | `db_mcp_database_pools_active` | gauge | `connection_id` |
+//! | `db_mcp_open_transactions` | gauge | - |
+
+use crate::db::{ConnectionManager, TransactionRegistry};
+use crate::error::{DbError, DbResult};
+use axum::Router;
+use axum::extract::State;
+use axum::http::header;
+use axum::response::IntoResponse;
+use axum::routing::get;
+use metrics_exporter_prometheus::{Matcher, PrometheusBuilder, PrometheusHandle};
+use metrics_util::MetricKindMask;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::net::TcpListener;
+use tracing::{error, info};
+
+/// Path of the scrape endpoint.
+pub const METRICS_PATH: &str = "/metrics";
+
+const TOOL_CALL_DURATION: &str = "db_mcp_tool_call_duration_seconds";
+
+/// Histogram buckets for tool call durations, in seconds.
+const TOOL_CALL_BUCKETS: &[f64] = &[
+    0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0, 60.0,
+];
+
+/// Gauges not sampled for this long are dropped, e.g. those of a removed
+/// connection. Scrape intervals must be shorter.
+const STALE_GAUGE_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// Install the Prometheus recorder for the whole process.
+///
+/// Fails if a recorder is already installed.
+pub fn install() -> DbResult<PrometheusHandle> {
+    PrometheusBuilder::new()
+        .set_buckets_for_metric(
+            Matcher::Full(TOOL_CALL_DURATION.to_string()),
+            TOOL_CALL_BUCKETS,
+        )
+        .and_then(|builder| {
+            builder
+                .idle_timeout(MetricKindMask::GAUGE, Some(STALE_GAUGE_TIMEOUT))
+                .install_recorder()
+        })
+        .map_err(|e| DbError::internal(format!("Failed to install metrics recorder: {}", e)))
+}
+
+/// Record a finished tool call.
+pub fn record_tool_call(tool: &str, duration: Duration, success: bool) {
+    let outcome = if success { "success" } else { "error" };
+    ::metrics::counter!(
+        "db_mcp_tool_calls_total",
+        "tool" => tool.to_string(),
+        "outcome" => outcome
+    )
+    .increment(1);
+    ::metrics::histogram!(TOOL_CALL_DURATION, "tool" => tool.to_string())
+        .record(duration.as_secs_f64());
+}
+
+/// Record an error returned to a client.
+pub fn record_error(error: &DbError) {
+    ::metrics::counter!("db_mcp_errors_total", "kind" => error.kind()).increment(1);
+}
+
+/// Renders the scrape payload, sampling pool and transaction gauges first.
+#[derive(Clone)]
+pub struct MetricsEndpoint {
+    handle: PrometheusHandle,
+    connection_manager: Arc<ConnectionManager>,
+    transaction_registry: Arc<TransactionRegistry>,
+}
+
+impl MetricsEndpoint {
+    pub fn new(
+        handle: PrometheusHandle,
+        connection_manager: Arc<ConnectionManager>,
+        transaction_registry: Arc<TransactionRegistry>,
+    ) -> Self {
+        Self {
+            handle,
+            connection_manager,
+            transaction_registry,
+        }
+    }
+
+    /// Prometheus text exposition of all metrics.
+    pub async fn render(&self) -> String {
+        for stats in self.connection_manager.pool_stats().await {
+            let id = stats.connection_id;
+            ::metrics::gauge!("db_mcp_pool_connections", "connection_id" => id.clone())
+                .set(stats.connections as f64);
+            ::metrics::gauge!("db_mcp_pool_idle_connections", "connection_id" => id.clone())
+                .set(stats.idle_connections as f64);
+            ::metrics::gauge!("db_mcp_database_pools", "connection_id" => id.clone())
+                .set(stats.database_pools as f64);
+            ::metrics::gauge!("db_mcp_database_pools_active", "connection_id" => id)
+                .set(stats.active_database_pools as f64);
+        }
+        ::metrics::gauge!("db_mcp_open_transactions")
+            .set(self.transaction_registry.count().await as f64);
+
+        self.handle.run_upkeep();
+        self.handle.render()
+    }
+
+    /// Router serving [`METRICS_PATH`].
+    pub fn router(self) -> Router {
+        Router::new()
+            .route(METRICS_PATH, get(serve_metrics))
+            .with_state(self)
+    }
+
+    /// Serve [`METRICS_PATH`] on its own listener in the background.
+    pub async fn spawn(self, bind_addr: &str) -> DbResult<()> {
+        let listener = TcpListener::bind(bind_addr).await.map_err(|e| {
+            DbError::connection(
+                format!("Failed to bind metrics listener to {}: {}", bind_addr, e),
+                "Check that the metrics port is available",
+            )
+        })?;
+        info!(addr = %bind_addr, path = METRICS_PATH, "Metrics endpoint ready");
+        let app = self.router();
+        tokio::spawn(async move {
+            if let Err(e) = axum::serve(listener, app).await {
+                error!(error = %e, "Metrics server error");
+            }
+        });
+        Ok(())
+    }
+}
+
+async fn serve_metrics(State(endpoint): State<MetricsEndpoint>) -> impl IntoResponse {
+    (
+        [(header::CONTENT_TYPE, "text/plain; version=0.0.4")],
+        endpoint.render().await,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_recorded_metrics_render() {
+        let recorder = PrometheusBuilder::new()
+            .set_buckets_for_metric(
+                Matcher::Full(TOOL_CALL_DURATION.to_string()),
+                TOOL_CALL_BUCKETS,
+            )
+            .unwrap()
+            .build_recorder();
+        let handle = recorder.handle();
+
+        ::metrics::with_local_recorder(&recorder, || {
+            record_tool_call("query", Duration::from_millis(20), true);
+            record_tool_call("query", Duration::from_millis(700), false);
+            record_error(&DbError::timeout("query execution", 1));
+        });
+
+        let output = handle.render();
+        assert!(output.contains(r#"db_mcp_tool_calls_total{tool="query",outcome="success"} 1"#));
+        assert!(output.contains(r#"db_mcp_tool_calls_total{tool="query",outcome="error"} 1"#));
+        assert!(
+            output
+                .contains(r#"db_mcp_tool_call_duration_seconds_bucket{tool="query",le="0.025"} 1"#)
+        );
+        assert!(output.contains(r#"db_mcp_tool_call_duration_seconds_count{tool="query"} 2"#));
+        assert!(output.contains(r#"db_mcp_errors_total{kind="timeout"} 1"#));
+    }
+}
@@ -49,6 +49,17 @@ impl DatabaseType {
             Self::SQLite => None,
         }
     }
+
+    /// Quote an identifier (table, column, index name) for this database.
+    ///
+    /// MySQL uses backticks; PostgreSQL and SQLite use double quotes.
+    /// Embedded quote characters are escaped by doubling them.
+    pub fn quote_identifier(&self, identifier: &str) -> String {
+        match self {
+            Self::MySQL => format!("`{}`", identifier.replace('`', "``")),
+            Self::PostgreSQL | Self::SQLite => format!("\"{}\"", identifier.replace('"', "\"\"")),
+        }
+    }
 }
 
 impl std::fmt::Display for DatabaseType {
@@ -58,7 +69,7 @@ impl std::fmt::Display for DatabaseType {
 }
 
 /// Configuration for a database connection.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ConnectionConfig {
     pub id: String,
     pub db_type: DatabaseType,
@@ -133,7 +144,9 @@ impl ConnectionConfig {
         })
     }
 
-    fn parse_url_components(connection_string: &str) -> (Option<String>, Option<u16>, Option<String>) {
+    fn parse_url_components(
+        connection_string: &str,
+    ) -> (Option<String>, Option<u16>, Option<String>) {
         let Ok(url) = Url::parse(connection_string) else {
             return (None, None, None);
         };
@@ -241,6 +254,17 @@ impl TransactionInfo {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_quote_identifier() {
+        assert_eq!(DatabaseType::MySQL.quote_identifier("users"), "`users`");
+        assert_eq!(DatabaseType::MySQL.quote_identifier("a`b"), "`a``b`");
+        assert_eq!(
+            DatabaseType::PostgreSQL.quote_identifier("users"),
+            "\"users\""
+        );
+        assert_eq!(DatabaseType::SQLite.quote_identifier("a\"b"), "\"a\"\"b\"");
+    }
+
     #[test]
     fn test_database_type_from_connection_string() {
         assert_eq!(
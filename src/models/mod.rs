@@ -16,6 +16,7 @@ pub use query::{
     MAX_QUERY_TIMEOUT_SECS, MAX_ROW_LIMIT, QueryParam, QueryParamInput, QueryRequest, QueryResult,
 };
 pub use schema::{
-    ColumnDefinition, DescribeTableRequest, ForeignKey, ForeignKeyAction, IndexInfo,
-    ListTablesRequest, TableInfo, TableSchema, TableType,
+    ColumnDefinition, ColumnStatistics, DescribeTableRequest, ForeignKey, ForeignKeyAction,
+    IndexInfo, ListTablesRequest, RoutineInfo, RoutineType, SequenceInfo, TableInfo, TableSchema,
+    TableStatistics, TableType, TriggerInfo, ViewSchema,
 };
@@ -186,6 +186,8 @@ pub struct TableSchema {
     pub primary_key: Vec<String>,
     pub foreign_keys: Vec<ForeignKey>,
     pub indexes: Vec<IndexInfo>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
 }
 
 impl TableSchema {
@@ -198,6 +200,7 @@ impl TableSchema {
             primary_key: Vec::new(),
             foreign_keys: Vec::new(),
             indexes: Vec::new(),
+            comment: None,
         }
     }
 
@@ -219,6 +222,12 @@ impl TableSchema {
         self
     }
 
+    /// Set the table comment.
+    pub fn with_comment(mut self, comment: impl Into<String>) -> Self {
+        self.comment = Some(comment.into());
+        self
+    }
+
     /// Get the fully qualified table name.
     pub fn qualified_name(&self) -> String {
         match &self.schema_name {
@@ -445,6 +454,290 @@ impl IndexInfo {
     }
 }
 
+/// A view (or materialized view) with its defining query.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ViewSchema {
+    pub view_name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub schema_name: Option<String>,
+    /// PostgreSQL only
+    pub materialized: bool,
+    /// SELECT statement (PostgreSQL, MySQL) or full CREATE VIEW (SQLite)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub definition: Option<String>,
+    pub columns: Vec<ColumnDefinition>,
+}
+
+impl ViewSchema {
+    /// Create a new view schema.
+    pub fn new(view_name: impl Into<String>) -> Self {
+        Self {
+            view_name: view_name.into(),
+            schema_name: None,
+            materialized: false,
+            definition: None,
+            columns: Vec::new(),
+        }
+    }
+
+    /// Set the schema name.
+    pub fn with_schema(mut self, schema: impl Into<String>) -> Self {
+        self.schema_name = Some(schema.into());
+        self
+    }
+
+    /// Set whether this is a materialized view.
+    pub fn with_materialized(mut self, materialized: bool) -> Self {
+        self.materialized = materialized;
+        self
+    }
+
+    /// Set the view definition.
+    pub fn with_definition(mut self, definition: impl Into<String>) -> Self {
+        self.definition = Some(definition.into());
+        self
+    }
+}
+
+/// Kind of stored routine.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RoutineType {
+    Function,
+    Procedure,
+    /// PostgreSQL only
+    Aggregate,
+    /// PostgreSQL only
+    Window,
+}
+
+impl RoutineType {
+    /// Parse routine type from database-specific string.
+    pub fn parse(s: &str) -> Self {
+        match s.to_lowercase().as_str() {
+            "procedure" | "p" => Self::Procedure,
+            "aggregate" | "a" => Self::Aggregate,
+            "window" | "w" => Self::Window,
+            _ => Self::Function,
+        }
+    }
+}
+
+impl std::fmt::Display for RoutineType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Function => write!(f, "function"),
+            Self::Procedure => write!(f, "procedure"),
+            Self::Aggregate => write!(f, "aggregate"),
+            Self::Window => write!(f, "window"),
+        }
+    }
+}
+
+/// A stored function or procedure.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RoutineInfo {
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub schema_name: Option<String>,
+    pub routine_type: RoutineType,
+    /// Argument list (e.g., `a integer, b text`, `IN id int`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub arguments: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub return_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+    /// Full definition (PostgreSQL) or routine body (MySQL); only set by describe
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub definition: Option<String>,
+}
+
+impl RoutineInfo {
+    /// Create a new routine info.
+    pub fn new(name: impl Into<String>, routine_type: RoutineType) -> Self {
+        Self {
+            name: name.into(),
+            schema_name: None,
+            routine_type,
+            arguments: None,
+            return_type: None,
+            language: None,
+            definition: None,
+        }
+    }
+
+    /// Set the schema name.
+    pub fn with_schema(mut self, schema: impl Into<String>) -> Self {
+        self.schema_name = Some(schema.into());
+        self
+    }
+
+    /// Set the argument list.
+    pub fn with_arguments(mut self, arguments: impl Into<String>) -> Self {
+        self.arguments = Some(arguments.into());
+        self
+    }
+
+    /// Set the return type.
+    pub fn with_return_type(mut self, return_type: impl Into<String>) -> Self {
+        self.return_type = Some(return_type.into());
+        self
+    }
+
+    /// Set the implementation language.
+    pub fn with_language(mut self, language: impl Into<String>) -> Self {
+        self.language = Some(language.into());
+        self
+    }
+
+    /// Set the routine definition.
+    pub fn with_definition(mut self, definition: impl Into<String>) -> Self {
+        self.definition = Some(definition.into());
+        self
+    }
+}
+
+/// A trigger attached to a table or view.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TriggerInfo {
+    pub name: String,
+    pub table_name: String,
+    /// BEFORE, AFTER or INSTEAD OF
+    pub timing: String,
+    /// INSERT, UPDATE, DELETE or TRUNCATE (combined with OR when several)
+    pub event: String,
+    /// Full CREATE TRIGGER statement (PostgreSQL, SQLite)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub definition: Option<String>,
+    /// Statements executed by the trigger (the trigger function's source on PostgreSQL)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub body: Option<String>,
+}
+
+impl TriggerInfo {
+    /// Create a new trigger info.
+    pub fn new(
+        name: impl Into<String>,
+        table_name: impl Into<String>,
+        timing: impl Into<String>,
+        event: impl Into<String>,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            table_name: table_name.into(),
+            timing: timing.into(),
+            event: event.into(),
+            definition: None,
+            body: None,
+        }
+    }
+
+    /// Set the full trigger definition.
+    pub fn with_definition(mut self, definition: impl Into<String>) -> Self {
+        self.definition = Some(definition.into());
+        self
+    }
+
+    /// Set the trigger body.
+    pub fn with_body(mut self, body: impl Into<String>) -> Self {
+        self.body = Some(body.into());
+        self
+    }
+}
+
+/// A sequence or auto-increment counter.
+///
+/// PostgreSQL reports real sequences. MySQL and SQLite have no sequence
+/// objects, so their per-table auto-increment counters are reported instead.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SequenceInfo {
+    pub name: String,
+    /// Table owning the sequence (`OWNED BY` / auto-increment table)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub table_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub column_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data_type: Option<String>,
+    /// Last value handed out; None if the sequence has not been used yet
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub current_value: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start_value: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub increment: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_value: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_value: Option<i64>,
+}
+
+impl SequenceInfo {
+    /// Create a new sequence info.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Set the owning table and column.
+    pub fn with_owner(mut self, table: impl Into<String>, column: Option<String>) -> Self {
+        self.table_name = Some(table.into());
+        self.column_name = column;
+        self
+    }
+
+    /// Set the last value handed out.
+    pub fn with_current_value(mut self, value: Option<i64>) -> Self {
+        self.current_value = value;
+        self
+    }
+}
+
+/// Planner statistics kept in the database catalog for a table.
+///
+/// Values are estimates and only as fresh as the last ANALYZE.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TableStatistics {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub row_count_estimate: Option<u64>,
+    pub columns: Vec<ColumnStatistics>,
+}
+
+impl TableStatistics {
+    /// Get the statistics for a column, if the catalog has any.
+    pub fn column(&self, name: &str) -> Option<&ColumnStatistics> {
+        self.columns.iter().find(|c| c.column == name)
+    }
+}
+
+/// Catalog statistics for a single column.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ColumnStatistics {
+    pub column: String,
+    /// PostgreSQL only
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub null_fraction: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub distinct_count: Option<u64>,
+    /// Most common values (as text) with the fraction of rows holding them.
+    /// PostgreSQL only
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub most_common_values: Vec<(String, f64)>,
+}
+
+impl ColumnStatistics {
+    /// Create empty statistics for a column.
+    pub fn new(column: impl Into<String>) -> Self {
+        Self {
+            column: column.into(),
+            ..Default::default()
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ListTablesRequest {
     pub connection_id: String,
@@ -577,6 +870,14 @@ mod tests {
         assert_eq!(schema.columns.len(), 2);
     }
 
+    #[test]
+    fn test_routine_type_parsing() {
+        assert_eq!(RoutineType::parse("PROCEDURE"), RoutineType::Procedure);
+        assert_eq!(RoutineType::parse("FUNCTION"), RoutineType::Function);
+        assert_eq!(RoutineType::parse("a"), RoutineType::Aggregate);
+        assert_eq!(RoutineType::Window.to_string(), "window");
+    }
+
     #[test]
     fn test_foreign_key_action_parsing() {
         assert_eq!(
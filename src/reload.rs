@@ -0,0 +1,348 @@
+//! Configuration reload without restart.
+//!
+//! The file given with `--config-file` adds connections and auth tokens to
+//! those given on the command line:
+//!
+//! ```json
+//! {
+//!   "databases": ["app=postgres://user:pass@db/app?writable=true"],
+//!   "auth_tokens": ["secret"]
+//! }
+//! ```
+//!
+//! The file is re-read on SIGHUP and whenever its modification time changes.
+//! A [`Reloader`] diffs the result against the [`ConnectionManager`]: new
+//! connections are added, changed ones (URL, access or pool options) get new
+//! pools, and removed ones are unregistered and closed once their running
+//! operations have finished. Open transactions on changed or removed
+//! connections are rolled back. Unchanged connections are not touched. The HTTP token list is swapped atomically. An invalid file is
+//! ignored as a whole and the running configuration stays in place.
+
+use crate::auth::{AuthConfig, SharedAuthConfig};
+use crate::config::{Config, DatabaseConfig};
+use crate::db::{ConnectionManager, RemovedConnection, TransactionRegistry};
+use crate::error::{DbError, DbResult};
+use crate::models::ConnectionConfig;
+use serde::Deserialize;
+use std::collections::HashSet;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+use tracing::{error, info, warn};
+
+/// How often the config file's modification time is checked.
+const CONFIG_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Contents of the `--config-file` JSON file.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ConfigFile {
+    /// Connection strings, in the `--database` format
+    #[serde(default)]
+    pub databases: Vec<String>,
+    /// Bearer tokens for the HTTP transport
+    #[serde(default)]
+    pub auth_tokens: Vec<String>,
+}
+
+impl ConfigFile {
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let contents = std::fs::read_to_string(path)
+            .map_err(|e| format!("Cannot read {}: {}", path.display(), e))?;
+        serde_json::from_str(&contents).map_err(|e| format!("Invalid {}: {}", path.display(), e))
+    }
+}
+
+/// The parts of the configuration that can change while the server runs.
+#[derive(Debug, Clone)]
+pub struct ReloadableConfig {
+    pub connections: Vec<ConnectionConfig>,
+    pub auth_tokens: Vec<String>,
+}
+
+impl ReloadableConfig {
+    /// Connections and tokens from the command line plus the config file.
+    pub fn load(config: &Config) -> Result<Self, String> {
+        let mut databases = config.databases.clone();
+        let mut auth_tokens = config.auth_tokens.clone();
+        if let Some(path) = &config.config_file {
+            let file = ConfigFile::load(path)?;
+            databases.extend(file.databases);
+            auth_tokens.extend(file.auth_tokens);
+        }
+
+        let mut ids = HashSet::new();
+        let mut connections = Vec::with_capacity(databases.len());
+        for database in &databases {
+            let connection = DatabaseConfig::parse(database)?
+                .to_connection_config()
+                .map_err(|e| e.to_string())?;
+            if !ids.insert(connection.id.clone()) {
+                return Err(format!("Duplicate connection ID '{}'", connection.id));
+            }
+            connections.push(connection);
+        }
+
+        Ok(Self {
+            connections,
+            auth_tokens,
+        })
+    }
+}
+
+/// What a reload changed.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ReloadSummary {
+    /// Connections registered
+    pub added: Vec<String>,
+    /// Connections whose pools were recreated for a changed configuration
+    pub updated: Vec<String>,
+    /// Connections unregistered and closed
+    pub removed: Vec<String>,
+    /// Connections that could not be added or updated; they are retried on
+    /// the next reload
+    pub failed: Vec<String>,
+    /// Connections left as they were
+    pub unchanged: usize,
+}
+
+/// Applies reloaded configurations to the running server.
+pub struct Reloader {
+    connection_manager: Arc<ConnectionManager>,
+    transaction_registry: Arc<TransactionRegistry>,
+    auth: Option<SharedAuthConfig>,
+    /// Whether the server started with HTTP auth, which a reload may not
+    /// turn off
+    auth_required: bool,
+    /// Connections from the configuration; connections added at runtime with
+    /// the admin tools are never removed by a reload
+    managed: HashSet<String>,
+}
+
+impl Reloader {
+    /// Create a reloader for a server started with `initial`.
+    pub fn new(
+        connection_manager: Arc<ConnectionManager>,
+        transaction_registry: Arc<TransactionRegistry>,
+        initial: &ReloadableConfig,
+    ) -> Self {
+        Self {
+            connection_manager,
+            transaction_registry,
+            auth: None,
+            auth_required: false,
+            managed: initial.connections.iter().map(|c| c.id.clone()).collect(),
+        }
+    }
+
+    /// Also swap the HTTP transport's auth tokens on reload.
+    pub fn with_auth(mut self, auth: SharedAuthConfig) -> Self {
+        self.auth_required = auth.current().is_enabled();
+        self.auth = Some(auth);
+        self
+    }
+
+    /// Bring the running server in line with `config`.
+    ///
+    /// Fails without changing anything when the token list is invalid, or
+    /// empty on a server that started with auth enabled.
+    pub async fn apply(&mut self, config: ReloadableConfig) -> DbResult<ReloadSummary> {
+        let auth_config = AuthConfig::from_tokens(config.auth_tokens)
+            .map_err(|e| DbError::invalid_input(format!("Invalid auth tokens: {}", e)))?;
+        if self.auth_required && !auth_config.is_enabled() {
+            error!(
+                "Refusing to reload a configuration without auth tokens; authentication stays enabled"
+            );
+            return Err(DbError::invalid_input(
+                "The configuration has no auth tokens. Authentication was enabled at startup and cannot be disabled by a reload",
+            ));
+        }
+        if let Some(auth) = &self.auth {
+            info!(
+                token_count = auth_config.token_count(),
+                "Authentication tokens reloaded"
+            );
+            auth.replace(auth_config);
+        }
+
+        let mut summary = ReloadSummary::default();
+        let mut managed = HashSet::new();
+        for connection in config.connections {
+            let id = connection.id.clone();
+            match self.connection_manager.get_config(&id).await {
+                Ok(current) if current == connection => summary.unchanged += 1,
+                Ok(_) => match self.connection_manager.replace(connection).await {
+                    Ok((_, old)) => {
+                        self.transaction_registry.rollback_connection(&id).await;
+                        drain(old);
+                        summary.updated.push(id.clone());
+                    }
+                    Err(e) => {
+                        warn!(connection_id = %id, error = %e, "Failed to reconfigure connection");
+                        summary.failed.push(id.clone());
+                    }
+                },
+                Err(_) => match self.connection_manager.connect(connection).await {
+                    Ok(_) => summary.added.push(id.clone()),
+                    Err(e) => {
+                        warn!(connection_id = %id, error = %e, "Failed to add connection");
+                        summary.failed.push(id.clone());
+                        continue;
+                    }
+                },
+            }
+            managed.insert(id);
+        }
+
+        for id in self.managed.difference(&managed) {
+            if let Ok(old) = self.connection_manager.remove(id).await {
+                self.transaction_registry.rollback_connection(id).await;
+                drain(old);
+                summary.removed.push(id.clone());
+            }
+        }
+        self.managed = managed;
+
+        info!(
+            added = ?summary.added,
+            updated = ?summary.updated,
+            removed = ?summary.removed,
+            failed = ?summary.failed,
+            unchanged = summary.unchanged,
+            "Configuration reloaded"
+        );
+        Ok(summary)
+    }
+
+    /// Reload `config` on SIGHUP and whenever its config file changes.
+    ///
+    /// Does nothing without a config file, leaving SIGHUP's default action
+    /// in place.
+    pub fn start(mut self, config: Config) {
+        let Some(path) = config.config_file.clone() else {
+            return;
+        };
+        tokio::spawn(async move {
+            let mut hangup = Hangup::install();
+            let mut modified = modified_time(&path);
+            loop {
+                tokio::select! {
+                    _ = hangup.recv() => info!("Received SIGHUP, reloading configuration"),
+                    _ = file_changed(&path, &mut modified) => {
+                        info!(path = %path.display(), "Configuration file changed, reloading");
+                    }
+                }
+                match ReloadableConfig::load(&config) {
+                    Ok(reloaded) => {
+                        if let Err(e) = self.apply(reloaded).await {
+                            warn!(error = %e, "Ignoring invalid configuration");
+                        }
+                    }
+                    Err(e) => warn!(error = %e, "Ignoring invalid configuration"),
+                }
+            }
+        });
+    }
+}
+
+/// Close a replaced or removed connection's pools in the background; closing
+/// waits for the operations still using them.
+fn drain(connection: RemovedConnection) {
+    tokio::spawn(connection.close());
+}
+
+fn modified_time(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+/// Wait until the file's modification time differs from `modified`.
+async fn file_changed(path: &Path, modified: &mut Option<SystemTime>) {
+    loop {
+        tokio::time::sleep(CONFIG_POLL_INTERVAL).await;
+        let current = modified_time(path);
+        if current != *modified {
+            *modified = current;
+            return;
+        }
+    }
+}
+
+/// SIGHUP listener; never fires on platforms without signals.
+struct Hangup {
+    #[cfg(unix)]
+    signal: Option<tokio::signal::unix::Signal>,
+}
+
+impl Hangup {
+    fn install() -> Self {
+        #[cfg(unix)]
+        {
+            use tokio::signal::unix::{SignalKind, signal};
+            let signal = signal(SignalKind::hangup())
+                .map_err(|e| warn!(error = %e, "Failed to install SIGHUP handler"))
+                .ok();
+            Self { signal }
+        }
+        #[cfg(not(unix))]
+        Self {}
+    }
+
+    async fn recv(&mut self) {
+        #[cfg(unix)]
+        if let Some(signal) = &mut self.signal {
+            signal.recv().await;
+            return;
+        }
+        std::future::pending::<()>().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    fn config_with_file(contents: &str) -> (Config, tempfile::NamedTempFile) {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        file.write_all(contents.as_bytes()).unwrap();
+        let mut config = Config::default_config();
+        config.databases = vec!["cli=sqlite:cli.db".to_string()];
+        config.auth_tokens = vec!["cli-token".to_string()];
+        config.config_file = Some(file.path().to_path_buf());
+        (config, file)
+    }
+
+    #[test]
+    fn test_load_merges_command_line_and_file() {
+        let (config, _file) = config_with_file(
+            r#"{"databases": ["app=sqlite:app.db?writable=true"], "auth_tokens": ["file-token"]}"#,
+        );
+        let loaded = ReloadableConfig::load(&config).unwrap();
+        let ids: Vec<&str> = loaded.connections.iter().map(|c| c.id.as_str()).collect();
+        assert_eq!(ids, vec!["cli", "app"]);
+        assert!(loaded.connections[1].writable);
+        assert_eq!(loaded.auth_tokens, vec!["cli-token", "file-token"]);
+    }
+
+    #[test]
+    fn test_load_rejects_invalid_files() {
+        for contents in [
+            r#"{"databases": ["cli=sqlite:other.db"]}"#,
+            r#"{"database": ["app=sqlite:app.db"]}"#,
+            r#"{"databases": ["app=oracle://host/db"]}"#,
+            "not json",
+        ] {
+            let (config, _file) = config_with_file(contents);
+            assert!(ReloadableConfig::load(&config).is_err(), "{contents}");
+        }
+    }
+
+    #[test]
+    fn test_load_without_file() {
+        let mut config = Config::default_config();
+        config.databases = vec!["sqlite:data.db".to_string()];
+        let loaded = ReloadableConfig::load(&config).unwrap();
+        assert_eq!(loaded.connections[0].id, "data");
+        assert!(loaded.auth_tokens.is_empty());
+    }
+}
@@ -0,0 +1,532 @@
+//! Query result comparison tool.
+//!
+//! This module implements the `compare_queries` MCP tool, which runs a query
+//! on two (connection_id, database) targets and matches the result rows on key
+//! columns. The source side is streamed into a map keyed by the key values (at
+//! most `max_rows` rows); the target side is then streamed and checked row by
+//! row, so memory stays bounded by `max_rows` whatever the result size.
+//!
+//! As in `diff_schema`, the result is reported from source to target: "added"
+//! rows exist only in the target, "removed" rows only in the source. Values are
+//! compared loosely across engines: numbers compare numerically (also against
+//! numeric strings) and booleans equal 0/1.
+
+use crate::db::{ConnectionManager, QueryExecutor};
+use crate::error::{DbError, DbResult};
+use crate::models::{
+    DEFAULT_QUERY_TIMEOUT_SECS, MAX_QUERY_TIMEOUT_SECS, QueryParam, QueryParamInput,
+};
+use crate::tools::sql_validator;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value as JsonValue};
+use std::collections::btree_map::Entry;
+use std::collections::{BTreeMap, HashSet};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tracing::info;
+
+/// Default number of rows read from each side.
+pub const DEFAULT_COMPARE_MAX_ROWS: u32 = 10000;
+
+/// Maximum number of rows read from each side.
+pub const MAX_COMPARE_ROWS: u32 = 100000;
+
+/// Default number of added, removed and changed rows listed.
+pub const DEFAULT_MAX_DIFFERENCES: u32 = 100;
+
+/// Maximum number of added, removed and changed rows listed.
+pub const MAX_DIFFERENCES: u32 = 1000;
+
+/// One side of a query comparison.
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
+pub struct QueryTarget {
+    /// Database connection ID from list_connections
+    pub connection_id: String,
+    /// SELECT query to run on this side
+    pub sql: String,
+    /// Positional parameters for parameterized queries
+    #[serde(default)]
+    pub params: Vec<QueryParamInput>,
+    /// Target database name (required for server-level connections)
+    #[serde(default)]
+    pub database: Option<String>,
+}
+
+/// Input for the compare_queries tool.
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
+pub struct CompareQueriesInput {
+    /// Reference side (e.g. production replica, or the result before a transformation)
+    pub source: QueryTarget,
+    /// Side compared against the source
+    pub target: QueryTarget,
+    /// Columns identifying a row on both sides, e.g. ["id"]
+    pub key_columns: Vec<String>,
+    /// Rows read from each side. Default: 10000, max: 100000
+    #[serde(default)]
+    pub max_rows: Option<u32>,
+    /// Added, removed and changed rows listed (counts are always complete). Default: 100, max: 1000
+    #[serde(default)]
+    pub max_differences: Option<u32>,
+    /// Timeout in seconds for each side. Default: 30, max: 300
+    #[serde(default)]
+    pub timeout_secs: Option<u32>,
+}
+
+/// A column whose value differs between source and target.
+#[derive(Debug, Clone, PartialEq, Serialize, JsonSchema)]
+pub struct ValueChange {
+    pub column: String,
+    pub source: JsonValue,
+    pub target: JsonValue,
+}
+
+/// A row present on both sides with different values.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct RowDiff {
+    /// Key column values
+    pub key: Map<String, JsonValue>,
+    pub changes: Vec<ValueChange>,
+}
+
+/// Output from the compare_queries tool.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct CompareQueriesOutput {
+    /// True when both results hold the same rows (compared columns only)
+    pub identical: bool,
+    pub source_rows: u64,
+    pub target_rows: u64,
+    /// True when a side had more than max_rows rows. Only the rows read were
+    /// compared; order both queries by the key columns for a meaningful result.
+    pub truncated: bool,
+    pub unchanged_count: u64,
+    pub added_count: u64,
+    pub removed_count: u64,
+    pub changed_count: u64,
+    /// Rows only in the target
+    pub added: Vec<Map<String, JsonValue>>,
+    /// Rows only in the source
+    pub removed: Vec<Map<String, JsonValue>>,
+    pub changed: Vec<RowDiff>,
+    /// Result columns only in the source (not compared)
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub source_only_columns: Vec<String>,
+    /// Result columns only in the target (not compared)
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub target_only_columns: Vec<String>,
+    /// Rows skipped because their key was already seen on the same side
+    #[serde(skip_serializing_if = "is_zero")]
+    pub duplicate_keys: u64,
+    pub execution_time_ms: u64,
+}
+
+fn is_zero(n: &u64) -> bool {
+    *n == 0
+}
+
+/// Row key: the key column values in text form, so `1`, `1.0` and `"1"` match.
+type RowKey = Vec<String>;
+
+pub(crate) fn key_text(value: &JsonValue) -> String {
+    match value {
+        JsonValue::String(s) => s.clone(),
+        JsonValue::Bool(b) => (*b as i64).to_string(),
+        JsonValue::Number(n) => match n.as_f64() {
+            Some(f) if f.fract() == 0.0 && f.abs() < 1e15 => (f as i64).to_string(),
+            _ => n.to_string(),
+        },
+        // NUL cannot appear in text values, so NULL never matches a string
+        JsonValue::Null => "\0NULL".to_string(),
+        other => other.to_string(),
+    }
+}
+
+/// Compare values loosely across engines.
+fn values_equal(a: &JsonValue, b: &JsonValue) -> bool {
+    match (a, b) {
+        (JsonValue::Number(x), JsonValue::Number(y)) => match (x.as_i64(), y.as_i64()) {
+            (Some(x), Some(y)) => x == y,
+            _ => x.as_f64() == y.as_f64(),
+        },
+        (JsonValue::Number(n), JsonValue::String(s))
+        | (JsonValue::String(s), JsonValue::Number(n)) => {
+            s.trim().parse::<f64>().ok() == n.as_f64()
+        }
+        (JsonValue::Bool(b), JsonValue::Number(n)) | (JsonValue::Number(n), JsonValue::Bool(b)) => {
+            n.as_i64() == Some(*b as i64)
+        }
+        _ => a == b,
+    }
+}
+
+/// Look up a column case-insensitively; engines differ in identifier case.
+fn get_column<'a>(
+    row: &'a Map<String, JsonValue>,
+    column: &str,
+) -> Option<(&'a String, &'a JsonValue)> {
+    row.get_key_value(column).or_else(|| {
+        row.iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(column))
+    })
+}
+
+fn row_key(row: &Map<String, JsonValue>, key_columns: &[String], side: &str) -> DbResult<RowKey> {
+    key_columns
+        .iter()
+        .map(|column| {
+            get_column(row, column)
+                .map(|(_, value)| key_text(value))
+                .ok_or_else(|| {
+                    DbError::invalid_input(format!(
+                        "Key column '{}' is not in the {} query result",
+                        column, side
+                    ))
+                })
+        })
+        .collect()
+}
+
+/// Matches streamed source and target rows.
+pub(crate) struct Comparison<'a> {
+    key_columns: &'a [String],
+    max_differences: usize,
+    /// Source rows not (yet) matched by a target row
+    pending: BTreeMap<RowKey, Map<String, JsonValue>>,
+    source_columns: Vec<String>,
+    /// (source, target) names of the columns present on both sides
+    compared: Option<Vec<(String, String)>>,
+    target_only_columns: Vec<String>,
+    target_keys: HashSet<RowKey>,
+    output: CompareQueriesOutput,
+}
+
+impl<'a> Comparison<'a> {
+    pub(crate) fn new(key_columns: &'a [String], max_differences: usize) -> Self {
+        Self {
+            key_columns,
+            max_differences,
+            pending: BTreeMap::new(),
+            source_columns: Vec::new(),
+            compared: None,
+            target_only_columns: Vec::new(),
+            target_keys: HashSet::new(),
+            output: CompareQueriesOutput {
+                identical: false,
+                source_rows: 0,
+                target_rows: 0,
+                truncated: false,
+                unchanged_count: 0,
+                added_count: 0,
+                removed_count: 0,
+                changed_count: 0,
+                added: Vec::new(),
+                removed: Vec::new(),
+                changed: Vec::new(),
+                source_only_columns: Vec::new(),
+                target_only_columns: Vec::new(),
+                duplicate_keys: 0,
+                execution_time_ms: 0,
+            },
+        }
+    }
+
+    pub(crate) fn add_source(&mut self, row: Map<String, JsonValue>) -> DbResult<()> {
+        if self.source_columns.is_empty() {
+            self.source_columns = row.keys().cloned().collect();
+        }
+        let key = row_key(&row, self.key_columns, "source")?;
+        match self.pending.entry(key) {
+            Entry::Occupied(_) => self.output.duplicate_keys += 1,
+            Entry::Vacant(entry) => {
+                entry.insert(row);
+            }
+        }
+        Ok(())
+    }
+
+    pub(crate) fn add_target(&mut self, row: Map<String, JsonValue>) -> DbResult<()> {
+        if self.compared.is_none() {
+            self.pair_columns(&row);
+        }
+        let key = row_key(&row, self.key_columns, "target")?;
+        if !self.target_keys.insert(key.clone()) {
+            self.output.duplicate_keys += 1;
+            return Ok(());
+        }
+
+        let Some(source) = self.pending.remove(&key) else {
+            self.output.added_count += 1;
+            if self.output.added.len() < self.max_differences {
+                self.output.added.push(row);
+            }
+            return Ok(());
+        };
+
+        let changes: Vec<ValueChange> = self
+            .compared
+            .iter()
+            .flatten()
+            .filter_map(|(source_name, target_name)| {
+                let before = source.get(source_name).unwrap_or(&JsonValue::Null);
+                let after = row.get(target_name).unwrap_or(&JsonValue::Null);
+                (!values_equal(before, after)).then(|| ValueChange {
+                    column: source_name.clone(),
+                    source: before.clone(),
+                    target: after.clone(),
+                })
+            })
+            .collect();
+        if changes.is_empty() {
+            self.output.unchanged_count += 1;
+        } else {
+            self.output.changed_count += 1;
+            if self.output.changed.len() < self.max_differences {
+                self.output.changed.push(RowDiff {
+                    key: self.key_values(&source),
+                    changes,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Pair source and target columns by case-insensitive name.
+    fn pair_columns(&mut self, target_row: &Map<String, JsonValue>) {
+        let mut compared = Vec::new();
+        let mut matched = HashSet::new();
+        for column in &self.source_columns {
+            match get_column(target_row, column) {
+                Some((target_name, _)) => {
+                    matched.insert(target_name.clone());
+                    compared.push((column.clone(), target_name.clone()));
+                }
+                None => self.output.source_only_columns.push(column.clone()),
+            }
+        }
+        self.target_only_columns = target_row
+            .keys()
+            .filter(|name| !matched.contains(*name))
+            .cloned()
+            .collect();
+        self.compared = Some(compared);
+    }
+
+    fn key_values(&self, row: &Map<String, JsonValue>) -> Map<String, JsonValue> {
+        self.key_columns
+            .iter()
+            .filter_map(|column| get_column(row, column))
+            .map(|(name, value)| (name.clone(), value.clone()))
+            .collect()
+    }
+
+    pub(crate) fn finish(self) -> CompareQueriesOutput {
+        let Comparison {
+            max_differences,
+            pending,
+            target_only_columns,
+            mut output,
+            ..
+        } = self;
+        output.removed_count = pending.len() as u64;
+        output.removed = pending.into_values().take(max_differences).collect();
+        output.target_only_columns = target_only_columns;
+        output.identical = output.added_count == 0
+            && output.removed_count == 0
+            && output.changed_count == 0
+            && output.duplicate_keys == 0;
+        output
+    }
+}
+
+pub struct CompareToolHandler {
+    connection_manager: Arc<ConnectionManager>,
+    executor: QueryExecutor,
+}
+
+impl CompareToolHandler {
+    pub fn new(connection_manager: Arc<ConnectionManager>) -> Self {
+        Self {
+            connection_manager,
+            executor: QueryExecutor::new(),
+        }
+    }
+
+    pub async fn compare_queries(
+        &self,
+        input: CompareQueriesInput,
+    ) -> DbResult<CompareQueriesOutput> {
+        let start = Instant::now();
+        if input.key_columns.is_empty() {
+            return Err(DbError::invalid_input(
+                "At least one key column is required to match rows",
+            ));
+        }
+        let max_rows = input
+            .max_rows
+            .unwrap_or(DEFAULT_COMPARE_MAX_ROWS)
+            .clamp(1, MAX_COMPARE_ROWS) as u64;
+        let max_differences = input
+            .max_differences
+            .unwrap_or(DEFAULT_MAX_DIFFERENCES)
+            .min(MAX_DIFFERENCES) as usize;
+        let timeout = Duration::from_secs(
+            input
+                .timeout_secs
+                .unwrap_or(DEFAULT_QUERY_TIMEOUT_SECS)
+                .clamp(1, MAX_QUERY_TIMEOUT_SECS) as u64,
+        );
+
+        let mut comparison = Comparison::new(&input.key_columns, max_differences);
+        let (source_rows, source_more) = self
+            .stream(&input.source, max_rows, timeout, |row| {
+                comparison.add_source(row)
+            })
+            .await?;
+        let (target_rows, target_more) = self
+            .stream(&input.target, max_rows, timeout, |row| {
+                comparison.add_target(row)
+            })
+            .await?;
+
+        let mut output = comparison.finish();
+        output.source_rows = source_rows;
+        output.target_rows = target_rows;
+        output.truncated = source_more || target_more;
+        output.execution_time_ms = start.elapsed().as_millis() as u64;
+
+        info!(
+            source = %input.source.connection_id,
+            target = %input.target.connection_id,
+            added = output.added_count,
+            removed = output.removed_count,
+            changed = output.changed_count,
+            "Compared query results"
+        );
+
+        Ok(output)
+    }
+
+    /// Run one side's query, passing each row to `on_row`.
+    async fn stream(
+        &self,
+        side: &QueryTarget,
+        max_rows: u64,
+        timeout: Duration,
+        on_row: impl FnMut(Map<String, JsonValue>) -> DbResult<()>,
+    ) -> DbResult<(u64, bool)> {
+        let config = self
+            .connection_manager
+            .get_config(&side.connection_id)
+            .await?;
+
+        if config.server_level && side.database.is_none() {
+            return Err(DbError::invalid_input(format!(
+                "Connection '{}' is server-level and requires a 'database' to query. \
+                Use list_databases to discover available databases.",
+                side.connection_id
+            )));
+        }
+        sql_validator::validate_readonly(&side.sql, config.db_type)?;
+
+        let database = side.database.as_deref();
+        let pool = self
+            .connection_manager
+            .get_pool_for_database(&side.connection_id, database)
+            .await?;
+
+        let params: Vec<QueryParam> = side.params.iter().cloned().map(Into::into).collect();
+        let result = self
+            .executor
+            .for_each_row(&pool, &side.sql, &params, max_rows, Some(timeout), on_row)
+            .await;
+
+        self.connection_manager
+            .release_pool_for_database(&side.connection_id, database)
+            .await;
+
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn row(value: JsonValue) -> Map<String, JsonValue> {
+        value.as_object().unwrap().clone()
+    }
+
+    #[test]
+    fn test_values_equal_across_engines() {
+        assert!(values_equal(&json!(1), &json!(1.0)));
+        assert!(values_equal(&json!("12.50"), &json!(12.5)));
+        assert!(values_equal(&json!(true), &json!(1)));
+        assert!(!values_equal(&json!(null), &json!("")));
+        assert!(!values_equal(&json!("a"), &json!("b")));
+    }
+
+    #[test]
+    fn test_key_text_normalizes_numbers() {
+        assert_eq!(key_text(&json!(1)), key_text(&json!("1")));
+        assert_eq!(key_text(&json!(2.0)), "2");
+        assert_ne!(key_text(&json!(null)), key_text(&json!("NULL")));
+    }
+
+    #[test]
+    fn test_comparison() {
+        let keys = vec!["id".to_string()];
+        let mut comparison = Comparison::new(&keys, 10);
+        comparison
+            .add_source(row(json!({"id": 1, "name": "a", "old": 1})))
+            .unwrap();
+        comparison
+            .add_source(row(json!({"id": 2, "name": "b", "old": 1})))
+            .unwrap();
+        comparison
+            .add_source(row(json!({"id": 3, "name": "c", "old": 1})))
+            .unwrap();
+        comparison
+            .add_target(row(json!({"ID": 1, "NAME": "a", "new": 1})))
+            .unwrap();
+        comparison
+            .add_target(row(json!({"ID": 2, "NAME": "B", "new": 1})))
+            .unwrap();
+        comparison
+            .add_target(row(json!({"ID": 4, "NAME": "d", "new": 1})))
+            .unwrap();
+        comparison
+            .add_target(row(json!({"ID": 4, "NAME": "d", "new": 1})))
+            .unwrap();
+
+        let output = comparison.finish();
+        assert!(!output.identical);
+        assert_eq!(output.unchanged_count, 1);
+        assert_eq!(output.changed_count, 1);
+        assert_eq!(output.changed[0].key, row(json!({"id": 2})));
+        assert_eq!(
+            output.changed[0].changes,
+            vec![ValueChange {
+                column: "name".to_string(),
+                source: json!("b"),
+                target: json!("B"),
+            }]
+        );
+        assert_eq!(output.added_count, 1);
+        assert_eq!(output.removed_count, 1);
+        assert_eq!(output.removed[0]["id"], json!(3));
+        assert_eq!(output.duplicate_keys, 1);
+        assert_eq!(output.source_only_columns, vec!["old"]);
+        assert_eq!(output.target_only_columns, vec!["new"]);
+    }
+
+    #[test]
+    fn test_missing_key_column() {
+        let keys = vec!["id".to_string()];
+        let mut comparison = Comparison::new(&keys, 10);
+        let err = comparison
+            .add_source(row(json!({"name": "a"})))
+            .unwrap_err();
+        assert!(err.to_string().contains("Key column 'id'"));
+    }
+}
@@ -0,0 +1,901 @@
+//! Table data diff tool.
+//!
+//! This module implements the `diff_table_data` MCP tool, which compares the
+//! rows of a table on two (connection_id, database) targets without reading
+//! every row. The key space is split into ranges; for each range both sides
+//! return a row count and an aggregate checksum, and only ranges whose
+//! checksums differ are split further, until they are small enough to be
+//! compared row by row (see [`crate::tools::compare`]).
+//!
+//! Checksums are computed by the database when both sides use the same engine
+//! and it has a hash function (PostgreSQL and MySQL: a sum of per-row MD5
+//! prefixes). For SQLite, and across engines where the text form of values
+//! differs, each side's rows are streamed and hashed here instead. Client-side
+//! hashes compare values in the same loose form as `compare_queries`; a range
+//! whose checksums differ only in representation is resolved by the row-level
+//! comparison, so it costs time but is never reported as a difference.
+//!
+//! As in `diff_schema`, "added" rows exist only in the target and "removed"
+//! rows only in the source.
+
+use crate::db::schema::SchemaInspector;
+use crate::db::{ConnectionManager, DbPool, QueryExecutor};
+use crate::error::{DbError, DbResult};
+use crate::models::{
+    DEFAULT_QUERY_TIMEOUT_SECS, DatabaseType, MAX_QUERY_TIMEOUT_SECS, QueryParam, TableSchema,
+};
+use crate::tools::compare::{Comparison, RowDiff, key_text};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value as JsonValue};
+use std::collections::VecDeque;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tracing::info;
+
+/// Default number of ranges a differing range is split into.
+pub const DEFAULT_BISECTION_FACTOR: u32 = 16;
+
+/// Maximum number of ranges a differing range is split into.
+pub const MAX_BISECTION_FACTOR: u32 = 64;
+
+/// Default row count below which a differing range is compared row by row.
+pub const DEFAULT_LEAF_ROWS: u32 = 1000;
+
+/// Maximum row count below which a differing range is compared row by row.
+pub const MAX_LEAF_ROWS: u32 = 10000;
+
+/// Default number of rows fetched for row-level comparison (both sides).
+pub const DEFAULT_MAX_COMPARED_ROWS: u32 = 100000;
+
+/// Maximum number of rows fetched for row-level comparison (both sides).
+pub const MAX_COMPARED_ROWS: u32 = 1000000;
+
+/// Default number of added, removed and changed rows listed.
+pub const DEFAULT_MAX_DIFFERENCES: u32 = 100;
+
+/// Maximum number of added, removed and changed rows listed.
+pub const MAX_DIFFERENCES: u32 = 1000;
+
+/// One side of a table data comparison.
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
+pub struct TableTarget {
+    /// Database connection ID from list_connections
+    pub connection_id: String,
+    /// Table to compare
+    pub table: String,
+    /// Target database name (required for server-level connections)
+    #[serde(default)]
+    pub database: Option<String>,
+}
+
+/// Input for the diff_table_data tool.
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
+pub struct DiffTableDataInput {
+    /// Reference table (e.g. the replication or ETL source)
+    pub source: TableTarget,
+    /// Table compared against the source
+    pub target: TableTarget,
+    /// Unique column used to split and match rows. Default: the source's single-column primary key
+    #[serde(default)]
+    pub key_column: Option<String>,
+    /// Only compare these columns (the key column is always included). Default: all columns present on both sides
+    #[serde(default)]
+    pub columns: Option<Vec<String>>,
+    /// Number of ranges a differing range is split into. Default: 16, max: 64
+    #[serde(default)]
+    pub bisection_factor: Option<u32>,
+    /// Differing ranges with at most this many rows are compared row by row. Default: 1000, max: 10000
+    #[serde(default)]
+    pub leaf_rows: Option<u32>,
+    /// Budget of rows fetched for row-level comparison (both sides). Default: 100000, max: 1000000
+    #[serde(default)]
+    pub max_compared_rows: Option<u32>,
+    /// Added, removed and changed rows listed. Default: 100, max: 1000
+    #[serde(default)]
+    pub max_differences: Option<u32>,
+    /// Timeout in seconds for each query. Default: 30, max: 300
+    #[serde(default)]
+    pub timeout_secs: Option<u32>,
+}
+
+/// Where range checksums are computed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ChecksumMode {
+    /// Aggregate MD5 checksum computed by the database
+    Database,
+    /// Rows streamed and hashed by the server
+    Client,
+}
+
+/// Output from the diff_table_data tool.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct DiffTableDataOutput {
+    /// True when the compared columns hold the same rows on both sides
+    pub identical: bool,
+    /// False when the row budget ran out before every differing range was compared;
+    /// counts then cover the compared ranges only
+    pub complete: bool,
+    pub key_column: String,
+    pub checksum_mode: ChecksumMode,
+    pub compared_columns: Vec<String>,
+    /// Columns only in the source (not compared)
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub source_only_columns: Vec<String>,
+    /// Columns only in the target (not compared)
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub target_only_columns: Vec<String>,
+    pub source_rows: u64,
+    pub target_rows: u64,
+    /// Ranges whose checksums were computed
+    pub chunks_checked: u64,
+    /// Ranges whose checksums differed
+    pub chunks_differing: u64,
+    /// Rows fetched for row-level comparison (both sides)
+    pub rows_compared: u64,
+    pub added_count: u64,
+    pub removed_count: u64,
+    pub changed_count: u64,
+    /// Rows only in the target
+    pub added: Vec<Map<String, JsonValue>>,
+    /// Rows only in the source
+    pub removed: Vec<Map<String, JsonValue>>,
+    pub changed: Vec<RowDiff>,
+    pub execution_time_ms: u64,
+}
+
+/// Key range `[lo, hi)`; `None` is unbounded.
+#[derive(Debug, Clone, Default, PartialEq)]
+struct KeyRange {
+    lo: Option<JsonValue>,
+    hi: Option<JsonValue>,
+}
+
+/// Row count, key bounds and checksum of a range on one side.
+#[derive(Debug, Clone, Default)]
+struct RangeStats {
+    count: u64,
+    min: Option<JsonValue>,
+    max: Option<JsonValue>,
+    checksum: Option<String>,
+}
+
+impl RangeStats {
+    fn matches(&self, other: &RangeStats) -> bool {
+        self.count == other.count && self.checksum == other.checksum
+    }
+}
+
+fn bound_param(value: &JsonValue) -> QueryParam {
+    match value {
+        JsonValue::Number(n) => match n.as_i64() {
+            Some(i) => QueryParam::Int(i),
+            None => QueryParam::Float(n.as_f64().unwrap_or_default()),
+        },
+        JsonValue::String(s) => QueryParam::String(s.clone()),
+        other => QueryParam::String(other.to_string()),
+    }
+}
+
+/// Split `range` at evenly spaced integer keys between `min` and `max`.
+fn split_integer(range: &KeyRange, min: i64, max: i64, factor: u32) -> Vec<KeyRange> {
+    let span = (max as i128 - min as i128 + 1) as u128;
+    let step = span.div_ceil(factor as u128).max(1) as i128;
+    let boundaries: Vec<JsonValue> = (1..factor as i128)
+        .map(|i| min as i128 + i * step)
+        .take_while(|b| *b <= max as i128)
+        .map(|b| JsonValue::from(b as i64))
+        .collect();
+    split_at(range, boundaries)
+}
+
+/// Split `range` at the given ascending boundaries.
+fn split_at(range: &KeyRange, boundaries: Vec<JsonValue>) -> Vec<KeyRange> {
+    let mut ranges = Vec::with_capacity(boundaries.len() + 1);
+    let mut lo = range.lo.clone();
+    for boundary in boundaries {
+        ranges.push(KeyRange {
+            lo,
+            hi: Some(boundary.clone()),
+        });
+        lo = Some(boundary);
+    }
+    ranges.push(KeyRange {
+        lo,
+        hi: range.hi.clone(),
+    });
+    ranges
+}
+
+fn row_hash(row: &Map<String, JsonValue>, columns: &[String]) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    for column in columns {
+        key_text(row.get(column).unwrap_or(&JsonValue::Null)).hash(&mut hasher);
+    }
+    hasher.finish()
+}
+
+/// Builds the range queries for one side.
+struct TableSql<'a> {
+    db_type: DatabaseType,
+    table: &'a str,
+    key: &'a str,
+    /// Compared columns, in this side's spelling
+    columns: &'a [String],
+}
+
+impl TableSql<'_> {
+    fn quote(&self, identifier: &str) -> String {
+        self.db_type.quote_identifier(identifier)
+    }
+
+    fn placeholder(&self, index: usize) -> String {
+        match self.db_type {
+            DatabaseType::PostgreSQL => format!("${}", index),
+            DatabaseType::MySQL | DatabaseType::SQLite => "?".to_string(),
+        }
+    }
+
+    fn where_clause(&self, range: &KeyRange) -> (String, Vec<QueryParam>) {
+        let key = self.quote(self.key);
+        let mut conditions = Vec::new();
+        let mut params = Vec::new();
+        if let Some(lo) = &range.lo {
+            params.push(bound_param(lo));
+            conditions.push(format!("{} >= {}", key, self.placeholder(params.len())));
+        }
+        if let Some(hi) = &range.hi {
+            params.push(bound_param(hi));
+            conditions.push(format!("{} < {}", key, self.placeholder(params.len())));
+        }
+        if conditions.is_empty() {
+            (String::new(), params)
+        } else {
+            (format!(" WHERE {}", conditions.join(" AND ")), params)
+        }
+    }
+
+    /// Aggregate checksum over the compared columns, if the database can compute one.
+    fn checksum(&self) -> Option<String> {
+        match self.db_type {
+            DatabaseType::PostgreSQL => {
+                let columns: Vec<String> = self.columns.iter().map(|c| self.quote(c)).collect();
+                Some(format!(
+                    "COALESCE(SUM(('x' || SUBSTR(MD5(ROW({})::text), 1, 15))::bit(60)::bigint), 0)::text",
+                    columns.join(", ")
+                ))
+            }
+            DatabaseType::MySQL => {
+                let columns: Vec<String> = self
+                    .columns
+                    .iter()
+                    .map(|c| {
+                        let c = self.quote(c);
+                        format!("CONCAT(ISNULL({c}), COALESCE(CAST({c} AS CHAR), ''))")
+                    })
+                    .collect();
+                Some(format!(
+                    "CAST(COALESCE(SUM(CAST(CONV(SUBSTRING(MD5(CONCAT_WS('|', {})), 1, 15), 16, 10) AS UNSIGNED)), 0) AS CHAR)",
+                    columns.join(", ")
+                ))
+            }
+            DatabaseType::SQLite => None,
+        }
+    }
+
+    fn stats(&self, range: &KeyRange, with_checksum: bool) -> (String, Vec<QueryParam>) {
+        let (where_clause, params) = self.where_clause(range);
+        let key = self.quote(self.key);
+        let checksum = match self.checksum().filter(|_| with_checksum) {
+            Some(expr) => format!(", {} AS checksum", expr),
+            None => String::new(),
+        };
+        (
+            format!(
+                "SELECT COUNT(*) AS row_count, MIN({key}) AS min_key, MAX({key}) AS max_key{checksum} FROM {}{where_clause}",
+                self.quote(self.table),
+            ),
+            params,
+        )
+    }
+
+    fn rows(&self, range: &KeyRange) -> (String, Vec<QueryParam>) {
+        let (where_clause, params) = self.where_clause(range);
+        let columns: Vec<String> = self.columns.iter().map(|c| self.quote(c)).collect();
+        (
+            format!(
+                "SELECT {} FROM {}{} ORDER BY {}",
+                columns.join(", "),
+                self.quote(self.table),
+                where_clause,
+                self.quote(self.key)
+            ),
+            params,
+        )
+    }
+
+    fn nth_key(&self, range: &KeyRange, offset: u64) -> (String, Vec<QueryParam>) {
+        let (where_clause, params) = self.where_clause(range);
+        let key = self.quote(self.key);
+        (
+            format!(
+                "SELECT {key} AS split_key FROM {}{where_clause} ORDER BY {key} LIMIT 1 OFFSET {offset}",
+                self.quote(self.table),
+            ),
+            params,
+        )
+    }
+}
+
+/// One side of the comparison: its pool and resolved table.
+struct Side<'a> {
+    pool: &'a DbPool,
+    sql: TableSql<'a>,
+}
+
+/// Compared columns, as `(source_name, target_name)` pairs.
+struct ColumnPairs {
+    pairs: Vec<(String, String)>,
+    source_only: Vec<String>,
+    target_only: Vec<String>,
+}
+
+/// Pair source and target columns by case-insensitive name, keeping `only`
+/// (when given) and the key column.
+fn pair_columns(
+    source: &TableSchema,
+    target: &TableSchema,
+    key: &str,
+    only: Option<&[String]>,
+) -> DbResult<ColumnPairs> {
+    let mut pairs = Vec::new();
+    let mut source_only = Vec::new();
+    for column in &source.columns {
+        match target
+            .columns
+            .iter()
+            .find(|c| c.name.eq_ignore_ascii_case(&column.name))
+        {
+            Some(other) => pairs.push((column.name.clone(), other.name.clone())),
+            None => source_only.push(column.name.clone()),
+        }
+    }
+    let target_only = target
+        .columns
+        .iter()
+        .filter(|c| !pairs.iter().any(|(_, t)| t == &c.name))
+        .map(|c| c.name.clone())
+        .collect();
+
+    let paired = |name: &str| pairs.iter().any(|(s, _)| s.eq_ignore_ascii_case(name));
+    if !paired(key) {
+        return Err(DbError::invalid_input(format!(
+            "Key column '{}' is not present in both tables",
+            key
+        )));
+    }
+    if let Some(only) = only {
+        if let Some(missing) = only.iter().find(|name| !paired(name)) {
+            return Err(DbError::invalid_input(format!(
+                "Column '{}' is not present in both tables",
+                missing
+            )));
+        }
+        pairs.retain(|(s, _)| {
+            s.eq_ignore_ascii_case(key) || only.iter().any(|name| s.eq_ignore_ascii_case(name))
+        });
+    }
+    Ok(ColumnPairs {
+        pairs,
+        source_only,
+        target_only,
+    })
+}
+
+async fn describe(pool: &DbPool, target: &TableTarget) -> DbResult<TableSchema> {
+    // PostgreSQL databases are selected by the pool; tables live in "public".
+    let schema = match pool {
+        DbPool::MySql(_) => target.database.as_deref(),
+        _ => None,
+    };
+    SchemaInspector::describe_table(pool, &target.table, schema).await
+}
+
+pub struct DataDiffToolHandler {
+    connection_manager: Arc<ConnectionManager>,
+    executor: QueryExecutor,
+}
+
+impl DataDiffToolHandler {
+    pub fn new(connection_manager: Arc<ConnectionManager>) -> Self {
+        Self {
+            connection_manager,
+            executor: QueryExecutor::new(),
+        }
+    }
+
+    pub async fn diff_table_data(
+        &self,
+        input: DiffTableDataInput,
+    ) -> DbResult<DiffTableDataOutput> {
+        let start = Instant::now();
+        let source_pool = self.acquire(&input.source).await?;
+        let target_pool = match self.acquire(&input.target).await {
+            Ok(pool) => pool,
+            Err(e) => {
+                self.release(&input.source).await;
+                return Err(e);
+            }
+        };
+
+        let result = self.diff(&input, &source_pool, &target_pool).await;
+
+        self.release(&input.source).await;
+        self.release(&input.target).await;
+
+        let mut output = result?;
+        output.execution_time_ms = start.elapsed().as_millis() as u64;
+
+        info!(
+            source = %input.source.connection_id,
+            target = %input.target.connection_id,
+            table = %input.source.table,
+            chunks = output.chunks_checked,
+            added = output.added_count,
+            removed = output.removed_count,
+            changed = output.changed_count,
+            "Compared table data"
+        );
+
+        Ok(output)
+    }
+
+    async fn acquire(&self, target: &TableTarget) -> DbResult<DbPool> {
+        let config = self
+            .connection_manager
+            .get_config(&target.connection_id)
+            .await?;
+
+        if config.server_level && target.database.is_none() {
+            return Err(DbError::invalid_input(format!(
+                "Connection '{}' is server-level and requires a 'database' to compare. \
+                Use list_databases to discover available databases.",
+                target.connection_id
+            )));
+        }
+
+        self.connection_manager
+            .get_pool_for_database(&target.connection_id, target.database.as_deref())
+            .await
+    }
+
+    async fn release(&self, target: &TableTarget) {
+        self.connection_manager
+            .release_pool_for_database(&target.connection_id, target.database.as_deref())
+            .await;
+    }
+
+    async fn diff(
+        &self,
+        input: &DiffTableDataInput,
+        source_pool: &DbPool,
+        target_pool: &DbPool,
+    ) -> DbResult<DiffTableDataOutput> {
+        let source_schema = describe(source_pool, &input.source).await?;
+        let target_schema = describe(target_pool, &input.target).await?;
+
+        let key_column = match &input.key_column {
+            Some(key) => key.clone(),
+            None => match source_schema.primary_key.as_slice() {
+                [pk] => pk.clone(),
+                _ => {
+                    return Err(DbError::invalid_input(format!(
+                        "Table '{}' has no single-column primary key; specify key_column",
+                        source_schema.table_name
+                    )));
+                }
+            },
+        };
+        let columns = pair_columns(
+            &source_schema,
+            &target_schema,
+            &key_column,
+            input.columns.as_deref(),
+        )?;
+        let (source_key, target_key) = columns
+            .pairs
+            .iter()
+            .find(|(s, _)| s.eq_ignore_ascii_case(&key_column))
+            .cloned()
+            .expect("key column is paired");
+        let source_columns: Vec<String> = columns.pairs.iter().map(|(s, _)| s.clone()).collect();
+        let target_columns: Vec<String> = columns.pairs.iter().map(|(_, t)| t.clone()).collect();
+
+        let source = Side {
+            pool: source_pool,
+            sql: TableSql {
+                db_type: source_pool.db_type(),
+                table: &source_schema.table_name,
+                key: &source_key,
+                columns: &source_columns,
+            },
+        };
+        let target = Side {
+            pool: target_pool,
+            sql: TableSql {
+                db_type: target_pool.db_type(),
+                table: &target_schema.table_name,
+                key: &target_key,
+                columns: &target_columns,
+            },
+        };
+
+        let checksum_mode =
+            if source.sql.db_type == target.sql.db_type && source.sql.checksum().is_some() {
+                ChecksumMode::Database
+            } else {
+                ChecksumMode::Client
+            };
+
+        let factor = input
+            .bisection_factor
+            .unwrap_or(DEFAULT_BISECTION_FACTOR)
+            .clamp(2, MAX_BISECTION_FACTOR);
+        let leaf_rows = input
+            .leaf_rows
+            .unwrap_or(DEFAULT_LEAF_ROWS)
+            .clamp(1, MAX_LEAF_ROWS) as u64;
+        let max_compared_rows = input
+            .max_compared_rows
+            .unwrap_or(DEFAULT_MAX_COMPARED_ROWS)
+            .min(MAX_COMPARED_ROWS) as u64;
+        let max_differences = input
+            .max_differences
+            .unwrap_or(DEFAULT_MAX_DIFFERENCES)
+            .min(MAX_DIFFERENCES) as usize;
+        let timeout = Duration::from_secs(
+            input
+                .timeout_secs
+                .unwrap_or(DEFAULT_QUERY_TIMEOUT_SECS)
+                .clamp(1, MAX_QUERY_TIMEOUT_SECS) as u64,
+        );
+
+        let mut output = DiffTableDataOutput {
+            identical: false,
+            complete: true,
+            key_column: source_key.clone(),
+            checksum_mode,
+            compared_columns: source_columns.clone(),
+            source_only_columns: columns.source_only,
+            target_only_columns: columns.target_only,
+            source_rows: 0,
+            target_rows: 0,
+            chunks_checked: 0,
+            chunks_differing: 0,
+            rows_compared: 0,
+            added_count: 0,
+            removed_count: 0,
+            changed_count: 0,
+            added: Vec::new(),
+            removed: Vec::new(),
+            changed: Vec::new(),
+            execution_time_ms: 0,
+        };
+
+        let key_columns = [source_key.clone()];
+        let database_checksum = checksum_mode == ChecksumMode::Database;
+        let mut queue = VecDeque::from([KeyRange::default()]);
+        let mut first = true;
+        while let Some(range) = queue.pop_front() {
+            let mut source_stats = self
+                .stats(&source, &range, database_checksum, timeout)
+                .await?;
+            let mut target_stats = self
+                .stats(&target, &range, database_checksum, timeout)
+                .await?;
+            if first {
+                output.source_rows = source_stats.count;
+                output.target_rows = target_stats.count;
+                first = false;
+            }
+            let larger = source_stats.count.max(target_stats.count);
+            if larger == 0 {
+                continue;
+            }
+
+            // Small ranges are cheaper to compare directly than to hash client-side
+            let leaf = larger <= leaf_rows;
+            if database_checksum || !leaf {
+                if !database_checksum {
+                    source_stats.checksum =
+                        Some(self.client_checksum(&source, &range, timeout).await?);
+                    target_stats.checksum =
+                        Some(self.client_checksum(&target, &range, timeout).await?);
+                }
+                output.chunks_checked += 1;
+                if source_stats.matches(&target_stats) {
+                    continue;
+                }
+                output.chunks_differing += 1;
+            }
+
+            if !leaf {
+                let ranges = self
+                    .split(
+                        &source,
+                        &target,
+                        &range,
+                        &source_stats,
+                        &target_stats,
+                        factor,
+                        timeout,
+                    )
+                    .await?;
+                if ranges.len() > 1 {
+                    queue.extend(ranges);
+                    continue;
+                }
+            }
+
+            if output.rows_compared + source_stats.count + target_stats.count > max_compared_rows {
+                output.complete = false;
+                break;
+            }
+            // Lists are truncated below; this only bounds what a leaf collects
+            let listed = output
+                .added
+                .len()
+                .min(output.removed.len())
+                .min(output.changed.len());
+            let mut comparison =
+                Comparison::new(&key_columns, max_differences.saturating_sub(listed));
+            let (source_read, _) = self
+                .rows(&source, &range, timeout, |row| comparison.add_source(row))
+                .await?;
+            let (target_read, _) = self
+                .rows(&target, &range, timeout, |row| comparison.add_target(row))
+                .await?;
+            output.rows_compared += source_read + target_read;
+
+            let leaf_output = comparison.finish();
+            output.added_count += leaf_output.added_count;
+            output.removed_count += leaf_output.removed_count;
+            output.changed_count += leaf_output.changed_count;
+            output.added.extend(leaf_output.added);
+            output.removed.extend(leaf_output.removed);
+            output.changed.extend(leaf_output.changed);
+        }
+        output.added.truncate(max_differences);
+        output.removed.truncate(max_differences);
+        output.changed.truncate(max_differences);
+
+        output.identical = output.complete
+            && output.added_count == 0
+            && output.removed_count == 0
+            && output.changed_count == 0;
+        Ok(output)
+    }
+
+    /// Row count, key bounds and (optionally) database checksum of a range.
+    async fn stats(
+        &self,
+        side: &Side<'_>,
+        range: &KeyRange,
+        with_checksum: bool,
+        timeout: Duration,
+    ) -> DbResult<RangeStats> {
+        let (sql, params) = side.sql.stats(range, with_checksum);
+        let mut stats = RangeStats::default();
+        self.executor
+            .for_each_row(side.pool, &sql, &params, 1, Some(timeout), |row| {
+                let value = |name: &str| row.get(name).cloned().filter(|v| !v.is_null());
+                stats = RangeStats {
+                    count: value("row_count").and_then(|v| v.as_u64()).unwrap_or(0),
+                    min: value("min_key"),
+                    max: value("max_key"),
+                    checksum: value("checksum").map(|v| match v {
+                        JsonValue::String(s) => s,
+                        other => other.to_string(),
+                    }),
+                };
+                Ok(())
+            })
+            .await?;
+        Ok(stats)
+    }
+
+    /// Checksum of a range computed from its streamed rows.
+    async fn client_checksum(
+        &self,
+        side: &Side<'_>,
+        range: &KeyRange,
+        timeout: Duration,
+    ) -> DbResult<String> {
+        let mut sum = 0u64;
+        self.rows(side, range, timeout, |row| {
+            sum = sum.wrapping_add(row_hash(&row, side.sql.columns));
+            Ok(())
+        })
+        .await?;
+        Ok(sum.to_string())
+    }
+
+    /// Stream the compared columns of a range, ordered by key.
+    async fn rows(
+        &self,
+        side: &Side<'_>,
+        range: &KeyRange,
+        timeout: Duration,
+        on_row: impl FnMut(Map<String, JsonValue>) -> DbResult<()>,
+    ) -> DbResult<(u64, bool)> {
+        let (sql, params) = side.sql.rows(range);
+        self.executor
+            .for_each_row(side.pool, &sql, &params, u64::MAX, Some(timeout), on_row)
+            .await
+    }
+
+    /// Split a differing range into up to `factor` sub-ranges.
+    ///
+    /// Integer keys are split arithmetically between the smallest and largest
+    /// key on either side; other keys at evenly spaced row offsets of the
+    /// side with more rows.
+    #[allow(clippy::too_many_arguments)]
+    async fn split(
+        &self,
+        source: &Side<'_>,
+        target: &Side<'_>,
+        range: &KeyRange,
+        source_stats: &RangeStats,
+        target_stats: &RangeStats,
+        factor: u32,
+        timeout: Duration,
+    ) -> DbResult<Vec<KeyRange>> {
+        let bound = |a: &Option<JsonValue>, b: &Option<JsonValue>, pick: fn(i64, i64) -> i64| match (
+            a.as_ref().map(|v| v.as_i64()),
+            b.as_ref().map(|v| v.as_i64()),
+        ) {
+            (Some(Some(a)), Some(Some(b))) => Some(pick(a, b)),
+            (Some(Some(a)), None) | (None, Some(Some(a))) => Some(a),
+            _ => None,
+        };
+        if let (Some(min), Some(max)) = (
+            bound(&source_stats.min, &target_stats.min, i64::min),
+            bound(&source_stats.max, &target_stats.max, i64::max),
+        ) {
+            return Ok(split_integer(range, min, max, factor));
+        }
+
+        let (side, count) = if source_stats.count >= target_stats.count {
+            (source, source_stats.count)
+        } else {
+            (target, target_stats.count)
+        };
+        let mut boundaries: Vec<JsonValue> = Vec::new();
+        for i in 1..factor as u64 {
+            let offset = count * i / factor as u64;
+            if offset == 0 {
+                continue;
+            }
+            let (sql, params) = side.sql.nth_key(range, offset);
+            let mut key = None;
+            self.executor
+                .for_each_row(side.pool, &sql, &params, 1, Some(timeout), |row| {
+                    key = row.get("split_key").cloned().filter(|v| !v.is_null());
+                    Ok(())
+                })
+                .await?;
+            if let Some(key) = key {
+                if boundaries.last() != Some(&key) {
+                    boundaries.push(key);
+                }
+            }
+        }
+        Ok(split_at(range, boundaries))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn sql(db_type: DatabaseType, columns: &[String]) -> TableSql<'_> {
+        TableSql {
+            db_type,
+            table: "users",
+            key: "id",
+            columns,
+        }
+    }
+
+    #[test]
+    fn test_split_integer() {
+        let ranges = split_integer(&KeyRange::default(), 1, 100, 4);
+        assert_eq!(ranges.len(), 4);
+        assert_eq!(ranges[0].lo, None);
+        assert_eq!(ranges[0].hi, Some(json!(26)));
+        assert_eq!(ranges[3].lo, Some(json!(76)));
+        assert_eq!(ranges[3].hi, None);
+
+        // Fewer keys than ranges
+        let range = KeyRange {
+            lo: Some(json!(5)),
+            hi: Some(json!(7)),
+        };
+        let ranges = split_integer(&range, 5, 6, 16);
+        assert_eq!(ranges.len(), 2);
+        assert_eq!(ranges[0].lo, Some(json!(5)));
+        assert_eq!(ranges[1].hi, Some(json!(7)));
+
+        assert_eq!(split_integer(&range, 5, 5, 16).len(), 1);
+    }
+
+    #[test]
+    fn test_range_queries() {
+        let columns = vec!["id".to_string(), "name".to_string()];
+        let range = KeyRange {
+            lo: Some(json!(10)),
+            hi: Some(json!("m")),
+        };
+        let (query, params) = sql(DatabaseType::PostgreSQL, &columns).nth_key(&range, 5);
+        assert_eq!(
+            query,
+            "SELECT \"id\" AS split_key FROM \"users\" WHERE \"id\" >= $1 AND \"id\" < $2 ORDER BY \"id\" LIMIT 1 OFFSET 5"
+        );
+        assert!(matches!(
+            params.as_slice(),
+            [QueryParam::Int(10), QueryParam::String(s)] if s == "m"
+        ));
+
+        let (query, params) = sql(DatabaseType::MySQL, &columns).rows(&KeyRange {
+            lo: None,
+            hi: Some(json!(3)),
+        });
+        assert_eq!(
+            query,
+            "SELECT `id`, `name` FROM `users` WHERE `id` < ? ORDER BY `id`"
+        );
+        assert_eq!(params.len(), 1);
+    }
+
+    #[test]
+    fn test_checksum_expressions() {
+        let columns = vec!["id".to_string(), "name".to_string()];
+        let (query, _) = sql(DatabaseType::PostgreSQL, &columns).stats(&KeyRange::default(), true);
+        assert_eq!(
+            query,
+            "SELECT COUNT(*) AS row_count, MIN(\"id\") AS min_key, MAX(\"id\") AS max_key, \
+             COALESCE(SUM(('x' || SUBSTR(MD5(ROW(\"id\", \"name\")::text), 1, 15))::bit(60)::bigint), 0)::text AS checksum \
+             FROM \"users\""
+        );
+        let (query, _) = sql(DatabaseType::MySQL, &columns).stats(&KeyRange::default(), true);
+        assert!(
+            query.contains(
+                "CONCAT_WS('|', CONCAT(ISNULL(`id`), COALESCE(CAST(`id` AS CHAR), '')), "
+            )
+        );
+
+        let (query, _) = sql(DatabaseType::MySQL, &columns).stats(&KeyRange::default(), false);
+        assert!(!query.contains("checksum"));
+        assert!(sql(DatabaseType::SQLite, &columns).checksum().is_none());
+    }
+
+    #[test]
+    fn test_row_hash_is_loose() {
+        let columns = vec!["id".to_string(), "score".to_string()];
+        let a = json!({"id": 1, "score": 2.0});
+        let b = json!({"id": "1", "score": 2});
+        assert_eq!(
+            row_hash(a.as_object().unwrap(), &columns),
+            row_hash(b.as_object().unwrap(), &columns)
+        );
+        let c = json!({"id": 1, "score": null});
+        assert_ne!(
+            row_hash(a.as_object().unwrap(), &columns),
+            row_hash(c.as_object().unwrap(), &columns)
+        );
+    }
+}
@@ -1,18 +1,40 @@
 //! Query execution plan tools.
 //!
 //! This module implements the `explain` MCP tool for viewing query execution plans.
-//! It supports all three database types (MySQL, PostgreSQL, SQLite) using their
-//! native EXPLAIN syntax.
+//! It requests machine-readable plans from each backend (`EXPLAIN (FORMAT JSON)`
+//! on PostgreSQL, `EXPLAIN FORMAT=JSON` on MySQL, `EXPLAIN QUERY PLAN` on SQLite)
+//! and normalizes them into a common plan tree with a cost summary and findings.
+//!
+//! With `analyze` the statement is executed: `EXPLAIN ANALYZE` on PostgreSQL and
+//! MySQL, a timed run on SQLite. Only SELECT, INSERT, UPDATE and DELETE can be
+//! analyzed, and statements the execute guard blocks are refused. Write
+//! statements are always analyzed inside a transaction that is rolled back
+//! afterwards. MySQL only analyzes multi-table UPDATE and DELETE statements.
+//!
+//! A [`QueryControl`] cancels a running EXPLAIN and receives its progress;
+//! cancelled and timed-out statements are stopped on the server. An analyzed
+//! write is the exception, since it runs on a registry transaction: it is
+//! rolled back in the background and the rollback waits for it to finish.
 
+use crate::db::control::PinnedConnection;
 use crate::db::params::{bind_mysql_param, bind_postgres_param, bind_sqlite_param};
-use crate::db::{ConnectionManager, DbPool, TransactionRegistry};
+use crate::db::{ConnectionManager, DbPool, QueryControl, TransactionRegistry};
 use crate::error::{DbError, DbResult};
-use crate::models::{QueryParam, QueryParamInput};
+use crate::models::{DatabaseType, QueryParam, QueryParamInput};
 use crate::tools::format::{ColumnInfo, OutputFormat, format_as_markdown, format_as_table};
+use crate::tools::guard::{DangerousOperationResult, check_dangerous_sql};
+use crate::tools::plan::{self, PlanFinding, PlanNode, PlanSummary};
+use crate::tools::reference_check;
+use crate::tools::sql_validator;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use sqlparser::ast::{FromTable, Statement};
+use sqlparser::parser::Parser;
+use sqlx::SqliteConnection;
 use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::time::{Duration, Instant};
+use tracing::warn;
 
 /// Default timeout for EXPLAIN operations in seconds.
 pub const DEFAULT_EXPLAIN_TIMEOUT_SECS: u32 = 30;
@@ -20,6 +42,9 @@ pub const DEFAULT_EXPLAIN_TIMEOUT_SECS: u32 = 30;
 /// Maximum timeout for EXPLAIN operations in seconds.
 pub const MAX_EXPLAIN_TIMEOUT_SECS: u32 = 30;
 
+/// Row limit when reading EXPLAIN output; plans are typically small.
+const EXPLAIN_ROW_LIMIT: u32 = 10000;
+
 /// Input for the explain tool.
 #[derive(Debug, Clone, Deserialize, JsonSchema)]
 pub struct ExplainInput {
@@ -42,6 +67,18 @@ pub struct ExplainInput {
     /// Target database name (optional)
     #[serde(default)]
     pub database: Option<String>,
+    /// Execute the statement and report actual row counts and timings
+    /// (EXPLAIN ANALYZE). Write statements run in a transaction that is always
+    /// rolled back. Cannot be combined with transaction_id. Default: false
+    #[serde(default)]
+    pub analyze: bool,
+}
+
+/// Outcome of a timed SQLite execution, which has no EXPLAIN ANALYZE.
+#[derive(Debug, Clone, Copy)]
+struct Execution {
+    rows: u64,
+    time_ms: f64,
 }
 
 /// Output from the explain tool.
@@ -52,16 +89,107 @@ pub struct ExplainOutput {
     pub plan: Vec<serde_json::Map<String, serde_json::Value>>,
     /// The SQL statement that was explained
     pub sql: String,
+    /// Plan normalized into a tree of operations. Omitted if format is table/markdown
+    /// or the backend did not return a machine-readable plan (SQLite INSERT bytecode).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tree: Option<PlanNode>,
+    /// Estimated cost and row totals of the plan
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub summary: Option<PlanSummary>,
+    /// Potential problems: full table scans, missing indexes, temporary structures
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub findings: Vec<PlanFinding>,
     /// Pre-formatted output when format is table or markdown
     #[serde(skip_serializing_if = "Option::is_none")]
     pub formatted: Option<String>,
     /// Time taken to run EXPLAIN in milliseconds
     pub execution_time_ms: u64,
+    /// True if the statement was executed (analyze)
+    pub analyzed: bool,
+}
+
+/// Flatten a plan tree into one row per node, indenting operations by depth.
+fn tree_rows(
+    root: &PlanNode,
+    analyzed: bool,
+) -> (
+    Vec<ColumnInfo>,
+    Vec<serde_json::Map<String, serde_json::Value>>,
+) {
+    let mut names = vec!["operation", "relation", "index", "rows", "cost"];
+    if analyzed {
+        names.extend(["actual_rows", "actual_ms", "loops"]);
+    }
+    let columns = names.into_iter().map(ColumnInfo::new).collect();
+    let mut rows = Vec::new();
+    root.walk(0, &mut |node, depth| {
+        let mut row = serde_json::Map::new();
+        row.insert(
+            "operation".to_string(),
+            format!("{}{}", "  ".repeat(depth), node.operation).into(),
+        );
+        row.insert("relation".to_string(), node.relation.clone().into());
+        row.insert("index".to_string(), node.index.clone().into());
+        row.insert("rows".to_string(), node.estimated_rows.into());
+        row.insert("cost".to_string(), node.estimated_cost.into());
+        if analyzed {
+            row.insert("actual_rows".to_string(), node.actual_rows.into());
+            row.insert("actual_ms".to_string(), node.actual_time_ms.into());
+            row.insert("loops".to_string(), node.loops.into());
+        }
+        rows.push(row);
+    });
+    (columns, rows)
+}
+
+/// Check that `sql` is a statement `analyze` may run and report whether it
+/// writes.
+fn check_analyze_statement(sql: &str, db_type: DatabaseType) -> DbResult<bool> {
+    if let DangerousOperationResult::Dangerous(op_type) = check_dangerous_sql(sql)? {
+        return Err(DbError::dangerous_operation_blocked(
+            op_type.operation_name(),
+            op_type.reason(),
+        ));
+    }
+
+    let dialect = sql_validator::get_dialect(db_type);
+    let statements = Parser::parse_sql(dialect.as_ref(), sql)
+        .map_err(|e| DbError::invalid_input(format!("Failed to parse SQL statement: {}", e)))?;
+    let [statement] = statements.as_slice() else {
+        return Err(DbError::invalid_input("analyze runs exactly one statement"));
+    };
+    let multi_table = match statement {
+        // A CTE may still modify data on PostgreSQL
+        Statement::Query(_) => {
+            return Ok(sql_validator::validate_readonly(sql, db_type).is_err());
+        }
+        Statement::Insert(_) => false,
+        Statement::Update(update) => !update.table.joins.is_empty() || update.from.is_some(),
+        Statement::Delete(delete) => {
+            let (FromTable::WithFromKeyword(from) | FromTable::WithoutKeyword(from)) = &delete.from;
+            !delete.tables.is_empty()
+                || delete.using.is_some()
+                || from.len() > 1
+                || from.iter().any(|table| !table.joins.is_empty())
+        }
+        _ => {
+            return Err(DbError::invalid_input(
+                "analyze only supports SELECT, INSERT, UPDATE and DELETE statements",
+            ));
+        }
+    };
+    if db_type == DatabaseType::MySQL && !multi_table {
+        return Err(DbError::invalid_input(
+            "MySQL cannot EXPLAIN ANALYZE a single-table INSERT, UPDATE or DELETE. Analyze the equivalent SELECT instead, or run explain without analyze",
+        ));
+    }
+    Ok(true)
 }
 
 pub struct ExplainToolHandler {
     connection_manager: Arc<ConnectionManager>,
     transaction_registry: Arc<TransactionRegistry>,
+    control: QueryControl,
 }
 
 impl ExplainToolHandler {
@@ -72,61 +200,125 @@ impl ExplainToolHandler {
         Self {
             connection_manager,
             transaction_registry,
+            control: QueryControl::default(),
         }
     }
 
-    /// SQLite uses EXPLAIN QUERY PLAN for SELECT, EXPLAIN for writes.
-    /// MySQL/PostgreSQL use EXPLAIN directly.
-    fn generate_explain_sql(pool: &DbPool, sql: &str) -> String {
+    /// Cancel EXPLAIN and report its progress through `control`.
+    pub fn with_control(mut self, control: QueryControl) -> Self {
+        self.control = control;
+        self
+    }
+
+    /// PostgreSQL and MySQL return JSON plans. SQLite uses EXPLAIN QUERY PLAN for
+    /// statements that read tables and falls back to the EXPLAIN bytecode listing
+    /// for INSERT, which has no query plan.
+    ///
+    /// With `analyze`, PostgreSQL keeps the JSON format and MySQL switches to
+    /// `EXPLAIN ANALYZE`, which only prints a text tree. SQLite has no analyze
+    /// variant; the statement is timed separately.
+    fn generate_explain_sql(pool: &DbPool, sql: &str, analyze: bool) -> String {
         match pool {
             DbPool::SQLite(_) => {
-                let trimmed = sql.trim_start().to_uppercase();
-                if trimmed.starts_with("SELECT") {
+                let keyword = sql
+                    .split_whitespace()
+                    .next()
+                    .unwrap_or_default()
+                    .to_uppercase();
+                if matches!(
+                    keyword.as_str(),
+                    "SELECT" | "WITH" | "UPDATE" | "DELETE" | "VALUES"
+                ) {
                     format!("EXPLAIN QUERY PLAN {}", sql)
                 } else {
                     format!("EXPLAIN {}", sql)
                 }
             }
-            DbPool::MySql(_) | DbPool::Postgres(_) => {
-                format!("EXPLAIN {}", sql)
-            }
+            DbPool::MySql(_) if analyze => format!("EXPLAIN ANALYZE {}", sql),
+            DbPool::MySql(_) => format!("EXPLAIN FORMAT=JSON {}", sql),
+            DbPool::Postgres(_) if analyze => format!("EXPLAIN (ANALYZE, FORMAT JSON) {}", sql),
+            DbPool::Postgres(_) => format!("EXPLAIN (FORMAT JSON) {}", sql),
         }
     }
 
     fn build_output(
         rows: Vec<serde_json::Map<String, serde_json::Value>>,
+        db_type: DatabaseType,
         sql: &str,
         execution_time_ms: u64,
         format: OutputFormat,
+        analyzed: bool,
+        execution: Option<Execution>,
     ) -> ExplainOutput {
+        let mut tree = plan::parse_plan(db_type, &rows);
+        // SQLite only reports totals for the whole statement
+        if let (Some(root), Some(run)) = (tree.as_mut(), execution) {
+            root.actual_rows = Some(run.rows as f64);
+            root.actual_time_ms = Some(run.time_ms);
+        }
+        let summary = match (&tree, execution) {
+            (Some(root), _) => Some(plan::summarize(root)),
+            (None, Some(run)) => Some(PlanSummary {
+                total_cost: None,
+                estimated_rows: None,
+                actual_rows: Some(run.rows as f64),
+                actual_time_ms: Some(run.time_ms),
+                node_count: 0,
+                full_table_scans: 0,
+            }),
+            (None, None) => None,
+        };
+        let findings = tree.as_ref().map(plan::find_issues).unwrap_or_default();
+
         match format {
             OutputFormat::Json => ExplainOutput {
                 plan: rows,
                 sql: sql.to_string(),
+                tree,
+                summary,
+                findings,
                 formatted: None,
                 execution_time_ms,
+                analyzed,
             },
             OutputFormat::Table | OutputFormat::Markdown => {
-                let columns: Vec<ColumnInfo> = if let Some(first_row) = rows.first() {
-                    first_row.keys().map(ColumnInfo::new).collect()
-                } else {
-                    Vec::new()
+                // Render the normalized tree when available, the raw rows otherwise
+                let (columns, rows) = match &tree {
+                    Some(root) => tree_rows(root, analyzed),
+                    None => {
+                        let columns: Vec<ColumnInfo> = if let Some(first_row) = rows.first() {
+                            first_row.keys().map(ColumnInfo::new).collect()
+                        } else {
+                            Vec::new()
+                        };
+                        (columns, rows)
+                    }
                 };
 
                 let row_count = rows.len();
-                let formatted = match format {
+                let mut formatted = match format {
                     OutputFormat::Table => {
                         format_as_table(&columns, &rows, row_count, execution_time_ms)
                     }
                     OutputFormat::Markdown => format_as_markdown(&columns, &rows, row_count),
                     _ => unreachable!(),
                 };
+                if !findings.is_empty() {
+                    formatted.push_str("\n\nFindings:\n");
+                    for finding in &findings {
+                        formatted.push_str(&format!("- {}\n", finding.message));
+                    }
+                }
 
                 ExplainOutput {
                     plan: Vec::new(),
                     sql: sql.to_string(),
+                    tree: None,
+                    summary,
+                    findings,
                     formatted: Some(formatted),
                     execution_time_ms,
+                    analyzed,
                 }
             }
         }
@@ -138,6 +330,11 @@ impl ExplainToolHandler {
         if sql.is_empty() {
             return Err(DbError::invalid_input("SQL statement is required"));
         }
+        if input.analyze && input.transaction_id.is_some() {
+            return Err(DbError::invalid_input(
+                "analyze runs the statement in its own rolled-back transaction and cannot be combined with transaction_id",
+            ));
+        }
 
         let timeout_secs = input
             .timeout_secs
@@ -153,14 +350,13 @@ impl ExplainToolHandler {
                 .is_valid(tx_id, &input.connection_id)
                 .await?;
 
-            let database = input.database.as_deref();
-            let pool = self
+            let guard = self
                 .connection_manager
-                .get_pool_for_database(&input.connection_id, database)
+                .get_pool_for_database_guarded(&input.connection_id, input.database.as_deref())
                 .await?;
-            let explain_sql = Self::generate_explain_sql(&pool, sql);
+            let db_type = guard.pool().db_type();
+            let explain_sql = Self::generate_explain_sql(guard.pool(), sql, false);
 
-            // EXPLAIN queries don't need streaming limits, use a high limit
             let result = self
                 .transaction_registry
                 .query_in_transaction(
@@ -168,99 +364,305 @@ impl ExplainToolHandler {
                     &input.connection_id,
                     &explain_sql,
                     &params,
-                    10000, // EXPLAIN results are typically small
-                    true,  // decode_binary
+                    EXPLAIN_ROW_LIMIT,
+                    true, // decode_binary
+                    timeout,
+                    &self.control,
                 )
                 .await;
 
-            self.connection_manager
-                .release_pool_for_database(&input.connection_id, database)
-                .await;
+            guard.release().await;
 
             let result = result?;
 
             return Ok(Self::build_output(
                 result.rows,
+                db_type,
                 sql,
                 start.elapsed().as_millis() as u64,
                 format,
+                false,
+                None,
             ));
         }
 
+        // Analyzing a write executes it, so it needs a writable connection even
+        // though the changes are rolled back. Only plain DML gets that far, and
+        // the execute guard applies, since a rollback doesn't undo everything
+        // (sequences, for one)
+        let analyze_write = if input.analyze {
+            let config = self
+                .connection_manager
+                .get_config(&input.connection_id)
+                .await?;
+            let is_write = check_analyze_statement(sql, config.db_type)?;
+            if is_write && !config.writable {
+                return Err(DbError::permission(
+                    "explain analyze",
+                    "Analyzing a write statement executes it. Use ?writable=true in the connection URL to enable writes",
+                ));
+            }
+            is_write
+        } else {
+            false
+        };
+
         let database = input.database.as_deref();
-        let pool = self
+        let guard = self
             .connection_manager
-            .get_pool_for_database(&input.connection_id, database)
+            .get_pool_for_database_guarded(&input.connection_id, database)
             .await?;
-        let explain_sql = Self::generate_explain_sql(&pool, sql);
-
-        let result = self
-            .execute_explain(&pool, &explain_sql, &params, timeout)
-            .await;
+        let pool = guard.pool();
+        let explain_sql = Self::generate_explain_sql(pool, sql, input.analyze);
+
+        let result = async {
+            reference_check::check_references(
+                &self.connection_manager,
+                &input.connection_id,
+                database,
+                pool,
+                sql,
+                timeout,
+            )
+            .await?;
+            if analyze_write {
+                self.analyze_in_rollback(
+                    &input.connection_id,
+                    pool,
+                    sql,
+                    &explain_sql,
+                    &params,
+                    timeout,
+                )
+                .await
+            } else {
+                self.explain_controlled(pool, sql, &explain_sql, &params, timeout, input.analyze)
+                    .await
+            }
+        }
+        .await;
 
-        self.connection_manager
-            .release_pool_for_database(&input.connection_id, database)
-            .await;
+        let db_type = pool.db_type();
+        guard.release().await;
 
-        let rows = result?;
+        let (rows, execution) = result?;
 
         Ok(Self::build_output(
             rows,
+            db_type,
             sql,
             start.elapsed().as_millis() as u64,
             format,
+            input.analyze,
+            execution,
         ))
     }
 
-    async fn execute_explain(
+    /// Analyze a write statement inside a registry transaction that is always
+    /// rolled back. A statement that is cancelled or times out is stopped on
+    /// the server before the rollback.
+    async fn analyze_in_rollback(
+        &self,
+        connection_id: &str,
+        pool: &DbPool,
+        sql: &str,
+        explain_sql: &str,
+        params: &[QueryParam],
+        timeout: Duration,
+    ) -> DbResult<(
+        Vec<serde_json::Map<String, serde_json::Value>>,
+        Option<Execution>,
+    )> {
+        let registry = &self.transaction_registry;
+        let timeout_secs = timeout.as_secs() as u32;
+        let tx_id = match pool {
+            DbPool::MySql(p) => {
+                registry
+                    .begin_mysql(p, connection_id.to_string(), Some(timeout_secs))
+                    .await?
+            }
+            DbPool::Postgres(p) => {
+                registry
+                    .begin_postgres(p, connection_id.to_string(), Some(timeout_secs))
+                    .await?
+            }
+            DbPool::SQLite(p) => {
+                registry
+                    .begin_sqlite(p, connection_id.to_string(), Some(timeout_secs))
+                    .await?
+            }
+        };
+
+        let deadline = Instant::now() + timeout;
+        let result = async {
+            let plan = registry
+                .query_in_transaction(
+                    &tx_id,
+                    connection_id,
+                    explain_sql,
+                    params,
+                    EXPLAIN_ROW_LIMIT,
+                    true,
+                    timeout,
+                    &self.control,
+                )
+                .await?;
+            let execution = match pool {
+                DbPool::SQLite(_) => {
+                    let started = Instant::now();
+                    let remaining = deadline.saturating_duration_since(started);
+                    let rows = registry
+                        .execute_in_transaction(
+                            &tx_id,
+                            connection_id,
+                            sql,
+                            params,
+                            remaining,
+                            &self.control,
+                        )
+                        .await?;
+                    Some(Execution {
+                        rows,
+                        time_ms: started.elapsed().as_secs_f64() * 1000.0,
+                    })
+                }
+                _ => None,
+            };
+            Ok((plan.rows, execution))
+        }
+        .await;
+
+        if let Err(e) = registry.rollback(&tx_id, connection_id).await {
+            warn!(transaction_id = %tx_id, error = %e, "Failed to roll back EXPLAIN ANALYZE");
+        }
+        result
+    }
+
+    /// Run EXPLAIN, and for an analyzed SQLite read the timed statement, on a
+    /// connection of their own under the handler's control.
+    async fn explain_controlled(
         &self,
         pool: &DbPool,
+        sql: &str,
         explain_sql: &str,
         params: &[QueryParam],
         timeout: Duration,
+        analyze: bool,
+    ) -> DbResult<(
+        Vec<serde_json::Map<String, serde_json::Value>>,
+        Option<Execution>,
+    )> {
+        let control = &self.control;
+        let operation = if analyze {
+            "EXPLAIN ANALYZE"
+        } else {
+            "EXPLAIN"
+        };
+        let mut pinned = control.acquire(pool).await?;
+        let (conn, canceller) = pinned.parts();
+        let result = control
+            .supervise(canceller, operation, timeout, async {
+                let rows = Self::execute_explain(conn, explain_sql, params).await?;
+                let execution = match conn {
+                    PinnedConnection::SQLite(c) if analyze => {
+                        Some(Self::time_sqlite_query(c, sql, params, control.row_counter()).await?)
+                    }
+                    _ => None,
+                };
+                Ok((rows, execution))
+            })
+            .await;
+        control.release(pinned).await;
+        result
+    }
+
+    /// Run a read statement to completion on SQLite, counting rows without
+    /// keeping them.
+    async fn time_sqlite_query(
+        conn: &mut SqliteConnection,
+        sql: &str,
+        params: &[QueryParam],
+        counter: &AtomicU64,
+    ) -> DbResult<Execution> {
+        use futures_util::TryStreamExt;
+
+        let mut query = sqlx::query(sql);
+        for param in params {
+            query = bind_sqlite_param(query, param);
+        }
+
+        let started = Instant::now();
+        let rows = query
+            .fetch(&mut *conn)
+            .try_fold(0u64, |n, _| async move {
+                counter.fetch_add(1, Ordering::Relaxed);
+                Ok(n + 1)
+            })
+            .await?;
+        Ok(Execution {
+            rows,
+            time_ms: started.elapsed().as_secs_f64() * 1000.0,
+        })
+    }
+
+    /// Plan tree for `sql` without executing it, for advisory tools such as
+    /// `suggest_indexes`.
+    pub(crate) async fn plan_tree(
+        pool: &DbPool,
+        sql: &str,
+        timeout: Duration,
+    ) -> DbResult<Option<PlanNode>> {
+        let explain_sql = Self::generate_explain_sql(pool, sql, false);
+        let control = QueryControl::default();
+        let mut pinned = control.acquire(pool).await?;
+        let (conn, canceller) = pinned.parts();
+        let rows = control
+            .supervise(
+                canceller,
+                "EXPLAIN",
+                timeout,
+                Self::execute_explain(conn, &explain_sql, &[]),
+            )
+            .await;
+        control.release(pinned).await;
+        Ok(plan::parse_plan(pool.db_type(), &rows?))
+    }
+
+    async fn execute_explain(
+        conn: &mut PinnedConnection,
+        explain_sql: &str,
+        params: &[QueryParam],
     ) -> DbResult<Vec<serde_json::Map<String, serde_json::Value>>> {
         use crate::db::types::RowToJson;
         use futures_util::TryStreamExt;
 
-        match pool {
-            DbPool::MySql(p) => {
+        match conn {
+            PinnedConnection::MySql(c) => {
                 let mut query = sqlx::query(explain_sql);
                 for param in params {
                     query = bind_mysql_param(query, param);
                 }
 
-                let rows_future = query.fetch(p).try_collect::<Vec<_>>();
-                match tokio::time::timeout(timeout, rows_future).await {
-                    Ok(Ok(rows)) => Ok(rows.iter().map(|r| r.to_json_map()).collect()),
-                    Ok(Err(e)) => Err(DbError::from(e)),
-                    Err(_) => Err(DbError::timeout("EXPLAIN", timeout.as_secs() as u32)),
-                }
+                let rows = query.fetch(&mut **c).try_collect::<Vec<_>>().await?;
+                Ok(rows.iter().map(|r| r.to_json_map()).collect())
             }
-            DbPool::Postgres(p) => {
+            PinnedConnection::Postgres(c) => {
                 let mut query = sqlx::query(explain_sql);
                 for param in params {
                     query = bind_postgres_param(query, param);
                 }
 
-                let rows_future = query.fetch(p).try_collect::<Vec<_>>();
-                match tokio::time::timeout(timeout, rows_future).await {
-                    Ok(Ok(rows)) => Ok(rows.iter().map(|r| r.to_json_map()).collect()),
-                    Ok(Err(e)) => Err(DbError::from(e)),
-                    Err(_) => Err(DbError::timeout("EXPLAIN", timeout.as_secs() as u32)),
-                }
+                let rows = query.fetch(&mut **c).try_collect::<Vec<_>>().await?;
+                Ok(rows.iter().map(|r| r.to_json_map()).collect())
             }
-            DbPool::SQLite(p) => {
+            PinnedConnection::SQLite(c) => {
                 let mut query = sqlx::query(explain_sql);
                 for param in params {
                     query = bind_sqlite_param(query, param);
                 }
 
-                let rows_future = query.fetch(p).try_collect::<Vec<_>>();
-                match tokio::time::timeout(timeout, rows_future).await {
-                    Ok(Ok(rows)) => Ok(rows.iter().map(|r| r.to_json_map()).collect()),
-                    Ok(Err(e)) => Err(DbError::from(e)),
-                    Err(_) => Err(DbError::timeout("EXPLAIN", timeout.as_secs() as u32)),
-                }
+                let rows = query.fetch(&mut **c).try_collect::<Vec<_>>().await?;
+                Ok(rows.iter().map(|r| r.to_json_map()).collect())
             }
         }
     }
@@ -342,4 +744,63 @@ mod tests {
         let s: QueryParam = QueryParamInput::String("hello".to_string()).into();
         assert!(matches!(s, QueryParam::String(_)));
     }
+
+    #[test]
+    fn test_check_analyze_statement() {
+        let sqlite = DatabaseType::SQLite;
+        assert!(!check_analyze_statement("SELECT * FROM users", sqlite).unwrap());
+        assert!(
+            check_analyze_statement("UPDATE users SET name = 'x' WHERE id = 1", sqlite).unwrap()
+        );
+        assert!(check_analyze_statement("INSERT INTO users (name) VALUES ('x')", sqlite).unwrap());
+
+        for sql in [
+            "CREATE TABLE t (id INTEGER)",
+            "ALTER TABLE users ADD COLUMN age INTEGER",
+            "SELECT 1; DELETE FROM users WHERE id = 1",
+        ] {
+            let err = check_analyze_statement(sql, sqlite).unwrap_err();
+            assert!(matches!(err, DbError::InvalidInput { .. }), "{sql}: {err}");
+        }
+        for sql in [
+            "DELETE FROM users",
+            "DROP TABLE users",
+            "TRUNCATE TABLE users",
+        ] {
+            let err = check_analyze_statement(sql, sqlite).unwrap_err();
+            assert!(
+                matches!(err, DbError::DangerousOperationBlocked { .. }),
+                "{sql}: {err}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_check_analyze_statement_mysql_single_table_writes() {
+        let mysql = DatabaseType::MySQL;
+        for sql in [
+            "INSERT INTO users (name) VALUES ('x')",
+            "UPDATE users SET name = 'x' WHERE id = 1",
+            "DELETE FROM users WHERE id = 1",
+        ] {
+            let err = check_analyze_statement(sql, mysql).unwrap_err();
+            assert!(err.to_string().contains("single-table"), "{sql}: {err}");
+        }
+
+        assert!(
+            check_analyze_statement(
+                "UPDATE users u JOIN orders o ON o.user_id = u.id SET u.name = 'x' WHERE o.id = 1",
+                mysql,
+            )
+            .unwrap()
+        );
+        assert!(
+            check_analyze_statement(
+                "DELETE FROM users USING users JOIN orders ON orders.user_id = users.id WHERE orders.id = 1",
+                mysql,
+            )
+            .unwrap()
+        );
+        assert!(!check_analyze_statement("SELECT * FROM users", mysql).unwrap());
+    }
 }
@@ -0,0 +1,1139 @@
+//! Index recommendation tool.
+//!
+//! This module implements the `suggest_indexes` MCP tool. Each query is parsed
+//! and the columns it filters, joins, sorts and groups on are collected per
+//! table. A candidate index puts equality and join columns first, followed by
+//! one range column or, failing that, the ORDER BY/GROUP BY columns.
+//!
+//! Candidates already served by an existing index (or the primary key) are
+//! dropped, candidates that are a prefix of another are merged into it, and the
+//! rest are ranked using the backend's EXPLAIN output: tables read by a full
+//! scan come first. Existing indexes that duplicate another or are a prefix of
+//! another are flagged as well.
+//!
+//! The tool is advisory only: it returns `CREATE INDEX` statements but never
+//! runs them.
+
+use crate::db::{ConnectionManager, DbPool};
+use crate::error::{DbError, DbResult};
+use crate::models::{DatabaseType, IndexInfo, TableSchema};
+use crate::tools::explain::ExplainToolHandler;
+use crate::tools::reference_check::join_constraint;
+use crate::tools::sql_validator::get_dialect;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use sqlparser::ast::{
+    BinaryOperator, Delete, Expr, FromTable, GroupByExpr, Ident, JoinConstraint, ObjectName,
+    OrderBy, OrderByKind, Query, Select, SetExpr, Statement, TableFactor, TableWithJoins, Update,
+    UpdateTableFromKind, Value,
+};
+use sqlparser::parser::Parser;
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::info;
+
+/// Maximum number of queries analyzed per call.
+pub const MAX_QUERIES: usize = 50;
+
+/// Maximum number of columns in a suggested index.
+const MAX_INDEX_COLUMNS: usize = 4;
+
+/// Longest identifier PostgreSQL accepts (MySQL allows 64).
+const MAX_INDEX_NAME_LEN: usize = 63;
+
+/// Timeout for the EXPLAIN of each query.
+const EXPLAIN_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Input for the suggest_indexes tool.
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
+pub struct SuggestIndexesInput {
+    /// Database connection ID from list_connections
+    pub connection_id: String,
+    /// Queries to analyze (SELECT, UPDATE or DELETE), max 50
+    pub queries: Vec<String>,
+    /// Target database name (optional)
+    #[serde(default)]
+    pub database: Option<String>,
+    /// Use EXPLAIN output to rank suggestions (queries are not executed). Default: true
+    #[serde(default = "default_true")]
+    pub explain: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+/// A proposed index.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct IndexSuggestion {
+    pub table: String,
+    pub columns: Vec<String>,
+    /// Statement that would create the index. Never executed by this tool.
+    pub create_statement: String,
+    /// Why the index is proposed
+    pub rationale: String,
+    /// 1-based positions of the input queries that would use the index
+    pub queries: Vec<usize>,
+    /// True when EXPLAIN shows a full scan of the table in one of the queries
+    pub full_scan_in_plan: bool,
+}
+
+/// Kind of problem with an existing index.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum IndexIssueKind {
+    /// Same columns in the same order as another index
+    Duplicate,
+    /// Its columns are a leading prefix of another index
+    Redundant,
+}
+
+/// An existing index that is likely unnecessary.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct IndexIssue {
+    pub table: String,
+    pub index: String,
+    pub kind: IndexIssueKind,
+    /// Index that makes this one unnecessary
+    pub covered_by: String,
+    pub message: String,
+}
+
+/// Output from the suggest_indexes tool.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct SuggestIndexesOutput {
+    /// Proposed indexes, most important first
+    pub suggestions: Vec<IndexSuggestion>,
+    /// Duplicate or redundant indexes on the analyzed tables
+    pub existing_index_issues: Vec<IndexIssue>,
+    /// Tables whose columns and indexes were compared
+    pub tables_analyzed: usize,
+    /// Queries or statements that could not be (fully) analyzed
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub notes: Vec<String>,
+}
+
+/// Table reference as written in the SQL.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+struct TableRef {
+    schema: Option<String>,
+    name: String,
+}
+
+impl TableRef {
+    fn from_object_name(name: &ObjectName) -> Option<Self> {
+        let parts: Vec<&Ident> = name.0.iter().filter_map(|p| p.as_ident()).collect();
+        if parts.len() != name.0.len() {
+            return None;
+        }
+        match parts.as_slice() {
+            [table] => Some(Self {
+                schema: None,
+                name: table.value.clone(),
+            }),
+            [.., schema, table] => Some(Self {
+                schema: Some(schema.value.clone()),
+                name: table.value.clone(),
+            }),
+            [] => None,
+        }
+    }
+
+    fn display(&self) -> String {
+        match &self.schema {
+            Some(schema) => format!("{}.{}", schema, self.name),
+            None => self.name.clone(),
+        }
+    }
+}
+
+/// How a query uses a column.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Usage {
+    Equality,
+    Join,
+    Range,
+    Sort,
+    Group,
+}
+
+impl Usage {
+    fn describe(self) -> &'static str {
+        match self {
+            Usage::Equality => "equality filter on",
+            Usage::Join => "join on",
+            Usage::Range => "range filter on",
+            Usage::Sort => "ORDER BY",
+            Usage::Group => "GROUP BY",
+        }
+    }
+}
+
+/// Table a column reference belongs to, as far as the SQL alone tells.
+#[derive(Debug, Clone)]
+enum Owner {
+    Table(TableRef),
+    /// Unqualified column: one of the tables in scope
+    OneOf(Vec<TableRef>),
+}
+
+#[derive(Debug, Clone)]
+struct ColumnUse {
+    owner: Owner,
+    column: String,
+    usage: Usage,
+}
+
+/// Column usage collected from one query.
+#[derive(Debug, Default)]
+struct QueryUsage {
+    /// Tables keyed by lowercased alias and name, to map EXPLAIN relations back
+    sources: HashMap<String, TableRef>,
+    uses: Vec<ColumnUse>,
+}
+
+/// AST walker collecting [`ColumnUse`]s.
+#[derive(Default)]
+struct Collector {
+    /// Sources of enclosing query scopes, innermost last; None for derived tables
+    scopes: Vec<Vec<(String, Option<TableRef>)>>,
+    /// Lowercased CTE names visible in each enclosing query
+    ctes: Vec<HashSet<String>>,
+    usage: QueryUsage,
+}
+
+impl Collector {
+    /// Returns false for statements that are not analyzed.
+    fn statement(&mut self, stmt: &Statement) -> bool {
+        match stmt {
+            Statement::Query(query) => self.query(query),
+            Statement::Update(update) => self.update(update),
+            Statement::Delete(delete) => self.delete(delete),
+            Statement::Insert(insert) => match &insert.source {
+                Some(source) => self.query(source),
+                None => return false,
+            },
+            _ => return false,
+        }
+        true
+    }
+
+    fn query(&mut self, query: &Query) {
+        let mut names = HashSet::new();
+        if let Some(with) = &query.with {
+            names.extend(
+                with.cte_tables
+                    .iter()
+                    .map(|cte| cte.alias.name.value.to_lowercase()),
+            );
+        }
+        self.ctes.push(names);
+        if let Some(with) = &query.with {
+            for cte in &with.cte_tables {
+                self.query(&cte.query);
+            }
+        }
+        match query.body.as_ref() {
+            SetExpr::Select(select) => self.select(select, query.order_by.as_ref()),
+            body => self.set_expr(body),
+        }
+        self.ctes.pop();
+    }
+
+    fn set_expr(&mut self, body: &SetExpr) {
+        match body {
+            SetExpr::Select(select) => self.select(select, None),
+            SetExpr::Query(query) => self.query(query),
+            SetExpr::SetOperation { left, right, .. } => {
+                self.set_expr(left);
+                self.set_expr(right);
+            }
+            _ => {}
+        }
+    }
+
+    fn select(&mut self, select: &Select, order_by: Option<&OrderBy>) {
+        self.scopes.push(Vec::new());
+        let mut conditions = Vec::new();
+        for table in &select.from {
+            self.table_with_joins(table, &mut conditions);
+        }
+        for condition in conditions {
+            self.predicate(condition);
+        }
+        if let Some(selection) = &select.selection {
+            self.predicate(selection);
+        }
+        if let GroupByExpr::Expressions(exprs, _) = &select.group_by {
+            for expr in exprs {
+                self.record(expr, Usage::Group);
+            }
+        }
+        if let Some(OrderBy {
+            kind: OrderByKind::Expressions(exprs),
+            ..
+        }) = order_by
+        {
+            for order in exprs {
+                self.record(&order.expr, Usage::Sort);
+            }
+        }
+        self.scopes.pop();
+    }
+
+    fn update(&mut self, update: &Update) {
+        self.scopes.push(Vec::new());
+        let mut conditions = Vec::new();
+        self.table_with_joins(&update.table, &mut conditions);
+        if let Some(UpdateTableFromKind::BeforeSet(from) | UpdateTableFromKind::AfterSet(from)) =
+            &update.from
+        {
+            for table in from {
+                self.table_with_joins(table, &mut conditions);
+            }
+        }
+        for condition in conditions {
+            self.predicate(condition);
+        }
+        if let Some(selection) = &update.selection {
+            self.predicate(selection);
+        }
+        self.scopes.pop();
+    }
+
+    fn delete(&mut self, delete: &Delete) {
+        self.scopes.push(Vec::new());
+        let (FromTable::WithFromKeyword(from) | FromTable::WithoutKeyword(from)) = &delete.from;
+        let mut conditions = Vec::new();
+        for table in from.iter().chain(delete.using.iter().flatten()) {
+            self.table_with_joins(table, &mut conditions);
+        }
+        for condition in conditions {
+            self.predicate(condition);
+        }
+        if let Some(selection) = &delete.selection {
+            self.predicate(selection);
+        }
+        self.scopes.pop();
+    }
+
+    fn table_with_joins<'q>(&mut self, table: &'q TableWithJoins, conditions: &mut Vec<&'q Expr>) {
+        self.table_factor(&table.relation, conditions);
+        for join in &table.joins {
+            self.table_factor(&join.relation, conditions);
+            if let Some(JoinConstraint::On(expr)) = join_constraint(&join.join_operator) {
+                conditions.push(expr);
+            }
+        }
+    }
+
+    fn table_factor<'q>(&mut self, factor: &'q TableFactor, conditions: &mut Vec<&'q Expr>) {
+        let (name, table) = match factor {
+            TableFactor::Table {
+                name,
+                alias,
+                args: None,
+                ..
+            } => {
+                let table = TableRef::from_object_name(name).filter(|t| {
+                    t.schema.is_some()
+                        || !self
+                            .ctes
+                            .iter()
+                            .any(|names| names.contains(&t.name.to_lowercase()))
+                });
+                let last = name
+                    .0
+                    .last()
+                    .and_then(|p| p.as_ident())
+                    .map(|i| i.value.clone())
+                    .unwrap_or_default();
+                let alias = alias.as_ref().map_or(last, |a| a.name.value.clone());
+                if let Some(table) = &table {
+                    self.usage
+                        .sources
+                        .insert(table.name.to_lowercase(), table.clone());
+                    self.usage
+                        .sources
+                        .insert(alias.to_lowercase(), table.clone());
+                }
+                (alias, table)
+            }
+            TableFactor::Derived {
+                subquery, alias, ..
+            } => {
+                self.query(subquery);
+                let alias = alias.as_ref().map(|a| a.name.value.clone());
+                (alias.unwrap_or_default(), None)
+            }
+            TableFactor::NestedJoin {
+                table_with_joins, ..
+            } => return self.table_with_joins(table_with_joins, conditions),
+            _ => (String::new(), None),
+        };
+        if let Some(scope) = self.scopes.last_mut() {
+            scope.push((name.to_lowercase(), table));
+        }
+    }
+
+    /// Collect column usage from a WHERE or ON condition. Only AND-ed
+    /// conditions are considered; an OR generally defeats a single index.
+    fn predicate(&mut self, expr: &Expr) {
+        match expr {
+            Expr::BinaryOp {
+                left,
+                op: BinaryOperator::And,
+                right,
+            } => {
+                self.predicate(left);
+                self.predicate(right);
+            }
+            Expr::Nested(inner) => self.predicate(inner),
+            Expr::BinaryOp { left, op, right } => {
+                let usage = match op {
+                    BinaryOperator::Eq => Usage::Equality,
+                    BinaryOperator::Lt
+                    | BinaryOperator::LtEq
+                    | BinaryOperator::Gt
+                    | BinaryOperator::GtEq => Usage::Range,
+                    _ => return,
+                };
+                match (self.column(left), self.column(right)) {
+                    (Some(l), Some(r)) if usage == Usage::Equality => {
+                        self.push(l, Usage::Join);
+                        self.push(r, Usage::Join);
+                    }
+                    (Some(c), None) | (None, Some(c)) => self.push(c, usage),
+                    _ => {}
+                }
+                for side in [left, right] {
+                    if let Expr::Subquery(query) = side.as_ref() {
+                        self.query(query);
+                    }
+                }
+            }
+            Expr::Between {
+                expr,
+                negated: false,
+                ..
+            } => self.record(expr, Usage::Range),
+            Expr::InList {
+                expr,
+                negated: false,
+                ..
+            }
+            | Expr::IsNull(expr) => self.record(expr, Usage::Equality),
+            Expr::InSubquery {
+                expr,
+                subquery,
+                negated,
+            } => {
+                if !negated {
+                    self.record(expr, Usage::Equality);
+                }
+                self.query(subquery);
+            }
+            // A LIKE pattern can only use an index up to its first wildcard
+            Expr::Like {
+                negated: false,
+                expr,
+                pattern,
+                ..
+            } if has_literal_prefix(pattern) => self.record(expr, Usage::Range),
+            Expr::Exists { subquery, .. } => self.query(subquery),
+            _ => {}
+        }
+    }
+
+    fn record(&mut self, expr: &Expr, usage: Usage) {
+        if let Some(column) = self.column(expr) {
+            self.push(column, usage);
+        }
+    }
+
+    fn push(&mut self, (owner, column): (Owner, String), usage: Usage) {
+        self.usage.uses.push(ColumnUse {
+            owner,
+            column,
+            usage,
+        });
+    }
+
+    /// Resolve a plain column reference. Expressions over columns (functions,
+    /// casts, arithmetic) cannot use a plain index and yield None.
+    fn column(&self, expr: &Expr) -> Option<(Owner, String)> {
+        match expr {
+            Expr::Nested(inner) => self.column(inner),
+            Expr::Identifier(ident) => {
+                let tables = self
+                    .scopes
+                    .last()?
+                    .iter()
+                    .filter_map(|(_, table)| table.clone())
+                    .collect();
+                Some((Owner::OneOf(tables), ident.value.clone()))
+            }
+            Expr::CompoundIdentifier(parts) => match parts.as_slice() {
+                [.., qualifier, column] => {
+                    let qualifier = qualifier.value.to_lowercase();
+                    let table = self
+                        .scopes
+                        .iter()
+                        .rev()
+                        .flatten()
+                        .find(|(name, _)| *name == qualifier)?
+                        .1
+                        .clone()?;
+                    Some((Owner::Table(table), column.value.clone()))
+                }
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+}
+
+fn has_literal_prefix(pattern: &Expr) -> bool {
+    match pattern {
+        Expr::Value(value) => match &value.value {
+            Value::SingleQuotedString(s) | Value::DoubleQuotedString(s) => {
+                !s.is_empty() && !s.starts_with(['%', '_'])
+            }
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+/// A candidate index for one table.
+#[derive(Debug, Clone)]
+struct Candidate {
+    table: TableRef,
+    /// Equality and join columns; their order does not matter
+    equality: Vec<String>,
+    /// Range, sort or group columns, in index order
+    ordered: Vec<String>,
+    usages: Vec<(Usage, String)>,
+    queries: BTreeSet<usize>,
+    full_scan_in_plan: bool,
+}
+
+impl Candidate {
+    /// Build the candidate for one table of one query: equality columns first,
+    /// then a single range column, or the sort/group columns when there is none.
+    fn new(table: TableRef, uses: &[(Usage, String)], query: usize) -> Option<Self> {
+        let of = |usage: Usage| -> Vec<String> {
+            let mut columns: Vec<String> = Vec::new();
+            for (u, c) in uses {
+                if *u == usage && !contains(&columns, c) {
+                    columns.push(c.clone());
+                }
+            }
+            columns
+        };
+
+        let mut equality = of(Usage::Equality);
+        for column in of(Usage::Join) {
+            if !contains(&equality, &column) {
+                equality.push(column);
+            }
+        }
+        let fresh = |columns: Vec<String>| -> Vec<String> {
+            columns
+                .into_iter()
+                .filter(|c| !contains(&equality, c))
+                .collect()
+        };
+        let mut ordered = fresh(of(Usage::Range));
+        ordered.truncate(1);
+        if ordered.is_empty() {
+            ordered = fresh(of(Usage::Sort));
+        }
+        if ordered.is_empty() {
+            ordered = fresh(of(Usage::Group));
+        }
+
+        equality.truncate(MAX_INDEX_COLUMNS);
+        ordered.truncate(MAX_INDEX_COLUMNS - equality.len());
+        if equality.is_empty() && ordered.is_empty() {
+            return None;
+        }
+
+        Some(Self {
+            table,
+            equality,
+            ordered,
+            usages: uses.to_vec(),
+            queries: BTreeSet::from([query]),
+            full_scan_in_plan: false,
+        })
+    }
+
+    fn columns(&self) -> Vec<String> {
+        self.equality.iter().chain(&self.ordered).cloned().collect()
+    }
+
+    /// Whether an index on `columns` serves this candidate: the equality
+    /// columns lead in any order, followed by the ordered columns.
+    fn served_by(&self, columns: &[String]) -> bool {
+        let k = self.equality.len();
+        if columns.len() < k + self.ordered.len() {
+            return false;
+        }
+        let head: HashSet<String> = columns[..k].iter().map(|c| c.to_lowercase()).collect();
+        let wanted: HashSet<String> = self.equality.iter().map(|c| c.to_lowercase()).collect();
+        head == wanted
+            && columns[k..k + self.ordered.len()]
+                .iter()
+                .zip(&self.ordered)
+                .all(|(a, b)| a.eq_ignore_ascii_case(b))
+    }
+
+    fn merge(&mut self, other: Candidate) {
+        self.queries.extend(other.queries);
+        self.usages.extend(other.usages);
+        self.full_scan_in_plan |= other.full_scan_in_plan;
+    }
+
+    fn rationale(&self, existing: &[IndexInfo]) -> String {
+        let queries: Vec<String> = self.queries.iter().map(|q| q.to_string()).collect();
+        let mut parts = Vec::new();
+        for usage in [
+            Usage::Equality,
+            Usage::Join,
+            Usage::Range,
+            Usage::Sort,
+            Usage::Group,
+        ] {
+            let mut columns: Vec<String> = Vec::new();
+            for (u, c) in &self.usages {
+                if *u == usage && !contains(&columns, c) {
+                    columns.push(c.clone());
+                }
+            }
+            if !columns.is_empty() {
+                parts.push(format!("{} {}", usage.describe(), columns.join(", ")));
+            }
+        }
+
+        let mut rationale = format!(
+            "Quer{} {} on '{}' use{} {}.",
+            if queries.len() == 1 { "y" } else { "ies" },
+            queries.join(", "),
+            self.table.display(),
+            if queries.len() == 1 { "s" } else { "" },
+            parts.join("; ")
+        );
+        if self.full_scan_in_plan {
+            rationale.push_str(&format!(
+                " EXPLAIN shows a full scan of '{}'.",
+                self.table.display()
+            ));
+        }
+        let leading = &self.columns()[0];
+        if let Some(index) = existing.iter().find(|i| {
+            i.columns
+                .first()
+                .is_some_and(|c| c.eq_ignore_ascii_case(leading))
+        }) {
+            rationale.push_str(&format!(
+                " Existing index '{}' ({}) only covers part of it.",
+                index.name,
+                index.columns.join(", ")
+            ));
+        }
+        rationale
+    }
+}
+
+fn contains(columns: &[String], column: &str) -> bool {
+    columns.iter().any(|c| c.eq_ignore_ascii_case(column))
+}
+
+/// Existing indexes of a table, with the primary key when it is not listed.
+fn existing_indexes(schema: &TableSchema) -> Vec<IndexInfo> {
+    let mut indexes = schema.indexes.clone();
+    if !schema.primary_key.is_empty() && !indexes.iter().any(|i| i.is_primary) {
+        indexes.push(IndexInfo::new("PRIMARY KEY", schema.primary_key.clone()).with_primary(true));
+    }
+    indexes
+}
+
+/// Flag indexes with the same columns as another, and non-unique indexes whose
+/// columns are a leading prefix of another index of the same kind.
+fn index_issues(table: &str, indexes: &[IndexInfo]) -> Vec<IndexIssue> {
+    let same = |a: &[String], b: &[String]| {
+        a.len() == b.len() && a.iter().zip(b).all(|(x, y)| x.eq_ignore_ascii_case(y))
+    };
+    let algorithm = |i: &IndexInfo| {
+        i.index_algorithm
+            .as_deref()
+            .unwrap_or("BTREE")
+            .to_uppercase()
+    };
+
+    let mut issues = Vec::new();
+    let mut reported = HashSet::new();
+    for (i, index) in indexes.iter().enumerate() {
+        for (j, other) in indexes.iter().enumerate() {
+            if i == j || reported.contains(&i) || algorithm(index) != algorithm(other) {
+                continue;
+            }
+            let issue = if same(&index.columns, &other.columns) {
+                // Keep the primary/unique one, or the first of two equals
+                let keep_other = (other.is_primary && !index.is_primary)
+                    || (other.is_unique && !index.is_unique)
+                    || (other.is_unique == index.is_unique
+                        && other.is_primary == index.is_primary
+                        && j < i);
+                keep_other.then(|| IndexIssue {
+                    table: table.to_string(),
+                    index: index.name.clone(),
+                    kind: IndexIssueKind::Duplicate,
+                    covered_by: other.name.clone(),
+                    message: format!(
+                        "Index '{}' duplicates '{}' ({}); one of them can be dropped",
+                        index.name,
+                        other.name,
+                        index.columns.join(", ")
+                    ),
+                })
+            } else if !index.is_unique
+                && index.columns.len() < other.columns.len()
+                && same(&index.columns, &other.columns[..index.columns.len()])
+            {
+                Some(IndexIssue {
+                    table: table.to_string(),
+                    index: index.name.clone(),
+                    kind: IndexIssueKind::Redundant,
+                    covered_by: other.name.clone(),
+                    message: format!(
+                        "Index '{}' ({}) is a prefix of '{}' ({}), which serves the same lookups",
+                        index.name,
+                        index.columns.join(", "),
+                        other.name,
+                        other.columns.join(", ")
+                    ),
+                })
+            } else {
+                None
+            };
+            if let Some(issue) = issue {
+                reported.insert(i);
+                issues.push(issue);
+            }
+        }
+    }
+    issues
+}
+
+fn create_statement(db_type: DatabaseType, table: &TableRef, columns: &[String]) -> String {
+    let mut name = format!("idx_{}_{}", table.name, columns.join("_")).to_lowercase();
+    if name.len() > MAX_INDEX_NAME_LEN {
+        let mut end = MAX_INDEX_NAME_LEN;
+        while !name.is_char_boundary(end) {
+            end -= 1;
+        }
+        name.truncate(end);
+    }
+    let target = match &table.schema {
+        Some(schema) => format!(
+            "{}.{}",
+            db_type.quote_identifier(schema),
+            db_type.quote_identifier(&table.name)
+        ),
+        None => db_type.quote_identifier(&table.name),
+    };
+    let columns: Vec<String> = columns
+        .iter()
+        .map(|c| db_type.quote_identifier(c))
+        .collect();
+    format!(
+        "CREATE INDEX {} ON {} ({})",
+        db_type.quote_identifier(&name),
+        target,
+        columns.join(", ")
+    )
+}
+
+pub struct IndexAdvisorToolHandler {
+    connection_manager: Arc<ConnectionManager>,
+}
+
+impl IndexAdvisorToolHandler {
+    pub fn new(connection_manager: Arc<ConnectionManager>) -> Self {
+        Self { connection_manager }
+    }
+
+    pub async fn suggest_indexes(
+        &self,
+        input: SuggestIndexesInput,
+    ) -> DbResult<SuggestIndexesOutput> {
+        if input.queries.iter().all(|q| q.trim().is_empty()) {
+            return Err(DbError::invalid_input("At least one query is required"));
+        }
+        if input.queries.len() > MAX_QUERIES {
+            return Err(DbError::invalid_input(format!(
+                "At most {} queries can be analyzed at once",
+                MAX_QUERIES
+            )));
+        }
+
+        let config = self
+            .connection_manager
+            .get_config(&input.connection_id)
+            .await?;
+
+        // For server-level connections, database parameter is required
+        if config.server_level && input.database.is_none() {
+            return Err(DbError::invalid_input(
+                "Server-level connections require a 'database' parameter to specify which database to query. \
+                Use list_databases first to discover available databases, then call suggest_indexes with database=<database_name>.",
+            ));
+        }
+
+        let database = input.database.as_deref();
+        let pool = self
+            .connection_manager
+            .get_pool_for_database(&input.connection_id, database)
+            .await?;
+
+        let output = self.analyze(&input, &pool, database).await;
+
+        self.connection_manager
+            .release_pool_for_database(&input.connection_id, database)
+            .await;
+
+        info!(
+            connection_id = %input.connection_id,
+            queries = input.queries.len(),
+            suggestions = output.suggestions.len(),
+            "Suggested indexes"
+        );
+
+        Ok(output)
+    }
+
+    async fn analyze(
+        &self,
+        input: &SuggestIndexesInput,
+        pool: &DbPool,
+        database: Option<&str>,
+    ) -> SuggestIndexesOutput {
+        let db_type = pool.db_type();
+        let mut notes = Vec::new();
+        let usages = collect_usages(db_type, &input.queries, &mut notes);
+
+        // Describe every referenced table through the schema cache
+        let mut tables: BTreeSet<TableRef> = BTreeSet::new();
+        for (_, _, usage) in &usages {
+            tables.extend(usage.sources.values().cloned());
+        }
+        let cache = self.connection_manager.schema_cache();
+        let mut schemas: HashMap<TableRef, TableSchema> = HashMap::new();
+        for table in tables {
+            let schema = match (&table.schema, db_type) {
+                (Some(schema), _) => Some(schema.as_str()),
+                (None, DatabaseType::MySQL) => database,
+                (None, _) => None,
+            };
+            match cache
+                .describe_table(&input.connection_id, database, pool, &table.name, schema)
+                .await
+            {
+                Ok(described) => {
+                    schemas.insert(table, described);
+                }
+                Err(_) => notes.push(format!(
+                    "Table '{}' could not be described and was skipped",
+                    table.display()
+                )),
+            }
+        }
+
+        let mut candidates: Vec<Candidate> = Vec::new();
+        for (number, sql, usage) in &usages {
+            let mut per_table: HashMap<TableRef, Vec<(Usage, String)>> = HashMap::new();
+            for column_use in &usage.uses {
+                if let Some((table, column)) = resolve(column_use, &schemas) {
+                    per_table
+                        .entry(table)
+                        .or_default()
+                        .push((column_use.usage, column));
+                }
+            }
+            if per_table.is_empty() {
+                continue;
+            }
+
+            let full_scans = if input.explain {
+                match ExplainToolHandler::plan_tree(pool, sql, EXPLAIN_TIMEOUT).await {
+                    Ok(tree) => {
+                        let mut scanned = HashSet::new();
+                        if let Some(root) = &tree {
+                            root.walk(0, &mut |node, _| {
+                                if node.is_full_scan() {
+                                    if let Some(table) = node
+                                        .relation
+                                        .as_ref()
+                                        .and_then(|r| usage.sources.get(&r.to_lowercase()))
+                                    {
+                                        scanned.insert(table.clone());
+                                    }
+                                }
+                            });
+                        }
+                        scanned
+                    }
+                    Err(e) => {
+                        notes.push(format!("EXPLAIN failed for query {}: {}", number, e));
+                        HashSet::new()
+                    }
+                }
+            } else {
+                HashSet::new()
+            };
+
+            let mut tables: Vec<_> = per_table.into_iter().collect();
+            tables.sort_by(|a, b| a.0.cmp(&b.0));
+            for (table, uses) in tables {
+                if let Some(mut candidate) = Candidate::new(table, &uses, *number) {
+                    candidate.full_scan_in_plan = full_scans.contains(&candidate.table);
+                    candidates.push(candidate);
+                }
+            }
+        }
+
+        // Widest candidates first, so narrower ones merge into them
+        candidates.sort_by(|a, b| {
+            a.table
+                .cmp(&b.table)
+                .then(b.columns().len().cmp(&a.columns().len()))
+        });
+        let mut kept: Vec<Candidate> = Vec::new();
+        for candidate in candidates {
+            let existing = existing_indexes(&schemas[&candidate.table]);
+            if existing.iter().any(|i| candidate.served_by(&i.columns)) {
+                continue;
+            }
+            match kept
+                .iter_mut()
+                .find(|k| k.table == candidate.table && candidate.served_by(&k.columns()))
+            {
+                Some(wider) => wider.merge(candidate),
+                None => kept.push(candidate),
+            }
+        }
+        kept.sort_by(|a, b| {
+            b.full_scan_in_plan
+                .cmp(&a.full_scan_in_plan)
+                .then(b.queries.len().cmp(&a.queries.len()))
+                .then(a.table.cmp(&b.table))
+        });
+
+        let suggestions = kept
+            .into_iter()
+            .map(|candidate| {
+                let existing = existing_indexes(&schemas[&candidate.table]);
+                let columns = candidate.columns();
+                IndexSuggestion {
+                    table: candidate.table.display(),
+                    create_statement: create_statement(db_type, &candidate.table, &columns),
+                    rationale: candidate.rationale(&existing),
+                    queries: candidate.queries.iter().copied().collect(),
+                    full_scan_in_plan: candidate.full_scan_in_plan,
+                    columns,
+                }
+            })
+            .collect();
+
+        let mut described: Vec<_> = schemas.iter().collect();
+        described.sort_by(|a, b| a.0.cmp(b.0));
+        let existing_index_issues = described
+            .into_iter()
+            .flat_map(|(table, schema)| index_issues(&table.display(), &existing_indexes(schema)))
+            .collect();
+
+        SuggestIndexesOutput {
+            suggestions,
+            existing_index_issues,
+            tables_analyzed: schemas.len(),
+            notes,
+        }
+    }
+}
+
+/// Parse each query and collect its column usage, with its 1-based position.
+fn collect_usages<'q>(
+    db_type: DatabaseType,
+    queries: &'q [String],
+    notes: &mut Vec<String>,
+) -> Vec<(usize, &'q str, QueryUsage)> {
+    let dialect = get_dialect(db_type);
+    let mut usages = Vec::new();
+    for (i, sql) in queries.iter().enumerate() {
+        let number = i + 1;
+        let statements = match Parser::parse_sql(dialect.as_ref(), sql) {
+            Ok(statements) => statements,
+            Err(e) => {
+                notes.push(format!("Query {} could not be parsed: {}", number, e));
+                continue;
+            }
+        };
+        let mut collector = Collector::default();
+        for stmt in &statements {
+            if !collector.statement(stmt) {
+                notes.push(format!(
+                    "Query {}: only SELECT, UPDATE, DELETE and INSERT ... SELECT statements are analyzed",
+                    number
+                ));
+            }
+        }
+        usages.push((number, sql.as_str(), collector.usage));
+    }
+    usages
+}
+
+/// Find the table and canonical column name a column use refers to.
+fn resolve(
+    column_use: &ColumnUse,
+    schemas: &HashMap<TableRef, TableSchema>,
+) -> Option<(TableRef, String)> {
+    let find = |table: &TableRef| {
+        schemas.get(table).and_then(|schema| {
+            schema
+                .columns
+                .iter()
+                .find(|c| c.name.eq_ignore_ascii_case(&column_use.column))
+                .map(|c| (table.clone(), c.name.clone()))
+        })
+    };
+    match &column_use.owner {
+        Owner::Table(table) => find(table),
+        Owner::OneOf(tables) => {
+            let mut found = tables.iter().filter_map(find);
+            let first = found.next()?;
+            // Ambiguous unqualified column: the database would reject it anyway
+            found.next().is_none().then_some(first)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn collect(sql: &str) -> QueryUsage {
+        let statements =
+            Parser::parse_sql(get_dialect(DatabaseType::SQLite).as_ref(), sql).unwrap();
+        let mut collector = Collector::default();
+        for stmt in &statements {
+            assert!(collector.statement(stmt));
+        }
+        collector.usage
+    }
+
+    fn table(name: &str) -> TableRef {
+        TableRef {
+            schema: None,
+            name: name.to_string(),
+        }
+    }
+
+    fn uses(usage: &QueryUsage) -> Vec<(Usage, String)> {
+        usage
+            .uses
+            .iter()
+            .map(|u| (u.usage, u.column.clone()))
+            .collect()
+    }
+
+    #[test]
+    fn test_collects_filters_joins_and_sorting() {
+        let usage = collect(
+            "SELECT * FROM orders o JOIN users u ON u.id = o.user_id \
+             WHERE o.status = 'paid' AND o.created_at > ? AND u.name LIKE 'A%' \
+             ORDER BY o.created_at",
+        );
+        assert_eq!(
+            uses(&usage),
+            vec![
+                (Usage::Join, "id".to_string()),
+                (Usage::Join, "user_id".to_string()),
+                (Usage::Equality, "status".to_string()),
+                (Usage::Range, "created_at".to_string()),
+                (Usage::Range, "name".to_string()),
+                (Usage::Sort, "created_at".to_string()),
+            ]
+        );
+        assert_eq!(usage.sources.get("o"), Some(&table("orders")));
+    }
+
+    #[test]
+    fn test_ignores_or_functions_and_leading_wildcards() {
+        let usage = collect(
+            "SELECT * FROM users WHERE (a = 1 OR b = 2) AND lower(email) = 'x' AND name LIKE '%x'",
+        );
+        assert!(usage.uses.is_empty());
+    }
+
+    #[test]
+    fn test_candidate_column_order() {
+        let uses = vec![
+            (Usage::Sort, "created_at".to_string()),
+            (Usage::Range, "total".to_string()),
+            (Usage::Equality, "status".to_string()),
+            (Usage::Join, "user_id".to_string()),
+        ];
+        let candidate = Candidate::new(table("orders"), &uses, 1).unwrap();
+        assert_eq!(candidate.columns(), vec!["status", "user_id", "total"]);
+
+        // Equality columns match in any order; ordered columns must follow
+        let existing = ["user_id", "status", "total", "id"].map(String::from);
+        assert!(candidate.served_by(&existing));
+        let existing = ["status", "total", "user_id"].map(String::from);
+        assert!(!candidate.served_by(&existing));
+    }
+
+    #[test]
+    fn test_sort_columns_used_without_range() {
+        let uses = vec![
+            (Usage::Equality, "status".to_string()),
+            (Usage::Sort, "created_at".to_string()),
+        ];
+        let candidate = Candidate::new(table("orders"), &uses, 1).unwrap();
+        assert_eq!(candidate.columns(), vec!["status", "created_at"]);
+    }
+
+    #[test]
+    fn test_index_issues() {
+        let indexes = vec![
+            IndexInfo::new("PRIMARY", vec!["id".to_string()]).with_primary(true),
+            IndexInfo::new("idx_a", vec!["a".to_string()]),
+            IndexInfo::new("idx_a_b", vec!["a".to_string(), "b".to_string()]),
+            IndexInfo::new("idx_id", vec!["id".to_string()]),
+        ];
+        let issues = index_issues("t", &indexes);
+        assert_eq!(issues.len(), 2);
+        assert_eq!(issues[0].index, "idx_a");
+        assert_eq!(issues[0].kind, IndexIssueKind::Redundant);
+        assert_eq!(issues[0].covered_by, "idx_a_b");
+        assert_eq!(issues[1].index, "idx_id");
+        assert_eq!(issues[1].kind, IndexIssueKind::Duplicate);
+        assert_eq!(issues[1].covered_by, "PRIMARY");
+    }
+
+    #[test]
+    fn test_create_statement() {
+        let sql = create_statement(
+            DatabaseType::MySQL,
+            &table("orders"),
+            &["status".to_string(), "created_at".to_string()],
+        );
+        assert_eq!(
+            sql,
+            "CREATE INDEX `idx_orders_status_created_at` ON `orders` (`status`, `created_at`)"
+        );
+    }
+}
@@ -0,0 +1,594 @@
+//! Schema migration tools.
+//!
+//! This module implements MCP tools for versioned migrations:
+//! - `migration_status`: Compare migration files with the history table
+//! - `migration_apply`: Apply pending migrations in version order
+//! - `migration_rollback`: Revert the most recently applied migrations
+//!
+//! Migration files are read from the directory configured with
+//! `--migrations-dir`. Applies and rollbacks are refused while any applied
+//! migration's checksum differs from its file on disk. Pending migrations
+//! older than the latest applied one are only applied with
+//! `allow_out_of_order`.
+
+use crate::db::migration::{
+    AppliedMigration, MigrationFile, MigrationRunner, load_migrations, supports_transactional_ddl,
+};
+use crate::db::{ConnectionManager, DbPool};
+use crate::error::{DbError, DbResult};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+use tracing::info;
+
+/// Input for the migration_status tool.
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
+pub struct MigrationStatusInput {
+    /// Database connection ID from list_connections
+    pub connection_id: String,
+    /// Target database name (optional)
+    #[serde(default)]
+    pub database: Option<String>,
+}
+
+/// Input for the migration_apply tool.
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
+pub struct MigrationApplyInput {
+    /// Database connection ID from list_connections. Must be a writable connection (writable: true).
+    pub connection_id: String,
+    /// Apply pending migrations up to and including this version. Default: all pending
+    #[serde(default)]
+    pub target_version: Option<i64>,
+    /// List the migrations that would be applied without running them
+    #[serde(default)]
+    pub dry_run: bool,
+    /// Also apply pending migrations older than the latest applied one. Default: false
+    #[serde(default)]
+    pub allow_out_of_order: bool,
+    /// Target database name (optional)
+    #[serde(default)]
+    pub database: Option<String>,
+}
+
+/// Input for the migration_rollback tool.
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
+pub struct MigrationRollbackInput {
+    /// Database connection ID from list_connections. Must be a writable connection (writable: true).
+    pub connection_id: String,
+    /// Number of applied migrations to revert, newest first. Default: 1
+    #[serde(default)]
+    pub steps: Option<u32>,
+    /// List the migrations that would be reverted without running them
+    #[serde(default)]
+    pub dry_run: bool,
+    /// Target database name (optional)
+    #[serde(default)]
+    pub database: Option<String>,
+}
+
+/// State of a migration relative to the history table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum MigrationState {
+    /// Recorded in the history table with a matching checksum
+    Applied,
+    /// Present on disk but not yet applied
+    Pending,
+    /// Applied, but the file on disk no longer matches the recorded checksum
+    Drifted,
+    /// Recorded in the history table but the file is missing from disk
+    Missing,
+}
+
+/// A single migration in the status report.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct MigrationStatusEntry {
+    /// Migration version
+    pub version: i64,
+    /// Migration name
+    pub name: String,
+    /// Current state
+    pub state: MigrationState,
+    /// Checksum of the file on disk (None if the file is missing)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub checksum: Option<String>,
+    /// Checksum recorded when the migration was applied
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub applied_checksum: Option<String>,
+    /// When the migration was applied (RFC 3339)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub applied_at: Option<String>,
+    /// Whether a .down.sql script is available for rollback
+    pub reversible: bool,
+}
+
+/// Output from the migration_status tool.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct MigrationStatusOutput {
+    /// All known migrations in version order
+    pub migrations: Vec<MigrationStatusEntry>,
+    /// Highest applied version, if any
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub current_version: Option<i64>,
+    /// Number of pending migrations
+    pub pending_count: usize,
+    /// Versions whose checksums have drifted or whose files are missing
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub drifted_versions: Vec<i64>,
+    /// Whether migrations run inside a transaction on this backend
+    pub transactional: bool,
+}
+
+/// A migration that was (or would be) applied or reverted.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct MigrationRunEntry {
+    /// Migration version
+    pub version: i64,
+    /// Migration name
+    pub name: String,
+    /// Execution time in milliseconds (0 for dry runs)
+    pub execution_time_ms: u64,
+}
+
+/// Output from the migration_apply and migration_rollback tools.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct MigrationRunOutput {
+    /// Migrations applied or reverted, in execution order
+    pub migrations: Vec<MigrationRunEntry>,
+    /// Whether this was a dry run
+    pub dry_run: bool,
+    /// Whether each migration ran inside a transaction
+    pub transactional: bool,
+    /// Human-readable status message
+    pub message: String,
+}
+
+/// Compare migration files with the history table.
+pub fn build_status(
+    files: &[MigrationFile],
+    applied: &[AppliedMigration],
+) -> Vec<MigrationStatusEntry> {
+    let applied_by_version: HashMap<i64, &AppliedMigration> =
+        applied.iter().map(|a| (a.version, a)).collect();
+
+    let mut entries: Vec<MigrationStatusEntry> = files
+        .iter()
+        .map(|file| {
+            let record = applied_by_version.get(&file.version);
+            let state = match record {
+                None => MigrationState::Pending,
+                Some(r) if r.checksum == file.checksum => MigrationState::Applied,
+                Some(_) => MigrationState::Drifted,
+            };
+            MigrationStatusEntry {
+                version: file.version,
+                name: file.name.clone(),
+                state,
+                checksum: Some(file.checksum.clone()),
+                applied_checksum: record.map(|r| r.checksum.clone()),
+                applied_at: record.map(|r| r.applied_at.clone()),
+                reversible: file.down_sql.is_some(),
+            }
+        })
+        .collect();
+
+    for record in applied {
+        if !files.iter().any(|f| f.version == record.version) {
+            entries.push(MigrationStatusEntry {
+                version: record.version,
+                name: record.name.clone(),
+                state: MigrationState::Missing,
+                checksum: None,
+                applied_checksum: Some(record.checksum.clone()),
+                applied_at: Some(record.applied_at.clone()),
+                reversible: false,
+            });
+        }
+    }
+
+    entries.sort_by_key(|e| e.version);
+    entries
+}
+
+/// Versions that block apply/rollback because history no longer matches disk.
+fn drifted_versions(entries: &[MigrationStatusEntry]) -> Vec<i64> {
+    entries
+        .iter()
+        .filter(|e| matches!(e.state, MigrationState::Drifted | MigrationState::Missing))
+        .map(|e| e.version)
+        .collect()
+}
+
+fn drift_error(versions: &[i64]) -> DbError {
+    let list = versions
+        .iter()
+        .map(|v| v.to_string())
+        .collect::<Vec<_>>()
+        .join(", ");
+    DbError::invalid_input(format!(
+        "Refusing to run migrations: applied migration(s) {} no longer match the files on disk. \
+         Restore the original files (or add a new migration instead of editing an applied one), \
+         then check migration_status.",
+        list
+    ))
+}
+
+/// Pending versions below the latest applied one, e.g. from a branch merged
+/// after newer migrations ran.
+fn out_of_order_versions(pending: &[&MigrationFile], latest_applied: i64) -> Vec<i64> {
+    pending
+        .iter()
+        .map(|m| m.version)
+        .filter(|v| *v < latest_applied)
+        .collect()
+}
+
+fn out_of_order_error(versions: &[i64], latest: i64) -> DbError {
+    let list = versions
+        .iter()
+        .map(|v| v.to_string())
+        .collect::<Vec<_>>()
+        .join(", ");
+    DbError::invalid_input(format!(
+        "Refusing to run migrations: pending migration(s) {} are older than the latest applied \
+         version {}. Check that they don't conflict with the newer migrations, then retry with \
+         allow_out_of_order: true.",
+        list, latest
+    ))
+}
+
+pub struct MigrationToolHandler {
+    connection_manager: Arc<ConnectionManager>,
+    migrations_dir: Option<PathBuf>,
+}
+
+impl MigrationToolHandler {
+    pub fn new(
+        connection_manager: Arc<ConnectionManager>,
+        migrations_dir: Option<PathBuf>,
+    ) -> Self {
+        Self {
+            connection_manager,
+            migrations_dir,
+        }
+    }
+
+    fn load_files(&self) -> DbResult<Vec<MigrationFile>> {
+        let dir = self.migrations_dir.as_deref().ok_or_else(|| {
+            DbError::invalid_input(
+                "No migrations directory configured. Start the server with --migrations-dir <path> (or MCP_MIGRATIONS_DIR).",
+            )
+        })?;
+        load_migrations(dir)
+    }
+
+    async fn ensure_writable(&self, connection_id: &str) -> DbResult<()> {
+        if !self.connection_manager.is_writable(connection_id).await? {
+            return Err(DbError::permission(
+                "migration",
+                "Connection is not writable. Use ?writable=true in the connection URL to enable migrations",
+            ));
+        }
+        Ok(())
+    }
+
+    pub async fn status(&self, input: MigrationStatusInput) -> DbResult<MigrationStatusOutput> {
+        let files = self.load_files()?;
+        let database = input.database.as_deref();
+        let pool = self
+            .connection_manager
+            .get_pool_for_database(&input.connection_id, database)
+            .await?;
+        let result = MigrationRunner::applied(&pool).await;
+        self.connection_manager
+            .release_pool_for_database(&input.connection_id, database)
+            .await;
+        let applied = result?;
+
+        let migrations = build_status(&files, &applied);
+        let pending_count = migrations
+            .iter()
+            .filter(|e| e.state == MigrationState::Pending)
+            .count();
+
+        Ok(MigrationStatusOutput {
+            current_version: applied.iter().map(|a| a.version).max(),
+            drifted_versions: drifted_versions(&migrations),
+            pending_count,
+            transactional: supports_transactional_ddl(pool.db_type()),
+            migrations,
+        })
+    }
+
+    pub async fn apply(&self, input: MigrationApplyInput) -> DbResult<MigrationRunOutput> {
+        self.ensure_writable(&input.connection_id).await?;
+        let files = self.load_files()?;
+        let database = input.database.as_deref();
+        let pool = self
+            .connection_manager
+            .get_pool_for_database(&input.connection_id, database)
+            .await?;
+        let result = self
+            .apply_with_pool(
+                &pool,
+                &files,
+                input.target_version,
+                input.dry_run,
+                input.allow_out_of_order,
+            )
+            .await;
+        self.connection_manager
+            .release_pool_for_database(&input.connection_id, database)
+            .await;
+        // Even a failed run may have applied some DDL on non-transactional backends
+        if !input.dry_run {
+            self.connection_manager
+                .schema_cache()
+                .invalidate(&input.connection_id, None);
+        }
+        let output = result?;
+
+        info!(
+            connection_id = %input.connection_id,
+            count = output.migrations.len(),
+            dry_run = output.dry_run,
+            "Migrations applied"
+        );
+        Ok(output)
+    }
+
+    async fn apply_with_pool(
+        &self,
+        pool: &DbPool,
+        files: &[MigrationFile],
+        target_version: Option<i64>,
+        dry_run: bool,
+        allow_out_of_order: bool,
+    ) -> DbResult<MigrationRunOutput> {
+        let transactional = supports_transactional_ddl(pool.db_type());
+        let applied = MigrationRunner::applied(pool).await?;
+        let status = build_status(files, &applied);
+
+        let drifted = drifted_versions(&status);
+        if !drifted.is_empty() {
+            return Err(drift_error(&drifted));
+        }
+
+        let pending: Vec<&MigrationFile> = status
+            .iter()
+            .filter(|e| e.state == MigrationState::Pending)
+            .filter(|e| target_version.is_none_or(|t| e.version <= t))
+            .filter_map(|e| files.iter().find(|f| f.version == e.version))
+            .collect();
+
+        let latest_applied = applied.iter().map(|m| m.version).max();
+        if let Some(latest) = latest_applied.filter(|_| !allow_out_of_order) {
+            let out_of_order = out_of_order_versions(&pending, latest);
+            if !out_of_order.is_empty() {
+                return Err(out_of_order_error(&out_of_order, latest));
+            }
+        }
+
+        if pending.is_empty() {
+            return Ok(MigrationRunOutput {
+                migrations: Vec::new(),
+                dry_run,
+                transactional,
+                message: "Database is up to date. No pending migrations.".to_string(),
+            });
+        }
+
+        if dry_run {
+            return Ok(MigrationRunOutput {
+                message: format!("{} migration(s) would be applied", pending.len()),
+                migrations: pending
+                    .iter()
+                    .map(|m| MigrationRunEntry {
+                        version: m.version,
+                        name: m.name.clone(),
+                        execution_time_ms: 0,
+                    })
+                    .collect(),
+                dry_run,
+                transactional,
+            });
+        }
+
+        MigrationRunner::ensure_history_table(pool).await?;
+
+        let mut ran = Vec::with_capacity(pending.len());
+        for migration in pending {
+            let execution_time_ms = MigrationRunner::apply(pool, migration).await?;
+            ran.push(MigrationRunEntry {
+                version: migration.version,
+                name: migration.name.clone(),
+                execution_time_ms,
+            });
+        }
+
+        Ok(MigrationRunOutput {
+            message: format!("Applied {} migration(s)", ran.len()),
+            migrations: ran,
+            dry_run,
+            transactional,
+        })
+    }
+
+    pub async fn rollback(&self, input: MigrationRollbackInput) -> DbResult<MigrationRunOutput> {
+        self.ensure_writable(&input.connection_id).await?;
+        let files = self.load_files()?;
+        let steps = input.steps.unwrap_or(1).max(1) as usize;
+        let database = input.database.as_deref();
+        let pool = self
+            .connection_manager
+            .get_pool_for_database(&input.connection_id, database)
+            .await?;
+        let result = self
+            .rollback_with_pool(&pool, &files, steps, input.dry_run)
+            .await;
+        self.connection_manager
+            .release_pool_for_database(&input.connection_id, database)
+            .await;
+        // Even a failed run may have applied some DDL on non-transactional backends
+        if !input.dry_run {
+            self.connection_manager
+                .schema_cache()
+                .invalidate(&input.connection_id, None);
+        }
+        let output = result?;
+
+        info!(
+            connection_id = %input.connection_id,
+            count = output.migrations.len(),
+            dry_run = output.dry_run,
+            "Migrations rolled back"
+        );
+        Ok(output)
+    }
+
+    async fn rollback_with_pool(
+        &self,
+        pool: &DbPool,
+        files: &[MigrationFile],
+        steps: usize,
+        dry_run: bool,
+    ) -> DbResult<MigrationRunOutput> {
+        let transactional = supports_transactional_ddl(pool.db_type());
+        let applied = MigrationRunner::applied(pool).await?;
+        let status = build_status(files, &applied);
+
+        let drifted = drifted_versions(&status);
+        if !drifted.is_empty() {
+            return Err(drift_error(&drifted));
+        }
+
+        let targets: Vec<&MigrationFile> = applied
+            .iter()
+            .rev()
+            .take(steps)
+            .filter_map(|a| files.iter().find(|f| f.version == a.version))
+            .collect();
+
+        if targets.is_empty() {
+            return Ok(MigrationRunOutput {
+                migrations: Vec::new(),
+                dry_run,
+                transactional,
+                message: "No applied migrations to roll back.".to_string(),
+            });
+        }
+
+        if let Some(m) = targets.iter().find(|m| m.down_sql.is_none()) {
+            return Err(DbError::invalid_input(format!(
+                "Migration {} ({}) has no .down.sql file and cannot be rolled back",
+                m.version, m.name
+            )));
+        }
+
+        if dry_run {
+            return Ok(MigrationRunOutput {
+                message: format!("{} migration(s) would be rolled back", targets.len()),
+                migrations: targets
+                    .iter()
+                    .map(|m| MigrationRunEntry {
+                        version: m.version,
+                        name: m.name.clone(),
+                        execution_time_ms: 0,
+                    })
+                    .collect(),
+                dry_run,
+                transactional,
+            });
+        }
+
+        let mut ran = Vec::with_capacity(targets.len());
+        for migration in targets {
+            let execution_time_ms = MigrationRunner::revert(pool, migration).await?;
+            ran.push(MigrationRunEntry {
+                version: migration.version,
+                name: migration.name.clone(),
+                execution_time_ms,
+            });
+        }
+
+        Ok(MigrationRunOutput {
+            message: format!("Rolled back {} migration(s)", ran.len()),
+            migrations: ran,
+            dry_run,
+            transactional,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::migration::checksum;
+
+    fn file(version: i64, sql: &str) -> MigrationFile {
+        MigrationFile {
+            version,
+            name: format!("m{}", version),
+            up_sql: sql.to_string(),
+            down_sql: None,
+            checksum: checksum(sql),
+        }
+    }
+
+    fn applied(version: i64, sql: &str) -> AppliedMigration {
+        AppliedMigration {
+            version,
+            name: format!("m{}", version),
+            checksum: checksum(sql),
+            applied_at: "2024-01-01T00:00:00+00:00".to_string(),
+            execution_time_ms: 1,
+        }
+    }
+
+    #[test]
+    fn test_build_status_pending_and_applied() {
+        let files = vec![file(1, "a"), file(2, "b")];
+        let history = vec![applied(1, "a")];
+        let status = build_status(&files, &history);
+        assert_eq!(status.len(), 2);
+        assert_eq!(status[0].state, MigrationState::Applied);
+        assert_eq!(status[1].state, MigrationState::Pending);
+        assert!(drifted_versions(&status).is_empty());
+    }
+
+    #[test]
+    fn test_build_status_detects_drift() {
+        let files = vec![file(1, "a changed")];
+        let history = vec![applied(1, "a")];
+        let status = build_status(&files, &history);
+        assert_eq!(status[0].state, MigrationState::Drifted);
+        assert_eq!(drifted_versions(&status), vec![1]);
+    }
+
+    #[test]
+    fn test_build_status_detects_missing_file() {
+        let files = vec![file(2, "b")];
+        let history = vec![applied(1, "a")];
+        let status = build_status(&files, &history);
+        assert_eq!(status[0].version, 1);
+        assert_eq!(status[0].state, MigrationState::Missing);
+        assert_eq!(status[1].state, MigrationState::Pending);
+        assert_eq!(drifted_versions(&status), vec![1]);
+    }
+
+    #[test]
+    fn test_rollback_input_defaults() {
+        let input: MigrationRollbackInput =
+            serde_json::from_str(r#"{"connection_id": "db"}"#).unwrap();
+        assert!(input.steps.is_none());
+        assert!(!input.dry_run);
+    }
+
+    #[test]
+    fn test_migration_state_serialization() {
+        let json = serde_json::to_string(&MigrationState::Drifted).unwrap();
+        assert_eq!(json, "\"drifted\"");
+    }
+}
@@ -4,32 +4,91 @@
 //! - `query`: Execute SELECT queries
 //! - `list_tables`: List tables in a database
 //! - `describe_table`: Get table schema information
+//! - `show_create`: Get CREATE TABLE DDL, optionally for another dialect
+//! - `describe_view`/`list_routines`/`describe_routine`/`list_triggers`/`list_sequences`:
+//!   Inspect views, stored routines, triggers and sequences
 //! - `execute`: Execute write operations (INSERT/UPDATE/DELETE)
 //! - `begin_transaction`: Start a transaction
 //! - `commit`: Commit a transaction
 //! - `rollback`: Rollback a transaction
 //! - `list_transactions`: List all active transactions
 //! - `explain`: Show query execution plans
+//! - `plan`: Normalized plan trees, cost summaries and findings for `explain`
+//! - `suggest_indexes`: Advisory index recommendations from query column usage
+//! - `profile_table`: Column value distributions from catalog stats and sampling
+//! - `sample_table`: Random or stratified row samples
+//! - `relationship_graph`/`find_join_path`: Foreign-key graph and join paths between tables
+//! - `search_schema`: Ranked search over table/column names and comments
+//! - `compare_queries`: Key-matched diff of two query results
+//! - `diff_schema`: Compare the schemas of two connections/databases
+//! - `diff_table_data`: Chunked-checksum data diff of a table on two connections/databases
+//! - `migration_status`/`migration_apply`/`migration_rollback`: Versioned schema migrations
+//! - `add_connection`/`remove_connection`/`test_connection`: Runtime connection administration
 //! - `sql_validator`: SQL statement validation for read-only enforcement
+//! - `reference_check`: Pre-execution validation of referenced tables and columns
 //! - `guard`: Dangerous operation detection for execute tool
 //! - `format`: Shared output formatting utilities
 
+pub mod admin;
+pub mod compare;
+pub mod data_diff;
 pub mod explain;
 pub mod format;
 pub mod guard;
+pub mod index_advisor;
+pub mod migration;
+pub mod plan;
+pub mod profile;
 pub mod query;
+pub mod reference_check;
+pub mod relationships;
+pub mod sample;
 pub mod schema;
+pub mod schema_diff;
+pub mod search;
 pub mod sql_validator;
 pub mod transaction;
 pub mod write;
 
 pub use crate::models::QueryParamInput;
+pub use admin::{
+    AddConnectionInput, AddConnectionOutput, AdminPolicy, AdminToolHandler, RemoveConnectionInput,
+    RemoveConnectionOutput, TestConnectionInput, TestConnectionOutput,
+};
+pub use compare::{
+    CompareQueriesInput, CompareQueriesOutput, CompareToolHandler, QueryTarget, RowDiff,
+    ValueChange,
+};
+pub use data_diff::{
+    ChecksumMode, DataDiffToolHandler, DiffTableDataInput, DiffTableDataOutput, TableTarget,
+};
 pub use explain::{ExplainInput, ExplainOutput, ExplainToolHandler};
 pub use format::OutputFormat;
+pub use index_advisor::{
+    IndexAdvisorToolHandler, IndexIssue, IndexIssueKind, IndexSuggestion, SuggestIndexesInput,
+    SuggestIndexesOutput,
+};
+pub use migration::{
+    MigrationApplyInput, MigrationRollbackInput, MigrationRunOutput, MigrationStatusInput,
+    MigrationStatusOutput, MigrationToolHandler,
+};
+pub use plan::{FindingKind, PlanFinding, PlanNode, PlanSummary};
+pub use profile::{ProfileTableInput, ProfileTableOutput, ProfileToolHandler};
 pub use query::{QueryInput, QueryOutput, QueryToolHandler};
+pub use relationships::{
+    FindJoinPathInput, FindJoinPathOutput, GraphFormat, RelationshipGraphInput,
+    RelationshipGraphOutput, RelationshipToolHandler,
+};
+pub use sample::{SampleTableInput, SampleTableOutput, SampleToolHandler, SamplingMethod};
 pub use schema::{
-    DescribeTableInput, DescribeTableOutput, ListTablesInput, ListTablesOutput, SchemaToolHandler,
+    DescribeRoutineInput, DescribeRoutineOutput, DescribeTableInput, DescribeTableOutput,
+    DescribeViewInput, DescribeViewOutput, ListRoutinesInput, ListRoutinesOutput,
+    ListSequencesInput, ListSequencesOutput, ListTablesInput, ListTablesOutput, ListTriggersInput,
+    ListTriggersOutput, RefreshSchemaInput, RefreshSchemaOutput, SchemaToolHandler,
+    ShowCreateInput, ShowCreateOutput,
 };
+pub use schema_diff::{DiffSchemaInput, DiffSchemaOutput, SchemaDiffToolHandler};
+pub use search::{SchemaHit, SearchSchemaInput, SearchSchemaOutput, SearchToolHandler};
 pub use transaction::{
     BeginTransactionInput, BeginTransactionOutput, CommitInput, CommitOutput,
     ListTransactionsInput, ListTransactionsOutput, RollbackInput, RollbackOutput, TransactionInfo,
@@ -0,0 +1,862 @@
+//! Normalized query plan trees.
+//!
+//! Each backend reports plans in its own shape: PostgreSQL `EXPLAIN (FORMAT JSON)`
+//! nests `Plans`, MySQL `EXPLAIN FORMAT=JSON` nests `query_block`/`nested_loop`
+//! objects, and SQLite `EXPLAIN QUERY PLAN` returns `id`/`parent`/`detail` rows.
+//! `EXPLAIN ANALYZE` adds actual row counts and timings: PostgreSQL in the same
+//! JSON document, MySQL as an indented `->` tree of text lines.
+//! This module converts all of them into a common [`PlanNode`] tree and derives
+//! a [`PlanSummary`] and [`PlanFinding`]s (full scans, missing indexes,
+//! temporary structures, row estimate mismatches) from it.
+
+use crate::models::DatabaseType;
+use schemars::JsonSchema;
+use serde::Serialize;
+use serde_json::{Map, Value as JsonValue};
+use std::collections::HashMap;
+
+/// Normalized operation name for scans reading every row of a table.
+pub const FULL_TABLE_SCAN: &str = "Full Table Scan";
+
+/// Index name reported for SQLite's transient automatic indexes.
+const AUTOMATIC_INDEX: &str = "automatic";
+
+/// Ratio between estimated and actual rows above which the estimate is flagged.
+const ESTIMATE_MISMATCH_RATIO: f64 = 10.0;
+
+/// Row counts below this are too small for a misestimate to matter.
+const ESTIMATE_MISMATCH_MIN_ROWS: f64 = 100.0;
+
+/// One operation in a query plan.
+#[derive(Debug, Clone, PartialEq, Serialize, JsonSchema)]
+pub struct PlanNode {
+    /// Operation, e.g. "Full Table Scan", "Index Scan", "Hash Join", "Sort"
+    pub operation: String,
+    /// Table the operation reads
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub relation: Option<String>,
+    /// Index used by the operation
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub index: Option<String>,
+    /// Planner row estimate
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub estimated_rows: Option<f64>,
+    /// Planner cost estimate (backend-specific units, cumulative for the subtree)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub estimated_cost: Option<f64>,
+    /// Condition applied to rows after they are read
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub filter: Option<String>,
+    /// Condition used to look rows up through the index
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub index_condition: Option<String>,
+    /// Temporary structure the operation builds (filesort, temp table, temp B-tree)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub temporary: Option<String>,
+    /// Rows actually produced per loop (EXPLAIN ANALYZE only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub actual_rows: Option<f64>,
+    /// Actual time per loop in milliseconds (EXPLAIN ANALYZE only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub actual_time_ms: Option<f64>,
+    /// Number of times the operation ran (EXPLAIN ANALYZE only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub loops: Option<f64>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub children: Vec<PlanNode>,
+}
+
+impl PlanNode {
+    pub fn new(operation: impl Into<String>) -> Self {
+        Self {
+            operation: operation.into(),
+            relation: None,
+            index: None,
+            estimated_rows: None,
+            estimated_cost: None,
+            filter: None,
+            index_condition: None,
+            temporary: None,
+            actual_rows: None,
+            actual_time_ms: None,
+            loops: None,
+            children: Vec::new(),
+        }
+    }
+
+    /// Whether the node reads a whole table without an index.
+    pub fn is_full_scan(&self) -> bool {
+        self.operation == FULL_TABLE_SCAN && self.relation.is_some()
+    }
+
+    /// Whether actual rows differ from the estimate by more than
+    /// [`ESTIMATE_MISMATCH_RATIO`] in either direction.
+    pub fn is_misestimated(&self) -> bool {
+        let (Some(estimated), Some(actual)) = (self.estimated_rows, self.actual_rows) else {
+            return false;
+        };
+        if self.loops == Some(0.0) {
+            return false;
+        }
+        let (low, high) = if estimated < actual {
+            (estimated, actual)
+        } else {
+            (actual, estimated)
+        };
+        high >= ESTIMATE_MISMATCH_MIN_ROWS && high >= low.max(1.0) * ESTIMATE_MISMATCH_RATIO
+    }
+
+    /// Visit the node and its descendants depth-first, with their depth.
+    pub fn walk<'a>(&'a self, depth: usize, visit: &mut impl FnMut(&'a PlanNode, usize)) {
+        visit(self, depth);
+        for child in &self.children {
+            child.walk(depth + 1, visit);
+        }
+    }
+}
+
+/// Totals for a plan.
+#[derive(Debug, Clone, PartialEq, Serialize, JsonSchema)]
+pub struct PlanSummary {
+    /// Estimated total cost of the root operation
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total_cost: Option<f64>,
+    /// Estimated rows returned by the root operation
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub estimated_rows: Option<f64>,
+    /// Rows actually returned or affected (EXPLAIN ANALYZE only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub actual_rows: Option<f64>,
+    /// Actual execution time in milliseconds (EXPLAIN ANALYZE only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub actual_time_ms: Option<f64>,
+    pub node_count: usize,
+    pub full_table_scans: usize,
+}
+
+/// Kind of plan finding.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum FindingKind {
+    /// A table is read in full
+    FullTableScan,
+    /// A table is read in full (or indexed on the fly) to evaluate a filter
+    MissingIndex,
+    /// A temporary table, filesort or B-tree is built
+    TemporaryStructure,
+    /// The planner's row estimate is far from the actual row count
+    RowEstimateMismatch,
+}
+
+/// A potential performance problem spotted in a plan.
+#[derive(Debug, Clone, PartialEq, Serialize, JsonSchema)]
+pub struct PlanFinding {
+    pub kind: FindingKind,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub relation: Option<String>,
+    pub message: String,
+}
+
+/// Build a plan tree from raw EXPLAIN rows. Returns None when the rows are not
+/// in the expected machine-readable shape (e.g. SQLite bytecode listings).
+pub fn parse_plan(db_type: DatabaseType, rows: &[Map<String, JsonValue>]) -> Option<PlanNode> {
+    match db_type {
+        DatabaseType::PostgreSQL => {
+            let document = json_document(rows.first()?.values().next()?)?;
+            let plan = document.as_array()?.first()?.get("Plan")?.as_object()?;
+            Some(postgres_node(plan))
+        }
+        DatabaseType::MySQL => {
+            let value = rows.first()?.values().next()?;
+            match json_document(value) {
+                Some(document) => mysql_root(document.as_object()?),
+                // EXPLAIN ANALYZE only produces the TREE format
+                None => mysql_tree(value.as_str()?),
+            }
+        }
+        DatabaseType::SQLite => sqlite_tree(rows),
+    }
+}
+
+/// Summarize a plan tree.
+pub fn summarize(root: &PlanNode) -> PlanSummary {
+    let mut node_count = 0;
+    let mut full_table_scans = 0;
+    root.walk(0, &mut |node, _| {
+        node_count += 1;
+        if node.is_full_scan() {
+            full_table_scans += 1;
+        }
+    });
+    PlanSummary {
+        total_cost: root.estimated_cost,
+        estimated_rows: root.estimated_rows,
+        actual_rows: root.actual_rows,
+        actual_time_ms: root.actual_time_ms,
+        node_count,
+        full_table_scans,
+    }
+}
+
+/// Flag full scans, missing indexes, temporary structures and, for analyzed
+/// plans, row estimates that are off by an order of magnitude.
+pub fn find_issues(root: &PlanNode) -> Vec<PlanFinding> {
+    let mut findings = Vec::new();
+    root.walk(0, &mut |node, _| {
+        let relation = node.relation.clone();
+        let table = relation.as_deref().unwrap_or("?");
+        if node.is_full_scan() {
+            let rows = node
+                .estimated_rows
+                .map(|r| format!(" (~{} rows)", r.round()))
+                .unwrap_or_default();
+            findings.push(match &node.filter {
+                Some(filter) => PlanFinding {
+                    kind: FindingKind::MissingIndex,
+                    relation: relation.clone(),
+                    message: format!(
+                        "Full table scan on '{}'{} filtered by {}; an index on the filtered columns could avoid it",
+                        table, rows, filter
+                    ),
+                },
+                None => PlanFinding {
+                    kind: FindingKind::FullTableScan,
+                    relation: relation.clone(),
+                    message: format!("Full table scan on '{}'{}", table, rows),
+                },
+            });
+        }
+        if node.index.as_deref() == Some(AUTOMATIC_INDEX) {
+            findings.push(PlanFinding {
+                kind: FindingKind::MissingIndex,
+                relation: relation.clone(),
+                message: format!(
+                    "SQLite builds a transient automatic index on '{}' for every execution; a permanent index would avoid it",
+                    table
+                ),
+            });
+        }
+        if let Some(temporary) = &node.temporary {
+            findings.push(PlanFinding {
+                kind: FindingKind::TemporaryStructure,
+                relation: relation.clone(),
+                message: format!("Query uses a {}", temporary),
+            });
+        }
+        if node.is_misestimated() {
+            let on = node
+                .relation
+                .as_ref()
+                .map(|r| format!(" on '{}'", r))
+                .unwrap_or_default();
+            findings.push(PlanFinding {
+                kind: FindingKind::RowEstimateMismatch,
+                relation,
+                message: format!(
+                    "{}{} was estimated at ~{} rows but produced {}; table statistics may be stale",
+                    node.operation,
+                    on,
+                    node.estimated_rows.unwrap_or_default().round(),
+                    node.actual_rows.unwrap_or_default().round()
+                ),
+            });
+        }
+    });
+    findings
+}
+
+/// EXPLAIN JSON arrives as a JSON value (PostgreSQL) or as text (MySQL).
+fn json_document(value: &JsonValue) -> Option<JsonValue> {
+    match value {
+        JsonValue::String(text) => serde_json::from_str(text).ok(),
+        JsonValue::Null => None,
+        other => Some(other.clone()),
+    }
+}
+
+fn text(obj: &Map<String, JsonValue>, key: &str) -> Option<String> {
+    obj.get(key).and_then(JsonValue::as_str).map(str::to_string)
+}
+
+/// Numbers are strings in MySQL JSON plans ("cost_info": {"query_cost": "1.25"}).
+fn number(value: Option<&JsonValue>) -> Option<f64> {
+    match value? {
+        JsonValue::Number(n) => n.as_f64(),
+        JsonValue::String(s) => s.parse().ok(),
+        _ => None,
+    }
+}
+
+fn postgres_node(plan: &Map<String, JsonValue>) -> PlanNode {
+    let node_type = text(plan, "Node Type").unwrap_or_else(|| "Unknown".to_string());
+    let operation = match (node_type.as_str(), text(plan, "Join Type")) {
+        ("Seq Scan", _) => FULL_TABLE_SCAN.to_string(),
+        (_, Some(join)) if join != "Inner" => format!("{} ({})", node_type, join),
+        _ => node_type,
+    };
+
+    let mut node = PlanNode::new(operation);
+    node.relation = text(plan, "Relation Name");
+    node.index = text(plan, "Index Name");
+    node.estimated_rows = number(plan.get("Plan Rows"));
+    node.estimated_cost = number(plan.get("Total Cost"));
+    node.filter = text(plan, "Filter").or_else(|| text(plan, "Join Filter"));
+    node.index_condition = text(plan, "Index Cond").or_else(|| text(plan, "Recheck Cond"));
+    node.actual_rows = number(plan.get("Actual Rows"));
+    node.actual_time_ms = number(plan.get("Actual Total Time"));
+    node.loops = number(plan.get("Actual Loops"));
+    node.children = plan
+        .get("Plans")
+        .and_then(JsonValue::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(JsonValue::as_object)
+        .map(postgres_node)
+        .collect();
+    node
+}
+
+fn mysql_root(document: &Map<String, JsonValue>) -> Option<PlanNode> {
+    if let Some(block) = document.get("query_block").and_then(JsonValue::as_object) {
+        return Some(mysql_block(block));
+    }
+    // explain_json_format_version=2 (MySQL 8.3+) uses an operation tree
+    document
+        .contains_key("operation")
+        .then(|| mysql_v2_node(document))
+}
+
+fn mysql_block(block: &Map<String, JsonValue>) -> PlanNode {
+    let mut node = PlanNode::new("Query Block");
+    node.estimated_cost = block
+        .get("cost_info")
+        .and_then(|c| number(c.get("query_cost")));
+    node.children = mysql_children(block);
+    node
+}
+
+fn mysql_children(obj: &Map<String, JsonValue>) -> Vec<PlanNode> {
+    let mut children = Vec::new();
+    for (key, value) in obj {
+        let Some(inner) = value.as_object() else {
+            if key == "nested_loop" {
+                let mut node = PlanNode::new("Nested Loop");
+                node.children = value
+                    .as_array()
+                    .into_iter()
+                    .flatten()
+                    .filter_map(JsonValue::as_object)
+                    .flat_map(mysql_children)
+                    .collect();
+                children.push(node);
+            }
+            continue;
+        };
+        let node = match key.as_str() {
+            "query_block" => mysql_block(inner),
+            "table" => mysql_table(inner),
+            "ordering_operation" => mysql_wrapper("Sort", inner),
+            "grouping_operation" => mysql_wrapper("Aggregate", inner),
+            "duplicates_removal" => mysql_wrapper("Distinct", inner),
+            "windowing" => mysql_wrapper("Window", inner),
+            "union_result" => {
+                let mut node = mysql_wrapper("Union", inner);
+                node.children.extend(
+                    inner
+                        .get("query_specifications")
+                        .and_then(JsonValue::as_array)
+                        .into_iter()
+                        .flatten()
+                        .filter_map(JsonValue::as_object)
+                        .flat_map(mysql_children),
+                );
+                node
+            }
+            _ => continue,
+        };
+        children.push(node);
+    }
+    children
+}
+
+fn mysql_wrapper(operation: &str, obj: &Map<String, JsonValue>) -> PlanNode {
+    let mut node = PlanNode::new(operation);
+    if obj.get("using_filesort").and_then(JsonValue::as_bool) == Some(true) {
+        node.temporary = Some("filesort".to_string());
+    } else if obj
+        .get("using_temporary_table")
+        .and_then(JsonValue::as_bool)
+        == Some(true)
+    {
+        node.temporary = Some("temporary table".to_string());
+    }
+    node.children = mysql_children(obj);
+    node
+}
+
+fn mysql_table(table: &Map<String, JsonValue>) -> PlanNode {
+    let access_type = text(table, "access_type").unwrap_or_default();
+    let operation = match access_type.as_str() {
+        "ALL" => FULL_TABLE_SCAN,
+        "index" => "Full Index Scan",
+        "range" | "index_merge" => "Index Range Scan",
+        "ref" | "eq_ref" | "ref_or_null" | "fulltext" | "unique_subquery" | "index_subquery" => {
+            "Index Lookup"
+        }
+        "const" | "system" => "Constant Lookup",
+        _ => "Table Access",
+    };
+
+    let mut node = PlanNode::new(operation);
+    node.relation = text(table, "table_name");
+    node.index = text(table, "key");
+    node.estimated_rows = number(table.get("rows_examined_per_scan"));
+    node.estimated_cost = table
+        .get("cost_info")
+        .and_then(|c| number(c.get("prefix_cost")));
+    node.filter = text(table, "attached_condition");
+    node.index_condition = text(table, "index_condition");
+    if let Some(derived) = table
+        .get("materialized_from_subquery")
+        .and_then(JsonValue::as_object)
+    {
+        node.children.extend(mysql_children(derived));
+    }
+    node.children.extend(
+        table
+            .get("attached_subqueries")
+            .and_then(JsonValue::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(JsonValue::as_object)
+            .flat_map(mysql_children),
+    );
+    node
+}
+
+fn mysql_v2_node(obj: &Map<String, JsonValue>) -> PlanNode {
+    let access_type = text(obj, "access_type");
+    let operation = match access_type.as_deref() {
+        Some("table") => FULL_TABLE_SCAN.to_string(),
+        _ => text(obj, "operation").unwrap_or_else(|| "Unknown".to_string()),
+    };
+
+    let mut node = PlanNode::new(operation);
+    node.relation = text(obj, "table_name");
+    node.index = text(obj, "index_name");
+    node.estimated_rows = number(obj.get("estimated_rows"));
+    node.estimated_cost = number(obj.get("estimated_total_cost"));
+    node.filter = text(obj, "condition");
+    node.children = obj
+        .get("inputs")
+        .and_then(JsonValue::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(JsonValue::as_object)
+        .map(mysql_v2_node)
+        .collect();
+    node
+}
+
+/// Parse MySQL `EXPLAIN ANALYZE` output: one `->` line per operation, children
+/// indented by four spaces, e.g.
+/// `-> Table scan on t  (cost=0.55 rows=3) (actual time=0.02..0.03 rows=3 loops=1)`.
+fn mysql_tree(text: &str) -> Option<PlanNode> {
+    let mut stack: Vec<(usize, PlanNode)> = Vec::new();
+    let mut roots = Vec::new();
+    for line in text.lines() {
+        let trimmed = line.trim_start();
+        let Some(entry) = trimmed.strip_prefix("-> ") else {
+            continue;
+        };
+        let depth = line.len() - trimmed.len();
+        let node = mysql_tree_node(entry);
+        while let Some((top_depth, _)) = stack.last() {
+            if *top_depth < depth {
+                break;
+            }
+            let (_, done) = stack.pop()?;
+            attach(&mut stack, &mut roots, done);
+        }
+        stack.push((depth, node));
+    }
+    while let Some((_, done)) = stack.pop() {
+        attach(&mut stack, &mut roots, done);
+    }
+
+    fn attach(stack: &mut [(usize, PlanNode)], roots: &mut Vec<PlanNode>, node: PlanNode) {
+        match stack.last_mut() {
+            Some((_, parent)) => parent.children.push(node),
+            None => roots.push(node),
+        }
+    }
+
+    roots.iter_mut().for_each(push_down_filter);
+    if roots.len() == 1 {
+        return roots.pop();
+    }
+    let mut root = PlanNode::new("Query Plan");
+    root.children = roots;
+    (!root.children.is_empty()).then_some(root)
+}
+
+/// MySQL prints the filter as a parent of the scan it applies to; copy it onto
+/// the scan so a filtered full scan is reported as a missing index.
+fn push_down_filter(node: &mut PlanNode) {
+    if let (Some(filter), [child]) = (&node.filter, node.children.as_mut_slice()) {
+        if child.is_full_scan() && child.filter.is_none() {
+            child.filter = Some(filter.clone());
+        }
+    }
+    node.children.iter_mut().for_each(push_down_filter);
+}
+
+/// Interpret one `EXPLAIN ANALYZE` line (without the leading `-> `).
+fn mysql_tree_node(entry: &str) -> PlanNode {
+    let (description, annotations) = match entry.find("  (") {
+        Some(pos) => (&entry[..pos], &entry[pos..]),
+        None => (entry, ""),
+    };
+
+    let mut node = if let Some(rest) = description.strip_prefix("Table scan on ") {
+        let mut node = PlanNode::new(FULL_TABLE_SCAN);
+        node.relation = rest.split_whitespace().next().map(str::to_string);
+        node
+    } else if let Some(filter) = description.strip_prefix("Filter: ") {
+        let mut node = PlanNode::new("Filter");
+        node.filter = Some(filter.to_string());
+        node
+    } else if let Some((operation, rest)) = [
+        ("Covering index lookup on ", "Index Only Lookup"),
+        ("Single-row covering index lookup on ", "Index Only Lookup"),
+        ("Single-row index lookup on ", "Index Lookup"),
+        ("Index lookup on ", "Index Lookup"),
+        ("Index range scan on ", "Index Range Scan"),
+        ("Covering index range scan on ", "Index Range Scan"),
+        ("Covering index scan on ", "Index Only Scan"),
+        ("Index scan on ", "Full Index Scan"),
+    ]
+    .iter()
+    .find_map(|(prefix, operation)| Some((*operation, description.strip_prefix(prefix)?)))
+    {
+        // "<table> using <index> (<condition>)" or "... over (<range>)"
+        let mut node = PlanNode::new(operation);
+        let (relation, access) = rest.split_once(" using ").unwrap_or((rest, ""));
+        node.relation = Some(relation.to_string());
+        let (index, condition) = match access.split_once(' ') {
+            Some((index, condition)) => (index, Some(condition)),
+            None => (access, None),
+        };
+        node.index = (!index.is_empty()).then(|| index.to_string());
+        node.index_condition = condition.map(|c| {
+            c.trim_start_matches("over ")
+                .trim_start_matches('(')
+                .trim_end_matches(')')
+                .to_string()
+        });
+        node
+    } else if description.starts_with("Sort") {
+        let mut node = PlanNode::new(description);
+        node.temporary = Some("filesort".to_string());
+        node
+    } else if description.starts_with("Temporary table") {
+        let mut node = PlanNode::new(description);
+        node.temporary = Some("temporary table".to_string());
+        node
+    } else {
+        PlanNode::new(description)
+    };
+
+    node.estimated_cost = annotation(annotations, "(cost=", "cost=");
+    node.estimated_rows = annotation(annotations, "(cost=", "rows=");
+    if let Some(time) = annotation_text(annotations, "(actual ", "time=") {
+        node.actual_time_ms = time.rsplit("..").next().and_then(|t| t.parse().ok());
+    }
+    node.actual_rows = annotation(annotations, "(actual ", "rows=");
+    node.loops = annotation(annotations, "(actual ", "loops=");
+    node
+}
+
+/// Value of `key` inside the parenthesized group that starts with `group`.
+fn annotation_text<'a>(annotations: &'a str, group: &str, key: &str) -> Option<&'a str> {
+    let start = annotations.find(group)?;
+    let inner = &annotations[start + 1..];
+    let inner = &inner[..inner.find(')')?];
+    inner
+        .split_whitespace()
+        .find_map(|part| part.strip_prefix(key))
+}
+
+fn annotation(annotations: &str, group: &str, key: &str) -> Option<f64> {
+    annotation_text(annotations, group, key)?.parse().ok()
+}
+
+/// Rebuild the tree from `EXPLAIN QUERY PLAN` rows linked by `parent` ids.
+fn sqlite_tree(rows: &[Map<String, JsonValue>]) -> Option<PlanNode> {
+    let mut by_parent: HashMap<i64, Vec<(i64, &str)>> = HashMap::new();
+    for row in rows {
+        let id = row.get("id")?.as_i64()?;
+        let parent = row.get("parent")?.as_i64()?;
+        let detail = row.get("detail")?.as_str()?;
+        by_parent.entry(parent).or_default().push((id, detail));
+    }
+
+    fn build(id: i64, detail: &str, by_parent: &HashMap<i64, Vec<(i64, &str)>>) -> PlanNode {
+        let mut node = sqlite_node(detail);
+        node.children = by_parent
+            .get(&id)
+            .into_iter()
+            .flatten()
+            .map(|(child, detail)| build(*child, detail, by_parent))
+            .collect();
+        node
+    }
+
+    let mut roots: Vec<PlanNode> = by_parent
+        .get(&0)?
+        .iter()
+        .map(|(id, detail)| build(*id, detail, &by_parent))
+        .collect();
+    if roots.len() == 1 {
+        return roots.pop();
+    }
+    let mut root = PlanNode::new("Query Plan");
+    root.children = roots;
+    Some(root)
+}
+
+/// Interpret one `detail` line, e.g. `SEARCH users USING INDEX idx_email (email=?)`.
+fn sqlite_node(detail: &str) -> PlanNode {
+    if let Some(what) = detail.strip_prefix("USE TEMP B-TREE FOR ") {
+        let mut node = PlanNode::new("Temp B-Tree");
+        node.temporary = Some(format!("temp B-tree for {}", what));
+        return node;
+    }
+
+    let (operation, rest) = match detail.split_once(' ') {
+        Some(("SCAN", rest)) if rest != "CONSTANT ROW" => ("SCAN", rest),
+        Some(("SEARCH", rest)) => ("SEARCH", rest),
+        _ => return PlanNode::new(detail),
+    };
+    // Older SQLite versions print "SCAN TABLE users"
+    let rest = rest.strip_prefix("TABLE ").unwrap_or(rest);
+    let (relation, access) = rest.split_once(' ').unwrap_or((rest, ""));
+    let (index, condition) = sqlite_index(access);
+
+    let mut node = PlanNode::new(match (operation, &index) {
+        ("SCAN", None) => FULL_TABLE_SCAN,
+        ("SCAN", Some(_)) if access.contains("COVERING INDEX") => "Index Only Scan",
+        ("SCAN", Some(_)) => "Full Index Scan",
+        _ if access.contains("COVERING INDEX") => "Index Only Lookup",
+        _ => "Index Lookup",
+    });
+    node.relation = Some(relation.to_string());
+    node.index = index;
+    node.index_condition = condition;
+    node
+}
+
+/// Index name and lookup condition from the `USING ...` part of a detail line.
+fn sqlite_index(access: &str) -> (Option<String>, Option<String>) {
+    let Some(using) = access.strip_prefix("USING ") else {
+        return (None, None);
+    };
+    let (name, condition) = match using.split_once(" (") {
+        Some((name, condition)) => (name, Some(condition.trim_end_matches(')').to_string())),
+        None => (using, None),
+    };
+    let index = if name.contains("AUTOMATIC") {
+        AUTOMATIC_INDEX.to_string()
+    } else if let Some(pk) = name.strip_prefix("INTEGER ") {
+        pk.to_string()
+    } else {
+        name.rsplit(' ').next().unwrap_or(name).to_string()
+    };
+    (Some(index), condition)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn row(value: JsonValue) -> Map<String, JsonValue> {
+        value.as_object().unwrap().clone()
+    }
+
+    #[test]
+    fn test_postgres_plan() {
+        let rows = vec![row(json!({"QUERY PLAN": [{"Plan": {
+            "Node Type": "Hash Join", "Join Type": "Left", "Total Cost": 35.5, "Plan Rows": 120,
+            "Plans": [
+                {"Node Type": "Seq Scan", "Relation Name": "orders", "Total Cost": 20.0,
+                 "Plan Rows": 1000, "Filter": "(total > 10)"},
+                {"Node Type": "Index Scan", "Relation Name": "users", "Index Name": "users_pkey",
+                 "Index Cond": "(id = orders.user_id)", "Total Cost": 8.3, "Plan Rows": 1}
+            ]
+        }}]}))];
+
+        let root = parse_plan(DatabaseType::PostgreSQL, &rows).unwrap();
+        assert_eq!(root.operation, "Hash Join (Left)");
+        assert_eq!(root.children[0].operation, FULL_TABLE_SCAN);
+        assert_eq!(root.children[1].index.as_deref(), Some("users_pkey"));
+
+        let summary = summarize(&root);
+        assert_eq!(summary.total_cost, Some(35.5));
+        assert_eq!((summary.node_count, summary.full_table_scans), (3, 1));
+
+        let findings = find_issues(&root);
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].kind, FindingKind::MissingIndex);
+        assert!(findings[0].message.contains("(total > 10)"));
+    }
+
+    #[test]
+    fn test_mysql_plan() {
+        let document = json!({"query_block": {
+            "select_id": 1,
+            "cost_info": {"query_cost": "12.50"},
+            "ordering_operation": {
+                "using_filesort": true,
+                "nested_loop": [
+                    {"table": {"table_name": "o", "access_type": "ALL", "rows_examined_per_scan": 100,
+                               "cost_info": {"prefix_cost": "10.25"}}},
+                    {"table": {"table_name": "u", "access_type": "eq_ref", "key": "PRIMARY",
+                               "rows_examined_per_scan": 1}}
+                ]
+            }
+        }});
+        let rows = vec![row(json!({"EXPLAIN": document.to_string()}))];
+
+        let root = parse_plan(DatabaseType::MySQL, &rows).unwrap();
+        assert_eq!(root.estimated_cost, Some(12.5));
+        let sort = &root.children[0];
+        assert_eq!(sort.operation, "Sort");
+        let scans = &sort.children[0].children;
+        assert_eq!(scans[0].operation, FULL_TABLE_SCAN);
+        assert_eq!(scans[0].estimated_rows, Some(100.0));
+        assert_eq!(scans[1].operation, "Index Lookup");
+
+        let kinds: Vec<FindingKind> = find_issues(&root).iter().map(|f| f.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![FindingKind::TemporaryStructure, FindingKind::FullTableScan]
+        );
+    }
+
+    #[test]
+    fn test_mysql_v2_plan() {
+        let rows = vec![row(json!({"EXPLAIN": json!({
+            "operation": "Filter: (t.a = 1)", "condition": "(t.a = 1)", "estimated_rows": 5,
+            "estimated_total_cost": 2.5,
+            "inputs": [{"operation": "Table scan on t", "access_type": "table", "table_name": "t",
+                        "estimated_rows": 50}]
+        }).to_string()}))];
+
+        let root = parse_plan(DatabaseType::MySQL, &rows).unwrap();
+        assert_eq!(root.estimated_cost, Some(2.5));
+        assert!(root.children[0].is_full_scan());
+    }
+
+    #[test]
+    fn test_postgres_analyze_actuals() {
+        let rows = vec![row(json!({"QUERY PLAN": [{"Plan": {
+            "Node Type": "Seq Scan", "Relation Name": "orders", "Total Cost": 20.0,
+            "Plan Rows": 5, "Actual Rows": 4800, "Actual Total Time": 3.25, "Actual Loops": 1
+        }}]}))];
+
+        let root = parse_plan(DatabaseType::PostgreSQL, &rows).unwrap();
+        assert_eq!(root.actual_rows, Some(4800.0));
+        assert!(root.is_misestimated());
+
+        let summary = summarize(&root);
+        assert_eq!(summary.actual_time_ms, Some(3.25));
+
+        let kinds: Vec<FindingKind> = find_issues(&root).iter().map(|f| f.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![FindingKind::FullTableScan, FindingKind::RowEstimateMismatch]
+        );
+    }
+
+    #[test]
+    fn test_mysql_analyze_tree() {
+        let text = "-> Nested loop inner join  (cost=4.70 rows=10) (actual time=0.061..0.071 rows=3 loops=1)
+    -> Filter: (o.total > 10)  (cost=1.25 rows=3) (actual time=0.030..0.034 rows=3 loops=1)
+        -> Table scan on o  (cost=1.25 rows=10) (actual time=0.028..0.031 rows=10 loops=1)
+    -> Single-row index lookup on u using PRIMARY (id=o.user_id)  (cost=0.28 rows=1) (actual time=0.011..0.011 rows=1 loops=3)
+";
+        let rows = vec![row(json!({"EXPLAIN": text}))];
+
+        let root = parse_plan(DatabaseType::MySQL, &rows).unwrap();
+        assert_eq!(root.operation, "Nested loop inner join");
+        assert_eq!(root.estimated_cost, Some(4.7));
+        assert_eq!(root.actual_time_ms, Some(0.071));
+        assert_eq!(root.children.len(), 2);
+
+        let scan = &root.children[0].children[0];
+        assert!(scan.is_full_scan());
+        assert_eq!(scan.actual_rows, Some(10.0));
+        assert_eq!(scan.filter.as_deref(), Some("(o.total > 10)"));
+
+        let lookup = &root.children[1];
+        assert_eq!(lookup.operation, "Index Lookup");
+        assert_eq!(lookup.relation.as_deref(), Some("u"));
+        assert_eq!(lookup.index.as_deref(), Some("PRIMARY"));
+        assert_eq!(lookup.index_condition.as_deref(), Some("id=o.user_id"));
+        assert_eq!(lookup.loops, Some(3.0));
+    }
+
+    #[test]
+    fn test_sqlite_plan() {
+        let rows = vec![
+            row(json!({"id": 3, "parent": 0, "notused": 0, "detail": "SCAN o"})),
+            row(json!({"id": 5, "parent": 0, "notused": 0,
+                       "detail": "SEARCH u USING INTEGER PRIMARY KEY (rowid=?)"})),
+            row(json!({"id": 8, "parent": 0, "notused": 0,
+                       "detail": "SEARCH i USING AUTOMATIC COVERING INDEX (order_id=?)"})),
+            row(json!({"id": 20, "parent": 0, "notused": 0,
+                       "detail": "USE TEMP B-TREE FOR ORDER BY"})),
+        ];
+
+        let root = parse_plan(DatabaseType::SQLite, &rows).unwrap();
+        assert_eq!(root.operation, "Query Plan");
+        assert!(root.children[0].is_full_scan());
+        assert_eq!(root.children[1].index.as_deref(), Some("PRIMARY KEY"));
+        assert_eq!(root.children[1].index_condition.as_deref(), Some("rowid=?"));
+        assert_eq!(root.children[2].operation, "Index Only Lookup");
+
+        let kinds: Vec<FindingKind> = find_issues(&root).iter().map(|f| f.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                FindingKind::FullTableScan,
+                FindingKind::MissingIndex,
+                FindingKind::TemporaryStructure
+            ]
+        );
+    }
+
+    #[test]
+    fn test_sqlite_index_scans() {
+        let node = sqlite_node("SEARCH users USING INDEX idx_users_email (email=?)");
+        assert_eq!(node.operation, "Index Lookup");
+        assert_eq!(node.index.as_deref(), Some("idx_users_email"));
+
+        let node = sqlite_node("SCAN TABLE users USING COVERING INDEX idx_users_email");
+        assert_eq!(node.operation, "Index Only Scan");
+        assert_eq!(node.relation.as_deref(), Some("users"));
+
+        assert_eq!(
+            sqlite_node("SCAN CONSTANT ROW").operation,
+            "SCAN CONSTANT ROW"
+        );
+    }
+
+    #[test]
+    fn test_unparseable_rows() {
+        let bytecode = vec![row(json!({"addr": 0, "opcode": "Init", "p1": 0}))];
+        assert!(parse_plan(DatabaseType::SQLite, &bytecode).is_none());
+        assert!(parse_plan(DatabaseType::PostgreSQL, &[]).is_none());
+    }
+}
@@ -0,0 +1,595 @@
+//! Table profiling tool.
+//!
+//! This module implements the `profile_table` MCP tool, which summarizes the
+//! value distribution of each column: null fraction, distinct count, min/max,
+//! most frequent values and, for text columns, value lengths.
+//!
+//! Catalog statistics (see [`SchemaInspector::table_statistics`]) are used
+//! where the database keeps them, since reading them costs nothing. The
+//! remaining metrics come from aggregate queries over a bounded sample (the
+//! first `sample_size` rows in storage order), run with the query timeout.
+
+use crate::db::ddl::PortableType;
+use crate::db::schema::SchemaInspector;
+use crate::db::{ConnectionManager, DbPool, QueryExecutor};
+use crate::error::{DbError, DbResult};
+use crate::models::{ColumnDefinition, DatabaseType, QueryRequest, TableStatistics};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+use std::sync::Arc;
+use tracing::info;
+
+/// Default number of rows sampled.
+pub const DEFAULT_SAMPLE_SIZE: u32 = 10_000;
+
+/// Maximum number of rows sampled.
+pub const MAX_SAMPLE_SIZE: u32 = 1_000_000;
+
+/// Default number of most frequent values reported per column.
+pub const DEFAULT_TOP_N: u32 = 5;
+
+/// Maximum number of most frequent values reported per column.
+pub const MAX_TOP_N: u32 = 50;
+
+/// Input for the profile_table tool.
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
+pub struct ProfileTableInput {
+    /// Database connection ID from list_connections
+    pub connection_id: String,
+    /// Name of the table to profile
+    pub table_name: String,
+    /// Target database name (optional)
+    #[serde(default)]
+    pub database: Option<String>,
+    /// Only profile these columns. Default: all columns
+    #[serde(default)]
+    pub columns: Option<Vec<String>>,
+    /// Maximum rows read for sampled metrics. Default: 10000, max: 1000000
+    #[serde(default)]
+    pub sample_size: Option<u32>,
+    /// Number of most frequent values per column. Default: 5, max: 50
+    #[serde(default)]
+    pub top_n: Option<u32>,
+    /// Prefer catalog statistics (estimates as of the last ANALYZE) over sampling. Default: true
+    #[serde(default = "default_true")]
+    pub use_catalog: bool,
+    /// Timeout per sampling query in seconds
+    #[serde(default)]
+    pub timeout_secs: Option<u32>,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+/// A frequent value and how often it occurs.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct TopValue {
+    pub value: JsonValue,
+    /// Occurrences in the sample (absent for catalog statistics)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub count: Option<u64>,
+    /// Fraction of rows holding this value
+    pub fraction: f64,
+}
+
+/// Length statistics for a text column (in characters).
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct LengthStats {
+    pub min: u64,
+    pub max: u64,
+    pub avg: f64,
+}
+
+/// Profile of a single column.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct ColumnProfile {
+    pub column: String,
+    pub data_type: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub null_fraction: Option<f64>,
+    /// Exact within the sample; a lower bound for the table when the sample is truncated
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub distinct_count: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min: Option<JsonValue>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max: Option<JsonValue>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub top_values: Vec<TopValue>,
+    /// Text columns only
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub length: Option<LengthStats>,
+    /// Metrics taken from catalog statistics rather than the sample
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub from_catalog: Vec<String>,
+}
+
+/// Output from the profile_table tool.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct ProfileTableOutput {
+    pub table_name: String,
+    /// Row count (estimate from catalog statistics unless row_count_exact)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub row_count: Option<u64>,
+    pub row_count_exact: bool,
+    /// Rows read for sampled metrics
+    pub sampled_rows: u64,
+    /// True when the table has more rows than were sampled
+    pub sample_truncated: bool,
+    pub columns: Vec<ColumnProfile>,
+}
+
+/// How a column can be aggregated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ColumnKind {
+    /// Text: min/max and length statistics
+    Text,
+    /// Numbers and temporal types: min/max
+    Ordered,
+    /// Booleans, UUIDs, JSON, ...: distinct and top values only
+    Unordered,
+    /// Binary data: distinct count only
+    Binary,
+}
+
+impl ColumnKind {
+    fn of(column: &ColumnDefinition, db_type: DatabaseType) -> Self {
+        match PortableType::parse(&column.data_type) {
+            PortableType::Varchar(_) | PortableType::Char(_) | PortableType::Text => Self::Text,
+            PortableType::Binary => Self::Binary,
+            PortableType::SmallInt
+            | PortableType::Integer
+            | PortableType::BigInt
+            | PortableType::Real
+            | PortableType::Double
+            | PortableType::Decimal(_)
+            | PortableType::Date
+            | PortableType::Time
+            | PortableType::Timestamp
+            | PortableType::TimestampTz => Self::Ordered,
+            // SQLite compares any values
+            _ if db_type == DatabaseType::SQLite => Self::Ordered,
+            _ => Self::Unordered,
+        }
+    }
+}
+
+/// Builds the sampling queries for one table.
+struct SampleQueries<'a> {
+    db_type: DatabaseType,
+    table: &'a str,
+    columns: &'a [(ColumnDefinition, ColumnKind)],
+    sample_size: u32,
+}
+
+impl SampleQueries<'_> {
+    fn quote(&self, identifier: &str) -> String {
+        self.db_type.quote_identifier(identifier)
+    }
+
+    /// Sample subquery exposing each profiled column as `c<i>`.
+    fn sample(&self) -> String {
+        let select = self
+            .columns
+            .iter()
+            .enumerate()
+            .map(|(i, (col, kind))| {
+                let expr = self.quote(&col.name);
+                // PostgreSQL cannot compare json or arbitrary types; profile their text form
+                if self.db_type == DatabaseType::PostgreSQL && *kind == ColumnKind::Unordered {
+                    format!("{}::text AS c{}", expr, i)
+                } else {
+                    format!("{} AS c{}", expr, i)
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+        format!(
+            "(SELECT {} FROM {} LIMIT {}) s",
+            select,
+            self.quote(self.table),
+            self.sample_size
+        )
+    }
+
+    fn length_fn(&self) -> &'static str {
+        match self.db_type {
+            DatabaseType::MySQL => "CHAR_LENGTH",
+            DatabaseType::PostgreSQL | DatabaseType::SQLite => "LENGTH",
+        }
+    }
+
+    /// One aggregate query computing counts, min/max and lengths for every column.
+    fn aggregate(&self) -> String {
+        let mut exprs = vec!["COUNT(*) AS sampled_rows".to_string()];
+        for (i, (_, kind)) in self.columns.iter().enumerate() {
+            exprs.push(format!("COUNT(c{i}) AS c{i}_non_null"));
+            exprs.push(format!("COUNT(DISTINCT c{i}) AS c{i}_distinct"));
+            if matches!(kind, ColumnKind::Text | ColumnKind::Ordered) {
+                exprs.push(format!("MIN(c{i}) AS c{i}_min"));
+                exprs.push(format!("MAX(c{i}) AS c{i}_max"));
+            }
+            if *kind == ColumnKind::Text {
+                let len = self.length_fn();
+                exprs.push(format!("MIN({len}(c{i})) AS c{i}_min_len"));
+                exprs.push(format!("MAX({len}(c{i})) AS c{i}_max_len"));
+                exprs.push(format!("AVG({len}(c{i})) AS c{i}_avg_len"));
+            }
+        }
+        format!("SELECT {} FROM {}", exprs.join(", "), self.sample())
+    }
+
+    /// Most frequent non-null values of column `i`.
+    fn top_values(&self, i: usize, top_n: u32) -> String {
+        format!(
+            "SELECT c{i} AS value, COUNT(*) AS freq FROM {} WHERE c{i} IS NOT NULL \
+             GROUP BY c{i} ORDER BY freq DESC, value LIMIT {top_n}",
+            self.sample()
+        )
+    }
+}
+
+/// Read a count or length that may be returned as a number or a string.
+fn as_u64(value: Option<&JsonValue>) -> Option<u64> {
+    match value? {
+        JsonValue::Number(n) => n.as_u64().or_else(|| n.as_f64().map(|f| f as u64)),
+        JsonValue::String(s) => s.parse::<f64>().ok().map(|f| f as u64),
+        _ => None,
+    }
+}
+
+/// Read an average that may be returned as a number or a decimal string.
+fn as_f64(value: Option<&JsonValue>) -> Option<f64> {
+    match value? {
+        JsonValue::Number(n) => n.as_f64(),
+        JsonValue::String(s) => s.parse().ok(),
+        _ => None,
+    }
+}
+
+fn non_null(value: Option<&JsonValue>) -> Option<JsonValue> {
+    value.filter(|v| !v.is_null()).cloned()
+}
+
+pub struct ProfileToolHandler {
+    connection_manager: Arc<ConnectionManager>,
+    executor: QueryExecutor,
+}
+
+impl ProfileToolHandler {
+    pub fn new(connection_manager: Arc<ConnectionManager>, default_timeout_secs: u64) -> Self {
+        Self {
+            connection_manager,
+            executor: QueryExecutor::with_defaults(default_timeout_secs, MAX_TOP_N),
+        }
+    }
+
+    pub async fn profile_table(&self, input: ProfileTableInput) -> DbResult<ProfileTableOutput> {
+        let config = self
+            .connection_manager
+            .get_config(&input.connection_id)
+            .await?;
+
+        // For server-level connections, database parameter is required
+        if config.server_level && input.database.is_none() {
+            return Err(DbError::invalid_input(
+                "Server-level connections require a 'database' parameter to specify which database to query. \
+                Use list_databases first to discover available databases, then call profile_table with database=<database_name>.",
+            ));
+        }
+
+        let database = input.database.as_deref();
+        let pool = self
+            .connection_manager
+            .get_pool_for_database(&input.connection_id, database)
+            .await?;
+
+        let result = self.profile(&pool, config.db_type, &input).await;
+
+        self.connection_manager
+            .release_pool_for_database(&input.connection_id, database)
+            .await;
+
+        let output = result?;
+
+        info!(
+            connection_id = %input.connection_id,
+            table = %input.table_name,
+            columns = output.columns.len(),
+            sampled_rows = output.sampled_rows,
+            "Profiled table"
+        );
+
+        Ok(output)
+    }
+
+    async fn profile(
+        &self,
+        pool: &DbPool,
+        db_type: DatabaseType,
+        input: &ProfileTableInput,
+    ) -> DbResult<ProfileTableOutput> {
+        let database = input.database.as_deref();
+        let schema = SchemaInspector::describe_table(pool, &input.table_name, database).await?;
+
+        let columns = select_columns(schema.columns, input.columns.as_deref())?
+            .into_iter()
+            .map(|col| {
+                let kind = ColumnKind::of(&col, db_type);
+                (col, kind)
+            })
+            .collect::<Vec<_>>();
+
+        let stats = if input.use_catalog {
+            SchemaInspector::table_statistics(pool, &schema.table_name, database).await?
+        } else {
+            TableStatistics::default()
+        };
+
+        let sample_size = input
+            .sample_size
+            .unwrap_or(DEFAULT_SAMPLE_SIZE)
+            .clamp(1, MAX_SAMPLE_SIZE);
+        let top_n = input.top_n.unwrap_or(DEFAULT_TOP_N).clamp(1, MAX_TOP_N);
+        let timeout_secs = input.timeout_secs;
+
+        let queries = SampleQueries {
+            db_type,
+            table: &schema.table_name,
+            columns: &columns,
+            sample_size,
+        };
+
+        let aggregate = self
+            .run(
+                pool,
+                &input.connection_id,
+                queries.aggregate(),
+                1,
+                timeout_secs,
+            )
+            .await?;
+        let agg = aggregate.into_iter().next().unwrap_or_default();
+        let sampled_rows = as_u64(agg.get("sampled_rows")).unwrap_or(0);
+        let sample_truncated = sampled_rows >= sample_size as u64;
+
+        let mut profiles = Vec::with_capacity(columns.len());
+        for (i, (col, kind)) in columns.iter().enumerate() {
+            let catalog = stats.column(&col.name);
+            let non_null_count = as_u64(agg.get(&format!("c{i}_non_null"))).unwrap_or(0);
+            let sample_distinct = as_u64(agg.get(&format!("c{i}_distinct")));
+
+            let mut profile = ColumnProfile {
+                column: col.name.clone(),
+                data_type: col.data_type.clone(),
+                null_fraction: (sampled_rows > 0)
+                    .then(|| (sampled_rows - non_null_count) as f64 / sampled_rows as f64),
+                distinct_count: sample_distinct,
+                min: non_null(agg.get(&format!("c{i}_min"))),
+                max: non_null(agg.get(&format!("c{i}_max"))),
+                top_values: Vec::new(),
+                length: None,
+                from_catalog: Vec::new(),
+            };
+
+            if *kind == ColumnKind::Text && non_null_count > 0 {
+                profile.length = Some(LengthStats {
+                    min: as_u64(agg.get(&format!("c{i}_min_len"))).unwrap_or(0),
+                    max: as_u64(agg.get(&format!("c{i}_max_len"))).unwrap_or(0),
+                    avg: as_f64(agg.get(&format!("c{i}_avg_len"))).unwrap_or(0.0),
+                });
+            }
+
+            if let Some(catalog) = catalog {
+                if let Some(fraction) = catalog.null_fraction {
+                    profile.null_fraction = Some(fraction);
+                    profile.from_catalog.push("null_fraction".to_string());
+                }
+                if let Some(distinct) = catalog.distinct_count {
+                    profile.distinct_count = Some(distinct);
+                    profile.from_catalog.push("distinct_count".to_string());
+                }
+                if !catalog.most_common_values.is_empty() {
+                    profile.top_values = catalog
+                        .most_common_values
+                        .iter()
+                        .take(top_n as usize)
+                        .map(|(value, fraction)| TopValue {
+                            value: JsonValue::String(value.clone()),
+                            count: None,
+                            fraction: *fraction,
+                        })
+                        .collect();
+                    profile.from_catalog.push("top_values".to_string());
+                }
+            }
+
+            // Columns where every sampled value is distinct have no frequent values
+            let all_distinct = sample_distinct == Some(non_null_count);
+            if profile.top_values.is_empty()
+                && *kind != ColumnKind::Binary
+                && non_null_count > 0
+                && !all_distinct
+            {
+                let rows = self
+                    .run(
+                        pool,
+                        &input.connection_id,
+                        queries.top_values(i, top_n),
+                        top_n,
+                        timeout_secs,
+                    )
+                    .await?;
+                profile.top_values = rows
+                    .into_iter()
+                    .map(|row| {
+                        let count = as_u64(row.get("freq")).unwrap_or(0);
+                        TopValue {
+                            value: row.get("value").cloned().unwrap_or(JsonValue::Null),
+                            count: Some(count),
+                            fraction: count as f64 / sampled_rows as f64,
+                        }
+                    })
+                    .collect();
+            }
+
+            profiles.push(profile);
+        }
+
+        let (row_count, row_count_exact) = if !sample_truncated {
+            (Some(sampled_rows), true)
+        } else {
+            (stats.row_count_estimate, false)
+        };
+
+        Ok(ProfileTableOutput {
+            table_name: schema.table_name,
+            row_count,
+            row_count_exact,
+            sampled_rows,
+            sample_truncated,
+            columns: profiles,
+        })
+    }
+
+    async fn run(
+        &self,
+        pool: &DbPool,
+        connection_id: &str,
+        sql: String,
+        limit: u32,
+        timeout_secs: Option<u32>,
+    ) -> DbResult<Vec<serde_json::Map<String, JsonValue>>> {
+        let mut request = QueryRequest::new(connection_id, sql).with_limit(limit);
+        if let Some(timeout_secs) = timeout_secs {
+            request = request.with_timeout(timeout_secs);
+        }
+        Ok(self.executor.execute_query(pool, &request).await?.rows)
+    }
+}
+
+/// Restrict the table's columns to the requested ones, preserving table order.
+fn select_columns(
+    columns: Vec<ColumnDefinition>,
+    requested: Option<&[String]>,
+) -> DbResult<Vec<ColumnDefinition>> {
+    let Some(requested) = requested else {
+        return Ok(columns);
+    };
+
+    if let Some(missing) = requested
+        .iter()
+        .find(|r| !columns.iter().any(|c| c.name.eq_ignore_ascii_case(r)))
+    {
+        return Err(DbError::invalid_input(format!(
+            "Column '{}' does not exist. Available columns: {}",
+            missing,
+            columns
+                .iter()
+                .map(|c| c.name.as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        )));
+    }
+
+    Ok(columns
+        .into_iter()
+        .filter(|c| requested.iter().any(|r| c.name.eq_ignore_ascii_case(r)))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn columns() -> Vec<(ColumnDefinition, ColumnKind)> {
+        vec![
+            (
+                ColumnDefinition::new("name", "varchar(50)", true),
+                ColumnKind::Text,
+            ),
+            (
+                ColumnDefinition::new("meta", "json", true),
+                ColumnKind::Unordered,
+            ),
+        ]
+    }
+
+    #[test]
+    fn test_column_kind() {
+        let col = |ty: &str| ColumnDefinition::new("c", ty, true);
+        let pg = DatabaseType::PostgreSQL;
+        assert_eq!(ColumnKind::of(&col("text"), pg), ColumnKind::Text);
+        assert_eq!(ColumnKind::of(&col("bigint"), pg), ColumnKind::Ordered);
+        assert_eq!(
+            ColumnKind::of(&col("timestamp with time zone"), pg),
+            ColumnKind::Ordered
+        );
+        assert_eq!(ColumnKind::of(&col("boolean"), pg), ColumnKind::Unordered);
+        assert_eq!(ColumnKind::of(&col("bytea"), pg), ColumnKind::Binary);
+        assert_eq!(
+            ColumnKind::of(&col("boolean"), DatabaseType::SQLite),
+            ColumnKind::Ordered
+        );
+    }
+
+    #[test]
+    fn test_aggregate_query_postgres() {
+        let columns = columns();
+        let queries = SampleQueries {
+            db_type: DatabaseType::PostgreSQL,
+            table: "users",
+            columns: &columns,
+            sample_size: 100,
+        };
+        let sql = queries.aggregate();
+        assert!(sql.contains(
+            "FROM (SELECT \"name\" AS c0, \"meta\"::text AS c1 FROM \"users\" LIMIT 100) s"
+        ));
+        assert!(sql.contains("AVG(LENGTH(c0)) AS c0_avg_len"));
+        assert!(sql.contains("COUNT(DISTINCT c1) AS c1_distinct"));
+        assert!(!sql.contains("MIN(c1)"));
+    }
+
+    #[test]
+    fn test_top_values_query_mysql() {
+        let columns = columns();
+        let queries = SampleQueries {
+            db_type: DatabaseType::MySQL,
+            table: "users",
+            columns: &columns,
+            sample_size: 100,
+        };
+        let sql = queries.top_values(1, 3);
+        assert!(sql.starts_with(
+            "SELECT c1 AS value, COUNT(*) AS freq FROM (SELECT `name` AS c0, `meta` AS c1"
+        ));
+        assert!(sql.ends_with("ORDER BY freq DESC, value LIMIT 3"));
+    }
+
+    #[test]
+    fn test_numeric_helpers() {
+        assert_eq!(as_u64(Some(&json!(5))), Some(5));
+        assert_eq!(as_u64(Some(&json!("12"))), Some(12));
+        assert_eq!(as_f64(Some(&json!("3.50"))), Some(3.5));
+        assert_eq!(as_u64(Some(&JsonValue::Null)), None);
+        assert_eq!(as_u64(None), None);
+    }
+
+    #[test]
+    fn test_select_columns() {
+        let cols = vec![
+            ColumnDefinition::new("id", "int", false),
+            ColumnDefinition::new("name", "text", true),
+        ];
+        let selected = select_columns(cols.clone(), Some(&["NAME".to_string()])).unwrap();
+        assert_eq!(selected.len(), 1);
+        assert_eq!(selected[0].name, "name");
+
+        let err = select_columns(cols, Some(&["nope".to_string()])).unwrap_err();
+        assert!(err.to_string().contains("Available columns: id, name"));
+    }
+}
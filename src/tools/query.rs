@@ -3,17 +3,19 @@
 //! This module implements the `query` MCP tool for executing SELECT queries.
 //! Write operations (INSERT, UPDATE, DELETE, DDL) are blocked with clear error messages.
 
-use crate::db::{ConnectionManager, QueryExecutor, TransactionRegistry};
+use crate::db::{ConnectionManager, QueryControl, QueryExecutor, TransactionRegistry};
 use crate::error::DbResult;
 use crate::models::{
     DEFAULT_ROW_LIMIT, MAX_ROW_LIMIT, QueryParam, QueryParamInput, QueryRequest, QueryResult,
 };
 use crate::tools::format::{ColumnInfo, OutputFormat, format_as_markdown, format_as_table};
+use crate::tools::reference_check;
 use crate::tools::sql_validator;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use serde_json::Value as JsonValue;
 use std::sync::Arc;
+use std::time::Duration;
 use tracing::info;
 
 fn default_decode_binary() -> bool {
@@ -125,6 +127,7 @@ pub struct QueryToolHandler {
     connection_manager: Arc<ConnectionManager>,
     transaction_registry: Option<Arc<TransactionRegistry>>,
     executor: QueryExecutor,
+    control: QueryControl,
 }
 
 impl QueryToolHandler {
@@ -133,6 +136,7 @@ impl QueryToolHandler {
             connection_manager,
             transaction_registry: None,
             executor: QueryExecutor::new(),
+            control: QueryControl::default(),
         }
     }
 
@@ -144,6 +148,7 @@ impl QueryToolHandler {
             connection_manager,
             transaction_registry: Some(transaction_registry),
             executor: QueryExecutor::new(),
+            control: QueryControl::default(),
         }
     }
 
@@ -157,6 +162,7 @@ impl QueryToolHandler {
             connection_manager,
             transaction_registry: Some(transaction_registry),
             executor: QueryExecutor::with_defaults(default_timeout_secs, default_row_limit),
+            control: QueryControl::default(),
         }
     }
 
@@ -168,9 +174,16 @@ impl QueryToolHandler {
             connection_manager,
             transaction_registry: None,
             executor,
+            control: QueryControl::default(),
         }
     }
 
+    /// Cancel queries and report their progress through `control`.
+    pub fn with_control(mut self, control: QueryControl) -> Self {
+        self.control = control;
+        self
+    }
+
     /// Validates that the SQL is a read-only statement before execution.
     /// Write operations are rejected with clear error messages.
     pub async fn query(&self, input: QueryInput) -> DbResult<QueryOutput> {
@@ -207,6 +220,10 @@ impl QueryToolHandler {
                 .limit
                 .unwrap_or(DEFAULT_ROW_LIMIT)
                 .clamp(1, MAX_ROW_LIMIT);
+            let timeout = input
+                .timeout_secs
+                .map(|t| Duration::from_secs(t as u64))
+                .unwrap_or(self.executor.default_timeout());
 
             let result = registry
                 .query_in_transaction(
@@ -216,6 +233,8 @@ impl QueryToolHandler {
                     &params,
                     effective_limit,
                     input.decode_binary,
+                    timeout,
+                    &self.control,
                 )
                 .await?;
 
@@ -235,10 +254,11 @@ impl QueryToolHandler {
         }
 
         let database = input.database.as_deref();
-        let pool = self
+        let guard = self
             .connection_manager
-            .get_pool_for_database(&input.connection_id, database)
+            .get_pool_for_database_guarded(&input.connection_id, database)
             .await?;
+        let pool = guard.pool();
 
         let request = QueryRequest {
             connection_id: input.connection_id.clone(),
@@ -249,12 +269,28 @@ impl QueryToolHandler {
             decode_binary: input.decode_binary,
         };
 
-        let result = self.executor.execute_query(&pool, &request).await;
+        let result = async {
+            reference_check::check_references(
+                &self.connection_manager,
+                &input.connection_id,
+                database,
+                pool,
+                &request.sql,
+                request
+                    .timeout_secs
+                    .map(|t| Duration::from_secs(t as u64))
+                    .unwrap_or(self.executor.default_timeout()),
+            )
+            .await?;
+            self.executor
+                .execute_query_controlled(pool, &request, &self.control)
+                .await
+        }
+        .await;
 
-        // Release the pool after use (decrements active_count)
-        self.connection_manager
-            .release_pool_for_database(&input.connection_id, database)
-            .await;
+        // Release the pool after use (decrements active_count); the guard
+        // also releases it if this future is dropped mid-query
+        guard.release().await;
 
         let result = result?;
 
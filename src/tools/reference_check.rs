@@ -0,0 +1,1047 @@
+//! Pre-execution validation of referenced tables and columns.
+//!
+//! Before `query`, `execute` and `explain` send SQL to the database, the
+//! parsed statement is checked against (cached) catalog metadata, so a
+//! misspelled table or column fails with a [`DbError::Schema`] carrying
+//! "did you mean" suggestions instead of a raw driver error.
+//!
+//! The check is conservative: anything it cannot resolve with certainty
+//! (unparseable SQL, DDL, table functions, derived tables, CTE columns,
+//! system catalogs) is left for the database to judge.
+
+use crate::db::{ConnectionManager, DbPool};
+use crate::error::{DbError, DbResult};
+use crate::models::DatabaseType;
+use crate::tools::search::edit_distance;
+use crate::tools::sql_validator::get_dialect;
+use sqlparser::ast::{
+    AssignmentTarget, Delete, Expr, FromTable, Function, FunctionArg, FunctionArgExpr,
+    FunctionArguments, GroupByExpr, Ident, Insert, JoinConstraint, JoinOperator, ObjectName,
+    OrderBy, OrderByKind, Query, Select, SelectItem, SetExpr, Statement, TableFactor, TableObject,
+    TableWithJoins, Update, UpdateTableFromKind, WindowType,
+};
+use sqlparser::keywords::ALL_KEYWORDS;
+use sqlparser::parser::Parser;
+use std::collections::{HashMap, HashSet};
+use std::time::Duration;
+use tracing::warn;
+
+/// Maximum number of "did you mean" suggestions per error.
+const MAX_SUGGESTIONS: usize = 3;
+
+/// Check that the tables and columns referenced by `sql` exist.
+///
+/// Only SELECT/INSERT/UPDATE/DELETE batches are checked; any other statement
+/// (DDL, SET, SHOW, ...) skips the check for the whole batch, since it may
+/// create the objects later statements refer to. When a reference does not
+/// resolve, the cached metadata is refreshed (see [`SchemaCache::refresh`])
+/// and checked again before reporting it. Loading the metadata is bounded by
+/// `timeout`; when it takes longer, the check is skipped.
+///
+/// [`SchemaCache::refresh`]: crate::db::SchemaCache::refresh
+pub async fn check_references(
+    connection_manager: &ConnectionManager,
+    connection_id: &str,
+    database: Option<&str>,
+    pool: &DbPool,
+    sql: &str,
+    timeout: Duration,
+) -> DbResult<()> {
+    let db_type = pool.db_type();
+    let Ok(statements) = Parser::parse_sql(get_dialect(db_type).as_ref(), sql) else {
+        return Ok(());
+    };
+    if statements.is_empty() || !statements.iter().all(is_checked_statement) {
+        return Ok(());
+    }
+
+    // With an empty catalog nothing resolves, so this pass only collects tables
+    let empty = Catalog::default();
+    let mut collector = Checker::new(db_type, &empty);
+    collector.statements(&statements)?;
+    if collector.requested.is_empty() {
+        return Ok(());
+    }
+
+    let loader = CatalogLoader {
+        connection_manager,
+        connection_id,
+        database,
+        pool,
+        timeout,
+    };
+    let Some(catalog) = loader.load(&collector.requested).await else {
+        return Ok(());
+    };
+    let result = Checker::new(db_type, &catalog).statements(&statements);
+    // The miss may come from metadata cached before an out-of-band schema
+    // change; refresh it, unless that was just done for this connection
+    if result.is_ok()
+        || !connection_manager
+            .schema_cache()
+            .refresh(connection_id, database)
+    {
+        return result;
+    }
+    let Some(catalog) = loader.load(&collector.requested).await else {
+        return Ok(());
+    };
+    Checker::new(db_type, &catalog).statements(&statements)
+}
+
+fn is_checked_statement(stmt: &Statement) -> bool {
+    matches!(
+        stmt,
+        Statement::Query(_) | Statement::Insert(_) | Statement::Update(_) | Statement::Delete(_)
+    )
+}
+
+/// Table reference as written in the SQL (one or two name parts).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct TableName {
+    schema: Option<String>,
+    name: String,
+}
+
+impl TableName {
+    fn from_object_name(name: &ObjectName) -> Option<Self> {
+        let parts: Vec<&Ident> = name.0.iter().filter_map(|p| p.as_ident()).collect();
+        if parts.len() != name.0.len() {
+            return None;
+        }
+        match parts.as_slice() {
+            [table] => Some(Self {
+                schema: None,
+                name: table.value.clone(),
+            }),
+            [schema, table] => Some(Self {
+                schema: Some(schema.value.clone()),
+                name: table.value.clone(),
+            }),
+            _ => None,
+        }
+    }
+
+    fn display(&self) -> String {
+        match &self.schema {
+            Some(schema) => format!("{}.{}", schema, self.name),
+            None => self.name.clone(),
+        }
+    }
+
+    /// System catalogs are not listed by `list_tables`, so they are never checked.
+    fn is_system(&self, db_type: DatabaseType) -> bool {
+        let name = self.name.to_lowercase();
+        let schema = self.schema.as_deref().map(str::to_lowercase);
+        match db_type {
+            DatabaseType::SQLite => schema.is_some() || name.starts_with("sqlite_"),
+            DatabaseType::PostgreSQL => {
+                name.starts_with("pg_")
+                    || matches!(schema.as_deref(), Some("pg_catalog" | "information_schema"))
+            }
+            DatabaseType::MySQL => {
+                name == "dual"
+                    || matches!(
+                        schema.as_deref(),
+                        Some("information_schema" | "mysql" | "performance_schema" | "sys")
+                    )
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct CatalogTable {
+    name: String,
+    schema: Option<String>,
+    /// Column names, or None when the table could not be described
+    columns: Option<Vec<String>>,
+}
+
+/// Catalog metadata for the schemas a statement refers to.
+#[derive(Debug, Default)]
+struct Catalog {
+    /// Tables keyed by lowercased name, per schema qualifier (None = unqualified)
+    schemas: HashMap<Option<String>, HashMap<String, CatalogTable>>,
+}
+
+enum Lookup<'a> {
+    /// The schema could not be loaded; the reference cannot be judged
+    Unknown,
+    Missing(Vec<String>),
+    Found(&'a CatalogTable),
+}
+
+impl Catalog {
+    fn lookup(&self, table: &TableName) -> Lookup<'_> {
+        let Some(tables) = self.schemas.get(&table.schema) else {
+            return Lookup::Unknown;
+        };
+        match tables.get(&table.name.to_lowercase()) {
+            Some(found) => Lookup::Found(found),
+            None => Lookup::Missing(closest(
+                &table.name,
+                tables.values().map(|t| (t.name.as_str(), t.name.clone())),
+            )),
+        }
+    }
+}
+
+struct CatalogLoader<'a> {
+    connection_manager: &'a ConnectionManager,
+    connection_id: &'a str,
+    database: Option<&'a str>,
+    pool: &'a DbPool,
+    timeout: Duration,
+}
+
+impl CatalogLoader<'_> {
+    /// Load the catalog for `requested`, or `None` when that takes longer
+    /// than the timeout.
+    async fn load(&self, requested: &HashSet<TableName>) -> Option<Catalog> {
+        let loaded = tokio::time::timeout(self.timeout, self.load_catalog(requested)).await;
+        if loaded.is_err() {
+            warn!(
+                connection_id = %self.connection_id,
+                timeout_secs = self.timeout.as_secs(),
+                "Loading metadata for the reference check timed out; skipping the check"
+            );
+        }
+        loaded.ok()
+    }
+
+    /// Load the tables of every referenced schema and describe the referenced
+    /// tables. Introspection failures leave the affected entries unknown.
+    async fn load_catalog(&self, requested: &HashSet<TableName>) -> Catalog {
+        let mut catalog = Catalog::default();
+        let schemas: HashSet<Option<&str>> =
+            requested.iter().map(|t| t.schema.as_deref()).collect();
+        for schema in schemas {
+            if let Some(tables) = self.tables(schema).await {
+                catalog.schemas.insert(schema.map(str::to_string), tables);
+            }
+        }
+
+        let cache = self.connection_manager.schema_cache();
+        for table in requested {
+            let Some(entry) = catalog
+                .schemas
+                .get_mut(&table.schema)
+                .and_then(|tables| tables.get_mut(&table.name.to_lowercase()))
+            else {
+                continue;
+            };
+            entry.columns = cache
+                .describe_table(
+                    self.connection_id,
+                    self.database,
+                    self.pool,
+                    &entry.name,
+                    entry.schema.as_deref(),
+                )
+                .await
+                .ok()
+                .map(|t| t.columns.into_iter().map(|c| c.name).collect());
+        }
+        catalog
+    }
+
+    /// Tables visible under a schema qualifier. Unqualified names resolve
+    /// through the search path on PostgreSQL and the current database on MySQL.
+    async fn tables(&self, schema: Option<&str>) -> Option<HashMap<String, CatalogTable>> {
+        let cache = self.connection_manager.schema_cache();
+        let search_path: Vec<Option<String>> = match (self.pool, schema) {
+            (_, Some(schema)) => vec![Some(schema.to_string())],
+            (DbPool::Postgres(_), None) => cache
+                .search_path(self.connection_id, self.database, self.pool)
+                .await
+                .ok()?
+                .into_iter()
+                .map(Some)
+                .collect(),
+            (DbPool::MySql(_), None) => vec![self.database.map(str::to_string)],
+            (DbPool::SQLite(_), None) => vec![None],
+        };
+
+        let mut tables = HashMap::new();
+        for schema in search_path {
+            let listed = cache
+                .list_tables(
+                    self.connection_id,
+                    self.database,
+                    self.pool,
+                    schema.as_deref(),
+                    true,
+                )
+                .await
+                .ok()?;
+            for table in listed {
+                // Earlier search path entries shadow later ones
+                tables
+                    .entry(table.name.to_lowercase())
+                    .or_insert(CatalogTable {
+                        name: table.name,
+                        schema: schema.clone(),
+                        columns: None,
+                    });
+            }
+        }
+        Some(tables)
+    }
+}
+
+/// A table or other row source in a FROM clause.
+#[derive(Debug)]
+struct Source {
+    /// Lowercased alias, or table name when there is no alias
+    name: String,
+    /// Name used in error messages
+    label: String,
+    /// Known columns; None when they cannot be determined
+    columns: Option<Vec<String>>,
+}
+
+#[derive(Debug, Default)]
+struct Scope {
+    sources: Vec<Source>,
+    /// Lowercased SELECT-list aliases
+    aliases: HashSet<String>,
+}
+
+/// AST walker resolving table and column references against a [`Catalog`].
+struct Checker<'a> {
+    db_type: DatabaseType,
+    catalog: &'a Catalog,
+    /// Enclosing query scopes, innermost last
+    scopes: Vec<Scope>,
+    /// Lowercased CTE names visible in each enclosing query
+    ctes: Vec<HashSet<String>>,
+    /// Every checkable table reference seen
+    requested: HashSet<TableName>,
+}
+
+impl<'a> Checker<'a> {
+    fn new(db_type: DatabaseType, catalog: &'a Catalog) -> Self {
+        Self {
+            db_type,
+            catalog,
+            scopes: Vec::new(),
+            ctes: Vec::new(),
+            requested: HashSet::new(),
+        }
+    }
+
+    fn statements(&mut self, statements: &[Statement]) -> DbResult<()> {
+        for stmt in statements {
+            match stmt {
+                Statement::Query(query) => self.query(query)?,
+                Statement::Insert(insert) => self.insert(insert)?,
+                Statement::Update(update) => self.update(update)?,
+                Statement::Delete(delete) => self.delete(delete)?,
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    fn query(&mut self, query: &Query) -> DbResult<()> {
+        let mut names = HashSet::new();
+        if let Some(with) = &query.with {
+            names.extend(
+                with.cte_tables
+                    .iter()
+                    .map(|cte| cte.alias.name.value.to_lowercase()),
+            );
+        }
+        self.ctes.push(names);
+        if let Some(with) = &query.with {
+            for cte in &with.cte_tables {
+                self.query(&cte.query)?;
+            }
+        }
+
+        match query.body.as_ref() {
+            SetExpr::Select(select) => self.select(select, query.order_by.as_ref())?,
+            // ORDER BY on a set operation refers to output columns
+            body => self.set_expr(body)?,
+        }
+        self.ctes.pop();
+        Ok(())
+    }
+
+    fn set_expr(&mut self, body: &SetExpr) -> DbResult<()> {
+        match body {
+            SetExpr::Select(select) => self.select(select, None),
+            SetExpr::Query(query) => self.query(query),
+            SetExpr::SetOperation { left, right, .. } => {
+                self.set_expr(left)?;
+                self.set_expr(right)
+            }
+            SetExpr::Values(values) => {
+                for expr in values.rows.iter().flatten() {
+                    self.expr(expr)?;
+                }
+                Ok(())
+            }
+            SetExpr::Insert(stmt) | SetExpr::Update(stmt) | SetExpr::Delete(stmt) => {
+                self.statements(std::slice::from_ref(stmt))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn select(&mut self, select: &Select, order_by: Option<&OrderBy>) -> DbResult<()> {
+        let mut scope = Scope::default();
+        for item in &select.projection {
+            if let SelectItem::ExprWithAlias { alias, .. } = item {
+                scope.aliases.insert(alias.value.to_lowercase());
+            }
+        }
+        self.scopes.push(scope);
+
+        let mut conditions = Vec::new();
+        for table in &select.from {
+            self.table_with_joins(table, &mut conditions)?;
+        }
+        for condition in conditions {
+            self.expr(condition)?;
+        }
+
+        for item in &select.projection {
+            match item {
+                SelectItem::UnnamedExpr(expr) | SelectItem::ExprWithAlias { expr, .. } => {
+                    self.expr(expr)?
+                }
+                SelectItem::QualifiedWildcard(..) | SelectItem::Wildcard(_) => {}
+            }
+        }
+        if let Some(selection) = &select.selection {
+            self.expr(selection)?;
+        }
+        if let GroupByExpr::Expressions(exprs, _) = &select.group_by {
+            for expr in exprs {
+                self.expr(expr)?;
+            }
+        }
+        if let Some(having) = &select.having {
+            self.expr(having)?;
+        }
+        if let Some(OrderBy {
+            kind: OrderByKind::Expressions(exprs),
+            ..
+        }) = order_by
+        {
+            for order in exprs {
+                self.expr(&order.expr)?;
+            }
+        }
+
+        self.scopes.pop();
+        Ok(())
+    }
+
+    fn insert(&mut self, insert: &Insert) -> DbResult<()> {
+        if let TableObject::TableName(name) = &insert.table {
+            let source = self.table_source(name, None)?;
+            if let Some(columns) = &source.columns {
+                if let Some(missing) = insert.columns.iter().find(|c| !contains(columns, &c.value))
+                {
+                    return Err(column_error(&missing.value, &[&source]));
+                }
+            }
+        }
+        match &insert.source {
+            Some(source) => self.query(source),
+            None => Ok(()),
+        }
+    }
+
+    fn update(&mut self, update: &Update) -> DbResult<()> {
+        self.scopes.push(Scope::default());
+        let mut conditions = Vec::new();
+        self.table_with_joins(&update.table, &mut conditions)?;
+        if let Some(UpdateTableFromKind::BeforeSet(from) | UpdateTableFromKind::AfterSet(from)) =
+            &update.from
+        {
+            for table in from {
+                self.table_with_joins(table, &mut conditions)?;
+            }
+        }
+
+        for assignment in &update.assignments {
+            let targets = match &assignment.target {
+                AssignmentTarget::ColumnName(name) => std::slice::from_ref(name),
+                AssignmentTarget::Tuple(names) => names.as_slice(),
+            };
+            for target in targets {
+                self.assignment_target(target)?;
+            }
+            self.expr(&assignment.value)?;
+        }
+        for condition in conditions {
+            self.expr(condition)?;
+        }
+        if let Some(selection) = &update.selection {
+            self.expr(selection)?;
+        }
+
+        self.scopes.pop();
+        Ok(())
+    }
+
+    fn delete(&mut self, delete: &Delete) -> DbResult<()> {
+        self.scopes.push(Scope::default());
+        let (FromTable::WithFromKeyword(from) | FromTable::WithoutKeyword(from)) = &delete.from;
+        let mut conditions = Vec::new();
+        for table in from.iter().chain(delete.using.iter().flatten()) {
+            self.table_with_joins(table, &mut conditions)?;
+        }
+        for condition in conditions {
+            self.expr(condition)?;
+        }
+        if let Some(selection) = &delete.selection {
+            self.expr(selection)?;
+        }
+
+        self.scopes.pop();
+        Ok(())
+    }
+
+    /// Unqualified SET targets belong to the UPDATE target table.
+    fn assignment_target(&self, target: &ObjectName) -> DbResult<()> {
+        let parts: Vec<&Ident> = target.0.iter().filter_map(|p| p.as_ident()).collect();
+        match parts.as_slice() {
+            [column] => {
+                let Some(source) = self.scopes.last().and_then(|s| s.sources.first()) else {
+                    return Ok(());
+                };
+                match &source.columns {
+                    Some(columns) if !contains(columns, &column.value) => {
+                        Err(column_error(&column.value, &[source]))
+                    }
+                    _ => Ok(()),
+                }
+            }
+            [.., qualifier, column] => self.column(Some(qualifier), column),
+            [] => Ok(()),
+        }
+    }
+
+    /// Add the sources of a FROM item to the current scope, collecting JOIN
+    /// conditions to check once every source is known.
+    fn table_with_joins<'q>(
+        &mut self,
+        table: &'q TableWithJoins,
+        conditions: &mut Vec<&'q Expr>,
+    ) -> DbResult<()> {
+        self.table_factor(&table.relation, conditions)?;
+        for join in &table.joins {
+            self.table_factor(&join.relation, conditions)?;
+            if let Some(JoinConstraint::On(expr)) = join_constraint(&join.join_operator) {
+                conditions.push(expr);
+            }
+        }
+        Ok(())
+    }
+
+    fn table_factor<'q>(
+        &mut self,
+        factor: &'q TableFactor,
+        conditions: &mut Vec<&'q Expr>,
+    ) -> DbResult<()> {
+        let source = match factor {
+            TableFactor::Table {
+                name,
+                alias,
+                args: None,
+                ..
+            } => {
+                let mut source = self.table_source(name, alias.as_ref().map(|a| &a.name))?;
+                if let Some(alias) = alias.as_ref().filter(|a| !a.columns.is_empty()) {
+                    source.columns =
+                        Some(alias.columns.iter().map(|c| c.name.value.clone()).collect());
+                }
+                source
+            }
+            TableFactor::Derived {
+                subquery, alias, ..
+            } => {
+                self.query(subquery)?;
+                opaque_source(alias.as_ref().map(|a| &a.name))
+            }
+            TableFactor::NestedJoin {
+                table_with_joins, ..
+            } => return self.table_with_joins(table_with_joins, conditions),
+            // Table functions, UNNEST, PIVOT, ...: columns cannot be determined
+            _ => opaque_source(None),
+        };
+        if let Some(scope) = self.scopes.last_mut() {
+            scope.sources.push(source);
+        }
+        Ok(())
+    }
+
+    /// Resolve a table reference, failing when the catalog proves it missing.
+    fn table_source(&mut self, name: &ObjectName, alias: Option<&Ident>) -> DbResult<Source> {
+        let table = TableName::from_object_name(name);
+        let last = name
+            .0
+            .last()
+            .and_then(|p| p.as_ident())
+            .map(|i| i.value.clone())
+            .unwrap_or_default();
+        let mut source = Source {
+            name: alias.map_or(&last, |a| &a.value).to_lowercase(),
+            label: alias.map_or(&last, |a| &a.value).clone(),
+            columns: None,
+        };
+
+        let Some(table) = table else {
+            return Ok(source);
+        };
+        let is_cte = table.schema.is_none()
+            && self
+                .ctes
+                .iter()
+                .any(|names| names.contains(&table.name.to_lowercase()));
+        if is_cte || table.is_system(self.db_type) {
+            return Ok(source);
+        }
+
+        match self.catalog.lookup(&table) {
+            Lookup::Unknown => {}
+            Lookup::Found(found) => source.columns = found.columns.clone(),
+            Lookup::Missing(suggestions) => {
+                let display = table.display();
+                return Err(DbError::schema(
+                    format!(
+                        "Table '{}' not found.{}",
+                        display,
+                        hint(&suggestions, "Use list_tables to see available tables.")
+                    ),
+                    display,
+                ));
+            }
+        }
+        self.requested.insert(table);
+        Ok(source)
+    }
+
+    fn expr(&mut self, expr: &Expr) -> DbResult<()> {
+        match expr {
+            Expr::Identifier(ident) => self.column(None, ident),
+            Expr::CompoundIdentifier(parts) => match parts.as_slice() {
+                [qualifier, column] | [_, qualifier, column] => {
+                    self.column(Some(qualifier), column)
+                }
+                _ => Ok(()),
+            },
+            Expr::IsFalse(e)
+            | Expr::IsNotFalse(e)
+            | Expr::IsTrue(e)
+            | Expr::IsNotTrue(e)
+            | Expr::IsNull(e)
+            | Expr::IsNotNull(e)
+            | Expr::IsUnknown(e)
+            | Expr::IsNotUnknown(e)
+            | Expr::Nested(e)
+            | Expr::UnaryOp { expr: e, .. }
+            | Expr::Cast { expr: e, .. }
+            | Expr::Collate { expr: e, .. }
+            | Expr::Extract { expr: e, .. }
+            | Expr::Ceil { expr: e, .. }
+            | Expr::Floor { expr: e, .. } => self.expr(e),
+            Expr::IsDistinctFrom(a, b)
+            | Expr::IsNotDistinctFrom(a, b)
+            | Expr::BinaryOp {
+                left: a, right: b, ..
+            }
+            | Expr::AnyOp {
+                left: a, right: b, ..
+            }
+            | Expr::AllOp {
+                left: a, right: b, ..
+            }
+            | Expr::Like {
+                expr: a,
+                pattern: b,
+                ..
+            }
+            | Expr::ILike {
+                expr: a,
+                pattern: b,
+                ..
+            }
+            | Expr::SimilarTo {
+                expr: a,
+                pattern: b,
+                ..
+            }
+            | Expr::RLike {
+                expr: a,
+                pattern: b,
+                ..
+            }
+            | Expr::Position { expr: a, r#in: b }
+            | Expr::AtTimeZone {
+                timestamp: a,
+                time_zone: b,
+            } => {
+                self.expr(a)?;
+                self.expr(b)
+            }
+            Expr::Between {
+                expr, low, high, ..
+            } => {
+                self.expr(expr)?;
+                self.expr(low)?;
+                self.expr(high)
+            }
+            Expr::InList { expr, list, .. } => {
+                self.expr(expr)?;
+                list.iter().try_for_each(|e| self.expr(e))
+            }
+            Expr::InSubquery { expr, subquery, .. } => {
+                self.expr(expr)?;
+                self.query(subquery)
+            }
+            Expr::Exists { subquery, .. } | Expr::Subquery(subquery) => self.query(subquery),
+            Expr::Tuple(exprs) => exprs.iter().try_for_each(|e| self.expr(e)),
+            Expr::Substring {
+                expr,
+                substring_from,
+                substring_for,
+                ..
+            } => {
+                self.expr(expr)?;
+                substring_from
+                    .iter()
+                    .chain(substring_for)
+                    .try_for_each(|e| self.expr(e))
+            }
+            Expr::Case {
+                operand,
+                conditions,
+                else_result,
+                ..
+            } => {
+                for e in operand.iter().chain(else_result) {
+                    self.expr(e)?;
+                }
+                for when in conditions {
+                    self.expr(&when.condition)?;
+                    self.expr(&when.result)?;
+                }
+                Ok(())
+            }
+            Expr::Function(function) => self.function(function),
+            _ => Ok(()),
+        }
+    }
+
+    fn function(&mut self, function: &Function) -> DbResult<()> {
+        match &function.args {
+            FunctionArguments::List(list) => {
+                for arg in &list.args {
+                    let (FunctionArg::Named { arg, .. }
+                    | FunctionArg::ExprNamed { arg, .. }
+                    | FunctionArg::Unnamed(arg)) = arg;
+                    if let FunctionArgExpr::Expr(expr) = arg {
+                        self.expr(expr)?;
+                    }
+                }
+            }
+            FunctionArguments::Subquery(query) => self.query(query)?,
+            FunctionArguments::None => {}
+        }
+        if let Some(filter) = &function.filter {
+            self.expr(filter)?;
+        }
+        if let Some(WindowType::WindowSpec(spec)) = &function.over {
+            for expr in &spec.partition_by {
+                self.expr(expr)?;
+            }
+            for order in &spec.order_by {
+                self.expr(&order.expr)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn column(&self, qualifier: Option<&Ident>, ident: &Ident) -> DbResult<()> {
+        if let Some(qualifier) = qualifier {
+            let qualifier = qualifier.value.to_lowercase();
+            let Some(source) = self
+                .scopes
+                .iter()
+                .rev()
+                .flat_map(|s| &s.sources)
+                .find(|s| s.name == qualifier)
+            else {
+                // Not a table alias: a struct field, schema-qualified name, ...
+                return Ok(());
+            };
+            return match &source.columns {
+                Some(columns) if !contains(columns, &ident.value) => {
+                    Err(column_error(&ident.value, &[source]))
+                }
+                _ => Ok(()),
+            };
+        }
+
+        if self.is_unresolvable(ident) {
+            return Ok(());
+        }
+        let name = ident.value.to_lowercase();
+        let mut has_sources = false;
+        for scope in self.scopes.iter().rev() {
+            if scope.aliases.contains(&name) {
+                return Ok(());
+            }
+            for source in &scope.sources {
+                has_sources = true;
+                match &source.columns {
+                    // The column may belong to a source we know nothing about
+                    None => return Ok(()),
+                    Some(columns) if contains(columns, &name) => return Ok(()),
+                    Some(_) if source.name == name => return Ok(()),
+                    Some(_) => {}
+                }
+            }
+        }
+        if !has_sources || (ident.quote_style.is_none() && is_keyword(&ident.value)) {
+            return Ok(());
+        }
+
+        let sources: Vec<&Source> = self
+            .scopes
+            .iter()
+            .rev()
+            .find(|s| !s.sources.is_empty())
+            .map(|s| s.sources.iter().collect())
+            .unwrap_or_default();
+        Err(column_error(&ident.value, &sources))
+    }
+
+    /// Identifiers that may not be column references at all: double-quoted
+    /// strings on SQLite/MySQL, variables and placeholders.
+    fn is_unresolvable(&self, ident: &Ident) -> bool {
+        (ident.quote_style == Some('"') && self.db_type != DatabaseType::PostgreSQL)
+            || ident.value.starts_with(['@', '$', ':', '?'])
+    }
+}
+
+pub(crate) fn join_constraint(operator: &JoinOperator) -> Option<&JoinConstraint> {
+    match operator {
+        JoinOperator::Join(c)
+        | JoinOperator::Inner(c)
+        | JoinOperator::Left(c)
+        | JoinOperator::LeftOuter(c)
+        | JoinOperator::Right(c)
+        | JoinOperator::RightOuter(c)
+        | JoinOperator::FullOuter(c)
+        | JoinOperator::CrossJoin(c)
+        | JoinOperator::Semi(c)
+        | JoinOperator::LeftSemi(c)
+        | JoinOperator::RightSemi(c)
+        | JoinOperator::Anti(c)
+        | JoinOperator::LeftAnti(c)
+        | JoinOperator::RightAnti(c)
+        | JoinOperator::StraightJoin(c)
+        | JoinOperator::AsOf { constraint: c, .. } => Some(c),
+        _ => None,
+    }
+}
+
+fn opaque_source(alias: Option<&Ident>) -> Source {
+    let alias = alias.map(|a| a.value.clone()).unwrap_or_default();
+    Source {
+        name: alias.to_lowercase(),
+        label: alias,
+        columns: None,
+    }
+}
+
+fn contains(columns: &[String], name: &str) -> bool {
+    columns.iter().any(|c| c.eq_ignore_ascii_case(name))
+}
+
+fn is_keyword(value: &str) -> bool {
+    ALL_KEYWORDS
+        .binary_search(&value.to_uppercase().as_str())
+        .is_ok()
+}
+
+fn column_error(column: &str, sources: &[&Source]) -> DbError {
+    let qualify = sources.len() > 1;
+    let suggestions = closest(
+        column,
+        sources.iter().flat_map(|s| {
+            s.columns.iter().flatten().map(move |c| {
+                let display = if qualify {
+                    format!("{}.{}", s.label, c)
+                } else {
+                    c.clone()
+                };
+                (c.as_str(), display)
+            })
+        }),
+    );
+
+    let (location, object) = match sources {
+        [source] => (
+            format!("table '{}'", source.label),
+            format!("{}.{}", source.label, column),
+        ),
+        _ => (
+            format!(
+                "any of: {}",
+                sources
+                    .iter()
+                    .map(|s| s.label.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            column.to_string(),
+        ),
+    };
+    DbError::schema(
+        format!(
+            "Column '{}' not found in {}.{}",
+            column,
+            location,
+            hint(&suggestions, "Use describe_table to see available columns.")
+        ),
+        object,
+    )
+}
+
+/// Closest candidates by edit distance, allowing roughly one edit per three characters.
+fn closest<'c>(name: &str, candidates: impl Iterator<Item = (&'c str, String)>) -> Vec<String> {
+    let name = name.to_lowercase();
+    let max_distance = (name.chars().count() / 3).clamp(1, 3);
+    let mut scored: Vec<(usize, String)> = candidates
+        .filter_map(|(candidate, display)| {
+            let distance = edit_distance(&name, &candidate.to_lowercase());
+            (distance <= max_distance).then_some((distance, display))
+        })
+        .collect();
+    scored.sort();
+    scored.dedup();
+    scored
+        .into_iter()
+        .take(MAX_SUGGESTIONS)
+        .map(|(_, display)| display)
+        .collect()
+}
+
+fn hint(suggestions: &[String], fallback: &str) -> String {
+    match suggestions {
+        [] => format!(" {}", fallback),
+        [one] => format!(" Did you mean '{}'?", one),
+        many => format!(
+            " Did you mean one of: {}?",
+            many.iter()
+                .map(|s| format!("'{}'", s))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlparser::dialect::SQLiteDialect;
+
+    fn catalog() -> Catalog {
+        let table = |name: &str, columns: &[&str]| {
+            (
+                name.to_string(),
+                CatalogTable {
+                    name: name.to_string(),
+                    schema: None,
+                    columns: Some(columns.iter().map(|c| c.to_string()).collect()),
+                },
+            )
+        };
+        let tables = HashMap::from([
+            table("users", &["id", "email", "name"]),
+            table("orders", &["id", "user_id", "total"]),
+        ]);
+        Catalog {
+            schemas: HashMap::from([(None, tables)]),
+        }
+    }
+
+    fn check(sql: &str) -> DbResult<()> {
+        let statements = Parser::parse_sql(&SQLiteDialect {}, sql).unwrap();
+        let catalog = catalog();
+        Checker::new(DatabaseType::SQLite, &catalog).statements(&statements)
+    }
+
+    fn message(sql: &str) -> String {
+        check(sql).unwrap_err().to_string()
+    }
+
+    #[test]
+    fn test_valid_references() {
+        check("SELECT u.email, o.total FROM users u JOIN orders o ON o.user_id = u.id").unwrap();
+        check("SELECT email AS mail FROM users ORDER BY mail").unwrap();
+        check("SELECT count(*) FROM users WHERE id IN (SELECT user_id FROM orders)").unwrap();
+        check("WITH big AS (SELECT user_id FROM orders) SELECT anything FROM big").unwrap();
+        check("SELECT x FROM (SELECT id AS x FROM users) d").unwrap();
+        check("SELECT * FROM sqlite_master").unwrap();
+    }
+
+    #[test]
+    fn test_correlated_subquery_sees_outer_columns() {
+        check(
+            "SELECT name FROM users WHERE EXISTS \
+             (SELECT 1 FROM orders WHERE orders.user_id = users.id AND total > 0 AND name <> '')",
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_missing_table_suggestion() {
+        let err = check("SELECT * FROM usres").unwrap_err();
+        assert!(matches!(err, DbError::Schema { ref object, .. } if object == "usres"));
+        assert!(err.to_string().contains("Did you mean 'users'?"));
+    }
+
+    #[test]
+    fn test_missing_column_suggestion() {
+        assert!(
+            message("SELECT emial FROM users")
+                .contains("Column 'emial' not found in table 'users'. Did you mean 'email'?")
+        );
+        assert!(message("SELECT u.nmae FROM users u").contains("Did you mean 'name'?"));
+        assert!(
+            message("SELECT totl FROM users JOIN orders ON orders.user_id = users.id")
+                .contains("any of: users, orders. Did you mean 'orders.total'?")
+        );
+        assert!(message("SELECT zzz FROM users").contains("Use describe_table"));
+    }
+
+    #[test]
+    fn test_write_statements() {
+        check("INSERT INTO users (id, email) VALUES (1, 'a')").unwrap();
+        check("UPDATE users SET name = 'x' WHERE id = 1").unwrap();
+        check("DELETE FROM orders WHERE user_id = 1").unwrap();
+
+        assert!(message("INSERT INTO users (id, emali) VALUES (1, 'a')").contains("'email'"));
+        assert!(message("UPDATE users SET nam = 'x'").contains("'name'"));
+        assert!(message("DELETE FROM ordrs").contains("'orders'"));
+    }
+
+    #[test]
+    fn test_unresolvable_identifiers_are_skipped() {
+        // SQLite treats unknown double-quoted identifiers as strings
+        check("SELECT * FROM users WHERE name = \"bob\"").unwrap();
+        // Keywords used as bare arguments, e.g. date parts
+        check("SELECT * FROM users WHERE id > CURRENT_DATE").unwrap();
+        // Table functions hide their columns
+        check("SELECT value FROM users, json_each(users.name)").unwrap();
+    }
+}
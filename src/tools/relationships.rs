@@ -0,0 +1,789 @@
+//! Foreign-key relationship tools.
+//!
+//! This module implements the `relationship_graph` and `find_join_path` MCP
+//! tools. Both build a [`RelationshipGraph`] from the foreign keys reported by
+//! [`SchemaInspector`](crate::db::schema::SchemaInspector): one edge per
+//! foreign key constraint, pointing from the referencing (child) table to the
+//! referenced (parent) table.
+//!
+//! Join paths are found by breadth-first search over the edges in both
+//! directions, so the shortest path between two tables may walk from a child
+//! to its parent or from a parent to its children.
+
+use crate::db::ConnectionManager;
+use crate::error::{DbError, DbResult};
+use crate::models::{DatabaseType, ForeignKey, TableSchema};
+use crate::tools::schema_diff::describe_all;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::sync::Arc;
+use tracing::info;
+
+/// Default maximum number of joins in a path.
+pub const DEFAULT_MAX_HOPS: u32 = 6;
+
+/// Output format for the relationship graph.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum GraphFormat {
+    /// Structured adjacency lists and relationships
+    #[default]
+    Json,
+    /// Mermaid `erDiagram` text
+    Mermaid,
+}
+
+/// Input for the relationship_graph tool.
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
+pub struct RelationshipGraphInput {
+    /// Database connection ID from list_connections
+    pub connection_id: String,
+    /// Target database name (optional)
+    #[serde(default)]
+    pub database: Option<String>,
+    /// Output format: "json" returns adjacency lists, "mermaid" returns an ER diagram
+    #[serde(default)]
+    pub format: GraphFormat,
+    /// Include column lists in the Mermaid diagram. Default: true
+    #[serde(default = "default_true")]
+    pub include_columns: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+/// A foreign key constraint between two tables.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct Relationship {
+    /// Constraint name
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Referencing (child) table
+    pub from_table: String,
+    pub from_columns: Vec<String>,
+    /// Referenced (parent) table
+    pub to_table: String,
+    pub to_columns: Vec<String>,
+    /// True if a child row may have no parent (nullable FK column)
+    pub optional: bool,
+    /// True if each parent has at most one child (FK columns are unique)
+    pub one_to_one: bool,
+}
+
+/// A table and the tables it is connected to.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct TableNode {
+    pub table: String,
+    /// Tables this table references
+    pub references: Vec<String>,
+    /// Tables referencing this table
+    pub referenced_by: Vec<String>,
+}
+
+/// Output from the relationship_graph tool.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct RelationshipGraphOutput {
+    pub table_count: usize,
+    pub relationship_count: usize,
+    /// Adjacency lists. Empty if format is mermaid.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub tables: Vec<TableNode>,
+    /// Empty if format is mermaid.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub relationships: Vec<Relationship>,
+    /// Mermaid erDiagram when format is mermaid
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mermaid: Option<String>,
+}
+
+/// Input for the find_join_path tool.
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
+pub struct FindJoinPathInput {
+    /// Database connection ID from list_connections
+    pub connection_id: String,
+    /// Table to start from
+    pub from_table: String,
+    /// Table to reach
+    pub to_table: String,
+    /// Target database name (optional)
+    #[serde(default)]
+    pub database: Option<String>,
+    /// Maximum number of joins. Default: 6
+    #[serde(default)]
+    pub max_hops: Option<u32>,
+}
+
+/// One join along a path.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct JoinHop {
+    /// Table already in the query
+    pub from_table: String,
+    pub from_columns: Vec<String>,
+    /// Table being joined
+    pub to_table: String,
+    pub to_columns: Vec<String>,
+    /// Foreign key constraint used
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub constraint: Option<String>,
+    /// "child_to_parent" when from_table holds the foreign key, otherwise "parent_to_child"
+    pub direction: String,
+}
+
+/// Output from the find_join_path tool.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct FindJoinPathOutput {
+    pub found: bool,
+    /// Tables along the path, starting with from_table
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub tables: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub hops: Vec<JoinHop>,
+    /// FROM ... JOIN ... ON ... clause following the path
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub join_sql: Option<String>,
+}
+
+/// Step of a path: the relationship used and whether it was walked from child
+/// to parent.
+#[derive(Debug, Clone, Copy)]
+struct Step {
+    relationship: usize,
+    to_parent: bool,
+}
+
+/// Foreign-key graph of a database.
+pub struct RelationshipGraph {
+    tables: Vec<TableSchema>,
+    relationships: Vec<Relationship>,
+}
+
+impl RelationshipGraph {
+    /// Build the graph from described tables.
+    ///
+    /// Per-column foreign keys sharing a constraint name are merged into one
+    /// composite relationship. SQLite does not name constraints, so unnamed
+    /// foreign keys to the same table are merged when their referenced columns
+    /// together form a multi-column key of that table.
+    pub fn new(tables: Vec<TableSchema>) -> Self {
+        let mut relationships = Vec::new();
+        for table in &tables {
+            let mut groups: Vec<(Option<&str>, &str, Vec<&ForeignKey>)> = Vec::new();
+            for fk in &table.foreign_keys {
+                let existing = groups.iter_mut().find(|(name, target, _)| {
+                    fk.name.is_some()
+                        && *name == fk.name.as_deref()
+                        && *target == fk.references_table
+                });
+                match existing {
+                    Some((_, _, fks)) => fks.push(fk),
+                    None => groups.push((fk.name.as_deref(), &fk.references_table, vec![fk])),
+                }
+            }
+            merge_unnamed_composites(&mut groups, &tables);
+
+            for (name, target, fks) in groups {
+                let from_columns: Vec<String> = fks.iter().map(|fk| fk.column.clone()).collect();
+                let optional = from_columns.iter().any(|c| {
+                    table
+                        .columns
+                        .iter()
+                        .any(|col| col.name == *c && col.nullable)
+                });
+                let one_to_one = is_unique(table, &from_columns);
+                relationships.push(Relationship {
+                    name: name.map(str::to_string),
+                    from_table: table.table_name.clone(),
+                    from_columns,
+                    to_table: target.to_string(),
+                    to_columns: fks.iter().map(|fk| fk.references_column.clone()).collect(),
+                    optional,
+                    one_to_one,
+                });
+            }
+        }
+
+        Self {
+            tables,
+            relationships,
+        }
+    }
+
+    pub fn tables(&self) -> &[TableSchema] {
+        &self.tables
+    }
+
+    pub fn relationships(&self) -> &[Relationship] {
+        &self.relationships
+    }
+
+    /// Resolve a table name case-insensitively.
+    pub fn table_name(&self, name: &str) -> Option<&str> {
+        self.tables
+            .iter()
+            .find(|t| t.table_name == name)
+            .or_else(|| {
+                self.tables
+                    .iter()
+                    .find(|t| t.table_name.eq_ignore_ascii_case(name))
+            })
+            .map(|t| t.table_name.as_str())
+    }
+
+    /// Adjacency lists, one entry per table, sorted by table name.
+    pub fn adjacency(&self) -> Vec<TableNode> {
+        let mut nodes: BTreeMap<&str, TableNode> = self
+            .tables
+            .iter()
+            .map(|t| {
+                (
+                    t.table_name.as_str(),
+                    TableNode {
+                        table: t.table_name.clone(),
+                        references: Vec::new(),
+                        referenced_by: Vec::new(),
+                    },
+                )
+            })
+            .collect();
+
+        for rel in &self.relationships {
+            if let Some(node) = nodes.get_mut(rel.from_table.as_str()) {
+                push_unique(&mut node.references, &rel.to_table);
+            }
+            if let Some(node) = nodes.get_mut(rel.to_table.as_str()) {
+                push_unique(&mut node.referenced_by, &rel.from_table);
+            }
+        }
+
+        nodes
+            .into_values()
+            .map(|mut node| {
+                node.references.sort();
+                node.referenced_by.sort();
+                node
+            })
+            .collect()
+    }
+
+    /// Render the graph as a Mermaid `erDiagram`.
+    pub fn to_mermaid(&self, include_columns: bool) -> String {
+        let mut out = String::from("erDiagram\n");
+
+        for rel in &self.relationships {
+            let parent = if rel.optional { "|o" } else { "||" };
+            let child = if rel.one_to_one { "o|" } else { "o{" };
+            out.push_str(&format!(
+                "    {} {}--{} {} : \"{}\"\n",
+                mermaid_name(&rel.to_table),
+                parent,
+                child,
+                mermaid_name(&rel.from_table),
+                rel.from_columns.join(", ")
+            ));
+        }
+
+        for table in &self.tables {
+            if !include_columns {
+                let connected = self
+                    .relationships
+                    .iter()
+                    .any(|r| r.from_table == table.table_name || r.to_table == table.table_name);
+                // Unconnected tables would otherwise be missing from the diagram
+                if !connected {
+                    out.push_str(&format!("    {}\n", mermaid_name(&table.table_name)));
+                }
+                continue;
+            }
+
+            out.push_str(&format!("    {} {{\n", mermaid_name(&table.table_name)));
+            for col in &table.columns {
+                let is_fk = table.foreign_keys.iter().any(|fk| fk.column == col.name);
+                let keys = match (col.is_primary_key, is_fk) {
+                    (true, true) => " PK, FK",
+                    (true, false) => " PK",
+                    (false, true) => " FK",
+                    (false, false) => "",
+                };
+                out.push_str(&format!(
+                    "        {} {}{}\n",
+                    mermaid_type(&col.data_type),
+                    mermaid_name(&col.name),
+                    keys
+                ));
+            }
+            out.push_str("    }\n");
+        }
+
+        out
+    }
+
+    /// Shortest path between two tables, walking foreign keys in either direction.
+    fn shortest_path(&self, from: &str, to: &str, max_hops: u32) -> Option<Vec<Step>> {
+        if from == to {
+            return Some(Vec::new());
+        }
+
+        // Visit neighbours in a stable order so equal-length paths are chosen deterministically
+        let mut edges: HashMap<&str, Vec<(&str, Step)>> = HashMap::new();
+        for (i, rel) in self.relationships.iter().enumerate() {
+            edges.entry(&rel.from_table).or_default().push((
+                &rel.to_table,
+                Step {
+                    relationship: i,
+                    to_parent: true,
+                },
+            ));
+            edges.entry(&rel.to_table).or_default().push((
+                &rel.from_table,
+                Step {
+                    relationship: i,
+                    to_parent: false,
+                },
+            ));
+        }
+        for neighbours in edges.values_mut() {
+            neighbours.sort_by(|a, b| a.0.cmp(b.0).then(b.1.to_parent.cmp(&a.1.to_parent)));
+        }
+
+        let mut previous: HashMap<&str, (&str, Step)> = HashMap::new();
+        let mut queue = VecDeque::from([(from, 0u32)]);
+        while let Some((table, depth)) = queue.pop_front() {
+            if depth == max_hops {
+                continue;
+            }
+            for &(next, step) in edges.get(table).into_iter().flatten() {
+                if next == from || previous.contains_key(next) {
+                    continue;
+                }
+                previous.insert(next, (table, step));
+                if next == to {
+                    let mut steps = Vec::new();
+                    let mut current = to;
+                    while current != from {
+                        let (prev, step) = previous[current];
+                        steps.push(step);
+                        current = prev;
+                    }
+                    steps.reverse();
+                    return Some(steps);
+                }
+                queue.push_back((next, depth + 1));
+            }
+        }
+        None
+    }
+
+    /// Find the shortest join path and render it as JOIN clauses.
+    pub fn find_join_path(
+        &self,
+        from: &str,
+        to: &str,
+        max_hops: u32,
+        db_type: DatabaseType,
+    ) -> FindJoinPathOutput {
+        let Some(steps) = self.shortest_path(from, to, max_hops) else {
+            return FindJoinPathOutput {
+                found: false,
+                tables: Vec::new(),
+                hops: Vec::new(),
+                join_sql: None,
+            };
+        };
+
+        let quote = |id: &str| db_type.quote_identifier(id);
+        let mut tables = vec![from.to_string()];
+        let mut hops = Vec::with_capacity(steps.len());
+        let mut sql = format!("FROM {}", quote(from));
+
+        for step in steps {
+            let rel = &self.relationships[step.relationship];
+            let hop = if step.to_parent {
+                JoinHop {
+                    from_table: rel.from_table.clone(),
+                    from_columns: rel.from_columns.clone(),
+                    to_table: rel.to_table.clone(),
+                    to_columns: rel.to_columns.clone(),
+                    constraint: rel.name.clone(),
+                    direction: "child_to_parent".to_string(),
+                }
+            } else {
+                JoinHop {
+                    from_table: rel.to_table.clone(),
+                    from_columns: rel.to_columns.clone(),
+                    to_table: rel.from_table.clone(),
+                    to_columns: rel.from_columns.clone(),
+                    constraint: rel.name.clone(),
+                    direction: "parent_to_child".to_string(),
+                }
+            };
+
+            let conditions = hop
+                .to_columns
+                .iter()
+                .zip(&hop.from_columns)
+                .map(|(to_col, from_col)| {
+                    format!(
+                        "{}.{} = {}.{}",
+                        quote(&hop.to_table),
+                        quote(to_col),
+                        quote(&hop.from_table),
+                        quote(from_col)
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join(" AND ");
+            sql.push_str(&format!(
+                "\nJOIN {} ON {}",
+                quote(&hop.to_table),
+                conditions
+            ));
+
+            tables.push(hop.to_table.clone());
+            hops.push(hop);
+        }
+
+        FindJoinPathOutput {
+            found: true,
+            tables,
+            hops,
+            join_sql: Some(sql),
+        }
+    }
+}
+
+/// Merge unnamed single-column foreign keys to the same table whose referenced
+/// columns together form a multi-column key of that table.
+fn merge_unnamed_composites<'a>(
+    groups: &mut Vec<(Option<&'a str>, &'a str, Vec<&'a ForeignKey>)>,
+    tables: &[TableSchema],
+) {
+    let mut targets: Vec<&str> = groups
+        .iter()
+        .filter(|(name, _, _)| name.is_none())
+        .map(|(_, target, _)| *target)
+        .collect();
+    targets.dedup();
+
+    for target in targets {
+        let Some(target_table) = tables.iter().find(|t| t.table_name == target) else {
+            continue;
+        };
+        let unnamed: Vec<&ForeignKey> = groups
+            .iter()
+            .filter(|(name, t, _)| name.is_none() && *t == target)
+            .flat_map(|(_, _, fks)| fks.iter().copied())
+            .collect();
+        let referenced: Vec<String> = unnamed
+            .iter()
+            .map(|fk| fk.references_column.clone())
+            .collect();
+        if unnamed.len() < 2 || !is_unique(target_table, &referenced) {
+            continue;
+        }
+
+        let position = groups
+            .iter()
+            .position(|(name, t, _)| name.is_none() && *t == target)
+            .unwrap_or(0);
+        groups.retain(|(name, t, _)| name.is_some() || *t != target);
+        groups.insert(position, (None, target, unnamed));
+    }
+}
+
+/// True if the columns are covered exactly by the primary key or a unique index.
+fn is_unique(table: &TableSchema, columns: &[String]) -> bool {
+    let same = |other: &[String]| {
+        other.len() == columns.len() && other.iter().all(|c| columns.contains(c))
+    };
+    same(&table.primary_key)
+        || table
+            .indexes
+            .iter()
+            .any(|idx| (idx.is_unique || idx.is_primary) && same(&idx.columns))
+}
+
+fn push_unique(list: &mut Vec<String>, value: &str) {
+    if !list.iter().any(|v| v == value) {
+        list.push(value.to_string());
+    }
+}
+
+/// Mermaid identifiers may only contain letters, digits, `_` and `-`.
+fn mermaid_name(name: &str) -> String {
+    name.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '_' || c == '-' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+/// First word of the type without length/precision, e.g. `varchar(50)` -> `varchar`.
+fn mermaid_type(data_type: &str) -> String {
+    let base = data_type
+        .split(['(', ' '])
+        .next()
+        .filter(|s| !s.is_empty())
+        .unwrap_or("unknown");
+    mermaid_name(base)
+}
+
+pub struct RelationshipToolHandler {
+    connection_manager: Arc<ConnectionManager>,
+}
+
+impl RelationshipToolHandler {
+    pub fn new(connection_manager: Arc<ConnectionManager>) -> Self {
+        Self { connection_manager }
+    }
+
+    pub async fn relationship_graph(
+        &self,
+        input: RelationshipGraphInput,
+    ) -> DbResult<RelationshipGraphOutput> {
+        let (_, graph) = self
+            .load_graph(
+                &input.connection_id,
+                input.database.as_deref(),
+                "relationship_graph",
+            )
+            .await?;
+
+        info!(
+            connection_id = %input.connection_id,
+            tables = graph.tables().len(),
+            relationships = graph.relationships().len(),
+            "Built relationship graph"
+        );
+
+        let (tables, relationships, mermaid) = match input.format {
+            GraphFormat::Json => (graph.adjacency(), graph.relationships().to_vec(), None),
+            GraphFormat::Mermaid => (
+                Vec::new(),
+                Vec::new(),
+                Some(graph.to_mermaid(input.include_columns)),
+            ),
+        };
+
+        Ok(RelationshipGraphOutput {
+            table_count: graph.tables().len(),
+            relationship_count: graph.relationships().len(),
+            tables,
+            relationships,
+            mermaid,
+        })
+    }
+
+    pub async fn find_join_path(&self, input: FindJoinPathInput) -> DbResult<FindJoinPathOutput> {
+        let (db_type, graph) = self
+            .load_graph(
+                &input.connection_id,
+                input.database.as_deref(),
+                "find_join_path",
+            )
+            .await?;
+
+        let resolve = |name: &str| {
+            graph.table_name(name).map(str::to_string).ok_or_else(|| {
+                DbError::schema(format!("Table '{}' not found", name), name.to_string())
+            })
+        };
+        let from = resolve(&input.from_table)?;
+        let to = resolve(&input.to_table)?;
+
+        let output = graph.find_join_path(
+            &from,
+            &to,
+            input.max_hops.unwrap_or(DEFAULT_MAX_HOPS),
+            db_type,
+        );
+
+        info!(
+            connection_id = %input.connection_id,
+            from = %from,
+            to = %to,
+            found = output.found,
+            hops = output.hops.len(),
+            "Searched join path"
+        );
+
+        Ok(output)
+    }
+
+    async fn load_graph(
+        &self,
+        connection_id: &str,
+        database: Option<&str>,
+        tool: &str,
+    ) -> DbResult<(DatabaseType, RelationshipGraph)> {
+        let config = self.connection_manager.get_config(connection_id).await?;
+
+        // For server-level connections, database parameter is required
+        if config.server_level && database.is_none() {
+            return Err(DbError::invalid_input(format!(
+                "Server-level connections require a 'database' parameter to specify which database to query. \
+                Use list_databases first to discover available databases, then call {} with database=<database_name>.",
+                tool
+            )));
+        }
+
+        let pool = self
+            .connection_manager
+            .get_pool_for_database(connection_id, database)
+            .await?;
+
+        let result = describe_all(&pool, database, None).await;
+
+        self.connection_manager
+            .release_pool_for_database(connection_id, database)
+            .await;
+
+        Ok((config.db_type, RelationshipGraph::new(result?)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{ColumnDefinition, IndexInfo};
+
+    fn table(name: &str, fks: &[(&str, &str)]) -> TableSchema {
+        let mut table = TableSchema::new(name)
+            .with_column(ColumnDefinition::new("id", "integer", false).with_primary_key(true))
+            .with_primary_key(vec!["id".to_string()]);
+        for (column, target) in fks {
+            table = table.with_column(ColumnDefinition::new(*column, "integer", true));
+            table
+                .foreign_keys
+                .push(ForeignKey::new(*column, *target, "id"));
+        }
+        table
+    }
+
+    fn graph() -> RelationshipGraph {
+        RelationshipGraph::new(vec![
+            table("regions", &[]),
+            table("countries", &[("region_id", "regions")]),
+            table("customers", &[("country_id", "countries")]),
+            table("orders", &[("customer_id", "customers")]),
+            table("order_items", &[("order_id", "orders")]),
+            table("audit", &[]),
+        ])
+    }
+
+    #[test]
+    fn test_adjacency() {
+        let nodes = graph().adjacency();
+        let customers = nodes.iter().find(|n| n.table == "customers").unwrap();
+        assert_eq!(customers.references, vec!["countries"]);
+        assert_eq!(customers.referenced_by, vec!["orders"]);
+        assert_eq!(nodes[0].table, "audit");
+    }
+
+    #[test]
+    fn test_join_path_child_to_parent() {
+        let output = graph().find_join_path("orders", "regions", 6, DatabaseType::PostgreSQL);
+        assert!(output.found);
+        assert_eq!(
+            output.tables,
+            vec!["orders", "customers", "countries", "regions"]
+        );
+        assert_eq!(
+            output.join_sql.unwrap(),
+            "FROM \"orders\"\n\
+             JOIN \"customers\" ON \"customers\".\"id\" = \"orders\".\"customer_id\"\n\
+             JOIN \"countries\" ON \"countries\".\"id\" = \"customers\".\"country_id\"\n\
+             JOIN \"regions\" ON \"regions\".\"id\" = \"countries\".\"region_id\""
+        );
+    }
+
+    #[test]
+    fn test_join_path_parent_to_child() {
+        let output = graph().find_join_path("customers", "order_items", 6, DatabaseType::MySQL);
+        assert_eq!(output.hops.len(), 2);
+        assert_eq!(output.hops[0].direction, "parent_to_child");
+        assert!(
+            output
+                .join_sql
+                .unwrap()
+                .ends_with("JOIN `order_items` ON `order_items`.`order_id` = `orders`.`id`")
+        );
+    }
+
+    #[test]
+    fn test_join_path_limits() {
+        let graph = graph();
+        assert!(
+            !graph
+                .find_join_path("order_items", "regions", 3, DatabaseType::SQLite)
+                .found
+        );
+        assert!(
+            !graph
+                .find_join_path("orders", "audit", 6, DatabaseType::SQLite)
+                .found
+        );
+        let same = graph.find_join_path("orders", "orders", 6, DatabaseType::SQLite);
+        assert!(same.found);
+        assert_eq!(same.join_sql.unwrap(), "FROM \"orders\"");
+    }
+
+    #[test]
+    fn test_composite_foreign_key_merged() {
+        let mut child = TableSchema::new("child");
+        for (column, target) in [("a", "x"), ("b", "y")] {
+            let mut fk = ForeignKey::new(column, "parent", target);
+            fk.name = Some("fk_parent".to_string());
+            child.foreign_keys.push(fk);
+            child
+                .columns
+                .push(ColumnDefinition::new(column, "int", false));
+        }
+        child.indexes.push(
+            IndexInfo::new("uq_ab", vec!["b".to_string(), "a".to_string()]).with_unique(true),
+        );
+
+        let graph = RelationshipGraph::new(vec![TableSchema::new("parent"), child]);
+        assert_eq!(graph.relationships().len(), 1);
+        let rel = &graph.relationships()[0];
+        assert_eq!(rel.from_columns, vec!["a", "b"]);
+        assert_eq!(rel.to_columns, vec!["x", "y"]);
+        assert!(!rel.optional);
+        assert!(rel.one_to_one);
+    }
+
+    #[test]
+    fn test_unnamed_foreign_keys_to_same_table_stay_separate() {
+        let graph = RelationshipGraph::new(vec![
+            table("users", &[]),
+            table("posts", &[("created_by", "users"), ("updated_by", "users")]),
+        ]);
+        assert_eq!(graph.relationships().len(), 2);
+    }
+
+    #[test]
+    fn test_mermaid() {
+        let mermaid = graph().to_mermaid(true);
+        assert!(mermaid.starts_with("erDiagram\n"));
+        assert!(mermaid.contains("    customers |o--o{ orders : \"customer_id\"\n"));
+        assert!(mermaid.contains(
+            "    orders {\n        integer id PK\n        integer customer_id FK\n    }\n"
+        ));
+
+        let compact = graph().to_mermaid(false);
+        assert!(compact.contains("    audit\n"));
+        assert!(!compact.contains("PK"));
+    }
+
+    #[test]
+    fn test_mermaid_names() {
+        assert_eq!(mermaid_name("order items"), "order_items");
+        assert_eq!(mermaid_type("varchar(50)"), "varchar");
+        assert_eq!(mermaid_type("timestamp with time zone"), "timestamp");
+    }
+}
@@ -0,0 +1,549 @@
+//! Row sampling tool.
+//!
+//! This module implements the `sample_table` MCP tool, which returns a few
+//! representative rows instead of whatever `LIMIT n` yields in storage order.
+//!
+//! Each backend uses its cheapest uniform-ish strategy:
+//! - PostgreSQL: `TABLESAMPLE` sized from the planner's row estimate
+//! - SQLite: random rowids drawn from the rowid range
+//! - MySQL: random keys drawn from the range of an integer primary key
+//!
+//! When the fast path cannot produce enough rows (small or sparse tables,
+//! selective filters, no usable key), the tool falls back to a full
+//! `ORDER BY random()` scan. Stratified samples always scan the table so that
+//! rare values are not missed.
+
+use crate::db::ddl::PortableType;
+use crate::db::schema::SchemaInspector;
+use crate::db::{ConnectionManager, DbPool, QueryExecutor};
+use crate::error::{DbError, DbResult};
+use crate::models::{DatabaseType, QueryRequest, QueryResult, TableSchema};
+use crate::tools::format::OutputFormat;
+use crate::tools::query::QueryOutput;
+use crate::tools::sql_validator;
+use rand::Rng;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::sync::Arc;
+use tracing::info;
+
+/// Default number of rows (or rows per stratum) returned.
+pub const DEFAULT_SAMPLE_ROWS: u32 = 10;
+
+/// Maximum number of rows (or rows per stratum) returned.
+pub const MAX_SAMPLE_ROWS: u32 = 1000;
+
+/// Default number of strata returned by a stratified sample.
+pub const DEFAULT_MAX_STRATA: u32 = 20;
+
+/// Maximum number of strata returned by a stratified sample.
+pub const MAX_STRATA: u32 = 100;
+
+/// How many more rows or keys than requested the fast paths draw, to make up
+/// for gaps in the key range and rows removed by the filter.
+const OVERSAMPLE: u64 = 4;
+
+/// Tables with at least this many estimated rows use block-level sampling
+/// (PostgreSQL `SYSTEM`), which reads only the sampled pages.
+const BLOCK_SAMPLE_MIN_ROWS: u64 = 100_000;
+
+/// Name of the helper column added by the stratified query.
+const RANK_COLUMN: &str = "_sample_rn";
+
+/// Name of the helper column numbering strata in the stratified query.
+const STRATUM_COLUMN: &str = "_sample_stratum";
+
+fn default_decode_binary() -> bool {
+    true
+}
+
+/// Input for the sample_table tool.
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
+pub struct SampleTableInput {
+    /// Database connection ID from list_connections
+    pub connection_id: String,
+    /// Name of the table to sample
+    pub table_name: String,
+    /// Target database name (optional)
+    #[serde(default)]
+    pub database: Option<String>,
+    /// Rows to return (per stratum when stratify_by is set). Default: 10, max: 1000
+    #[serde(default)]
+    pub sample_size: Option<u32>,
+    /// Optional WHERE condition restricting the sampled rows (without the WHERE keyword)
+    #[serde(default)]
+    pub filter: Option<String>,
+    /// Column to stratify by: sample_size rows are returned for each distinct value
+    #[serde(default)]
+    pub stratify_by: Option<String>,
+    /// Maximum number of strata returned when stratify_by is set. Default: 20, max: 100
+    #[serde(default)]
+    pub max_strata: Option<u32>,
+    /// Query timeout in seconds. Default: 30
+    #[serde(default)]
+    pub timeout_secs: Option<u32>,
+    /// Output format: "json" returns structured data, "table" returns ASCII table, "markdown" returns markdown table
+    #[serde(default)]
+    pub format: OutputFormat,
+    /// Decode binary as UTF-8 when possible (default: true). Falls back to base64.
+    #[serde(default = "default_decode_binary")]
+    pub decode_binary: bool,
+}
+
+/// Strategy used to draw the sample.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SamplingMethod {
+    /// PostgreSQL TABLESAMPLE
+    Tablesample,
+    /// Random keys drawn from the rowid / integer primary key range
+    KeyRange,
+    /// Full scan ordered by a random value
+    Random,
+    /// Per-stratum random rows from a full scan
+    Stratified,
+}
+
+/// Output from the sample_table tool.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct SampleTableOutput {
+    pub table_name: String,
+    pub method: SamplingMethod,
+    /// Number of strata returned (stratified samples only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub strata: Option<usize>,
+    #[serde(flatten)]
+    pub result: QueryOutput,
+}
+
+/// Builds the sampling queries for one table.
+struct SampleSql<'a> {
+    db_type: DatabaseType,
+    table: &'a str,
+    filter: Option<&'a str>,
+}
+
+impl SampleSql<'_> {
+    fn quote(&self, identifier: &str) -> String {
+        self.db_type.quote_identifier(identifier)
+    }
+
+    fn random_fn(&self) -> &'static str {
+        match self.db_type {
+            DatabaseType::MySQL => "RAND()",
+            DatabaseType::PostgreSQL | DatabaseType::SQLite => "random()",
+        }
+    }
+
+    /// `WHERE`/`AND` clause for the caller's filter, combined with `extra`.
+    fn where_clause(&self, extra: Option<String>) -> String {
+        let conditions: Vec<String> = extra
+            .into_iter()
+            .chain(self.filter.map(|f| format!("({})", f)))
+            .collect();
+        if conditions.is_empty() {
+            String::new()
+        } else {
+            format!(" WHERE {}", conditions.join(" AND "))
+        }
+    }
+
+    fn random(&self, limit: u32) -> String {
+        format!(
+            "SELECT * FROM {}{} ORDER BY {} LIMIT {}",
+            self.quote(self.table),
+            self.where_clause(None),
+            self.random_fn(),
+            limit
+        )
+    }
+
+    fn tablesample(&self, percent: f64, block: bool, limit: u32) -> String {
+        format!(
+            "SELECT * FROM {} TABLESAMPLE {} ({:.6}){} ORDER BY {} LIMIT {}",
+            self.quote(self.table),
+            if block { "SYSTEM" } else { "BERNOULLI" },
+            percent,
+            self.where_clause(None),
+            self.random_fn(),
+            limit
+        )
+    }
+
+    fn key_bounds(&self, key: &str) -> String {
+        format!(
+            "SELECT MIN({key}) AS lo, MAX({key}) AS hi FROM {}",
+            self.quote(self.table)
+        )
+    }
+
+    fn key_range(&self, key: &str, keys: &BTreeSet<i64>, limit: u32) -> String {
+        let list = keys
+            .iter()
+            .map(|k| k.to_string())
+            .collect::<Vec<_>>()
+            .join(", ");
+        format!(
+            "SELECT * FROM {}{} LIMIT {}",
+            self.quote(self.table),
+            self.where_clause(Some(format!("{} IN ({})", key, list))),
+            limit
+        )
+    }
+
+    fn stratified(&self, column: &str, per_stratum: u32, max_strata: u32) -> String {
+        let column = self.quote(column);
+        format!(
+            "SELECT * FROM (SELECT t.*, \
+             ROW_NUMBER() OVER (PARTITION BY {column} ORDER BY {random}) AS {RANK_COLUMN}, \
+             DENSE_RANK() OVER (ORDER BY {column}) AS {STRATUM_COLUMN} \
+             FROM {table} t{where_clause}) s \
+             WHERE {RANK_COLUMN} <= {per_stratum} AND {STRATUM_COLUMN} <= {max_strata} \
+             ORDER BY {STRATUM_COLUMN}, {RANK_COLUMN}",
+            random = self.random_fn(),
+            table = self.quote(self.table),
+            where_clause = self.where_clause(None),
+        )
+    }
+}
+
+/// Draw up to `count` distinct random keys from `lo..=hi`.
+///
+/// Returns `None` when the range is not larger than `count`, in which case a
+/// full scan is as cheap as probing keys.
+fn random_keys(lo: i64, hi: i64, count: u64) -> Option<BTreeSet<i64>> {
+    let span = hi.checked_sub(lo)? as u64 + 1;
+    if span <= count {
+        return None;
+    }
+    let mut rng = rand::thread_rng();
+    let mut keys = BTreeSet::new();
+    while (keys.len() as u64) < count {
+        keys.insert(rng.gen_range(lo..=hi));
+    }
+    Some(keys)
+}
+
+/// Integer key usable for key-range sampling, if any.
+fn sampling_key(db_type: DatabaseType, schema: &TableSchema) -> Option<String> {
+    match db_type {
+        // Every ordinary SQLite table has a rowid (WITHOUT ROWID tables fail the
+        // bounds query and fall back to a full scan)
+        DatabaseType::SQLite => Some("rowid".to_string()),
+        DatabaseType::MySQL => match schema.primary_key.as_slice() {
+            [pk] => schema
+                .columns
+                .iter()
+                .find(|c| &c.name == pk)
+                .filter(|c| {
+                    matches!(
+                        PortableType::parse(&c.data_type),
+                        PortableType::SmallInt | PortableType::Integer | PortableType::BigInt
+                    )
+                })
+                .map(|c| db_type.quote_identifier(&c.name)),
+            _ => None,
+        },
+        DatabaseType::PostgreSQL => None,
+    }
+}
+
+/// Remove the stratified query's helper columns from the result.
+fn strip_helper_columns(result: &mut QueryResult) {
+    result
+        .columns
+        .retain(|c| c != RANK_COLUMN && c != STRATUM_COLUMN);
+    for row in &mut result.rows {
+        row.remove(RANK_COLUMN);
+        row.remove(STRATUM_COLUMN);
+    }
+}
+
+pub struct SampleToolHandler {
+    connection_manager: Arc<ConnectionManager>,
+    executor: QueryExecutor,
+}
+
+impl SampleToolHandler {
+    pub fn new(connection_manager: Arc<ConnectionManager>, default_timeout_secs: u64) -> Self {
+        Self {
+            connection_manager,
+            executor: QueryExecutor::with_defaults(default_timeout_secs, MAX_SAMPLE_ROWS),
+        }
+    }
+
+    pub async fn sample_table(&self, input: SampleTableInput) -> DbResult<SampleTableOutput> {
+        let config = self
+            .connection_manager
+            .get_config(&input.connection_id)
+            .await?;
+
+        // For server-level connections, database parameter is required
+        if config.server_level && input.database.is_none() {
+            return Err(DbError::invalid_input(
+                "Server-level connections require a 'database' parameter to specify which database to query. \
+                Use list_databases first to discover available databases, then call sample_table with database=<database_name>.",
+            ));
+        }
+
+        if let Some(filter) = &input.filter {
+            sql_validator::validate_filter(filter, config.db_type)?;
+        }
+
+        let database = input.database.as_deref();
+        let pool = self
+            .connection_manager
+            .get_pool_for_database(&input.connection_id, database)
+            .await?;
+
+        let result = self.sample(&pool, config.db_type, &input).await;
+
+        self.connection_manager
+            .release_pool_for_database(&input.connection_id, database)
+            .await;
+
+        let (table_name, method, mut result) = result?;
+
+        let strata = (method == SamplingMethod::Stratified).then(|| {
+            result
+                .rows
+                .iter()
+                .filter_map(|row| row.get(STRATUM_COLUMN).map(|v| v.to_string()))
+                .collect::<BTreeSet<_>>()
+                .len()
+        });
+        strip_helper_columns(&mut result);
+
+        info!(
+            connection_id = %input.connection_id,
+            table = %table_name,
+            method = ?method,
+            row_count = result.rows.len(),
+            "Sampled table"
+        );
+
+        Ok(SampleTableOutput {
+            table_name,
+            method,
+            strata,
+            result: QueryOutput::from_result(result, input.format),
+        })
+    }
+
+    async fn sample(
+        &self,
+        pool: &DbPool,
+        db_type: DatabaseType,
+        input: &SampleTableInput,
+    ) -> DbResult<(String, SamplingMethod, QueryResult)> {
+        let database = input.database.as_deref();
+        let schema = SchemaInspector::describe_table(pool, &input.table_name, database).await?;
+        let sample_size = input
+            .sample_size
+            .unwrap_or(DEFAULT_SAMPLE_ROWS)
+            .clamp(1, MAX_SAMPLE_ROWS);
+
+        let sql = SampleSql {
+            db_type,
+            table: &schema.table_name,
+            filter: input.filter.as_deref(),
+        };
+
+        if let Some(column) = &input.stratify_by {
+            let column = schema
+                .columns
+                .iter()
+                .find(|c| c.name.eq_ignore_ascii_case(column))
+                .ok_or_else(|| {
+                    DbError::invalid_input(format!(
+                        "Column '{}' does not exist in table '{}'",
+                        column, schema.table_name
+                    ))
+                })?;
+            let max_strata = input
+                .max_strata
+                .unwrap_or(DEFAULT_MAX_STRATA)
+                .clamp(1, MAX_STRATA);
+            let query = sql.stratified(&column.name, sample_size, max_strata);
+            let result = self
+                .run(pool, input, query, sample_size * max_strata)
+                .await?;
+            return Ok((schema.table_name, SamplingMethod::Stratified, result));
+        }
+
+        let fast = match db_type {
+            DatabaseType::PostgreSQL => {
+                self.tablesample(pool, &sql, &schema, input, sample_size)
+                    .await?
+            }
+            DatabaseType::MySQL | DatabaseType::SQLite => {
+                self.key_range(pool, &sql, &schema, input, sample_size)
+                    .await?
+            }
+        };
+        if let Some(sampled) = fast {
+            return Ok((schema.table_name, sampled.0, sampled.1));
+        }
+
+        let result = self
+            .run(pool, input, sql.random(sample_size), sample_size)
+            .await?;
+        Ok((schema.table_name, SamplingMethod::Random, result))
+    }
+
+    /// PostgreSQL `TABLESAMPLE`, sized from the planner's row estimate.
+    async fn tablesample(
+        &self,
+        pool: &DbPool,
+        sql: &SampleSql<'_>,
+        schema: &TableSchema,
+        input: &SampleTableInput,
+        sample_size: u32,
+    ) -> DbResult<Option<(SamplingMethod, QueryResult)>> {
+        let stats =
+            SchemaInspector::table_statistics(pool, &schema.table_name, input.database.as_deref())
+                .await?;
+        let Some(estimate) = stats.row_count_estimate else {
+            return Ok(None);
+        };
+
+        let wanted = sample_size as u64 * OVERSAMPLE;
+        if estimate <= wanted {
+            return Ok(None);
+        }
+        let percent = wanted as f64 / estimate as f64 * 100.0;
+        let query = sql.tablesample(percent, estimate >= BLOCK_SAMPLE_MIN_ROWS, sample_size);
+
+        let result = self.run(pool, input, query, sample_size).await?;
+        Ok((result.rows.len() as u32 >= sample_size)
+            .then_some((SamplingMethod::Tablesample, result)))
+    }
+
+    /// Random keys from the rowid (SQLite) or integer primary key (MySQL) range.
+    async fn key_range(
+        &self,
+        pool: &DbPool,
+        sql: &SampleSql<'_>,
+        schema: &TableSchema,
+        input: &SampleTableInput,
+        sample_size: u32,
+    ) -> DbResult<Option<(SamplingMethod, QueryResult)>> {
+        let Some(key) = sampling_key(sql.db_type, schema) else {
+            return Ok(None);
+        };
+
+        let Ok(bounds) = self.run(pool, input, sql.key_bounds(&key), 1).await else {
+            return Ok(None);
+        };
+        let bound = |name: &str| {
+            bounds
+                .rows
+                .first()
+                .and_then(|row| row.get(name))
+                .and_then(|v| v.as_i64())
+        };
+        let (Some(lo), Some(hi)) = (bound("lo"), bound("hi")) else {
+            return Ok(None);
+        };
+
+        let Some(keys) = random_keys(lo, hi, sample_size as u64 * OVERSAMPLE) else {
+            return Ok(None);
+        };
+        let result = self
+            .run(
+                pool,
+                input,
+                sql.key_range(&key, &keys, sample_size),
+                sample_size,
+            )
+            .await?;
+        Ok((result.rows.len() as u32 >= sample_size).then_some((SamplingMethod::KeyRange, result)))
+    }
+
+    async fn run(
+        &self,
+        pool: &DbPool,
+        input: &SampleTableInput,
+        sql: String,
+        limit: u32,
+    ) -> DbResult<QueryResult> {
+        let mut request = QueryRequest::new(&input.connection_id, sql).with_limit(limit);
+        if let Some(timeout_secs) = input.timeout_secs {
+            request = request.with_timeout(timeout_secs);
+        }
+        request.decode_binary = input.decode_binary;
+        self.executor.execute_query(pool, &request).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::ColumnDefinition;
+
+    fn sql(db_type: DatabaseType, filter: Option<&str>) -> SampleSql<'_> {
+        SampleSql {
+            db_type,
+            table: "users",
+            filter,
+        }
+    }
+
+    #[test]
+    fn test_random_query() {
+        assert_eq!(
+            sql(DatabaseType::MySQL, Some("age > 21")).random(5),
+            "SELECT * FROM `users` WHERE (age > 21) ORDER BY RAND() LIMIT 5"
+        );
+    }
+
+    #[test]
+    fn test_tablesample_query() {
+        assert_eq!(
+            sql(DatabaseType::PostgreSQL, None).tablesample(0.5, true, 10),
+            "SELECT * FROM \"users\" TABLESAMPLE SYSTEM (0.500000) ORDER BY random() LIMIT 10"
+        );
+    }
+
+    #[test]
+    fn test_key_range_query() {
+        let keys = BTreeSet::from([7, 3]);
+        assert_eq!(
+            sql(DatabaseType::SQLite, Some("active = 1")).key_range("rowid", &keys, 2),
+            "SELECT * FROM \"users\" WHERE rowid IN (3, 7) AND (active = 1) LIMIT 2"
+        );
+    }
+
+    #[test]
+    fn test_stratified_query() {
+        let query = sql(DatabaseType::PostgreSQL, None).stratified("country", 3, 20);
+        assert!(query.contains("ROW_NUMBER() OVER (PARTITION BY \"country\" ORDER BY random())"));
+        assert!(query.contains("WHERE _sample_rn <= 3 AND _sample_stratum <= 20"));
+    }
+
+    #[test]
+    fn test_random_keys() {
+        assert!(random_keys(1, 10, 10).is_none());
+        let keys = random_keys(1, 1000, 40).unwrap();
+        assert_eq!(keys.len(), 40);
+        assert!(keys.iter().all(|k| (1..=1000).contains(k)));
+    }
+
+    #[test]
+    fn test_sampling_key() {
+        let schema = TableSchema::new("t")
+            .with_column(ColumnDefinition::new("id", "bigint unsigned", false))
+            .with_primary_key(vec!["id".to_string()]);
+        assert_eq!(
+            sampling_key(DatabaseType::MySQL, &schema).as_deref(),
+            Some("`id`")
+        );
+        assert_eq!(sampling_key(DatabaseType::PostgreSQL, &schema), None);
+
+        let schema = TableSchema::new("t")
+            .with_column(ColumnDefinition::new("code", "varchar(10)", false))
+            .with_primary_key(vec!["code".to_string()]);
+        assert_eq!(sampling_key(DatabaseType::MySQL, &schema), None);
+    }
+}
@@ -1,11 +1,18 @@
 //! Schema introspection tools.
 //!
-//! This module implements the `list_tables` and `describe_table` MCP tools.
+//! This module implements the `list_tables`, `describe_table` and `show_create`
+//! MCP tools, plus introspection of views, routines, triggers and sequences.
+//! Table listings and descriptions are served through the connection manager's
+//! schema cache; `refresh_schema` clears it.
 
-use crate::db::ConnectionManager;
+use crate::db::ddl::DdlRenderer;
 use crate::db::schema::SchemaInspector;
+use crate::db::{ConnectionManager, DbPool, SchemaCacheStats};
 use crate::error::{DbError, DbResult};
-use crate::models::{ColumnDefinition, ForeignKey, IndexInfo, TableInfo, TableSchema};
+use crate::models::{
+    ColumnDefinition, ConnectionConfig, DatabaseType, ForeignKey, IndexInfo, RoutineInfo,
+    SequenceInfo, TableInfo, TableSchema, TriggerInfo, ViewSchema,
+};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
@@ -186,6 +193,39 @@ pub struct DescribeTableOutput {
     pub foreign_keys: Vec<ForeignKeyOutput>,
     /// Index definitions on the table
     pub indexes: Vec<IndexOutput>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+}
+
+/// Input for the show_create tool.
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
+pub struct ShowCreateInput {
+    /// Database connection ID from list_connections
+    pub connection_id: String,
+    /// Name of the table
+    pub table_name: String,
+    /// Target database name (optional)
+    #[serde(default)]
+    pub database: Option<String>,
+    /// Render the DDL for another database type (postgresql, mysql, sqlite).
+    /// Default: the connection's own type
+    #[serde(default)]
+    pub dialect: Option<DatabaseType>,
+}
+
+/// Output from the show_create tool.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct ShowCreateOutput {
+    pub table_name: String,
+    /// Database type the DDL is written for
+    pub dialect: DatabaseType,
+    /// True when the DDL was reported by the database itself rather than
+    /// synthesized from table metadata
+    pub native: bool,
+    /// CREATE TABLE followed by index and comment statements
+    pub statements: Vec<String>,
+    /// All statements as a single script
+    pub ddl: String,
 }
 
 #[derive(Debug, Clone, Serialize, JsonSchema)]
@@ -317,10 +357,256 @@ impl From<TableSchema> for DescribeTableOutput {
             primary_key: schema.primary_key,
             foreign_keys: schema.foreign_keys.into_iter().map(Into::into).collect(),
             indexes: schema.indexes.into_iter().map(Into::into).collect(),
+            comment: schema.comment,
+        }
+    }
+}
+
+/// Input for the describe_view tool.
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
+pub struct DescribeViewInput {
+    /// Database connection ID from list_connections
+    pub connection_id: String,
+    /// Name of the view to describe
+    pub view_name: String,
+    /// Target database name (optional)
+    #[serde(default)]
+    pub database: Option<String>,
+}
+
+/// Output from the describe_view tool.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct DescribeViewOutput {
+    pub view_name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub schema: Option<String>,
+    /// PostgreSQL only
+    pub materialized: bool,
+    /// Defining query
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub definition: Option<String>,
+    pub columns: Vec<ColumnOutput>,
+}
+
+impl From<ViewSchema> for DescribeViewOutput {
+    fn from(view: ViewSchema) -> Self {
+        Self {
+            view_name: view.view_name,
+            schema: view.schema_name,
+            materialized: view.materialized,
+            definition: view.definition,
+            columns: view.columns.into_iter().map(Into::into).collect(),
         }
     }
 }
 
+/// Input for the list_routines tool.
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
+pub struct ListRoutinesInput {
+    /// Database connection ID from list_connections
+    pub connection_id: String,
+    /// Target database name (optional)
+    #[serde(default)]
+    pub database: Option<String>,
+}
+
+/// Input for the describe_routine tool.
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
+pub struct DescribeRoutineInput {
+    /// Database connection ID from list_connections
+    pub connection_id: String,
+    /// Name of the function or procedure
+    pub routine_name: String,
+    /// Target database name (optional)
+    #[serde(default)]
+    pub database: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct RoutineOutput {
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub schema: Option<String>,
+    /// function, procedure, aggregate or window
+    pub routine_type: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub arguments: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub return_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+    /// Full definition (PostgreSQL) or routine body (MySQL); describe_routine only
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub definition: Option<String>,
+}
+
+impl From<RoutineInfo> for RoutineOutput {
+    fn from(routine: RoutineInfo) -> Self {
+        Self {
+            name: routine.name,
+            schema: routine.schema_name,
+            routine_type: routine.routine_type.to_string(),
+            arguments: routine.arguments,
+            return_type: routine.return_type,
+            language: routine.language,
+            definition: routine.definition,
+        }
+    }
+}
+
+/// Output from the list_routines tool.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct ListRoutinesOutput {
+    pub routines: Vec<RoutineOutput>,
+    pub count: usize,
+}
+
+/// Output from the describe_routine tool.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct DescribeRoutineOutput {
+    /// Every routine with the requested name (PostgreSQL allows overloads)
+    pub routines: Vec<RoutineOutput>,
+}
+
+/// Input for the list_triggers tool.
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
+pub struct ListTriggersInput {
+    /// Database connection ID from list_connections
+    pub connection_id: String,
+    /// Only list triggers on this table (optional)
+    #[serde(default)]
+    pub table_name: Option<String>,
+    /// Target database name (optional)
+    #[serde(default)]
+    pub database: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct TriggerOutput {
+    pub name: String,
+    pub table_name: String,
+    /// BEFORE, AFTER or INSTEAD OF
+    pub timing: String,
+    /// INSERT, UPDATE, DELETE or TRUNCATE
+    pub event: String,
+    /// Full CREATE TRIGGER statement (PostgreSQL, SQLite)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub definition: Option<String>,
+    /// Trigger action; the trigger function's source on PostgreSQL
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub body: Option<String>,
+}
+
+impl From<TriggerInfo> for TriggerOutput {
+    fn from(trigger: TriggerInfo) -> Self {
+        Self {
+            name: trigger.name,
+            table_name: trigger.table_name,
+            timing: trigger.timing,
+            event: trigger.event,
+            definition: trigger.definition,
+            body: trigger.body,
+        }
+    }
+}
+
+/// Output from the list_triggers tool.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct ListTriggersOutput {
+    pub triggers: Vec<TriggerOutput>,
+    pub count: usize,
+}
+
+/// Input for the list_sequences tool.
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
+pub struct ListSequencesInput {
+    /// Database connection ID from list_connections
+    pub connection_id: String,
+    /// Target database name (optional)
+    #[serde(default)]
+    pub database: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct SequenceOutput {
+    /// Sequence name (table name for auto-increment counters)
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub table_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub column_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data_type: Option<String>,
+    /// Last value handed out (absent if never used)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub current_value: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start_value: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub increment: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_value: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_value: Option<i64>,
+}
+
+impl From<SequenceInfo> for SequenceOutput {
+    fn from(seq: SequenceInfo) -> Self {
+        Self {
+            name: seq.name,
+            table_name: seq.table_name,
+            column_name: seq.column_name,
+            data_type: seq.data_type,
+            current_value: seq.current_value,
+            start_value: seq.start_value,
+            increment: seq.increment,
+            min_value: seq.min_value,
+            max_value: seq.max_value,
+        }
+    }
+}
+
+/// Output from the list_sequences tool.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct ListSequencesOutput {
+    pub sequences: Vec<SequenceOutput>,
+    pub count: usize,
+}
+
+/// Input for the refresh_schema tool.
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
+pub struct RefreshSchemaInput {
+    /// Database connection ID from list_connections
+    pub connection_id: String,
+    /// Only refresh this database. Default: all databases of the connection
+    #[serde(default)]
+    pub database: Option<String>,
+}
+
+/// Output from the refresh_schema tool.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct RefreshSchemaOutput {
+    /// Cached entries dropped
+    pub cleared: usize,
+    /// Cache counters after the refresh
+    pub cache: SchemaCacheStats,
+}
+
+/// Database whose pool is used to list a connection's databases.
+///
+/// Server-level connections go through a system database; SQLite does not
+/// support listing databases.
+pub(crate) fn listing_database(config: &ConnectionConfig) -> Option<&'static str> {
+    if !config.server_level {
+        return None;
+    }
+    match config.db_type {
+        DatabaseType::MySQL => Some("information_schema"),
+        DatabaseType::PostgreSQL => Some("postgres"),
+        DatabaseType::SQLite => None,
+    }
+}
+
 pub struct SchemaToolHandler {
     connection_manager: Arc<ConnectionManager>,
 }
@@ -350,7 +636,17 @@ impl SchemaToolHandler {
             .get_pool_for_database(&input.connection_id, database)
             .await?;
 
-        let result = SchemaInspector::list_tables(&pool, database, input.include_views).await;
+        let result = self
+            .connection_manager
+            .schema_cache()
+            .list_tables(
+                &input.connection_id,
+                database,
+                &pool,
+                database,
+                input.include_views,
+            )
+            .await;
 
         self.connection_manager
             .release_pool_for_database(&input.connection_id, database)
@@ -391,7 +687,17 @@ impl SchemaToolHandler {
             .get_pool_for_database(&input.connection_id, database)
             .await?;
 
-        let result = SchemaInspector::describe_table(&pool, &input.table_name, database).await;
+        let result = self
+            .connection_manager
+            .schema_cache()
+            .describe_table(
+                &input.connection_id,
+                database,
+                &pool,
+                &input.table_name,
+                database,
+            )
+            .await;
 
         self.connection_manager
             .release_pool_for_database(&input.connection_id, database)
@@ -409,6 +715,232 @@ impl SchemaToolHandler {
         Ok(schema.into())
     }
 
+    pub async fn show_create(&self, input: ShowCreateInput) -> DbResult<ShowCreateOutput> {
+        let config = self
+            .connection_manager
+            .get_config(&input.connection_id)
+            .await?;
+
+        // For server-level connections, database parameter is required
+        if config.server_level && input.database.is_none() {
+            return Err(DbError::invalid_input(
+                "Server-level connections require a 'database' parameter to specify which database to query. \
+                Use list_databases first to discover available databases, then call show_create with database=<database_name>.",
+            ));
+        }
+
+        let dialect = input.dialect.unwrap_or(config.db_type);
+        let database = input.database.as_deref();
+        let pool = self
+            .connection_manager
+            .get_pool_for_database(&input.connection_id, database)
+            .await?;
+
+        let result = async {
+            if dialect == config.db_type {
+                if let Some(statements) =
+                    SchemaInspector::show_create(&pool, &input.table_name, database).await?
+                {
+                    return Ok((statements, true));
+                }
+            }
+            let schema = self
+                .connection_manager
+                .schema_cache()
+                .describe_table(
+                    &input.connection_id,
+                    database,
+                    &pool,
+                    &input.table_name,
+                    database,
+                )
+                .await?;
+            let renderer = DdlRenderer::new(config.db_type, dialect);
+            Ok::<_, DbError>((renderer.create_table(&schema, true), false))
+        }
+        .await;
+
+        self.connection_manager
+            .release_pool_for_database(&input.connection_id, database)
+            .await;
+
+        let (statements, native) = result?;
+
+        info!(
+            connection_id = %input.connection_id,
+            table = %input.table_name,
+            dialect = %dialect,
+            native = native,
+            "Generated table DDL"
+        );
+
+        Ok(ShowCreateOutput {
+            table_name: input.table_name,
+            dialect,
+            native,
+            ddl: format_script(&statements),
+            statements,
+        })
+    }
+
+    /// Check the database requirement for server-level connections and get a pool.
+    /// Callers must release the pool with `release_pool_for_database`.
+    async fn acquire_pool(
+        &self,
+        connection_id: &str,
+        database: Option<&str>,
+        tool: &str,
+    ) -> DbResult<DbPool> {
+        let config = self.connection_manager.get_config(connection_id).await?;
+
+        if config.server_level && database.is_none() {
+            return Err(DbError::invalid_input(format!(
+                "Server-level connections require a 'database' parameter to specify which database to query. \
+                Use list_databases first to discover available databases, then call {} with database=<database_name>.",
+                tool
+            )));
+        }
+
+        self.connection_manager
+            .get_pool_for_database(connection_id, database)
+            .await
+    }
+
+    pub async fn describe_view(&self, input: DescribeViewInput) -> DbResult<DescribeViewOutput> {
+        let database = input.database.as_deref();
+        let pool = self
+            .acquire_pool(&input.connection_id, database, "describe_view")
+            .await?;
+
+        let result = SchemaInspector::describe_view(&pool, &input.view_name, database).await;
+
+        self.connection_manager
+            .release_pool_for_database(&input.connection_id, database)
+            .await;
+
+        let view = result?;
+
+        info!(
+            connection_id = %input.connection_id,
+            view = %input.view_name,
+            columns = view.columns.len(),
+            "Described view"
+        );
+
+        Ok(view.into())
+    }
+
+    pub async fn list_routines(&self, input: ListRoutinesInput) -> DbResult<ListRoutinesOutput> {
+        let database = input.database.as_deref();
+        let pool = self
+            .acquire_pool(&input.connection_id, database, "list_routines")
+            .await?;
+
+        let result = SchemaInspector::list_routines(&pool, database).await;
+
+        self.connection_manager
+            .release_pool_for_database(&input.connection_id, database)
+            .await;
+
+        let routines = result?;
+        let count = routines.len();
+
+        info!(
+            connection_id = %input.connection_id,
+            count = count,
+            "Listed routines"
+        );
+
+        Ok(ListRoutinesOutput {
+            routines: routines.into_iter().map(Into::into).collect(),
+            count,
+        })
+    }
+
+    pub async fn describe_routine(
+        &self,
+        input: DescribeRoutineInput,
+    ) -> DbResult<DescribeRoutineOutput> {
+        let database = input.database.as_deref();
+        let pool = self
+            .acquire_pool(&input.connection_id, database, "describe_routine")
+            .await?;
+
+        let result = SchemaInspector::describe_routine(&pool, &input.routine_name, database).await;
+
+        self.connection_manager
+            .release_pool_for_database(&input.connection_id, database)
+            .await;
+
+        let routines = result?;
+
+        info!(
+            connection_id = %input.connection_id,
+            routine = %input.routine_name,
+            overloads = routines.len(),
+            "Described routine"
+        );
+
+        Ok(DescribeRoutineOutput {
+            routines: routines.into_iter().map(Into::into).collect(),
+        })
+    }
+
+    pub async fn list_triggers(&self, input: ListTriggersInput) -> DbResult<ListTriggersOutput> {
+        let database = input.database.as_deref();
+        let pool = self
+            .acquire_pool(&input.connection_id, database, "list_triggers")
+            .await?;
+
+        let result =
+            SchemaInspector::list_triggers(&pool, database, input.table_name.as_deref()).await;
+
+        self.connection_manager
+            .release_pool_for_database(&input.connection_id, database)
+            .await;
+
+        let triggers = result?;
+        let count = triggers.len();
+
+        info!(
+            connection_id = %input.connection_id,
+            count = count,
+            "Listed triggers"
+        );
+
+        Ok(ListTriggersOutput {
+            triggers: triggers.into_iter().map(Into::into).collect(),
+            count,
+        })
+    }
+
+    pub async fn list_sequences(&self, input: ListSequencesInput) -> DbResult<ListSequencesOutput> {
+        let database = input.database.as_deref();
+        let pool = self
+            .acquire_pool(&input.connection_id, database, "list_sequences")
+            .await?;
+
+        let result = SchemaInspector::list_sequences(&pool, database).await;
+
+        self.connection_manager
+            .release_pool_for_database(&input.connection_id, database)
+            .await;
+
+        let sequences = result?;
+        let count = sequences.len();
+
+        info!(
+            connection_id = %input.connection_id,
+            count = count,
+            "Listed sequences"
+        );
+
+        Ok(ListSequencesOutput {
+            sequences: sequences.into_iter().map(Into::into).collect(),
+            count,
+        })
+    }
+
     /// SQLite returns an error as it doesn't support listing databases.
     pub async fn list_databases(&self, input: ListDatabasesInput) -> DbResult<ListDatabasesOutput> {
         let config = self
@@ -416,16 +948,7 @@ impl SchemaToolHandler {
             .get_config(&input.connection_id)
             .await?;
 
-        // For server-level connections, use a system database to list all databases
-        let system_db = if config.server_level {
-            match config.db_type {
-                crate::models::DatabaseType::MySQL => Some("information_schema"),
-                crate::models::DatabaseType::PostgreSQL => Some("postgres"),
-                crate::models::DatabaseType::SQLite => None, // SQLite doesn't support list_databases
-            }
-        } else {
-            None
-        };
+        let system_db = listing_database(&config);
 
         let pool = self
             .connection_manager
@@ -461,6 +984,32 @@ impl SchemaToolHandler {
 
         Ok(ListDatabasesOutput { databases, count })
     }
+
+    pub fn refresh_schema(&self, input: RefreshSchemaInput) -> DbResult<RefreshSchemaOutput> {
+        let cache = self.connection_manager.schema_cache();
+        let cleared = cache.invalidate(&input.connection_id, input.database.as_deref());
+
+        info!(
+            connection_id = %input.connection_id,
+            database = ?input.database,
+            cleared = cleared,
+            "Refreshed schema cache"
+        );
+
+        Ok(RefreshSchemaOutput {
+            cleared,
+            cache: cache.stats(),
+        })
+    }
+}
+
+/// Join statements into a script, each terminated by a semicolon.
+fn format_script(statements: &[String]) -> String {
+    statements
+        .iter()
+        .map(|s| format!("{};", s.trim_end().trim_end_matches(';')))
+        .collect::<Vec<_>>()
+        .join("\n\n")
 }
 
 #[cfg(test)]
@@ -538,6 +1087,29 @@ mod tests {
         assert_eq!(input.database, Some("public".to_string()));
     }
 
+    #[test]
+    fn test_show_create_input_dialect() {
+        let json = r#"{"connection_id": "conn1", "table_name": "users", "dialect": "postgresql"}"#;
+        let input: ShowCreateInput = serde_json::from_str(json).unwrap();
+        assert_eq!(input.dialect, Some(DatabaseType::PostgreSQL));
+
+        let json = r#"{"connection_id": "conn1", "table_name": "users"}"#;
+        let input: ShowCreateInput = serde_json::from_str(json).unwrap();
+        assert!(input.dialect.is_none());
+    }
+
+    #[test]
+    fn test_format_script() {
+        let statements = vec![
+            "CREATE TABLE t (id INTEGER)".to_string(),
+            "CREATE INDEX i ON t (id);".to_string(),
+        ];
+        assert_eq!(
+            format_script(&statements),
+            "CREATE TABLE t (id INTEGER);\n\nCREATE INDEX i ON t (id);"
+        );
+    }
+
     #[test]
     fn test_output_serialization() {
         let output = ListTablesOutput {
@@ -0,0 +1,749 @@
+//! Schema diff tool.
+//!
+//! This module implements the `diff_schema` MCP tool, which compares the
+//! tables of two (connection_id, database) targets. The targets may use
+//! different database engines: column types and defaults are normalized via
+//! [`crate::db::ddl`] before comparison, so `INTEGER` in SQLite and `int4`
+//! in PostgreSQL are considered equal.
+//!
+//! The diff is reported from source to target: "added" objects exist only in
+//! the target, "removed" objects exist only in the source. The optional
+//! reconcile DDL, rendered in the target's dialect, makes the target match
+//! the source. It is advisory and never executed by this tool.
+
+use crate::db::ddl::{DdlRenderer, DefaultValue, PortableType};
+use crate::db::migration::MIGRATIONS_TABLE;
+use crate::db::schema::SchemaInspector;
+use crate::db::{ConnectionManager, DbPool};
+use crate::error::{DbError, DbResult};
+use crate::models::{ColumnDefinition, DatabaseType, ForeignKey, IndexInfo, TableSchema};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::sync::Arc;
+use tracing::info;
+
+/// One side of a schema comparison.
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
+pub struct SchemaTarget {
+    /// Database connection ID from list_connections
+    pub connection_id: String,
+    /// Target database name (required for server-level connections)
+    #[serde(default)]
+    pub database: Option<String>,
+}
+
+/// Input for the diff_schema tool.
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
+pub struct DiffSchemaInput {
+    /// Reference schema (the desired state)
+    pub source: SchemaTarget,
+    /// Schema compared against the source
+    pub target: SchemaTarget,
+    /// Only compare these tables. Default: all tables
+    #[serde(default)]
+    pub tables: Option<Vec<String>>,
+    /// Include DDL (in the target's dialect) that would make the target match the source
+    #[serde(default)]
+    pub include_ddl: bool,
+}
+
+/// Summary of one compared target.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct DiffTargetInfo {
+    pub connection_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub database: Option<String>,
+    /// Database engine (postgresql, mysql, sqlite)
+    pub db_type: DatabaseType,
+    /// Number of tables compared
+    pub table_count: usize,
+}
+
+/// A single attribute that differs between source and target.
+#[derive(Debug, Clone, PartialEq, Serialize, JsonSchema)]
+pub struct FieldChange {
+    /// Changed attribute: "type", "nullable" or "default"
+    pub field: String,
+    pub source: serde_json::Value,
+    pub target: serde_json::Value,
+}
+
+/// Differences for a column present on both sides.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct ColumnDiff {
+    pub column: String,
+    pub changes: Vec<FieldChange>,
+}
+
+/// Differences for a table present on both sides.
+#[derive(Debug, Clone, Default, Serialize, JsonSchema)]
+pub struct TableDiff {
+    pub table: String,
+    /// Columns only in the target
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub added_columns: Vec<String>,
+    /// Columns only in the source
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub removed_columns: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub changed_columns: Vec<ColumnDiff>,
+    /// Primary key columns, if they differ
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub primary_key: Option<FieldChange>,
+    /// Foreign keys only in the target
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub added_foreign_keys: Vec<String>,
+    /// Foreign keys only in the source
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub removed_foreign_keys: Vec<String>,
+    /// Indexes only in the target
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub added_indexes: Vec<String>,
+    /// Indexes only in the source
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub removed_indexes: Vec<String>,
+}
+
+impl TableDiff {
+    fn is_empty(&self) -> bool {
+        self.added_columns.is_empty()
+            && self.removed_columns.is_empty()
+            && self.changed_columns.is_empty()
+            && self.primary_key.is_none()
+            && self.added_foreign_keys.is_empty()
+            && self.removed_foreign_keys.is_empty()
+            && self.added_indexes.is_empty()
+            && self.removed_indexes.is_empty()
+    }
+}
+
+/// Output from the diff_schema tool.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct DiffSchemaOutput {
+    pub source: DiffTargetInfo,
+    pub target: DiffTargetInfo,
+    /// True when no differences were found
+    pub identical: bool,
+    /// Tables only in the target
+    pub added_tables: Vec<String>,
+    /// Tables only in the source
+    pub removed_tables: Vec<String>,
+    /// Tables present on both sides with differences
+    pub changed_tables: Vec<TableDiff>,
+    /// DDL in the target's dialect that makes the target match the source (when include_ddl)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reconcile_sql: Option<Vec<String>>,
+    /// Changes that cannot be expressed as DDL on the target
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+}
+
+/// Result of comparing two sets of table schemas.
+#[derive(Debug, Clone, Default)]
+pub struct SchemaDiff {
+    pub added_tables: Vec<String>,
+    pub removed_tables: Vec<String>,
+    pub changed_tables: Vec<TableDiff>,
+    pub reconcile_sql: Vec<String>,
+    pub warnings: Vec<String>,
+}
+
+/// Statements grouped by the order they must run in.
+#[derive(Default)]
+struct ReconcilePlan {
+    drop_foreign_keys: Vec<String>,
+    drop_indexes: Vec<String>,
+    create_tables: Vec<String>,
+    add_columns: Vec<String>,
+    alter_columns: Vec<String>,
+    drop_columns: Vec<String>,
+    create_indexes: Vec<String>,
+    add_foreign_keys: Vec<String>,
+    drop_tables: Vec<String>,
+}
+
+impl ReconcilePlan {
+    fn into_statements(self) -> Vec<String> {
+        [
+            self.drop_foreign_keys,
+            self.drop_indexes,
+            self.create_tables,
+            self.add_columns,
+            self.alter_columns,
+            self.drop_columns,
+            self.create_indexes,
+            self.add_foreign_keys,
+            self.drop_tables,
+        ]
+        .concat()
+    }
+}
+
+fn key(name: &str) -> String {
+    name.to_lowercase()
+}
+
+fn index_signature(index: &IndexInfo) -> String {
+    format!(
+        "{}({})",
+        if index.is_unique { "unique " } else { "" },
+        index
+            .columns
+            .iter()
+            .map(|c| key(c))
+            .collect::<Vec<_>>()
+            .join(",")
+    )
+}
+
+fn describe_index(index: &IndexInfo) -> String {
+    format!(
+        "{}{} ({})",
+        index.name,
+        if index.is_unique { " UNIQUE" } else { "" },
+        index.columns.join(", ")
+    )
+}
+
+fn foreign_key_signature(fk: &ForeignKey) -> String {
+    format!(
+        "{}->{}.{} {} {}",
+        key(&fk.column),
+        key(&fk.references_table),
+        key(&fk.references_column),
+        fk.on_delete,
+        fk.on_update
+    )
+}
+
+fn describe_foreign_key(fk: &ForeignKey) -> String {
+    format!(
+        "{} -> {}({}) ON DELETE {} ON UPDATE {}",
+        fk.column, fk.references_table, fk.references_column, fk.on_delete, fk.on_update
+    )
+}
+
+/// Primary key columns count as NOT NULL even where the engine reports otherwise
+/// (SQLite reports `notnull = 0` for most primary key columns).
+fn effective_nullable(column: &ColumnDefinition) -> bool {
+    column.nullable && !column.is_primary_key
+}
+
+fn compare_columns(
+    source: &ColumnDefinition,
+    source_type: DatabaseType,
+    target: &ColumnDefinition,
+    target_type: DatabaseType,
+) -> Vec<FieldChange> {
+    let mut changes = Vec::new();
+
+    let (source_ty, target_ty) = if source_type == target_type {
+        (
+            source.data_type.to_lowercase(),
+            target.data_type.to_lowercase(),
+        )
+    } else {
+        (
+            PortableType::parse(&source.data_type).canonical(),
+            PortableType::parse(&target.data_type).canonical(),
+        )
+    };
+    if source_ty != target_ty {
+        changes.push(FieldChange {
+            field: "type".to_string(),
+            source: source.data_type.clone().into(),
+            target: target.data_type.clone().into(),
+        });
+    }
+
+    if effective_nullable(source) != effective_nullable(target) {
+        changes.push(FieldChange {
+            field: "nullable".to_string(),
+            source: effective_nullable(source).into(),
+            target: effective_nullable(target).into(),
+        });
+    }
+
+    let source_default = DefaultValue::from_column(source, source_type).map(|d| d.canonical());
+    let target_default = DefaultValue::from_column(target, target_type).map(|d| d.canonical());
+    if source_default != target_default {
+        changes.push(FieldChange {
+            field: "default".to_string(),
+            source: source.default_value.clone().unwrap_or_default(),
+            target: target.default_value.clone().unwrap_or_default(),
+        });
+    }
+
+    changes
+}
+
+/// Compare two sets of table schemas.
+///
+/// `source_type`/`target_type` are the engines the schemas were introspected
+/// from. Reconcile statements are rendered in the target's dialect.
+pub fn diff_schemas(
+    source: &[TableSchema],
+    source_type: DatabaseType,
+    target: &[TableSchema],
+    target_type: DatabaseType,
+) -> SchemaDiff {
+    let source_map: BTreeMap<String, &TableSchema> =
+        source.iter().map(|t| (key(&t.table_name), t)).collect();
+    let target_map: BTreeMap<String, &TableSchema> =
+        target.iter().map(|t| (key(&t.table_name), t)).collect();
+
+    let renderer = DdlRenderer::new(source_type, target_type);
+    let drop_renderer = DdlRenderer::new(target_type, target_type);
+    let mut plan = ReconcilePlan::default();
+    let mut diff = SchemaDiff::default();
+
+    for (name, table) in &source_map {
+        if !target_map.contains_key(name) {
+            diff.removed_tables.push(table.table_name.clone());
+            plan.create_tables
+                .extend(renderer.create_table(table, false));
+            for fk in &table.foreign_keys {
+                if let Some(sql) = renderer.add_foreign_key(table, fk) {
+                    plan.add_foreign_keys.push(sql);
+                }
+            }
+        }
+    }
+
+    for (name, table) in &target_map {
+        if !source_map.contains_key(name) {
+            diff.added_tables.push(table.table_name.clone());
+            plan.drop_tables.push(drop_renderer.drop_table(table));
+        }
+    }
+
+    for (name, source_table) in &source_map {
+        let Some(target_table) = target_map.get(name) else {
+            continue;
+        };
+        let table_diff = diff_table(
+            source_table,
+            target_table,
+            &renderer,
+            &drop_renderer,
+            &mut plan,
+            &mut diff.warnings,
+        );
+        if !table_diff.is_empty() {
+            diff.changed_tables.push(table_diff);
+        }
+    }
+
+    diff.reconcile_sql = plan.into_statements();
+    diff
+}
+
+fn diff_table(
+    source: &TableSchema,
+    target: &TableSchema,
+    renderer: &DdlRenderer,
+    drop_renderer: &DdlRenderer,
+    plan: &mut ReconcilePlan,
+    warnings: &mut Vec<String>,
+) -> TableDiff {
+    let source_type = renderer.source();
+    let target_type = drop_renderer.target();
+    let mut table_diff = TableDiff {
+        table: source.table_name.clone(),
+        ..Default::default()
+    };
+
+    // Columns
+    for column in &source.columns {
+        match target
+            .columns
+            .iter()
+            .find(|c| key(&c.name) == key(&column.name))
+        {
+            None => {
+                table_diff.removed_columns.push(column.name.clone());
+                plan.add_columns.push(renderer.add_column(target, column));
+            }
+            Some(target_column) => {
+                let changes = compare_columns(column, source_type, target_column, target_type);
+                if changes.is_empty() {
+                    continue;
+                }
+                table_diff.changed_columns.push(ColumnDiff {
+                    column: column.name.clone(),
+                    changes,
+                });
+                match renderer.alter_column(target, column) {
+                    Some(statements) => plan.alter_columns.extend(statements),
+                    None => warnings.push(format!(
+                        "{}.{}: {} cannot alter columns in place; rebuild the table to change it",
+                        target.table_name,
+                        column.name,
+                        target_type.display_name()
+                    )),
+                }
+            }
+        }
+    }
+    for column in &target.columns {
+        if !source
+            .columns
+            .iter()
+            .any(|c| key(&c.name) == key(&column.name))
+        {
+            table_diff.added_columns.push(column.name.clone());
+            plan.drop_columns
+                .push(drop_renderer.drop_column(target, &column.name));
+        }
+    }
+
+    // Primary key
+    let source_pk: Vec<String> = source.primary_key.iter().map(|c| key(c)).collect();
+    let target_pk: Vec<String> = target.primary_key.iter().map(|c| key(c)).collect();
+    if source_pk != target_pk {
+        table_diff.primary_key = Some(FieldChange {
+            field: "primary_key".to_string(),
+            source: source.primary_key.clone().into(),
+            target: target.primary_key.clone().into(),
+        });
+        warnings.push(format!(
+            "{}: primary key differs; change it manually",
+            target.table_name
+        ));
+    }
+
+    // Foreign keys
+    let source_fks: HashSet<String> = source
+        .foreign_keys
+        .iter()
+        .map(foreign_key_signature)
+        .collect();
+    let target_fks: HashSet<String> = target
+        .foreign_keys
+        .iter()
+        .map(foreign_key_signature)
+        .collect();
+    for fk in &source.foreign_keys {
+        if !target_fks.contains(&foreign_key_signature(fk)) {
+            table_diff
+                .removed_foreign_keys
+                .push(describe_foreign_key(fk));
+            match renderer.add_foreign_key(target, fk) {
+                Some(sql) => plan.add_foreign_keys.push(sql),
+                None => warnings.push(format!(
+                    "{}: {} cannot add foreign key {} to an existing table",
+                    target.table_name,
+                    target_type.display_name(),
+                    describe_foreign_key(fk)
+                )),
+            }
+        }
+    }
+    for fk in &target.foreign_keys {
+        if !source_fks.contains(&foreign_key_signature(fk)) {
+            table_diff.added_foreign_keys.push(describe_foreign_key(fk));
+            match drop_renderer.drop_foreign_key(target, fk) {
+                Some(sql) => plan.drop_foreign_keys.push(sql),
+                None => warnings.push(format!(
+                    "{}: cannot generate DDL to drop foreign key {}",
+                    target.table_name,
+                    describe_foreign_key(fk)
+                )),
+            }
+        }
+    }
+
+    // Secondary indexes, matched by columns and uniqueness rather than name
+    let source_indexes = renderer.secondary_indexes(source);
+    let target_indexes = drop_renderer.secondary_indexes(target);
+    let source_sigs: HashSet<String> = source_indexes.iter().map(|i| index_signature(i)).collect();
+    let target_sigs: HashSet<String> = target_indexes.iter().map(|i| index_signature(i)).collect();
+    for index in source_indexes {
+        if !target_sigs.contains(&index_signature(index)) {
+            table_diff.removed_indexes.push(describe_index(index));
+            plan.create_indexes
+                .push(renderer.create_index(target, index));
+        }
+    }
+    for index in target_indexes {
+        if !source_sigs.contains(&index_signature(index)) {
+            table_diff.added_indexes.push(describe_index(index));
+            if index.name.starts_with("sqlite_autoindex") {
+                warnings.push(format!(
+                    "{}: implicit index {} belongs to a UNIQUE constraint and cannot be dropped directly",
+                    target.table_name, index.name
+                ));
+            } else {
+                plan.drop_indexes
+                    .push(drop_renderer.drop_index(target, &index.name));
+            }
+        }
+    }
+
+    table_diff
+}
+
+pub struct SchemaDiffToolHandler {
+    connection_manager: Arc<ConnectionManager>,
+}
+
+impl SchemaDiffToolHandler {
+    pub fn new(connection_manager: Arc<ConnectionManager>) -> Self {
+        Self { connection_manager }
+    }
+
+    pub async fn diff_schema(&self, input: DiffSchemaInput) -> DbResult<DiffSchemaOutput> {
+        let filter = input
+            .tables
+            .as_ref()
+            .map(|t| t.iter().map(|n| key(n)).collect::<HashSet<_>>());
+
+        let (source_type, source_tables) = self.load_tables(&input.source, filter.as_ref()).await?;
+        let (target_type, target_tables) = self.load_tables(&input.target, filter.as_ref()).await?;
+
+        let diff = diff_schemas(&source_tables, source_type, &target_tables, target_type);
+        let identical = diff.added_tables.is_empty()
+            && diff.removed_tables.is_empty()
+            && diff.changed_tables.is_empty();
+
+        info!(
+            source = %input.source.connection_id,
+            target = %input.target.connection_id,
+            added = diff.added_tables.len(),
+            removed = diff.removed_tables.len(),
+            changed = diff.changed_tables.len(),
+            "Compared schemas"
+        );
+
+        Ok(DiffSchemaOutput {
+            source: DiffTargetInfo {
+                connection_id: input.source.connection_id,
+                database: input.source.database,
+                db_type: source_type,
+                table_count: source_tables.len(),
+            },
+            target: DiffTargetInfo {
+                connection_id: input.target.connection_id,
+                database: input.target.database,
+                db_type: target_type,
+                table_count: target_tables.len(),
+            },
+            identical,
+            added_tables: diff.added_tables,
+            removed_tables: diff.removed_tables,
+            changed_tables: diff.changed_tables,
+            reconcile_sql: input.include_ddl.then_some(diff.reconcile_sql),
+            warnings: if input.include_ddl {
+                diff.warnings
+            } else {
+                Vec::new()
+            },
+        })
+    }
+
+    /// Describe all base tables of one target.
+    async fn load_tables(
+        &self,
+        target: &SchemaTarget,
+        filter: Option<&HashSet<String>>,
+    ) -> DbResult<(DatabaseType, Vec<TableSchema>)> {
+        let config = self
+            .connection_manager
+            .get_config(&target.connection_id)
+            .await?;
+
+        if config.server_level && target.database.is_none() {
+            return Err(DbError::invalid_input(format!(
+                "Connection '{}' is server-level and requires a 'database' to compare. \
+                Use list_databases to discover available databases.",
+                target.connection_id
+            )));
+        }
+
+        let database = target.database.as_deref();
+        let pool = self
+            .connection_manager
+            .get_pool_for_database(&target.connection_id, database)
+            .await?;
+
+        let result = describe_all(&pool, database, filter).await;
+
+        self.connection_manager
+            .release_pool_for_database(&target.connection_id, database)
+            .await;
+
+        Ok((config.db_type, result?))
+    }
+}
+
+/// Describe all base tables of a database, skipping the migration history table.
+pub(crate) async fn describe_all(
+    pool: &DbPool,
+    database: Option<&str>,
+    filter: Option<&HashSet<String>>,
+) -> DbResult<Vec<TableSchema>> {
+    // PostgreSQL databases are selected by the pool; tables live in "public".
+    let schema = match pool {
+        DbPool::MySql(_) => database,
+        _ => None,
+    };
+
+    let tables = SchemaInspector::list_tables(pool, schema, false).await?;
+    let mut schemas = Vec::with_capacity(tables.len());
+    for table in tables {
+        if table.name == MIGRATIONS_TABLE {
+            continue;
+        }
+        if filter.is_some_and(|f| !f.contains(&key(&table.name))) {
+            continue;
+        }
+        schemas.push(SchemaInspector::describe_table(pool, &table.name, schema).await?);
+    }
+    Ok(schemas)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn users(db: DatabaseType) -> TableSchema {
+        let (int_ty, text_ty) = match db {
+            DatabaseType::SQLite => ("INTEGER", "TEXT"),
+            DatabaseType::PostgreSQL => ("integer", "text"),
+            DatabaseType::MySQL => ("int", "text"),
+        };
+        let mut table = TableSchema::new("users").with_primary_key(vec!["id".to_string()]);
+        table.columns = vec![
+            ColumnDefinition::new("id", int_ty, db == DatabaseType::SQLite).with_primary_key(true),
+            ColumnDefinition::new("name", text_ty, false),
+        ];
+        table
+    }
+
+    #[test]
+    fn test_identical_across_dialects() {
+        let diff = diff_schemas(
+            &[users(DatabaseType::SQLite)],
+            DatabaseType::SQLite,
+            &[users(DatabaseType::PostgreSQL)],
+            DatabaseType::PostgreSQL,
+        );
+        assert!(diff.added_tables.is_empty());
+        assert!(diff.removed_tables.is_empty());
+        assert!(diff.changed_tables.is_empty(), "{:?}", diff.changed_tables);
+        assert!(diff.reconcile_sql.is_empty());
+    }
+
+    #[test]
+    fn test_added_and_removed_tables() {
+        let source = vec![users(DatabaseType::PostgreSQL)];
+        let target = vec![TableSchema::new("legacy")];
+        let diff = diff_schemas(
+            &source,
+            DatabaseType::PostgreSQL,
+            &target,
+            DatabaseType::PostgreSQL,
+        );
+        assert_eq!(diff.removed_tables, vec!["users"]);
+        assert_eq!(diff.added_tables, vec!["legacy"]);
+        assert!(diff.reconcile_sql[0].starts_with("CREATE TABLE \"users\""));
+        assert_eq!(diff.reconcile_sql.last().unwrap(), "DROP TABLE \"legacy\"");
+    }
+
+    #[test]
+    fn test_column_changes() {
+        let source = users(DatabaseType::MySQL);
+        let mut target = users(DatabaseType::MySQL);
+        target.columns[1] =
+            ColumnDefinition::new("name", "varchar(50)", true).with_default(json!("anonymous"));
+        target
+            .columns
+            .push(ColumnDefinition::new("age", "int", true));
+
+        let diff = diff_schemas(
+            &[source],
+            DatabaseType::MySQL,
+            &[target],
+            DatabaseType::MySQL,
+        );
+        let table = &diff.changed_tables[0];
+        assert_eq!(table.added_columns, vec!["age"]);
+        let fields: Vec<&str> = table.changed_columns[0]
+            .changes
+            .iter()
+            .map(|c| c.field.as_str())
+            .collect();
+        assert_eq!(fields, vec!["type", "nullable", "default"]);
+        assert!(
+            diff.reconcile_sql
+                .contains(&"ALTER TABLE `users` MODIFY COLUMN `name` text NOT NULL".to_string())
+        );
+        assert!(
+            diff.reconcile_sql
+                .contains(&"ALTER TABLE `users` DROP COLUMN `age`".to_string())
+        );
+    }
+
+    #[test]
+    fn test_index_matched_by_columns_not_name() {
+        let mut source = users(DatabaseType::PostgreSQL);
+        source.indexes =
+            vec![IndexInfo::new("users_name_key", vec!["name".to_string()]).with_unique(true)];
+        let mut target = users(DatabaseType::SQLite);
+        target.indexes = vec![
+            IndexInfo::new("sqlite_autoindex_users_1", vec!["name".to_string()]).with_unique(true),
+        ];
+
+        let diff = diff_schemas(
+            &[source],
+            DatabaseType::PostgreSQL,
+            &[target],
+            DatabaseType::SQLite,
+        );
+        assert!(diff.changed_tables.is_empty());
+    }
+
+    #[test]
+    fn test_sqlite_target_warns_on_alter_column() {
+        let source = users(DatabaseType::PostgreSQL);
+        let mut target = users(DatabaseType::SQLite);
+        target.columns[1].nullable = true;
+
+        let diff = diff_schemas(
+            &[source],
+            DatabaseType::PostgreSQL,
+            &[target],
+            DatabaseType::SQLite,
+        );
+        assert_eq!(diff.changed_tables.len(), 1);
+        assert!(diff.reconcile_sql.is_empty());
+        assert!(diff.warnings[0].contains("cannot alter columns"));
+    }
+
+    #[test]
+    fn test_foreign_key_differences() {
+        let mut source = TableSchema::new("orders");
+        source.columns = vec![ColumnDefinition::new("user_id", "integer", false)];
+        source.foreign_keys = vec![ForeignKey::new("user_id", "users", "id")];
+        let mut target = source.clone();
+        target.foreign_keys =
+            vec![ForeignKey::new("user_id", "accounts", "id").with_name("fk_acc")];
+
+        let diff = diff_schemas(
+            &[source],
+            DatabaseType::PostgreSQL,
+            &[target],
+            DatabaseType::PostgreSQL,
+        );
+        let table = &diff.changed_tables[0];
+        assert_eq!(table.removed_foreign_keys.len(), 1);
+        assert_eq!(table.added_foreign_keys.len(), 1);
+        assert_eq!(
+            diff.reconcile_sql[0],
+            "ALTER TABLE \"orders\" DROP CONSTRAINT \"fk_acc\""
+        );
+        assert!(diff.reconcile_sql[1].contains("REFERENCES \"users\""));
+    }
+}
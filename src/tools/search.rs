@@ -0,0 +1,499 @@
+//! Schema search tool.
+//!
+//! This module implements the `search_schema` MCP tool, which finds tables,
+//! views and columns matching a free-text query. The index is built on demand
+//! from [`SchemaInspector`] results and scored locally:
+//!
+//! - names and comments are split into lowercase tokens (`customerOrders`,
+//!   `customer_orders` and "Customer orders" all become `customer`, `orders`)
+//! - each query token scores against each field by exact, prefix, substring
+//!   or fuzzy (edit distance) match, with simple plural folding
+//! - name matches weigh more than comment matches, and a name equal to the
+//!   whole query scores highest
+
+use crate::db::migration::MIGRATIONS_TABLE;
+use crate::db::schema::SchemaInspector;
+use crate::db::{ConnectionManager, DbPool};
+use crate::error::{DbError, DbResult};
+use crate::models::{TableInfo, TableSchema};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tracing::info;
+
+/// Default number of hits returned.
+pub const DEFAULT_SEARCH_LIMIT: u32 = 20;
+
+/// Maximum number of hits returned.
+pub const MAX_SEARCH_LIMIT: u32 = 200;
+
+/// Weight of a comment match relative to a name match.
+const COMMENT_WEIGHT: f64 = 0.5;
+
+/// Input for the search_schema tool.
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
+pub struct SearchSchemaInput {
+    /// Database connection ID from list_connections
+    pub connection_id: String,
+    /// Words to look for in table/column names and comments (e.g. "customer email")
+    pub query: String,
+    /// Target database name (optional)
+    #[serde(default)]
+    pub database: Option<String>,
+    /// Maximum hits to return. Default: 20, max: 200
+    #[serde(default)]
+    pub limit: Option<u32>,
+    /// Also search column names and comments. Default: true
+    #[serde(default = "default_true")]
+    pub include_columns: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+/// A table, view or column matching the query.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct SchemaHit {
+    /// "table", "view", "materialized_view", ... or "column"
+    pub kind: String,
+    pub table: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub column: Option<String>,
+    /// Column type (column hits only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data_type: Option<String>,
+    /// Comment of the matched table or column
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+    /// Comment of the containing table (column hits only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub table_comment: Option<String>,
+    /// Fields that matched: "name" and/or "comment"
+    pub matched: Vec<String>,
+    pub score: f64,
+}
+
+/// Output from the search_schema tool.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct SearchSchemaOutput {
+    pub query: String,
+    /// Total matches before the limit was applied
+    pub total_matches: usize,
+    /// Tables and views searched
+    pub tables_searched: usize,
+    pub hits: Vec<SchemaHit>,
+}
+
+/// One searchable object.
+struct Entry {
+    kind: String,
+    table: String,
+    column: Option<String>,
+    data_type: Option<String>,
+    comment: Option<String>,
+    table_comment: Option<String>,
+    name_tokens: Vec<String>,
+    comment_tokens: Vec<String>,
+}
+
+/// Local scoring index over table and column names and comments.
+pub struct SchemaIndex {
+    entries: Vec<Entry>,
+    table_count: usize,
+}
+
+impl SchemaIndex {
+    /// Build the index from listed tables and, where available, their descriptions.
+    pub fn new(tables: Vec<(TableInfo, Option<TableSchema>)>) -> Self {
+        let table_count = tables.len();
+        let mut entries = Vec::new();
+
+        for (info, schema) in tables {
+            let table_comment = info
+                .comment
+                .clone()
+                .or_else(|| schema.as_ref().and_then(|s| s.comment.clone()))
+                .filter(|c| !c.is_empty());
+
+            entries.push(Entry {
+                kind: info.table_type.to_string(),
+                table: info.name.clone(),
+                column: None,
+                data_type: None,
+                comment: table_comment.clone(),
+                table_comment: None,
+                name_tokens: tokenize(&info.name),
+                comment_tokens: table_comment.as_deref().map(tokenize).unwrap_or_default(),
+            });
+
+            for column in schema.map(|s| s.columns).unwrap_or_default() {
+                let comment = column.comment.filter(|c| !c.is_empty());
+                entries.push(Entry {
+                    kind: "column".to_string(),
+                    table: info.name.clone(),
+                    name_tokens: tokenize(&column.name),
+                    comment_tokens: comment.as_deref().map(tokenize).unwrap_or_default(),
+                    column: Some(column.name),
+                    data_type: Some(column.data_type),
+                    comment,
+                    table_comment: table_comment.clone(),
+                });
+            }
+        }
+
+        Self {
+            entries,
+            table_count,
+        }
+    }
+
+    /// Number of tables and views indexed.
+    pub fn table_count(&self) -> usize {
+        self.table_count
+    }
+
+    /// All matches, best first.
+    pub fn search(&self, query: &str, include_columns: bool) -> Vec<SchemaHit> {
+        let query_tokens = tokenize(query);
+        if query_tokens.is_empty() {
+            return Vec::new();
+        }
+        let whole_query = query_tokens.join("_");
+
+        let mut hits: Vec<SchemaHit> = self
+            .entries
+            .iter()
+            .filter(|e| include_columns || e.column.is_none())
+            .filter_map(|entry| {
+                let mut name_score = 0.0;
+                let mut comment_score = 0.0;
+                for token in &query_tokens {
+                    name_score += best_token_score(token, &entry.name_tokens);
+                    comment_score += best_token_score(token, &entry.comment_tokens);
+                }
+                let n = query_tokens.len() as f64;
+                name_score /= n;
+                comment_score = comment_score / n * COMMENT_WEIGHT;
+
+                if entry.name_tokens.join("_") == whole_query {
+                    name_score += 1.0;
+                }
+
+                let mut matched = Vec::new();
+                if name_score > 0.0 {
+                    matched.push("name".to_string());
+                }
+                if comment_score > 0.0 {
+                    matched.push("comment".to_string());
+                }
+                let score = name_score.max(comment_score) + name_score.min(comment_score) * 0.25;
+                if score <= 0.0 {
+                    return None;
+                }
+
+                Some(SchemaHit {
+                    kind: entry.kind.clone(),
+                    table: entry.table.clone(),
+                    column: entry.column.clone(),
+                    data_type: entry.data_type.clone(),
+                    comment: entry.comment.clone(),
+                    table_comment: entry.table_comment.clone(),
+                    matched,
+                    score: (score * 1000.0).round() / 1000.0,
+                })
+            })
+            .collect();
+
+        hits.sort_by(|a, b| {
+            b.score
+                .total_cmp(&a.score)
+                // Tables before their columns on ties
+                .then(a.column.is_some().cmp(&b.column.is_some()))
+                .then_with(|| a.table.cmp(&b.table))
+                .then_with(|| a.column.cmp(&b.column))
+        });
+        hits
+    }
+}
+
+/// Split an identifier or text into lowercase tokens on non-alphanumerics and
+/// camelCase boundaries.
+fn tokenize(text: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    let mut prev_lower = false;
+
+    for c in text.chars() {
+        if !c.is_alphanumeric() {
+            if !current.is_empty() {
+                tokens.push(std::mem::take(&mut current));
+            }
+            prev_lower = false;
+            continue;
+        }
+        if c.is_uppercase() && prev_lower && !current.is_empty() {
+            tokens.push(std::mem::take(&mut current));
+        }
+        prev_lower = c.is_lowercase() || c.is_numeric();
+        current.extend(c.to_lowercase());
+    }
+    if !current.is_empty() {
+        tokens.push(current);
+    }
+    tokens
+}
+
+/// Fold simple English plurals: `orders` -> `order`, `categories` -> `category`.
+fn singular(token: &str) -> String {
+    if token.len() > 4 && token.ends_with("ies") {
+        format!("{}y", &token[..token.len() - 3])
+    } else if token.len() > 3 && token.ends_with('s') && !token.ends_with("ss") {
+        token[..token.len() - 1].to_string()
+    } else {
+        token.to_string()
+    }
+}
+
+/// Score of a query token against the best-matching field token (0.0 - 1.0).
+fn best_token_score(query: &str, tokens: &[String]) -> f64 {
+    tokens
+        .iter()
+        .map(|t| token_score(query, t))
+        .fold(0.0, f64::max)
+}
+
+fn token_score(query: &str, token: &str) -> f64 {
+    if query == token {
+        return 1.0;
+    }
+    let (q, t) = (singular(query), singular(token));
+    let (q, t) = (q.as_str(), t.as_str());
+    if q == t {
+        return 0.9;
+    }
+    if q.len() >= 2 && t.starts_with(q) {
+        return 0.7;
+    }
+    if q.len() >= 3 && t.contains(q) {
+        return 0.5;
+    }
+    let allowed = match q.len() {
+        0..=3 => 0,
+        4..=6 => 1,
+        _ => 2,
+    };
+    if allowed > 0 && edit_distance(q, t) <= allowed {
+        return 0.4;
+    }
+    0.0
+}
+
+/// Edit distance counting insertions, deletions, substitutions and adjacent
+/// transpositions (optimal string alignment), so `emial` is one edit from `email`.
+pub(crate) fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut d = vec![vec![0usize; b.len() + 1]; a.len() + 1];
+    for (i, row) in d.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, cell) in d[0].iter_mut().enumerate() {
+        *cell = j;
+    }
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            d[i][j] = (d[i - 1][j] + 1)
+                .min(d[i][j - 1] + 1)
+                .min(d[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                d[i][j] = d[i][j].min(d[i - 2][j - 2] + 1);
+            }
+        }
+    }
+    d[a.len()][b.len()]
+}
+
+pub struct SearchToolHandler {
+    connection_manager: Arc<ConnectionManager>,
+}
+
+impl SearchToolHandler {
+    pub fn new(connection_manager: Arc<ConnectionManager>) -> Self {
+        Self { connection_manager }
+    }
+
+    pub async fn search_schema(&self, input: SearchSchemaInput) -> DbResult<SearchSchemaOutput> {
+        if tokenize(&input.query).is_empty() {
+            return Err(DbError::invalid_input(
+                "Search query must contain at least one letter or digit",
+            ));
+        }
+
+        let config = self
+            .connection_manager
+            .get_config(&input.connection_id)
+            .await?;
+
+        // For server-level connections, database parameter is required
+        if config.server_level && input.database.is_none() {
+            return Err(DbError::invalid_input(
+                "Server-level connections require a 'database' parameter to specify which database to query. \
+                Use list_databases first to discover available databases, then call search_schema with database=<database_name>.",
+            ));
+        }
+
+        let database = input.database.as_deref();
+        let pool = self
+            .connection_manager
+            .get_pool_for_database(&input.connection_id, database)
+            .await?;
+
+        let result = load_index(&pool, database, input.include_columns).await;
+
+        self.connection_manager
+            .release_pool_for_database(&input.connection_id, database)
+            .await;
+
+        let index = result?;
+        let mut hits = index.search(&input.query, input.include_columns);
+        let total_matches = hits.len();
+        let limit = input
+            .limit
+            .unwrap_or(DEFAULT_SEARCH_LIMIT)
+            .clamp(1, MAX_SEARCH_LIMIT);
+        hits.truncate(limit as usize);
+
+        info!(
+            connection_id = %input.connection_id,
+            query = %input.query,
+            total_matches,
+            "Searched schema"
+        );
+
+        Ok(SearchSchemaOutput {
+            query: input.query,
+            total_matches,
+            tables_searched: index.table_count(),
+            hits,
+        })
+    }
+}
+
+/// List tables and views and, when columns are searched, describe each of them.
+async fn load_index(
+    pool: &DbPool,
+    database: Option<&str>,
+    include_columns: bool,
+) -> DbResult<SchemaIndex> {
+    // PostgreSQL databases are selected by the pool; tables live in "public".
+    let schema = match pool {
+        DbPool::MySql(_) => database,
+        _ => None,
+    };
+
+    let tables = SchemaInspector::list_tables(pool, schema, true).await?;
+    let mut described = Vec::with_capacity(tables.len());
+    for table in tables {
+        if table.name == MIGRATIONS_TABLE {
+            continue;
+        }
+        let columns = if include_columns {
+            // Views whose definition no longer resolves cannot be described; keep the name
+            SchemaInspector::describe_table(pool, &table.name, schema)
+                .await
+                .ok()
+        } else {
+            None
+        };
+        described.push((table, columns));
+    }
+    Ok(SchemaIndex::new(described))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{ColumnDefinition, TableType};
+
+    fn index() -> SchemaIndex {
+        let customers = TableSchema::new("customers")
+            .with_column(ColumnDefinition::new("id", "integer", false))
+            .with_column(
+                ColumnDefinition::new("emailAddress", "text", true)
+                    .with_comment("Primary contact address"),
+            );
+        let orders = TableSchema::new("customer_orders")
+            .with_column(ColumnDefinition::new("id", "integer", false))
+            .with_column(ColumnDefinition::new("shipped_at", "timestamp", true));
+        let audit = TableInfo::new("audit_log", TableType::Table)
+            .with_comment("Changes to customer records");
+
+        SchemaIndex::new(vec![
+            (
+                TableInfo::new("customers", TableType::Table),
+                Some(customers),
+            ),
+            (
+                TableInfo::new("customer_orders", TableType::View),
+                Some(orders),
+            ),
+            (audit, None),
+        ])
+    }
+
+    #[test]
+    fn test_tokenize() {
+        assert_eq!(tokenize("emailAddress"), vec!["email", "address"]);
+        assert_eq!(tokenize("customer_orders"), vec!["customer", "orders"]);
+        assert_eq!(tokenize("Order ID (UTC)"), vec!["order", "id", "utc"]);
+        assert!(tokenize(" -- ").is_empty());
+    }
+
+    #[test]
+    fn test_token_score() {
+        assert_eq!(token_score("order", "order"), 1.0);
+        assert_eq!(token_score("orders", "order"), 0.9);
+        assert_eq!(token_score("categories", "category"), 0.9);
+        assert_eq!(token_score("cust", "customer"), 0.7);
+        assert_eq!(token_score("adress", "address"), 0.4);
+        assert_eq!(token_score("emial", "email"), 0.4);
+        assert_eq!(token_score("id", "paid"), 0.0);
+    }
+
+    #[test]
+    fn test_exact_table_name_ranks_first() {
+        let hits = index().search("customers", true);
+        assert_eq!(hits[0].table, "customers");
+        assert!(hits[0].column.is_none());
+        assert_eq!(hits[0].kind, "table");
+        // Other customer-related objects follow
+        assert!(hits.iter().any(|h| h.table == "customer_orders"));
+        assert!(
+            hits.iter()
+                .any(|h| h.table == "audit_log" && h.matched == vec!["comment"])
+        );
+    }
+
+    #[test]
+    fn test_column_hits_carry_table_context() {
+        let hits = index().search("email", true);
+        assert_eq!(hits[0].column.as_deref(), Some("emailAddress"));
+        assert_eq!(hits[0].table, "customers");
+        assert_eq!(hits[0].data_type.as_deref(), Some("text"));
+
+        let hits = index().search("contact", true);
+        assert_eq!(hits[0].column.as_deref(), Some("emailAddress"));
+        assert_eq!(hits[0].matched, vec!["comment"]);
+
+        assert!(index().search("email", false).is_empty());
+    }
+
+    #[test]
+    fn test_multi_token_query() {
+        let hits = index().search("customer order", true);
+        assert_eq!(hits[0].table, "customer_orders");
+        assert_eq!(hits[0].kind, "view");
+    }
+}
@@ -13,7 +13,9 @@ use crate::error::{DbError, DbResult};
 use crate::models::DatabaseType;
 use sqlparser::ast::Statement;
 use sqlparser::dialect::{Dialect, MySqlDialect, PostgreSqlDialect, SQLiteDialect};
+use sqlparser::keywords::Keyword;
 use sqlparser::parser::Parser;
+use sqlparser::tokenizer::{Token, Tokenizer, Whitespace};
 
 /// Type of SQL statement detected by the validator.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -51,7 +53,7 @@ mod error_messages {
 }
 
 /// Get the appropriate SQL dialect for the given database type.
-fn get_dialect(db_type: DatabaseType) -> Box<dyn Dialect> {
+pub(crate) fn get_dialect(db_type: DatabaseType) -> Box<dyn Dialect> {
     match db_type {
         DatabaseType::PostgreSQL => Box::new(PostgreSqlDialect {}),
         DatabaseType::MySQL => Box::new(MySqlDialect {}),
@@ -102,6 +104,113 @@ pub fn validate_readonly(sql: &str, db_type: DatabaseType) -> DbResult<()> {
     Ok(())
 }
 
+/// Validate a caller-supplied `WHERE` condition before it is spliced into
+/// generated SQL (e.g. by `sample_table`).
+///
+/// The condition must parse as a single expression with nothing after it, so
+/// it cannot close its parentheses and continue the surrounding query.
+/// Comments are rejected since they could hide the rest of that query, and
+/// subqueries must pass the read-only check.
+pub fn validate_filter(filter: &str, db_type: DatabaseType) -> DbResult<()> {
+    let dialect = get_dialect(db_type);
+    let invalid = |e: &dyn std::fmt::Display| {
+        DbError::invalid_input(format!("Invalid filter condition. Error: {}", e))
+    };
+
+    let tokens = Tokenizer::new(dialect.as_ref(), filter)
+        .tokenize()
+        .map_err(|e| invalid(&e))?;
+    let has_comment = tokens.iter().any(|t| {
+        matches!(
+            t,
+            Token::Whitespace(
+                Whitespace::SingleLineComment { .. } | Whitespace::MultiLineComment(_)
+            )
+        )
+    });
+    if has_comment {
+        return Err(DbError::invalid_input("Filter must not contain comments"));
+    }
+
+    let mut parser = Parser::new(dialect.as_ref()).with_tokens(tokens.clone());
+    parser.parse_expr().map_err(|e| invalid(&e))?;
+    if parser.peek_token().token != Token::EOF {
+        return Err(DbError::invalid_input(
+            "Filter must be a single condition with nothing after it",
+        ));
+    }
+
+    validate_nested_statements(&tokens, dialect.as_ref())
+}
+
+/// Run the read-only check on every parenthesized statement in `tokens`,
+/// such as subqueries and CTE bodies.
+fn validate_nested_statements(tokens: &[Token], dialect: &dyn Dialect) -> DbResult<()> {
+    for (start, token) in tokens.iter().enumerate() {
+        if *token != Token::LParen {
+            continue;
+        }
+        let first = tokens[start + 1..]
+            .iter()
+            .find(|t| !matches!(t, Token::Whitespace(_)));
+        let Some(Token::Word(word)) = first else {
+            continue;
+        };
+        if !matches!(
+            word.keyword,
+            Keyword::SELECT
+                | Keyword::WITH
+                | Keyword::VALUES
+                | Keyword::TABLE
+                | Keyword::INSERT
+                | Keyword::UPDATE
+                | Keyword::DELETE
+                | Keyword::MERGE
+        ) {
+            continue;
+        }
+
+        let mut depth = 0;
+        let end = tokens[start..].iter().position(|t| {
+            match t {
+                Token::LParen => depth += 1,
+                Token::RParen => depth -= 1,
+                _ => {}
+            }
+            depth == 0
+        });
+        let Some(end) = end else {
+            continue;
+        };
+        let inner: String = tokens[start + 1..start + end]
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        let statements = Parser::parse_sql(dialect, &inner).map_err(|e| {
+            DbError::invalid_input(format!("{} Error: {}", error_messages::PARSE_ERROR, e))
+        })?;
+        for stmt in &statements {
+            validate_statement(stmt)?;
+        }
+    }
+    Ok(())
+}
+
+/// Whether the SQL may change the schema (DDL), e.g. to invalidate cached
+/// schema metadata after `execute`.
+///
+/// SQL that cannot be parsed is treated as DDL, since that is typically
+/// dialect-specific DDL such as `CREATE PROCEDURE` run with `skip_sql_check`.
+pub fn contains_ddl(sql: &str, db_type: DatabaseType) -> bool {
+    let dialect = get_dialect(db_type);
+    match Parser::parse_sql(dialect.as_ref(), sql) {
+        Ok(statements) => statements
+            .iter()
+            .any(|stmt| classify_statement(stmt).0 == SqlStatementType::Ddl),
+        Err(_) => true,
+    }
+}
+
 /// Validate a single parsed statement.
 fn validate_statement(stmt: &Statement) -> DbResult<()> {
     let (stmt_type, operation_name) = classify_statement(stmt);
@@ -375,6 +484,45 @@ mod tests {
         assert!(validate_readonly(sql, TEST_DB_TYPE).is_err());
     }
 
+    #[test]
+    fn test_contains_ddl() {
+        assert!(contains_ddl(
+            "ALTER TABLE users ADD COLUMN age INT",
+            TEST_DB_TYPE
+        ));
+        assert!(contains_ddl(
+            "INSERT INTO log VALUES (1); DROP INDEX idx_users_name",
+            TEST_DB_TYPE
+        ));
+        assert!(!contains_ddl(
+            "UPDATE users SET name = 'x' WHERE id = 1",
+            TEST_DB_TYPE
+        ));
+        assert!(contains_ddl(
+            "CREATE PROCEDURE p() BEGIN END$$",
+            TEST_DB_TYPE
+        ));
+    }
+
+    #[test]
+    fn test_validate_filter() {
+        assert!(validate_filter("age > 21 AND name LIKE 'a%'", TEST_DB_TYPE).is_ok());
+        assert!(validate_filter("id IN (SELECT user_id FROM orders)", TEST_DB_TYPE).is_ok());
+        assert!(validate_filter("1 = 1); DELETE FROM users; --", TEST_DB_TYPE).is_err());
+        assert!(validate_filter("1 = 1 -- ", TEST_DB_TYPE).is_err());
+        assert!(validate_filter("1 = 1 /* x */", TEST_DB_TYPE).is_err());
+        assert!(validate_filter("1=1) UNION SELECT 1 WHERE (1=1", TEST_DB_TYPE).is_err());
+        assert!(validate_filter("1 = 1 ORDER BY 1", TEST_DB_TYPE).is_err());
+        assert!(
+            validate_filter(
+                "id IN (WITH d AS (DELETE FROM users RETURNING id) SELECT id FROM d)",
+                TEST_DB_TYPE
+            )
+            .is_err()
+        );
+        assert!(validate_filter("", TEST_DB_TYPE).is_err());
+    }
+
     #[test]
     fn test_insert_select_blocked() {
         // INSERT ... SELECT should be blocked even though it contains SELECT
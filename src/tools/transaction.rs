@@ -200,9 +200,20 @@ impl TransactionToolHandler {
     }
 
     pub async fn commit(&self, input: CommitInput) -> DbResult<CommitOutput> {
-        self.transaction_registry
-            .commit(&input.transaction_id, &input.connection_id)
+        let schema_changed = self
+            .transaction_registry
+            .schema_changed(&input.transaction_id)
             .await?;
+        let result = self
+            .transaction_registry
+            .commit(&input.transaction_id, &input.connection_id)
+            .await;
+        if schema_changed {
+            self.connection_manager
+                .schema_cache()
+                .invalidate(&input.connection_id, None);
+        }
+        result?;
 
         info!(
             transaction_id = %input.transaction_id,
@@ -218,9 +229,20 @@ impl TransactionToolHandler {
     }
 
     pub async fn rollback(&self, input: RollbackInput) -> DbResult<RollbackOutput> {
-        self.transaction_registry
-            .rollback(&input.transaction_id, &input.connection_id)
+        let schema_changed = self
+            .transaction_registry
+            .schema_changed(&input.transaction_id)
             .await?;
+        let result = self
+            .transaction_registry
+            .rollback(&input.transaction_id, &input.connection_id)
+            .await;
+        if schema_changed {
+            self.connection_manager
+                .schema_cache()
+                .invalidate(&input.connection_id, None);
+        }
+        result?;
 
         info!(
             transaction_id = %input.transaction_id,
@@ -3,12 +3,14 @@
 //! This module implements the `execute` MCP tool for executing
 //! INSERT, UPDATE, and DELETE operations.
 
-use crate::db::{ConnectionManager, QueryExecutor, TransactionRegistry};
+use crate::db::{ConnectionManager, QueryControl, QueryExecutor, TransactionRegistry};
 use crate::error::{DbError, DbResult};
-use crate::models::{QueryParam, QueryParamInput};
+use crate::models::{DatabaseType, QueryParam, QueryParamInput};
 use crate::tools::guard::{
     DangerousOperationResult, ReadOnlyCheckResult, check_dangerous_sql, check_readonly_sql,
 };
+use crate::tools::reference_check;
+use crate::tools::sql_validator;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
@@ -63,6 +65,7 @@ pub struct WriteToolHandler {
     connection_manager: Arc<ConnectionManager>,
     transaction_registry: Arc<TransactionRegistry>,
     executor: QueryExecutor,
+    control: QueryControl,
 }
 
 impl WriteToolHandler {
@@ -74,6 +77,7 @@ impl WriteToolHandler {
             connection_manager,
             transaction_registry,
             executor: QueryExecutor::new(),
+            control: QueryControl::default(),
         }
     }
 
@@ -86,16 +90,23 @@ impl WriteToolHandler {
             connection_manager,
             transaction_registry,
             executor: QueryExecutor::with_defaults(default_timeout_secs, 100),
+            control: QueryControl::default(),
         }
     }
 
+    /// Cancel statements through `control`.
+    pub fn with_control(mut self, control: QueryControl) -> Self {
+        self.control = control;
+        self
+    }
+
     pub async fn execute(&self, input: ExecuteInput) -> DbResult<ExecuteOutput> {
-        let is_writable = self
+        let config = self
             .connection_manager
-            .is_writable(&input.connection_id)
+            .get_config(&input.connection_id)
             .await?;
 
-        if !is_writable {
+        if !config.writable {
             return Err(DbError::permission(
                 "write operation",
                 "Connection is not writable. Use ?writable=true in the connection URL to enable writes",
@@ -118,14 +129,34 @@ impl WriteToolHandler {
             }
         }
 
+        // Schema changes make cached list_tables/describe_table results stale
+        let is_ddl = sql_validator::contains_ddl(&input.sql, config.db_type);
+
         let params: Vec<QueryParam> = input.params.into_iter().map(Into::into).collect();
+        let timeout = input.timeout_secs.map(|t| Duration::from_secs(t as u64));
 
         if let Some(ref tx_id) = input.transaction_id {
             let start = std::time::Instant::now();
-            let rows_affected = self
+            let result = self
                 .transaction_registry
-                .execute_in_transaction(tx_id, &input.connection_id, &input.sql, &params)
-                .await?;
+                .execute_in_transaction(
+                    tx_id,
+                    &input.connection_id,
+                    &input.sql,
+                    &params,
+                    timeout.unwrap_or(self.executor.default_timeout()),
+                    &self.control,
+                )
+                .await;
+            if is_ddl {
+                // Other sessions see the old schema until the transaction
+                // ends, except on MySQL, where DDL commits implicitly
+                self.transaction_registry.mark_schema_changed(tx_id).await?;
+                if config.db_type == DatabaseType::MySQL {
+                    self.invalidate_schema_cache(&input.connection_id);
+                }
+            }
+            let rows_affected = result?;
             let execution_time_ms = start.elapsed().as_millis() as u64;
 
             info!(
@@ -143,20 +174,35 @@ impl WriteToolHandler {
         }
 
         let database = input.database.as_deref();
-        let pool = self
+        let guard = self
             .connection_manager
-            .get_pool_for_database(&input.connection_id, database)
+            .get_pool_for_database_guarded(&input.connection_id, database)
             .await?;
-        let timeout = input.timeout_secs.map(|t| Duration::from_secs(t as u64));
-        let result = self
-            .executor
-            .execute_write(&pool, &input.sql, &params, timeout)
-            .await;
+        let pool = guard.pool();
+        let result = async {
+            if !input.skip_sql_check {
+                reference_check::check_references(
+                    &self.connection_manager,
+                    &input.connection_id,
+                    database,
+                    pool,
+                    &input.sql,
+                    timeout.unwrap_or(self.executor.default_timeout()),
+                )
+                .await?;
+            }
+            self.executor
+                .execute_write_controlled(pool, &input.sql, &params, timeout, &self.control)
+                .await
+        }
+        .await;
 
-        self.connection_manager
-            .release_pool_for_database(&input.connection_id, database)
-            .await;
+        guard.release().await;
 
+        // Even a failed multi-statement script may have applied some DDL
+        if is_ddl {
+            self.invalidate_schema_cache(&input.connection_id);
+        }
         let (rows_affected, execution_time_ms) = result?;
 
         info!(
@@ -171,6 +217,14 @@ impl WriteToolHandler {
             execution_time_ms,
         })
     }
+
+    /// Drop cached schema metadata for every database of the connection, since
+    /// DDL may name tables in databases other than the target one.
+    fn invalidate_schema_cache(&self, connection_id: &str) {
+        self.connection_manager
+            .schema_cache()
+            .invalidate(connection_id, None);
+    }
 }
 
 #[cfg(test)]
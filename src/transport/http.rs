@@ -3,16 +3,19 @@
 //! This transport uses HTTP with SSE streaming responses,
 //! which is suitable for web-based MCP integrations.
 
-use crate::auth::{AuthConfig, auth_middleware};
+use crate::auth::{AuthConfig, SharedAuthConfig, auth_middleware};
 use crate::db::{ConnectionManager, TransactionRegistry};
 use crate::error::DbResult;
 use crate::mcp::DbService;
+use crate::mcp::logging::LogForwarder;
+use crate::metrics::{METRICS_PATH, MetricsEndpoint};
+use crate::tools::admin::AdminPolicy;
 use crate::transport::Transport;
 use axum::middleware;
-use tower::util::option_layer;
 use rmcp::transport::streamable_http_server::{
     StreamableHttpService, session::local::LocalSessionManager,
 };
+use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::net::TcpListener;
@@ -39,7 +42,12 @@ pub struct HttpTransport {
     endpoint: String,
     query_timeout_secs: u64,
     row_limit: u32,
-    auth_config: Arc<AuthConfig>,
+    auth_config: SharedAuthConfig,
+    migrations_dir: Option<PathBuf>,
+    prompts_dir: Option<PathBuf>,
+    log_forwarder: Option<LogForwarder>,
+    admin_policy: Option<AdminPolicy>,
+    metrics: Option<MetricsEndpoint>,
 }
 
 impl HttpTransport {
@@ -67,7 +75,12 @@ impl HttpTransport {
             endpoint: endpoint.into(),
             query_timeout_secs: DEFAULT_QUERY_TIMEOUT_SECS,
             row_limit: DEFAULT_ROW_LIMIT,
-            auth_config: Arc::new(AuthConfig::default()),
+            auth_config: SharedAuthConfig::default(),
+            migrations_dir: None,
+            prompts_dir: None,
+            log_forwarder: None,
+            admin_policy: None,
+            metrics: None,
         }
     }
 
@@ -91,10 +104,50 @@ impl HttpTransport {
             endpoint: endpoint.into(),
             query_timeout_secs,
             row_limit,
-            auth_config: Arc::new(auth_config),
+            auth_config: SharedAuthConfig::new(auth_config),
+            migrations_dir: None,
+            prompts_dir: None,
+            log_forwarder: None,
+            admin_policy: None,
+            metrics: None,
         }
     }
 
+    /// Set the directory used by the migration tools.
+    pub fn with_migrations_dir(mut self, migrations_dir: Option<PathBuf>) -> Self {
+        self.migrations_dir = migrations_dir;
+        self
+    }
+
+    /// Set the directory of additional prompt templates.
+    pub fn with_prompts_dir(mut self, prompts_dir: Option<PathBuf>) -> Self {
+        self.prompts_dir = prompts_dir;
+        self
+    }
+
+    /// Set the source of the log messages forwarded to clients.
+    pub fn with_log_forwarder(mut self, log_forwarder: Option<LogForwarder>) -> Self {
+        self.log_forwarder = log_forwarder;
+        self
+    }
+
+    /// Enable the connection administration tools, restricted by `policy`.
+    pub fn with_admin_policy(mut self, admin_policy: Option<AdminPolicy>) -> Self {
+        self.admin_policy = admin_policy;
+        self
+    }
+
+    /// Serve Prometheus metrics at `/metrics`, behind the same authentication.
+    pub fn with_metrics(mut self, metrics: Option<MetricsEndpoint>) -> Self {
+        self.metrics = metrics;
+        self
+    }
+
+    /// Handle for replacing the authentication configuration while running.
+    pub fn auth_config(&self) -> SharedAuthConfig {
+        self.auth_config.clone()
+    }
+
     /// Get the bind address.
     pub fn bind_addr(&self) -> String {
         format!("{}:{}", self.host, self.port)
@@ -115,6 +168,10 @@ impl Transport for HttpTransport {
         let transaction_registry = self.transaction_registry.clone();
         let query_timeout_secs = self.query_timeout_secs;
         let row_limit = self.row_limit;
+        let migrations_dir = self.migrations_dir.clone();
+        let prompts_dir = self.prompts_dir.clone();
+        let log_forwarder = self.log_forwarder.clone();
+        let admin_policy = self.admin_policy.clone();
 
         let service = StreamableHttpService::new(
             move || {
@@ -123,27 +180,37 @@ impl Transport for HttpTransport {
                     transaction_registry.clone(),
                     query_timeout_secs,
                     row_limit,
-                ))
+                )
+                .with_migrations_dir(migrations_dir.clone())
+                .with_prompts_dir(prompts_dir.clone())
+                .with_log_forwarder(log_forwarder.clone())
+                .with_admin_policy(admin_policy.clone()))
             },
             LocalSessionManager::default().into(),
             Default::default(),
         );
 
-        let auth_layer = self.auth_config.is_enabled().then(|| {
+        // Installed even without tokens: a configuration reload can add them
+        let auth = self.auth_config.current();
+        if auth.is_enabled() {
             info!(
-                token_count = self.auth_config.token_count(),
+                token_count = auth.token_count(),
                 "Authentication enabled for HTTP transport"
             );
-            middleware::from_fn_with_state(self.auth_config.clone(), auth_middleware)
-        });
+        }
+        let auth_layer = middleware::from_fn_with_state(self.auth_config.clone(), auth_middleware);
 
         // nest_service doesn't support root path "/", use fallback_service instead
-        let app = if self.endpoint == "/" {
+        let mut app = if self.endpoint == "/" {
             axum::Router::new().fallback_service(service)
         } else {
             axum::Router::new().nest_service(&self.endpoint, service)
+        };
+        if let Some(metrics) = self.metrics.clone() {
+            info!(path = METRICS_PATH, "Metrics endpoint enabled");
+            app = app.merge(metrics.router());
         }
-        .layer(option_layer(auth_layer));
+        let app = app.layer(auth_layer);
 
         let listener = TcpListener::bind(&bind_addr).await.map_err(|e| {
             crate::error::DbError::connection(
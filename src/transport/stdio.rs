@@ -12,8 +12,11 @@
 use crate::db::{ConnectionManager, TransactionRegistry};
 use crate::error::DbResult;
 use crate::mcp::DbService;
+use crate::mcp::logging::LogForwarder;
+use crate::tools::admin::AdminPolicy;
 use crate::transport::Transport;
 use rmcp::{ServiceExt, transport::stdio};
+use std::path::PathBuf;
 use std::sync::Arc;
 use tokio::signal;
 use tracing::info;
@@ -32,6 +35,10 @@ pub struct StdioTransport {
     transaction_registry: Arc<TransactionRegistry>,
     query_timeout_secs: u64,
     row_limit: u32,
+    migrations_dir: Option<PathBuf>,
+    prompts_dir: Option<PathBuf>,
+    log_forwarder: Option<LogForwarder>,
+    admin_policy: Option<AdminPolicy>,
 }
 
 impl StdioTransport {
@@ -50,6 +57,10 @@ impl StdioTransport {
             transaction_registry,
             query_timeout_secs: DEFAULT_QUERY_TIMEOUT_SECS,
             row_limit: DEFAULT_ROW_LIMIT,
+            migrations_dir: None,
+            prompts_dir: None,
+            log_forwarder: None,
+            admin_policy: None,
         }
     }
 
@@ -65,8 +76,36 @@ impl StdioTransport {
             transaction_registry,
             query_timeout_secs,
             row_limit,
+            migrations_dir: None,
+            prompts_dir: None,
+            log_forwarder: None,
+            admin_policy: None,
         }
     }
+
+    /// Set the directory used by the migration tools.
+    pub fn with_migrations_dir(mut self, migrations_dir: Option<PathBuf>) -> Self {
+        self.migrations_dir = migrations_dir;
+        self
+    }
+
+    /// Set the directory of additional prompt templates.
+    pub fn with_prompts_dir(mut self, prompts_dir: Option<PathBuf>) -> Self {
+        self.prompts_dir = prompts_dir;
+        self
+    }
+
+    /// Set the source of the log messages forwarded to clients.
+    pub fn with_log_forwarder(mut self, log_forwarder: Option<LogForwarder>) -> Self {
+        self.log_forwarder = log_forwarder;
+        self
+    }
+
+    /// Enable the connection administration tools, restricted by `policy`.
+    pub fn with_admin_policy(mut self, admin_policy: Option<AdminPolicy>) -> Self {
+        self.admin_policy = admin_policy;
+        self
+    }
 }
 
 impl Transport for StdioTransport {
@@ -78,7 +117,12 @@ impl Transport for StdioTransport {
             self.transaction_registry.clone(),
             self.query_timeout_secs,
             self.row_limit,
-        );
+        )
+        .with_migrations_dir(self.migrations_dir.clone())
+        .with_prompts_dir(self.prompts_dir.clone())
+        .with_log_forwarder(self.log_forwarder.clone())
+        .with_admin_policy(self.admin_policy.clone());
+        let session = service.clone();
 
         let transport = stdio();
         let running_service = service.serve(transport).await.map_err(|e| {
@@ -116,6 +160,8 @@ impl Transport for StdioTransport {
             });
         }
 
+        // Roll back transactions the client left open
+        session.close_session().await;
         self.connection_manager.close_all().await;
 
         if shutdown_requested {
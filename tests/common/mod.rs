@@ -0,0 +1,62 @@
+//! SQLite fixtures shared by the integration tests.
+
+// Each test binary compiles this module and uses only some of it.
+#![allow(dead_code)]
+
+use db_mcp_server::config::PoolOptions;
+use db_mcp_server::db::{ConnectionManager, DbPool};
+use db_mcp_server::models::ConnectionConfig;
+use sqlx::SqlitePool;
+use std::path::Path;
+use std::sync::Arc;
+use tempfile::NamedTempFile;
+
+/// Connect `id` to a fresh SQLite database and run `sql` against it.
+///
+/// The database file is deleted when the returned handle is dropped, so keep
+/// it alive for as long as the connection is used.
+pub async fn connect_sqlite(
+    manager: &ConnectionManager,
+    id: &str,
+    writable: bool,
+    sql: &str,
+) -> NamedTempFile {
+    let temp_file = NamedTempFile::new().expect("Failed to create temp file");
+    connect_sqlite_at(manager, id, temp_file.path(), writable, sql).await;
+    temp_file
+}
+
+/// Connect `id` to the SQLite database at `path`, creating it if needed, and
+/// run `sql` against it.
+pub async fn connect_sqlite_at(
+    manager: &ConnectionManager,
+    id: &str,
+    path: &Path,
+    writable: bool,
+    sql: &str,
+) {
+    let url = format!("sqlite:{}?mode=rwc", path.display());
+    let config = ConnectionConfig::new(id, &url, writable, false, None, PoolOptions::default())
+        .expect("Failed to create config");
+    manager.connect(config).await.expect("Failed to connect");
+
+    if !sql.is_empty() {
+        let pool = sqlite_pool(manager, id).await;
+        sqlx::raw_sql(sql).execute(&pool).await.unwrap();
+    }
+}
+
+/// A manager holding a single writable SQLite connection `id` set up with `sql`.
+pub async fn setup(id: &str, sql: &str) -> (Arc<ConnectionManager>, NamedTempFile) {
+    let manager = Arc::new(ConnectionManager::new());
+    let temp_file = connect_sqlite(&manager, id, true, sql).await;
+    (manager, temp_file)
+}
+
+/// The SQLite pool behind connection `id`.
+pub async fn sqlite_pool(manager: &ConnectionManager, id: &str) -> SqlitePool {
+    let DbPool::SQLite(pool) = manager.get_pool(id).await.unwrap() else {
+        panic!("expected SQLite pool");
+    };
+    pool
+}
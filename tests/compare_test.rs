@@ -0,0 +1,116 @@
+//! Integration tests for the compare_queries tool.
+
+mod common;
+
+use db_mcp_server::db::ConnectionManager;
+use db_mcp_server::error::DbError;
+use db_mcp_server::tools::{CompareQueriesInput, CompareToolHandler, QueryTarget};
+use serde_json::json;
+use std::sync::Arc;
+use tempfile::NamedTempFile;
+
+const SOURCE: &str = "source_db";
+const TARGET: &str = "target_db";
+
+async fn setup() -> (CompareToolHandler, Vec<NamedTempFile>) {
+    let manager = Arc::new(ConnectionManager::new());
+    let source = common::connect_sqlite(
+        &manager,
+        SOURCE,
+        true,
+        r#"
+        CREATE TABLE users (id INTEGER PRIMARY KEY, name TEXT, score REAL);
+        INSERT INTO users VALUES (1, 'alice', 10.0), (2, 'bob', 20.0), (3, 'carol', 30.0);
+        "#,
+    )
+    .await;
+    let target = common::connect_sqlite(
+        &manager,
+        TARGET,
+        true,
+        r#"
+        CREATE TABLE users (id INTEGER PRIMARY KEY, name TEXT, score REAL);
+        INSERT INTO users VALUES (1, 'alice', 10.0), (2, 'bob', 25.0), (4, 'dave', 40.0);
+        "#,
+    )
+    .await;
+    (CompareToolHandler::new(manager), vec![source, target])
+}
+
+fn input(source_sql: &str, target_sql: &str) -> CompareQueriesInput {
+    CompareQueriesInput {
+        source: QueryTarget {
+            connection_id: SOURCE.to_string(),
+            sql: source_sql.to_string(),
+            params: vec![],
+            database: None,
+        },
+        target: QueryTarget {
+            connection_id: TARGET.to_string(),
+            sql: target_sql.to_string(),
+            params: vec![],
+            database: None,
+        },
+        key_columns: vec!["id".to_string()],
+        max_rows: None,
+        max_differences: None,
+        timeout_secs: None,
+    }
+}
+
+#[tokio::test]
+async fn test_compare_across_connections() {
+    let (handler, _dbs) = setup().await;
+    let sql = "SELECT id, name, score FROM users ORDER BY id";
+
+    let output = handler.compare_queries(input(sql, sql)).await.unwrap();
+
+    assert!(!output.identical);
+    assert_eq!((output.source_rows, output.target_rows), (3, 3));
+    assert!(!output.truncated);
+    assert_eq!(output.unchanged_count, 1);
+    assert_eq!(output.added_count, 1);
+    assert_eq!(output.added[0]["name"], json!("dave"));
+    assert_eq!(output.removed_count, 1);
+    assert_eq!(output.removed[0]["name"], json!("carol"));
+    assert_eq!(output.changed_count, 1);
+    assert_eq!(output.changed[0].key["id"], json!(2));
+    assert_eq!(output.changed[0].changes.len(), 1);
+    assert_eq!(output.changed[0].changes[0].column, "score");
+}
+
+#[tokio::test]
+async fn test_identical_results_and_limits() {
+    let (handler, _dbs) = setup().await;
+    let sql = "SELECT id, name FROM users WHERE id <= 2 ORDER BY id";
+
+    let output = handler.compare_queries(input(sql, sql)).await.unwrap();
+    assert!(output.identical);
+    assert_eq!(output.unchanged_count, 2);
+
+    let sql = "SELECT id, name, score FROM users ORDER BY id";
+    let mut limited = input(sql, sql);
+    limited.max_rows = Some(2);
+    limited.max_differences = Some(0);
+    let output = handler.compare_queries(limited).await.unwrap();
+    assert!(output.truncated);
+    assert_eq!(output.changed_count, 1);
+    assert!(output.changed.is_empty());
+}
+
+#[tokio::test]
+async fn test_rejects_writes_and_missing_keys() {
+    let (handler, _dbs) = setup().await;
+
+    let err = handler
+        .compare_queries(input("DELETE FROM users", "SELECT id FROM users"))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, DbError::Permission { .. }));
+
+    let err = handler
+        .compare_queries(input("SELECT name FROM users", "SELECT name FROM users"))
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("Key column 'id'"));
+}
@@ -0,0 +1,126 @@
+//! Integration tests for MCP argument completions.
+
+mod common;
+
+use db_mcp_server::db::ConnectionManager;
+use db_mcp_server::mcp::completions::CompletionProvider;
+use rmcp::model::{ArgumentInfo, CompleteRequestParam, CompletionContext, Reference};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tempfile::NamedTempFile;
+
+async fn setup() -> (Arc<ConnectionManager>, Vec<NamedTempFile>) {
+    let manager = Arc::new(ConnectionManager::new());
+    let analytics = common::connect_sqlite(&manager, "analytics", true, "").await;
+    let app = common::connect_sqlite(
+        &manager,
+        "app",
+        true,
+        r#"
+        CREATE TABLE users (id INTEGER PRIMARY KEY, email TEXT, email_verified INTEGER);
+        CREATE TABLE user_roles (user_id INTEGER, role TEXT);
+        CREATE TABLE orders (id INTEGER PRIMARY KEY);
+        "#,
+    )
+    .await;
+    (manager, vec![analytics, app])
+}
+
+fn request(
+    reference: Reference,
+    name: &str,
+    value: &str,
+    context: &[(&str, &str)],
+) -> CompleteRequestParam {
+    let arguments: HashMap<String, String> = context
+        .iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect();
+    CompleteRequestParam {
+        r#ref: reference,
+        argument: ArgumentInfo {
+            name: name.to_string(),
+            value: value.to_string(),
+        },
+        context: Some(CompletionContext::with_arguments(arguments)),
+    }
+}
+
+fn prompt() -> Reference {
+    Reference::for_prompt("summarize_table")
+}
+
+#[tokio::test]
+async fn test_completes_connections_tables_and_columns() {
+    let (manager, _files) = setup().await;
+    let provider = CompletionProvider::new(manager);
+
+    let info = provider
+        .complete(&request(prompt(), "connection_id", "a", &[]))
+        .await;
+    assert_eq!(info.values, vec!["analytics", "app"]);
+
+    let info = provider
+        .complete(&request(
+            prompt(),
+            "table",
+            "us",
+            &[("connection_id", "app")],
+        ))
+        .await;
+    assert_eq!(info.values, vec!["user_roles", "users"]);
+
+    let info = provider
+        .complete(&request(
+            prompt(),
+            "column",
+            "EMAIL",
+            &[("connection_id", "app"), ("table", "users")],
+        ))
+        .await;
+    assert_eq!(info.values, vec!["email", "email_verified"]);
+}
+
+#[tokio::test]
+async fn test_missing_context_and_unknown_arguments_yield_nothing() {
+    let (manager, _files) = setup().await;
+    let provider = CompletionProvider::new(manager);
+
+    for req in [
+        request(prompt(), "table", "", &[]),
+        request(prompt(), "table", "", &[("connection_id", "missing")]),
+        request(prompt(), "column", "", &[("connection_id", "app")]),
+        request(prompt(), "sql", "SEL", &[("connection_id", "app")]),
+    ] {
+        let info = provider.complete(&req).await;
+        assert!(info.values.is_empty(), "{:?}", req.argument);
+    }
+}
+
+#[tokio::test]
+async fn test_resource_template_database_and_cache() {
+    let (manager, _files) = setup().await;
+    let provider = CompletionProvider::new(manager.clone());
+    let resource = || Reference::for_resource("db://{connection_id}/{database}/{table}");
+
+    let info = provider
+        .complete(&request(
+            resource(),
+            "database",
+            "",
+            &[("connection_id", "app")],
+        ))
+        .await;
+    assert_eq!(info.values, vec!["default"]);
+
+    let context = [("connection_id", "app"), ("database", "default")];
+    provider
+        .complete(&request(resource(), "table", "o", &context))
+        .await;
+    let hits = manager.schema_cache().stats().hits;
+    let info = provider
+        .complete(&request(resource(), "table", "or", &context))
+        .await;
+    assert_eq!(info.values, vec!["orders"]);
+    assert_eq!(manager.schema_cache().stats().hits, hits + 1);
+}
@@ -0,0 +1,132 @@
+//! Integration tests for the diff_table_data tool.
+
+mod common;
+
+use db_mcp_server::db::ConnectionManager;
+use db_mcp_server::tools::{
+    ChecksumMode, DataDiffToolHandler, DiffTableDataInput, DiffTableDataOutput, TableTarget,
+};
+use serde_json::json;
+use std::sync::Arc;
+use tempfile::NamedTempFile;
+
+const SOURCE: &str = "source_db";
+const TARGET: &str = "target_db";
+
+const SCHEMA: &str = r#"
+CREATE TABLE items (id INTEGER PRIMARY KEY, sku TEXT UNIQUE NOT NULL, qty INTEGER, note TEXT);
+WITH RECURSIVE n(i) AS (SELECT 1 UNION ALL SELECT i + 1 FROM n WHERE i < 2000)
+INSERT INTO items SELECT i, printf('sku-%05d', i), i % 7, NULL FROM n;
+"#;
+
+/// Target differs from the source in three rows: one changed, one removed, one added.
+async fn setup() -> (DataDiffToolHandler, Vec<NamedTempFile>) {
+    let manager = Arc::new(ConnectionManager::new());
+    let source = common::connect_sqlite(&manager, SOURCE, true, SCHEMA).await;
+    let target = common::connect_sqlite(&manager, TARGET, true, SCHEMA).await;
+    let pool = common::sqlite_pool(&manager, TARGET).await;
+    sqlx::raw_sql(
+        "UPDATE items SET qty = 100 WHERE id = 777;
+         DELETE FROM items WHERE id = 1500;
+         INSERT INTO items VALUES (5000, 'sku-zzz', 1, 'new');",
+    )
+    .execute(&pool)
+    .await
+    .unwrap();
+    (DataDiffToolHandler::new(manager), vec![source, target])
+}
+
+fn input(leaf_rows: u32) -> DiffTableDataInput {
+    DiffTableDataInput {
+        source: TableTarget {
+            connection_id: SOURCE.to_string(),
+            table: "items".to_string(),
+            database: None,
+        },
+        target: TableTarget {
+            connection_id: TARGET.to_string(),
+            table: "items".to_string(),
+            database: None,
+        },
+        key_column: None,
+        columns: None,
+        bisection_factor: Some(4),
+        leaf_rows: Some(leaf_rows),
+        max_compared_rows: None,
+        max_differences: None,
+        timeout_secs: None,
+    }
+}
+
+fn assert_differences(output: &DiffTableDataOutput) {
+    assert!(!output.identical);
+    assert!(output.complete);
+    assert_eq!((output.source_rows, output.target_rows), (2000, 2000));
+    assert_eq!(
+        (
+            output.added_count,
+            output.removed_count,
+            output.changed_count
+        ),
+        (1, 1, 1)
+    );
+    assert_eq!(output.added[0]["sku"], json!("sku-zzz"));
+    assert_eq!(output.removed[0]["id"], json!(1500));
+    assert_eq!(output.changed[0].changes[0].column, "qty");
+    assert_eq!(output.changed[0].changes[0].target, json!(100));
+}
+
+#[tokio::test]
+async fn test_narrows_down_to_differing_rows() {
+    let (handler, _dbs) = setup().await;
+
+    let output = handler.diff_table_data(input(50)).await.unwrap();
+
+    assert_eq!(output.key_column, "id");
+    assert_eq!(output.checksum_mode, ChecksumMode::Client);
+    assert_differences(&output);
+    assert!(output.chunks_checked > 1);
+    // Only the ranges around the three differences (out of 4000 rows) are
+    // compared row by row
+    assert!(output.rows_compared < 1000, "{}", output.rows_compared);
+}
+
+#[tokio::test]
+async fn test_text_key_and_column_filter() {
+    let (handler, _dbs) = setup().await;
+
+    let mut text_key = input(50);
+    text_key.key_column = Some("sku".to_string());
+    let output = handler.diff_table_data(text_key).await.unwrap();
+    assert_eq!(output.key_column, "sku");
+    assert_differences(&output);
+
+    let mut filtered = input(50);
+    filtered.columns = Some(vec!["note".to_string()]);
+    let output = handler.diff_table_data(filtered).await.unwrap();
+    assert_eq!(output.compared_columns, vec!["id", "note"]);
+    assert_eq!(output.changed_count, 0);
+    assert_eq!((output.added_count, output.removed_count), (1, 1));
+}
+
+#[tokio::test]
+async fn test_identical_tables_and_budget() {
+    let (handler, _dbs) = setup().await;
+
+    let mut same = input(50);
+    same.target.connection_id = SOURCE.to_string();
+    let output = handler.diff_table_data(same).await.unwrap();
+    assert!(output.identical);
+    assert_eq!(output.chunks_checked, 1);
+    assert_eq!(output.rows_compared, 0);
+
+    let mut limited = input(50);
+    limited.max_compared_rows = Some(10);
+    let output = handler.diff_table_data(limited).await.unwrap();
+    assert!(!output.complete);
+    assert!(!output.identical);
+
+    let mut bad_key = input(50);
+    bad_key.key_column = Some("missing".to_string());
+    assert!(handler.diff_table_data(bad_key).await.is_err());
+}
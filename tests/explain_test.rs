@@ -1,48 +1,22 @@
 //! Integration tests for the explain tool.
 
-use db_mcp_server::config::PoolOptions;
+mod common;
+
 use db_mcp_server::db::{ConnectionManager, TransactionRegistry};
-use db_mcp_server::models::ConnectionConfig;
-use db_mcp_server::tools::{ExplainInput, ExplainOutput, ExplainToolHandler};
+use db_mcp_server::error::DbError;
+use db_mcp_server::tools::{ExplainInput, ExplainOutput, ExplainToolHandler, FindingKind};
 use std::sync::Arc;
 use tempfile::NamedTempFile;
 
 /// Create a test SQLite database with a sample table.
 async fn create_test_connection_manager() -> (Arc<ConnectionManager>, String, NamedTempFile) {
-    let temp_file = NamedTempFile::new().expect("Failed to create temp file");
-    let db_path = temp_file.path().to_str().unwrap();
     let connection_id = "test_db";
-    let url = format!("sqlite:{}?mode=rwc", db_path);
-
-    let manager = Arc::new(ConnectionManager::new());
-    let config = ConnectionConfig::new(
+    let (manager, temp_file) = common::setup(
         connection_id,
-        &url,
-        true,  // writable
-        false, // not server_level
-        None,
-        PoolOptions::default(),
+        "CREATE TABLE IF NOT EXISTS users (id INTEGER PRIMARY KEY, name TEXT, email TEXT);
+         CREATE INDEX IF NOT EXISTS idx_users_email ON users(email);",
     )
-    .expect("Failed to create config");
-    manager
-        .connect(config)
-        .await
-        .expect("Failed to connect to test database");
-
-    // Create a sample table for testing
-    let pool = manager.get_pool(connection_id).await.unwrap();
-    if let db_mcp_server::db::DbPool::SQLite(p) = pool {
-        sqlx::query(
-            "CREATE TABLE IF NOT EXISTS users (id INTEGER PRIMARY KEY, name TEXT, email TEXT)",
-        )
-        .execute(&p)
-        .await
-        .expect("Failed to create test table");
-        sqlx::query("CREATE INDEX IF NOT EXISTS idx_users_email ON users(email)")
-            .execute(&p)
-            .await
-            .expect("Failed to create index");
-    }
+    .await;
 
     (manager, connection_id.to_string(), temp_file)
 }
@@ -61,6 +35,7 @@ async fn test_explain_simple_select() {
         timeout_secs: None,
         format: Default::default(),
         database: None,
+        analyze: false,
     };
 
     let result = handler.explain(input).await;
@@ -87,6 +62,7 @@ async fn test_explain_select_with_where() {
         timeout_secs: None,
         format: Default::default(),
         database: None,
+        analyze: false,
     };
 
     let result = handler.explain(input).await;
@@ -113,6 +89,7 @@ async fn test_explain_with_parameters() {
         timeout_secs: None,
         format: Default::default(),
         database: None,
+        analyze: false,
     };
 
     let result = handler.explain(input).await;
@@ -134,6 +111,7 @@ async fn test_explain_insert_statement() {
         timeout_secs: None,
         format: Default::default(),
         database: None,
+        analyze: false,
     };
 
     let result = handler.explain(input).await;
@@ -158,6 +136,7 @@ async fn test_explain_invalid_sql() {
         timeout_secs: None,
         format: Default::default(),
         database: None,
+        analyze: false,
     };
 
     let result = handler.explain(input).await;
@@ -178,6 +157,7 @@ async fn test_explain_empty_sql_returns_error() {
         timeout_secs: None,
         format: Default::default(),
         database: None,
+        analyze: false,
     };
 
     let result = handler.explain(input).await;
@@ -198,6 +178,7 @@ async fn test_explain_invalid_connection_returns_error() {
         timeout_secs: None,
         format: Default::default(),
         database: None,
+        analyze: false,
     };
 
     let result = handler.explain(input).await;
@@ -218,6 +199,7 @@ async fn test_explain_output_has_execution_time() {
         timeout_secs: None,
         format: Default::default(),
         database: None,
+        analyze: false,
     };
 
     let result = handler.explain(input).await;
@@ -238,8 +220,12 @@ async fn test_explain_output_serialization() {
             map
         }],
         sql: "SELECT * FROM users".to_string(),
+        tree: None,
+        summary: None,
+        findings: vec![],
         formatted: None,
         execution_time_ms: 5,
+        analyzed: false,
     };
 
     let json = serde_json::to_string(&output).unwrap();
@@ -262,6 +248,7 @@ async fn test_explain_with_table_format() {
         timeout_secs: None,
         format: db_mcp_server::tools::format::OutputFormat::Table,
         database: None,
+        analyze: false,
     };
 
     let result = handler.explain(input).await;
@@ -290,6 +277,7 @@ async fn test_explain_with_markdown_format() {
         timeout_secs: None,
         format: db_mcp_server::tools::format::OutputFormat::Markdown,
         database: None,
+        analyze: false,
     };
 
     let result = handler.explain(input).await;
@@ -302,3 +290,151 @@ async fn test_explain_with_markdown_format() {
     // Markdown format should have header separator
     assert!(formatted.contains("|---"));
 }
+
+#[tokio::test]
+async fn test_explain_plan_tree_and_findings() {
+    let (manager, conn_id, _temp_file) = create_test_connection_manager().await;
+    let registry = Arc::new(TransactionRegistry::new());
+    let handler = ExplainToolHandler::new(manager, registry);
+
+    let input = |sql: &str| ExplainInput {
+        connection_id: conn_id.clone(),
+        sql: sql.to_string(),
+        params: vec![],
+        transaction_id: None,
+        timeout_secs: None,
+        format: Default::default(),
+        database: None,
+        analyze: false,
+    };
+
+    let output = handler
+        .explain(input("SELECT * FROM users ORDER BY name"))
+        .await
+        .unwrap();
+    let tree = output.tree.unwrap();
+    assert!(tree.children.iter().any(|n| n.is_full_scan()));
+    assert_eq!(output.summary.unwrap().full_table_scans, 1);
+    let kinds: Vec<FindingKind> = output.findings.iter().map(|f| f.kind).collect();
+    assert!(kinds.contains(&FindingKind::FullTableScan));
+    assert!(kinds.contains(&FindingKind::TemporaryStructure));
+
+    let output = handler
+        .explain(input(
+            "SELECT * FROM users WHERE email = 'test@example.com'",
+        ))
+        .await
+        .unwrap();
+    let tree = output.tree.unwrap();
+    assert_eq!(tree.operation, "Index Lookup");
+    assert_eq!(tree.index.as_deref(), Some("idx_users_email"));
+    assert!(output.findings.is_empty());
+}
+
+#[tokio::test]
+async fn test_explain_analyze_select() {
+    let (manager, conn_id, _temp_file) = create_test_connection_manager().await;
+    let pool = common::sqlite_pool(&manager, &conn_id).await;
+    sqlx::query("INSERT INTO users (name, email) VALUES ('a', 'a@x'), ('b', 'b@x')")
+        .execute(&pool)
+        .await
+        .unwrap();
+    let registry = Arc::new(TransactionRegistry::new());
+    let handler = ExplainToolHandler::new(manager, registry);
+
+    let output = handler
+        .explain(ExplainInput {
+            connection_id: conn_id,
+            sql: "SELECT * FROM users".to_string(),
+            params: vec![],
+            transaction_id: None,
+            timeout_secs: None,
+            format: Default::default(),
+            database: None,
+            analyze: true,
+        })
+        .await
+        .unwrap();
+
+    assert!(output.analyzed);
+    let summary = output.summary.unwrap();
+    assert_eq!(summary.actual_rows, Some(2.0));
+    assert!(summary.actual_time_ms.is_some());
+}
+
+#[tokio::test]
+async fn test_explain_analyze_write_is_rolled_back() {
+    let (manager, conn_id, _temp_file) = create_test_connection_manager().await;
+    let p = common::sqlite_pool(&manager, &conn_id).await;
+    sqlx::query("INSERT INTO users (id, name) VALUES (1, 'before')")
+        .execute(&p)
+        .await
+        .unwrap();
+    let registry = Arc::new(TransactionRegistry::new());
+    let handler = ExplainToolHandler::new(manager, registry.clone());
+
+    let input = |transaction_id: Option<String>| ExplainInput {
+        connection_id: conn_id.clone(),
+        sql: "UPDATE users SET name = 'after' WHERE id = 1".to_string(),
+        params: vec![],
+        transaction_id,
+        timeout_secs: None,
+        format: Default::default(),
+        database: None,
+        analyze: true,
+    };
+
+    let output = handler.explain(input(None)).await.unwrap();
+    assert_eq!(output.summary.unwrap().actual_rows, Some(1.0));
+
+    let name: String = sqlx::query_scalar("SELECT name FROM users WHERE id = 1")
+        .fetch_one(&p)
+        .await
+        .unwrap();
+    assert_eq!(name, "before");
+    assert_eq!(registry.count().await, 0);
+
+    // analyze always uses its own transaction
+    let result = handler.explain(input(Some("tx_abc".to_string()))).await;
+    assert!(result.is_err());
+}
+
+#[tokio::test]
+async fn test_explain_analyze_rejects_ddl_and_dangerous_statements() {
+    let (manager, conn_id, _temp_file) = create_test_connection_manager().await;
+    let registry = Arc::new(TransactionRegistry::new());
+    let handler = ExplainToolHandler::new(manager.clone(), registry);
+
+    let input = |sql: &str| ExplainInput {
+        connection_id: conn_id.clone(),
+        sql: sql.to_string(),
+        params: vec![],
+        transaction_id: None,
+        timeout_secs: None,
+        format: Default::default(),
+        database: None,
+        analyze: true,
+    };
+
+    let err = handler
+        .explain(input("CREATE TABLE created (id INTEGER)"))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, DbError::InvalidInput { .. }), "{err}");
+    let err = handler
+        .explain(input("DELETE FROM users"))
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(err, DbError::DangerousOperationBlocked { .. }),
+        "{err}"
+    );
+
+    let pool = common::sqlite_pool(&manager, &conn_id).await;
+    let created: i64 =
+        sqlx::query_scalar("SELECT COUNT(*) FROM sqlite_master WHERE name = 'created'")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+    assert_eq!(created, 0);
+}
@@ -261,6 +261,7 @@ async fn fuzz_explain_tool_malformed_sql() {
             timeout_secs: Some(1),
             format: db_mcp_server::tools::format::OutputFormat::Json,
             database: None,
+            analyze: false,
         };
 
         let result = handler.explain(input).await;
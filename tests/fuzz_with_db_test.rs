@@ -303,6 +303,7 @@ async fn fuzz_explain_queries() {
             timeout_secs: Some(5),
             format: OutputFormat::Json,
             database: None,
+            analyze: false,
         };
 
         let result = handler.explain(input).await;
@@ -0,0 +1,112 @@
+//! Integration tests for the suggest_indexes tool.
+
+mod common;
+
+use db_mcp_server::tools::{IndexAdvisorToolHandler, IndexIssueKind, SuggestIndexesInput};
+use tempfile::NamedTempFile;
+
+const CONN_ID: &str = "test_db";
+
+const SCHEMA: &str = r#"
+CREATE TABLE users (id INTEGER PRIMARY KEY, email TEXT, name TEXT);
+CREATE TABLE orders (id INTEGER PRIMARY KEY, user_id INTEGER, status TEXT, created_at TEXT);
+CREATE INDEX idx_users_email ON users(email);
+CREATE INDEX idx_users_email_name ON users(email, name);
+CREATE INDEX idx_users_email_copy ON users(email);
+"#;
+
+async fn setup() -> (IndexAdvisorToolHandler, NamedTempFile) {
+    let (manager, temp_file) = common::setup(CONN_ID, SCHEMA).await;
+
+    (IndexAdvisorToolHandler::new(manager), temp_file)
+}
+
+fn input(queries: &[&str]) -> SuggestIndexesInput {
+    SuggestIndexesInput {
+        connection_id: CONN_ID.to_string(),
+        queries: queries.iter().map(|q| q.to_string()).collect(),
+        database: None,
+        explain: true,
+    }
+}
+
+#[tokio::test]
+async fn test_suggests_composite_index_for_filter_and_sort() {
+    let (handler, _db) = setup().await;
+
+    let output = handler
+        .suggest_indexes(input(&[
+            "SELECT * FROM orders WHERE status = 'paid' ORDER BY created_at",
+            "SELECT o.id FROM orders o JOIN users u ON u.id = o.user_id WHERE o.status = ?",
+        ]))
+        .await
+        .unwrap();
+
+    assert_eq!(output.tables_analyzed, 2);
+    let orders: Vec<_> = output
+        .suggestions
+        .iter()
+        .filter(|s| s.table == "orders")
+        .collect();
+    assert!(
+        orders
+            .iter()
+            .any(|s| s.columns == ["status", "created_at"] && s.full_scan_in_plan)
+    );
+    assert!(
+        orders
+            .iter()
+            .any(|s| s.columns == ["status", "user_id"] && s.queries == [2])
+    );
+    assert!(orders[0].create_statement.starts_with("CREATE INDEX "));
+    assert!(orders[0].rationale.contains("status"));
+
+    // users.id is the primary key, so the join needs no index there
+    assert!(output.suggestions.iter().all(|s| s.table != "users"));
+}
+
+#[tokio::test]
+async fn test_skips_served_columns_and_flags_existing_indexes() {
+    let (handler, _db) = setup().await;
+
+    let output = handler
+        .suggest_indexes(input(&[
+            "SELECT * FROM users WHERE email = 'a@example.com'",
+        ]))
+        .await
+        .unwrap();
+
+    assert!(output.suggestions.is_empty());
+    // One single-column copy duplicates the other and the remaining one is a
+    // prefix of the composite index, so both can go; the composite stays
+    let mut kinds: Vec<IndexIssueKind> = output
+        .existing_index_issues
+        .iter()
+        .map(|i| i.kind)
+        .collect();
+    kinds.sort_by_key(|k| *k == IndexIssueKind::Redundant);
+    assert_eq!(
+        kinds,
+        vec![IndexIssueKind::Duplicate, IndexIssueKind::Redundant]
+    );
+    assert!(
+        output
+            .existing_index_issues
+            .iter()
+            .all(|i| i.index != "idx_users_email_name")
+    );
+}
+
+#[tokio::test]
+async fn test_reports_unanalyzable_queries() {
+    let (handler, _db) = setup().await;
+
+    let output = handler
+        .suggest_indexes(input(&["CREATE TABLE t (id INT)", "SELEC nonsense"]))
+        .await
+        .unwrap();
+    assert!(output.suggestions.is_empty());
+    assert_eq!(output.notes.len(), 2);
+
+    assert!(handler.suggest_indexes(input(&[])).await.is_err());
+}
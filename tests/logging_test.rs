@@ -0,0 +1,118 @@
+//! Integration tests for forwarding server log events to MCP clients.
+
+use db_mcp_server::config::PoolOptions;
+use db_mcp_server::db::ConnectionManager;
+use db_mcp_server::mcp::logging::{self, LogForwarder, SessionLog};
+use db_mcp_server::models::ConnectionConfig;
+use db_mcp_server::tools::format::OutputFormat;
+use db_mcp_server::tools::query::{QueryInput, QueryToolHandler};
+use rmcp::model::{LoggingLevel, LoggingMessageNotificationParam};
+use std::sync::Arc;
+use tempfile::NamedTempFile;
+use tracing::Instrument;
+use tracing_subscriber::layer::SubscriberExt;
+
+async fn setup() -> (QueryToolHandler, NamedTempFile) {
+    let temp_file = NamedTempFile::new().expect("Failed to create temp file");
+    let url = format!("sqlite:{}?mode=rwc", temp_file.path().display());
+    let config = ConnectionConfig::new("app", &url, false, false, None, PoolOptions::default())
+        .expect("Failed to create config");
+    let manager = Arc::new(ConnectionManager::new());
+    manager.connect(config).await.expect("Failed to connect");
+    (QueryToolHandler::new(manager), temp_file)
+}
+
+fn query_input(sql: &str, limit: Option<u32>, timeout_secs: u32) -> QueryInput {
+    QueryInput {
+        connection_id: "app".to_string(),
+        sql: sql.to_string(),
+        params: vec![],
+        limit,
+        timeout_secs: Some(timeout_secs),
+        format: OutputFormat::Json,
+        decode_binary: true,
+        transaction_id: None,
+        database: None,
+    }
+}
+
+/// Forwarded warnings with the given logger name.
+fn warnings(messages: &mut SessionLog, logger: &str) -> Vec<LoggingMessageNotificationParam> {
+    std::iter::from_fn(|| messages.try_recv().ok())
+        .filter(|m| m.level == LoggingLevel::Warning && m.logger.as_deref() == Some(logger))
+        .collect()
+}
+
+#[tokio::test]
+async fn test_truncation_warning_is_forwarded() {
+    let forwarder = LogForwarder::new();
+    let mut messages = forwarder.subscribe("session_a");
+    let _guard =
+        tracing::subscriber::set_default(tracing_subscriber::registry().with(forwarder.layer()));
+    let (handler, _db) = setup().await;
+
+    let output = handler
+        .query(query_input(
+            "WITH RECURSIVE counter(x) AS (SELECT 1 UNION ALL SELECT x + 1 FROM counter LIMIT 20) \
+             SELECT x FROM counter",
+            Some(10),
+            30,
+        ))
+        .await
+        .unwrap();
+    assert_eq!(output.row_count, 10);
+
+    let warnings = warnings(&mut messages, "db_mcp_server::db::executor");
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(warnings[0].data["message"], "Query result truncated");
+    assert_eq!(warnings[0].data["limit"], 10);
+}
+
+#[tokio::test]
+async fn test_timeout_warning_is_forwarded() {
+    let forwarder = LogForwarder::new();
+    let mut messages = forwarder.subscribe("session_a");
+    let _guard =
+        tracing::subscriber::set_default(tracing_subscriber::registry().with(forwarder.layer()));
+    let (handler, _db) = setup().await;
+
+    handler
+        .query(query_input(
+            "WITH RECURSIVE counter(x) AS (SELECT 1 UNION ALL SELECT x + 1 FROM counter) \
+             SELECT count(*) FROM counter",
+            None,
+            1,
+        ))
+        .await
+        .unwrap_err();
+
+    let warnings = warnings(&mut messages, "db_mcp_server::db::control");
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(warnings[0].data["operation"], "query execution");
+    assert_eq!(warnings[0].data["timeout_secs"], 1);
+}
+
+#[tokio::test]
+async fn test_sessions_do_not_see_each_others_sql() {
+    let forwarder = LogForwarder::new();
+    let mut session_a = forwarder.subscribe("session_a");
+    let mut session_b = forwarder.subscribe("session_b");
+    let _guard =
+        tracing::subscriber::set_default(tracing_subscriber::registry().with(forwarder.layer()));
+    let (handler, _db) = setup().await;
+
+    let sql = "SELECT 'secret of session a' AS value";
+    handler
+        .query(query_input(sql, None, 30))
+        .instrument(logging::session_span("session_a"))
+        .await
+        .unwrap();
+
+    let mentions_sql = |messages: &mut SessionLog| {
+        std::iter::from_fn(|| messages.try_recv().ok())
+            .filter(|m| m.data.to_string().contains("secret of session a"))
+            .count()
+    };
+    assert!(mentions_sql(&mut session_a) > 0);
+    assert_eq!(mentions_sql(&mut session_b), 0);
+}
@@ -0,0 +1,52 @@
+//! Integration tests for the Prometheus metrics endpoint.
+
+use db_mcp_server::config::PoolOptions;
+use db_mcp_server::db::{ConnectionManager, TransactionRegistry};
+use db_mcp_server::error::DbError;
+use db_mcp_server::metrics::{self, MetricsEndpoint};
+use db_mcp_server::models::ConnectionConfig;
+use db_mcp_server::tools::transaction::{BeginTransactionInput, TransactionToolHandler};
+use std::sync::Arc;
+use std::time::Duration;
+
+#[tokio::test]
+async fn test_metrics_endpoint_reports_tools_errors_pools_and_transactions() {
+    let dir = tempfile::tempdir().expect("Failed to create temp dir");
+    let url = format!("sqlite:{}?mode=rwc", dir.path().join("app.db").display());
+    let config = ConnectionConfig::new("app", &url, true, false, None, PoolOptions::default())
+        .expect("Failed to create config");
+    let manager = Arc::new(ConnectionManager::new());
+    manager.connect(config).await.unwrap();
+    let registry = Arc::new(TransactionRegistry::new());
+
+    let endpoint = MetricsEndpoint::new(
+        metrics::install().expect("Failed to install recorder"),
+        manager.clone(),
+        registry.clone(),
+    );
+    // Only one recorder per process
+    assert!(metrics::install().is_err());
+
+    metrics::record_tool_call("query", Duration::from_millis(5), true);
+    metrics::record_tool_call("execute", Duration::from_millis(5), false);
+    let _: rmcp::ErrorData = DbError::connection_not_found("missing").into();
+
+    let transactions = TransactionToolHandler::new(manager.clone(), registry.clone());
+    transactions
+        .begin_transaction(BeginTransactionInput {
+            connection_id: "app".to_string(),
+            timeout_secs: None,
+            database: None,
+        })
+        .await
+        .unwrap();
+
+    let output = endpoint.render().await;
+    assert!(output.contains(r#"db_mcp_tool_calls_total{tool="query",outcome="success"} 1"#));
+    assert!(output.contains(r#"db_mcp_tool_calls_total{tool="execute",outcome="error"} 1"#));
+    assert!(output.contains(r#"db_mcp_tool_call_duration_seconds_count{tool="query"} 1"#));
+    assert!(output.contains(r#"db_mcp_errors_total{kind="connection_not_found"} 1"#));
+    assert!(output.contains(r#"db_mcp_pool_connections{connection_id="app"}"#));
+    assert!(output.contains(r#"db_mcp_database_pools{connection_id="app"} 0"#));
+    assert!(output.contains("db_mcp_open_transactions 1"));
+}
@@ -0,0 +1,261 @@
+//! Integration tests for the migration tools.
+
+mod common;
+
+use db_mcp_server::db::ConnectionManager;
+use db_mcp_server::tools::migration::MigrationState;
+use db_mcp_server::tools::{
+    MigrationApplyInput, MigrationRollbackInput, MigrationStatusInput, MigrationToolHandler,
+};
+use std::path::Path;
+use std::sync::Arc;
+use tempfile::{NamedTempFile, TempDir};
+
+const CONN_ID: &str = "test_db";
+
+async fn setup(writable: bool) -> (Arc<ConnectionManager>, NamedTempFile, TempDir) {
+    let manager = Arc::new(ConnectionManager::new());
+    let temp_file = common::connect_sqlite(&manager, CONN_ID, writable, "").await;
+
+    let dir = tempfile::tempdir().expect("Failed to create migrations dir");
+    write(
+        dir.path(),
+        "0001_create_users.up.sql",
+        "CREATE TABLE users (id INTEGER PRIMARY KEY, name TEXT NOT NULL);",
+    );
+    write(
+        dir.path(),
+        "0001_create_users.down.sql",
+        "DROP TABLE users;",
+    );
+    write(
+        dir.path(),
+        "0002_add_email.sql",
+        "ALTER TABLE users ADD COLUMN email TEXT;\nCREATE INDEX idx_users_email ON users(email);",
+    );
+
+    (manager, temp_file, dir)
+}
+
+fn write(dir: &Path, name: &str, sql: &str) {
+    std::fs::write(dir.join(name), sql).expect("Failed to write migration file");
+}
+
+fn status_input() -> MigrationStatusInput {
+    MigrationStatusInput {
+        connection_id: CONN_ID.to_string(),
+        database: None,
+    }
+}
+
+fn apply_input(target_version: Option<i64>, dry_run: bool) -> MigrationApplyInput {
+    MigrationApplyInput {
+        connection_id: CONN_ID.to_string(),
+        target_version,
+        dry_run,
+        allow_out_of_order: false,
+        database: None,
+    }
+}
+
+fn rollback_input(steps: Option<u32>) -> MigrationRollbackInput {
+    MigrationRollbackInput {
+        connection_id: CONN_ID.to_string(),
+        steps,
+        dry_run: false,
+        database: None,
+    }
+}
+
+async fn table_exists(manager: &ConnectionManager, table: &str) -> bool {
+    let pool = common::sqlite_pool(manager, CONN_ID).await;
+    let count: i64 =
+        sqlx::query_scalar("SELECT COUNT(*) FROM sqlite_master WHERE type = 'table' AND name = ?")
+            .bind(table)
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+    count > 0
+}
+
+#[tokio::test]
+async fn test_status_before_apply_lists_pending() {
+    let (manager, _db, dir) = setup(true).await;
+    let handler = MigrationToolHandler::new(manager.clone(), Some(dir.path().to_path_buf()));
+
+    let status = handler.status(status_input()).await.unwrap();
+    assert_eq!(status.migrations.len(), 2);
+    assert_eq!(status.pending_count, 2);
+    assert!(status.current_version.is_none());
+    assert!(status.transactional);
+    // Status is read-only and must not create the history table
+    assert!(!table_exists(&manager, "_mcp_migrations").await);
+}
+
+#[tokio::test]
+async fn test_apply_records_history() {
+    let (manager, _db, dir) = setup(true).await;
+    let handler = MigrationToolHandler::new(manager.clone(), Some(dir.path().to_path_buf()));
+
+    let output = handler.apply(apply_input(None, false)).await.unwrap();
+    assert_eq!(output.migrations.len(), 2);
+    assert!(!output.dry_run);
+    assert!(table_exists(&manager, "users").await);
+
+    let status = handler.status(status_input()).await.unwrap();
+    assert_eq!(status.pending_count, 0);
+    assert_eq!(status.current_version, Some(2));
+    assert!(
+        status
+            .migrations
+            .iter()
+            .all(|m| m.state == MigrationState::Applied)
+    );
+
+    // Re-running is a no-op
+    let output = handler.apply(apply_input(None, false)).await.unwrap();
+    assert!(output.migrations.is_empty());
+}
+
+#[tokio::test]
+async fn test_apply_dry_run_and_target_version() {
+    let (manager, _db, dir) = setup(true).await;
+    let handler = MigrationToolHandler::new(manager.clone(), Some(dir.path().to_path_buf()));
+
+    let output = handler.apply(apply_input(None, true)).await.unwrap();
+    assert!(output.dry_run);
+    assert_eq!(output.migrations.len(), 2);
+    assert!(!table_exists(&manager, "users").await);
+
+    let output = handler.apply(apply_input(Some(1), false)).await.unwrap();
+    assert_eq!(output.migrations.len(), 1);
+    assert_eq!(output.migrations[0].version, 1);
+
+    let status = handler.status(status_input()).await.unwrap();
+    assert_eq!(status.current_version, Some(1));
+    assert_eq!(status.pending_count, 1);
+}
+
+#[tokio::test]
+async fn test_apply_refuses_on_checksum_drift() {
+    let (manager, _db, dir) = setup(true).await;
+    let handler = MigrationToolHandler::new(manager.clone(), Some(dir.path().to_path_buf()));
+
+    handler.apply(apply_input(Some(1), false)).await.unwrap();
+
+    write(
+        dir.path(),
+        "0001_create_users.up.sql",
+        "CREATE TABLE users (id INTEGER PRIMARY KEY, name TEXT);",
+    );
+
+    let status = handler.status(status_input()).await.unwrap();
+    assert_eq!(status.drifted_versions, vec![1]);
+    assert_eq!(status.migrations[0].state, MigrationState::Drifted);
+
+    let err = handler.apply(apply_input(None, false)).await.unwrap_err();
+    assert!(err.to_string().contains("no longer match"));
+    // The pending migration must not have run
+    let status = handler.status(status_input()).await.unwrap();
+    assert_eq!(status.pending_count, 1);
+}
+
+#[tokio::test]
+async fn test_apply_refuses_out_of_order_migrations() {
+    let (manager, _db, dir) = setup(true).await;
+    write(
+        dir.path(),
+        "0010_create_audit_log.sql",
+        "CREATE TABLE audit_log (id INTEGER PRIMARY KEY);",
+    );
+    let handler = MigrationToolHandler::new(manager.clone(), Some(dir.path().to_path_buf()));
+    handler.apply(apply_input(None, false)).await.unwrap();
+
+    // Added after 0010 was applied, e.g. from a merged branch
+    write(
+        dir.path(),
+        "0005_create_orders.sql",
+        "CREATE TABLE orders (id INTEGER PRIMARY KEY);",
+    );
+    let status = handler.status(status_input()).await.unwrap();
+    assert_eq!(status.pending_count, 1);
+
+    for dry_run in [true, false] {
+        let err = handler.apply(apply_input(None, dry_run)).await.unwrap_err();
+        assert!(err.to_string().contains("allow_out_of_order"), "{err}");
+    }
+    assert!(!table_exists(&manager, "orders").await);
+
+    let output = handler
+        .apply(MigrationApplyInput {
+            allow_out_of_order: true,
+            ..apply_input(None, false)
+        })
+        .await
+        .unwrap();
+    assert_eq!(output.migrations.len(), 1);
+    assert!(table_exists(&manager, "orders").await);
+}
+
+#[tokio::test]
+async fn test_failed_migration_is_rolled_back() {
+    let (manager, _db, dir) = setup(true).await;
+    write(
+        dir.path(),
+        "0003_broken.sql",
+        "CREATE TABLE audit (id INTEGER);\nINSERT INTO missing_table VALUES (1);",
+    );
+    let handler = MigrationToolHandler::new(manager.clone(), Some(dir.path().to_path_buf()));
+
+    let err = handler.apply(apply_input(None, false)).await.unwrap_err();
+    assert!(err.to_string().contains("Migration 3"));
+
+    // Earlier migrations stay applied; the failed one leaves nothing behind
+    assert!(!table_exists(&manager, "audit").await);
+    let status = handler.status(status_input()).await.unwrap();
+    assert_eq!(status.current_version, Some(2));
+    assert_eq!(status.pending_count, 1);
+}
+
+#[tokio::test]
+async fn test_rollback_requires_down_script() {
+    let (manager, _db, dir) = setup(true).await;
+    let handler = MigrationToolHandler::new(manager.clone(), Some(dir.path().to_path_buf()));
+    handler.apply(apply_input(None, false)).await.unwrap();
+
+    // 0002 has no .down.sql file
+    let err = handler.rollback(rollback_input(None)).await.unwrap_err();
+    assert!(err.to_string().contains("no .down.sql"));
+
+    write(
+        dir.path(),
+        "0002_add_email.down.sql",
+        "DROP INDEX idx_users_email;\nALTER TABLE users DROP COLUMN email;",
+    );
+    let output = handler.rollback(rollback_input(Some(2))).await.unwrap();
+    assert_eq!(output.migrations.len(), 2);
+    assert_eq!(output.migrations[0].version, 2);
+    assert_eq!(output.migrations[1].version, 1);
+    assert!(!table_exists(&manager, "users").await);
+
+    let status = handler.status(status_input()).await.unwrap();
+    assert_eq!(status.pending_count, 2);
+}
+
+#[tokio::test]
+async fn test_apply_requires_writable_connection() {
+    let (manager, _db, dir) = setup(false).await;
+    let handler = MigrationToolHandler::new(manager, Some(dir.path().to_path_buf()));
+
+    let err = handler.apply(apply_input(None, false)).await.unwrap_err();
+    assert!(err.to_string().contains("not writable"));
+}
+
+#[tokio::test]
+async fn test_missing_migrations_dir_config() {
+    let (manager, _db, _dir) = setup(true).await;
+    let handler = MigrationToolHandler::new(manager, None);
+
+    let err = handler.status(status_input()).await.unwrap_err();
+    assert!(err.to_string().contains("--migrations-dir"));
+}
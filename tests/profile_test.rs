@@ -0,0 +1,115 @@
+//! Integration tests for the profile_table tool.
+
+mod common;
+
+use db_mcp_server::tools::{ProfileTableInput, ProfileToolHandler};
+use serde_json::json;
+use tempfile::NamedTempFile;
+
+const CONN_ID: &str = "test_db";
+
+const SCHEMA: &str = r#"
+CREATE TABLE users (id INTEGER PRIMARY KEY, name TEXT, country TEXT, score REAL);
+CREATE INDEX idx_users_country ON users(country);
+INSERT INTO users (name, country, score) VALUES
+    ('alice', 'DE', 1.5),
+    ('bob', 'DE', 2.0),
+    ('carol', 'FR', NULL),
+    ('dave', 'DE', 4.5),
+    (NULL, 'US', 3.0);
+"#;
+
+async fn setup() -> (ProfileToolHandler, sqlx::SqlitePool, NamedTempFile) {
+    let (manager, temp_file) = common::setup(CONN_ID, SCHEMA).await;
+    let pool = common::sqlite_pool(&manager, CONN_ID).await;
+
+    (ProfileToolHandler::new(manager, 30), pool, temp_file)
+}
+
+fn input() -> ProfileTableInput {
+    ProfileTableInput {
+        connection_id: CONN_ID.to_string(),
+        table_name: "users".to_string(),
+        database: None,
+        columns: None,
+        sample_size: None,
+        top_n: None,
+        use_catalog: true,
+        timeout_secs: None,
+    }
+}
+
+#[tokio::test]
+async fn test_profile_from_sample() {
+    let (handler, _pool, _db) = setup().await;
+
+    let output = handler.profile_table(input()).await.unwrap();
+    assert_eq!(output.row_count, Some(5));
+    assert!(output.row_count_exact);
+    assert!(!output.sample_truncated);
+    assert_eq!(output.columns.len(), 4);
+
+    let name = &output.columns[1];
+    assert_eq!(name.null_fraction, Some(0.2));
+    assert_eq!(name.distinct_count, Some(4));
+    assert_eq!(name.min, Some(json!("alice")));
+    assert_eq!(name.max, Some(json!("dave")));
+    // All values distinct, so no frequent values
+    assert!(name.top_values.is_empty());
+    let length = name.length.as_ref().unwrap();
+    assert_eq!((length.min, length.max, length.avg), (3, 5, 4.25));
+
+    let country = &output.columns[2];
+    assert_eq!(country.distinct_count, Some(3));
+    assert_eq!(country.top_values[0].value, json!("DE"));
+    assert_eq!(country.top_values[0].count, Some(3));
+    assert_eq!(country.top_values[0].fraction, 0.6);
+    assert!(country.from_catalog.is_empty());
+
+    let score = &output.columns[3];
+    assert_eq!(score.min, Some(json!(1.5)));
+    assert_eq!(score.max, Some(json!(4.5)));
+    assert!(score.length.is_none());
+}
+
+#[tokio::test]
+async fn test_profile_uses_sqlite_stat1() {
+    let (handler, pool, _db) = setup().await;
+    sqlx::raw_sql("ANALYZE").execute(&pool).await.unwrap();
+
+    let mut request = input();
+    request.columns = Some(vec!["country".to_string()]);
+    let output = handler.profile_table(request).await.unwrap();
+
+    assert_eq!(output.columns.len(), 1);
+    let country = &output.columns[0];
+    assert_eq!(country.from_catalog, vec!["distinct_count"]);
+    // sqlite_stat1 averages rows per key: ceil(5 / 2) -> estimate of 3 distinct values
+    assert_eq!(country.distinct_count, Some(3));
+    assert!(!country.top_values.is_empty());
+}
+
+#[tokio::test]
+async fn test_profile_truncated_sample() {
+    let (handler, _pool, _db) = setup().await;
+
+    let mut request = input();
+    request.sample_size = Some(2);
+    request.use_catalog = false;
+    let output = handler.profile_table(request).await.unwrap();
+
+    assert_eq!(output.sampled_rows, 2);
+    assert!(output.sample_truncated);
+    assert!(!output.row_count_exact);
+    assert_eq!(output.row_count, None);
+}
+
+#[tokio::test]
+async fn test_profile_unknown_column() {
+    let (handler, _pool, _db) = setup().await;
+
+    let mut request = input();
+    request.columns = Some(vec!["missing".to_string()]);
+    let err = handler.profile_table(request).await.unwrap_err();
+    assert!(err.to_string().contains("Column 'missing' does not exist"));
+}
@@ -0,0 +1,149 @@
+//! Integration tests for the MCP prompts.
+
+mod common;
+
+use db_mcp_server::mcp::prompts::{PromptLibrary, PromptRenderer};
+use rmcp::model::{JsonObject, PromptMessageContent};
+use serde_json::json;
+use tempfile::NamedTempFile;
+
+async fn setup() -> (PromptRenderer, NamedTempFile) {
+    let (manager, temp_file) = common::setup(
+        "app",
+        r#"
+        CREATE TABLE users (id INTEGER PRIMARY KEY, email TEXT NOT NULL);
+        CREATE TABLE orders (id INTEGER PRIMARY KEY, user_id INTEGER REFERENCES users(id));
+        CREATE INDEX idx_orders_user ON orders(user_id);
+        CREATE TABLE audit_log (id INTEGER PRIMARY KEY, entry TEXT);
+        "#,
+    )
+    .await;
+    (PromptRenderer::new(manager), temp_file)
+}
+
+fn arguments(value: serde_json::Value) -> Option<JsonObject> {
+    value.as_object().cloned()
+}
+
+async fn render(
+    renderer: &PromptRenderer,
+    library: &PromptLibrary,
+    name: &str,
+    args: serde_json::Value,
+) -> String {
+    let result = renderer
+        .render(library.get(name).unwrap(), arguments(args))
+        .await
+        .unwrap();
+    let PromptMessageContent::Text { text } = &result.messages[0].content else {
+        panic!("expected text message");
+    };
+    text.clone()
+}
+
+#[tokio::test]
+async fn test_builtin_prompts_include_schema_context() {
+    let (renderer, _db) = setup().await;
+    let library = PromptLibrary::builtin();
+
+    let text = render(
+        &renderer,
+        &library,
+        "summarize_table",
+        json!({"connection_id": "app", "table": "orders"}),
+    )
+    .await;
+    assert!(text.contains("`orders` on connection `app` (SQLite)"));
+    assert!(text.contains("- user_id INTEGER REFERENCES users(id)"));
+    assert!(text.contains("INDEX idx_orders_user (user_id)"));
+    assert!(!text.contains("### users"));
+
+    let text = render(
+        &renderer,
+        &library,
+        "explain_slow_query",
+        json!({"connection_id": "app", "sql": "SELECT * FROM orders o JOIN users u ON u.id = o.user_id"}),
+    )
+    .await;
+    assert!(text.contains("### orders") && text.contains("### users"));
+    assert!(!text.contains("### audit_log"));
+
+    let text = render(
+        &renderer,
+        &library,
+        "explore_database",
+        json!({"connection_id": "app"}),
+    )
+    .await;
+    assert!(text.contains("### audit_log"));
+    assert!(!text.contains("{{"));
+}
+
+#[tokio::test]
+async fn test_missing_arguments_are_rejected() {
+    let (renderer, _db) = setup().await;
+    let library = PromptLibrary::builtin();
+
+    let err = renderer
+        .render(
+            library.get("summarize_table").unwrap(),
+            arguments(json!({"connection_id": "app"})),
+        )
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("'table'"));
+
+    let err = renderer
+        .render(
+            library.get("explore_database").unwrap(),
+            arguments(json!({"connection_id": "missing"})),
+        )
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("missing"));
+}
+
+#[tokio::test]
+async fn test_prompts_directory_adds_and_overrides() {
+    let (renderer, _db) = setup().await;
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(
+        dir.path().join("pii_review.md"),
+        "---\ndescription: Review a table for personal data\narguments: connection_id, table\n---\n\
+         Check {{table}} ({{db_type}}) for personal data:\n\n{{schema}}\n",
+    )
+    .unwrap();
+    std::fs::write(
+        dir.path().join("explore_database.md"),
+        "---\narguments: connection_id\n---\nHouse rules for {{connection_id}}.\n",
+    )
+    .unwrap();
+    std::fs::write(dir.path().join("broken.md"), "---\nowner: me\n---\nbody").unwrap();
+    std::fs::write(dir.path().join("notes.txt"), "ignored").unwrap();
+
+    let library = PromptLibrary::load(Some(dir.path()));
+    let names: Vec<String> = library.list().into_iter().map(|p| p.name).collect();
+    assert!(names.contains(&"pii_review".to_string()));
+    assert!(!names.contains(&"broken".to_string()));
+    assert!(!names.contains(&"notes".to_string()));
+    assert_eq!(names.len(), 5);
+
+    let text = render(
+        &renderer,
+        &library,
+        "pii_review",
+        json!({"connection_id": "app", "table": "users"}),
+    )
+    .await;
+    assert!(text.starts_with("Check users (SQLite) for personal data"));
+    assert!(text.contains("- email TEXT NOT NULL"));
+
+    let text = render(
+        &renderer,
+        &library,
+        "explore_database",
+        json!({"connection_id": "app"}),
+    )
+    .await;
+    assert_eq!(text, "House rules for app.");
+}
@@ -0,0 +1,143 @@
+//! Integration tests for pre-execution table and column validation.
+
+mod common;
+
+use db_mcp_server::db::{ConnectionManager, TransactionRegistry};
+use db_mcp_server::error::DbError;
+use db_mcp_server::tools::{
+    ExecuteInput, ExplainInput, ExplainToolHandler, OutputFormat, QueryInput, QueryToolHandler,
+    WriteToolHandler,
+};
+use std::sync::Arc;
+use tempfile::NamedTempFile;
+
+const CONN_ID: &str = "test_db";
+
+const SCHEMA: &str = r#"
+CREATE TABLE customers (id INTEGER PRIMARY KEY, email TEXT, name TEXT);
+CREATE TABLE orders (id INTEGER PRIMARY KEY, customer_id INTEGER, total REAL);
+"#;
+
+async fn setup() -> (Arc<ConnectionManager>, sqlx::SqlitePool, NamedTempFile) {
+    let (manager, temp_file) = common::setup(CONN_ID, SCHEMA).await;
+    let pool = common::sqlite_pool(&manager, CONN_ID).await;
+    (manager, pool, temp_file)
+}
+
+fn query_input(sql: &str) -> QueryInput {
+    QueryInput {
+        connection_id: CONN_ID.to_string(),
+        sql: sql.to_string(),
+        params: vec![],
+        limit: None,
+        timeout_secs: None,
+        format: OutputFormat::Json,
+        decode_binary: true,
+        transaction_id: None,
+        database: None,
+    }
+}
+
+fn execute_input(sql: &str, skip_sql_check: bool) -> ExecuteInput {
+    ExecuteInput {
+        connection_id: CONN_ID.to_string(),
+        sql: sql.to_string(),
+        params: vec![],
+        timeout_secs: None,
+        transaction_id: None,
+        skip_sql_check,
+        database: None,
+    }
+}
+
+#[tokio::test]
+async fn test_query_reports_misspelled_column() {
+    let (manager, _pool, _db) = setup().await;
+    let handler = QueryToolHandler::new(manager);
+
+    let err = handler
+        .query(query_input(
+            "SELECT c.emial FROM customers c JOIN orders o ON o.customer_id = c.id",
+        ))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, DbError::Schema { ref object, .. } if object == "c.emial"));
+    assert!(err.to_string().contains("Did you mean 'email'?"));
+
+    let output = handler
+        .query(query_input(
+            "SELECT c.email, SUM(o.total) AS spent FROM customers c \
+             JOIN orders o ON o.customer_id = c.id GROUP BY c.email ORDER BY spent",
+        ))
+        .await
+        .unwrap();
+    assert_eq!(output.row_count, 0);
+}
+
+#[tokio::test]
+async fn test_explain_and_execute_report_misspelled_table() {
+    let (manager, _pool, _db) = setup().await;
+
+    let err = ExplainToolHandler::new(manager.clone(), Arc::new(TransactionRegistry::new()))
+        .explain(ExplainInput {
+            connection_id: CONN_ID.to_string(),
+            sql: "SELECT * FROM custmers".to_string(),
+            params: vec![],
+            transaction_id: None,
+            timeout_secs: None,
+            format: OutputFormat::Json,
+            database: None,
+            analyze: false,
+        })
+        .await
+        .unwrap_err();
+    assert!(matches!(err, DbError::Schema { ref object, .. } if object == "custmers"));
+    assert!(err.to_string().contains("Did you mean 'customers'?"));
+
+    let writer = WriteToolHandler::new(manager.clone(), Arc::new(TransactionRegistry::new()));
+    let err = writer
+        .execute(execute_input(
+            "UPDATE ordres SET total = 0 WHERE id = 1",
+            false,
+        ))
+        .await
+        .unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("Table 'ordres' not found. Did you mean 'orders'?")
+    );
+
+    // skip_sql_check bypasses the reference check and surfaces the driver error
+    let err = writer
+        .execute(execute_input(
+            "UPDATE ordres SET total = 0 WHERE id = 1",
+            true,
+        ))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, DbError::Database { .. }));
+}
+
+#[tokio::test]
+async fn test_stale_cache_is_refreshed_before_reporting() {
+    let (manager, pool, _db) = setup().await;
+    let handler = QueryToolHandler::new(manager.clone());
+    handler
+        .query(query_input("SELECT id FROM customers"))
+        .await
+        .unwrap();
+
+    // Schema changed behind the server's back while the catalog is cached
+    sqlx::raw_sql("CREATE TABLE refunds (id INTEGER PRIMARY KEY, amount REAL)")
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let mut changes = manager.schema_cache().subscribe();
+    handler
+        .query(query_input("SELECT amount FROM refunds"))
+        .await
+        .unwrap();
+    // The refresh is not announced to the sessions as a schema change
+    assert!(changes.try_recv().is_err());
+}
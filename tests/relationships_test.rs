@@ -0,0 +1,152 @@
+//! Integration tests for the relationship_graph and find_join_path tools.
+
+mod common;
+
+use db_mcp_server::tools::{
+    FindJoinPathInput, GraphFormat, RelationshipGraphInput, RelationshipToolHandler,
+};
+use tempfile::NamedTempFile;
+
+const CONN_ID: &str = "test_db";
+
+const SCHEMA: &str = r#"
+CREATE TABLE regions (id INTEGER PRIMARY KEY, name TEXT);
+CREATE TABLE countries (id INTEGER PRIMARY KEY, region_id INTEGER NOT NULL REFERENCES regions(id));
+CREATE TABLE customers (id INTEGER PRIMARY KEY, country_id INTEGER REFERENCES countries(id));
+CREATE TABLE orders (id INTEGER PRIMARY KEY, customer_id INTEGER NOT NULL REFERENCES customers(id));
+CREATE TABLE shipments (
+    order_id INTEGER NOT NULL,
+    seq INTEGER NOT NULL,
+    PRIMARY KEY (order_id, seq)
+);
+CREATE TABLE shipment_events (
+    id INTEGER PRIMARY KEY,
+    order_id INTEGER,
+    seq INTEGER,
+    FOREIGN KEY (order_id, seq) REFERENCES shipments(order_id, seq)
+);
+"#;
+
+async fn setup() -> (RelationshipToolHandler, NamedTempFile) {
+    let (manager, temp_file) = common::setup(CONN_ID, SCHEMA).await;
+
+    (RelationshipToolHandler::new(manager), temp_file)
+}
+
+fn path_input(from: &str, to: &str) -> FindJoinPathInput {
+    FindJoinPathInput {
+        connection_id: CONN_ID.to_string(),
+        from_table: from.to_string(),
+        to_table: to.to_string(),
+        database: None,
+        max_hops: None,
+    }
+}
+
+#[tokio::test]
+async fn test_relationship_graph_json() {
+    let (handler, _db) = setup().await;
+
+    let output = handler
+        .relationship_graph(RelationshipGraphInput {
+            connection_id: CONN_ID.to_string(),
+            database: None,
+            format: GraphFormat::Json,
+            include_columns: true,
+        })
+        .await
+        .unwrap();
+
+    assert_eq!(output.table_count, 6);
+    assert_eq!(output.relationship_count, 4);
+    assert!(output.mermaid.is_none());
+
+    let countries = output
+        .tables
+        .iter()
+        .find(|t| t.table == "countries")
+        .unwrap();
+    assert_eq!(countries.references, vec!["regions"]);
+    assert_eq!(countries.referenced_by, vec!["customers"]);
+
+    let composite = output
+        .relationships
+        .iter()
+        .find(|r| r.from_table == "shipment_events")
+        .unwrap();
+    assert_eq!(composite.from_columns, vec!["order_id", "seq"]);
+    assert_eq!(composite.to_columns, vec!["order_id", "seq"]);
+}
+
+#[tokio::test]
+async fn test_relationship_graph_mermaid() {
+    let (handler, _db) = setup().await;
+
+    let output = handler
+        .relationship_graph(RelationshipGraphInput {
+            connection_id: CONN_ID.to_string(),
+            database: None,
+            format: GraphFormat::Mermaid,
+            include_columns: true,
+        })
+        .await
+        .unwrap();
+
+    let mermaid = output.mermaid.unwrap();
+    assert!(output.tables.is_empty());
+    assert!(mermaid.starts_with("erDiagram\n"));
+    assert!(mermaid.contains("regions ||--o{ countries : \"region_id\""));
+    assert!(mermaid.contains("countries |o--o{ customers : \"country_id\""));
+    assert!(mermaid.contains("INTEGER region_id FK"));
+}
+
+#[tokio::test]
+async fn test_find_join_path_four_hops() {
+    let (handler, _db) = setup().await;
+
+    let output = handler
+        .find_join_path(path_input("ORDERS", "regions"))
+        .await
+        .unwrap();
+
+    assert!(output.found);
+    assert_eq!(
+        output.tables,
+        vec!["orders", "customers", "countries", "regions"]
+    );
+    assert_eq!(
+        output.join_sql.unwrap(),
+        "FROM \"orders\"\n\
+         JOIN \"customers\" ON \"customers\".\"id\" = \"orders\".\"customer_id\"\n\
+         JOIN \"countries\" ON \"countries\".\"id\" = \"customers\".\"country_id\"\n\
+         JOIN \"regions\" ON \"regions\".\"id\" = \"countries\".\"region_id\""
+    );
+}
+
+#[tokio::test]
+async fn test_find_join_path_composite_and_unreachable() {
+    let (handler, _db) = setup().await;
+
+    let output = handler
+        .find_join_path(path_input("shipments", "shipment_events"))
+        .await
+        .unwrap();
+    assert_eq!(output.hops[0].direction, "parent_to_child");
+    assert!(output.join_sql.unwrap().ends_with(
+        "ON \"shipment_events\".\"order_id\" = \"shipments\".\"order_id\" \
+         AND \"shipment_events\".\"seq\" = \"shipments\".\"seq\""
+    ));
+
+    let output = handler
+        .find_join_path(path_input("orders", "shipments"))
+        .await
+        .unwrap();
+    assert!(!output.found);
+    assert!(output.join_sql.is_none());
+
+    let err = handler
+        .find_join_path(path_input("orders", "missing"))
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("not found"));
+}
@@ -0,0 +1,245 @@
+//! Integration tests for reloading connections and auth tokens without restart.
+
+use db_mcp_server::auth::{AuthConfig, SharedAuthConfig};
+use db_mcp_server::config::PoolOptions;
+use db_mcp_server::db::{ConnectionManager, TransactionRegistry};
+use db_mcp_server::models::ConnectionConfig;
+use db_mcp_server::reload::{ReloadSummary, ReloadableConfig, Reloader};
+use db_mcp_server::tools::transaction::{
+    BeginTransactionInput, CommitInput, TransactionToolHandler,
+};
+use db_mcp_server::tools::write::{ExecuteInput, WriteToolHandler};
+use std::path::Path;
+use std::sync::Arc;
+use tempfile::TempDir;
+
+fn connection(dir: &Path, id: &str, max_connections: Option<u32>) -> ConnectionConfig {
+    let url = format!("sqlite:{}?mode=rwc", dir.join(format!("{id}.db")).display());
+    let pool_options = PoolOptions {
+        max_connections,
+        ..PoolOptions::default()
+    };
+    ConnectionConfig::new(id, &url, true, false, None, pool_options)
+        .expect("Failed to create config")
+}
+
+fn reloadable(connections: Vec<ConnectionConfig>, auth_tokens: &[&str]) -> ReloadableConfig {
+    ReloadableConfig {
+        connections,
+        auth_tokens: auth_tokens.iter().map(|t| t.to_string()).collect(),
+    }
+}
+
+fn execute(sql: &str, transaction_id: Option<String>) -> ExecuteInput {
+    ExecuteInput {
+        connection_id: "dropped".to_string(),
+        sql: sql.to_string(),
+        params: vec![],
+        timeout_secs: None,
+        transaction_id,
+        skip_sql_check: false,
+        database: None,
+    }
+}
+
+async fn setup() -> (
+    Arc<ConnectionManager>,
+    Arc<TransactionRegistry>,
+    Reloader,
+    SharedAuthConfig,
+    TempDir,
+) {
+    let dir = tempfile::tempdir().expect("Failed to create temp dir");
+    let manager = Arc::new(ConnectionManager::new());
+    let initial = reloadable(
+        vec![
+            connection(dir.path(), "kept", None),
+            connection(dir.path(), "resized", None),
+            connection(dir.path(), "dropped", None),
+        ],
+        &["old-token"],
+    );
+    for config in &initial.connections {
+        manager.connect(config.clone()).await.unwrap();
+    }
+    let auth = SharedAuthConfig::new(AuthConfig::from_tokens(initial.auth_tokens.clone()).unwrap());
+    let registry = Arc::new(TransactionRegistry::new());
+    let reloader =
+        Reloader::new(manager.clone(), registry.clone(), &initial).with_auth(auth.clone());
+    (manager, registry, reloader, auth, dir)
+}
+
+#[tokio::test]
+async fn test_reload_diffs_connections_and_swaps_tokens() {
+    let (manager, _registry, mut reloader, auth, dir) = setup().await;
+    // Added at runtime with the admin tools; not part of the configuration
+    manager
+        .connect(connection(dir.path(), "runtime", None))
+        .await
+        .unwrap();
+
+    let summary = reloader
+        .apply(reloadable(
+            vec![
+                connection(dir.path(), "kept", None),
+                connection(dir.path(), "resized", Some(3)),
+                connection(dir.path(), "new", None),
+            ],
+            &["new-token-1", "new-token-2"],
+        ))
+        .await
+        .unwrap();
+
+    assert_eq!(
+        summary,
+        ReloadSummary {
+            added: vec!["new".to_string()],
+            updated: vec!["resized".to_string()],
+            removed: vec!["dropped".to_string()],
+            failed: vec![],
+            unchanged: 1,
+        }
+    );
+    let mut ids = manager.list_connections().await;
+    ids.sort();
+    assert_eq!(ids, vec!["kept", "new", "resized", "runtime"]);
+    let resized = manager.get_config("resized").await.unwrap();
+    assert_eq!(resized.pool_options.max_connections, Some(3));
+    assert_eq!(auth.current().token_count(), 2);
+
+    // Applying the same configuration again changes nothing
+    let summary = reloader
+        .apply(reloadable(
+            vec![
+                connection(dir.path(), "kept", None),
+                connection(dir.path(), "resized", Some(3)),
+                connection(dir.path(), "new", None),
+            ],
+            &["new-token-1", "new-token-2"],
+        ))
+        .await
+        .unwrap();
+    assert_eq!(summary.unchanged, 3);
+    assert!(summary.added.is_empty() && summary.updated.is_empty() && summary.removed.is_empty());
+}
+
+#[tokio::test]
+async fn test_removed_connection_rolls_back_open_transactions() {
+    let (manager, registry, mut reloader, _auth, dir) = setup().await;
+    let writes = WriteToolHandler::new(manager.clone(), registry.clone());
+    writes
+        .execute(execute("CREATE TABLE items (id INTEGER PRIMARY KEY)", None))
+        .await
+        .unwrap();
+    let transactions = TransactionToolHandler::new(manager.clone(), registry.clone());
+    let begun = transactions
+        .begin_transaction(BeginTransactionInput {
+            connection_id: "dropped".to_string(),
+            timeout_secs: None,
+            database: None,
+        })
+        .await
+        .unwrap();
+    writes
+        .execute(execute(
+            "INSERT INTO items (id) VALUES (1)",
+            Some(begun.transaction_id.clone()),
+        ))
+        .await
+        .unwrap();
+
+    let summary = reloader
+        .apply(reloadable(
+            vec![
+                connection(dir.path(), "kept", None),
+                connection(dir.path(), "resized", None),
+            ],
+            &["old-token"],
+        ))
+        .await
+        .unwrap();
+    assert_eq!(summary.removed, vec!["dropped"]);
+    assert!(!manager.exists("dropped").await);
+    assert_eq!(registry.count().await, 0);
+
+    // The transaction was rolled back with its connection
+    let committed = transactions
+        .commit(CommitInput {
+            connection_id: "dropped".to_string(),
+            transaction_id: begun.transaction_id,
+        })
+        .await;
+    assert!(committed.is_err());
+    let url = format!("sqlite:{}", dir.path().join("dropped.db").display());
+    let pool = sqlx::SqlitePool::connect(&url).await.unwrap();
+    let (rows,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM items")
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(rows, 0);
+}
+
+#[tokio::test]
+async fn test_invalid_reload_changes_nothing() {
+    let (manager, _registry, mut reloader, auth, dir) = setup().await;
+
+    let result = reloader
+        .apply(reloadable(
+            vec![connection(dir.path(), "kept", None)],
+            &[" "],
+        ))
+        .await;
+    assert!(result.is_err());
+    assert_eq!(manager.connection_count().await, 3);
+    assert_eq!(auth.current().token_count(), 1);
+}
+
+#[tokio::test]
+async fn test_reload_cannot_disable_auth() {
+    let (manager, _registry, mut reloader, auth, dir) = setup().await;
+
+    let result = reloader
+        .apply(reloadable(vec![connection(dir.path(), "kept", None)], &[]))
+        .await;
+    assert!(result.is_err());
+    assert_eq!(manager.connection_count().await, 3);
+    assert!(auth.current().is_enabled());
+    assert_eq!(auth.current().token_count(), 1);
+}
+
+#[tokio::test]
+async fn test_failed_connections_are_retried() {
+    let (manager, _registry, mut reloader, _auth, dir) = setup().await;
+    let missing = dir.path().join("missing").join("app.db");
+    let broken = ConnectionConfig::new(
+        "broken",
+        format!("sqlite:{}", missing.display()),
+        false,
+        false,
+        None,
+        PoolOptions::default(),
+    )
+    .unwrap();
+
+    let mut connections = vec![
+        connection(dir.path(), "kept", None),
+        connection(dir.path(), "resized", None),
+        connection(dir.path(), "dropped", None),
+    ];
+    connections.push(broken.clone());
+    let summary = reloader
+        .apply(reloadable(connections.clone(), &["old-token"]))
+        .await
+        .unwrap();
+    assert_eq!(summary.failed, vec!["broken"]);
+    assert_eq!(summary.unchanged, 3);
+
+    std::fs::create_dir(missing.parent().unwrap()).unwrap();
+    std::fs::File::create(&missing).unwrap();
+    let summary = reloader
+        .apply(reloadable(connections, &["old-token"]))
+        .await
+        .unwrap();
+    assert_eq!(summary.added, vec!["broken"]);
+    assert!(manager.exists("broken").await);
+}
@@ -0,0 +1,100 @@
+//! Integration tests for the table schema and sample resources.
+
+mod common;
+
+use db_mcp_server::db::{ConnectionManager, TransactionRegistry};
+use db_mcp_server::mcp::resources::{ResourceProvider, TableResource};
+use db_mcp_server::tools::write::{ExecuteInput, WriteToolHandler};
+use rmcp::model::ResourceContents;
+use serde_json::Value;
+use std::sync::Arc;
+use tempfile::NamedTempFile;
+
+async fn setup() -> (Arc<ConnectionManager>, NamedTempFile) {
+    let (manager, temp_file) = common::setup(
+        "app",
+        r#"
+        CREATE TABLE users (id INTEGER PRIMARY KEY, name TEXT NOT NULL);
+        CREATE TABLE orders (id INTEGER PRIMARY KEY, user_id INTEGER REFERENCES users(id));
+        INSERT INTO users VALUES (1, 'alice'), (2, 'bob');
+        "#,
+    )
+    .await;
+    (manager, temp_file)
+}
+
+async fn read_json(provider: &ResourceProvider, uri: &str) -> Value {
+    let ResourceContents::TextResourceContents {
+        text, mime_type, ..
+    } = provider.read(uri).await.unwrap()
+    else {
+        panic!("expected text contents");
+    };
+    assert_eq!(mime_type.as_deref(), Some("application/json"));
+    serde_json::from_str(&text).unwrap()
+}
+
+#[tokio::test]
+async fn test_list_and_read_resources() {
+    let (manager, _db) = setup().await;
+    let provider = ResourceProvider::new(manager, 30);
+
+    let uris: Vec<String> = provider
+        .list()
+        .await
+        .into_iter()
+        .map(|r| r.raw.uri)
+        .collect();
+    assert_eq!(uris.len(), 4);
+    assert!(uris.contains(&"db://app/default/users".to_string()));
+    assert!(uris.contains(&"db://app/default/orders/sample".to_string()));
+
+    let schema = read_json(&provider, "db://app/default/users").await;
+    assert_eq!(schema["table_name"], "users");
+    assert_eq!(schema["primary_key"][0], "id");
+
+    let sample = read_json(&provider, "db://app/default/users/sample").await;
+    assert_eq!(sample["row_count"], 2);
+}
+
+#[tokio::test]
+async fn test_read_rejects_unknown_targets() {
+    let (manager, _db) = setup().await;
+    let provider = ResourceProvider::new(manager, 30);
+
+    assert!(provider.read("db://app/default/missing").await.is_err());
+    assert!(provider.read("db://other/default/users").await.is_err());
+    assert!(provider.read("db://app/otherdb/users").await.is_err());
+    assert!(provider.read("db://app/default").await.is_err());
+}
+
+#[tokio::test]
+async fn test_ddl_notifies_resource_changes() {
+    let (manager, _db) = setup().await;
+    let mut changes = manager.schema_cache().subscribe();
+    let writer = WriteToolHandler::new(manager.clone(), Arc::new(TransactionRegistry::new()));
+
+    writer
+        .execute(ExecuteInput {
+            connection_id: "app".to_string(),
+            sql: "CREATE TABLE audit (id INTEGER PRIMARY KEY)".to_string(),
+            params: vec![],
+            timeout_secs: None,
+            transaction_id: None,
+            skip_sql_check: false,
+            database: None,
+        })
+        .await
+        .unwrap();
+
+    assert_eq!(changes.try_recv().unwrap(), "app");
+    let provider = ResourceProvider::new(manager, 30);
+    let audit = TableResource::parse("db://app/default/audit").unwrap();
+    assert!(
+        provider
+            .list()
+            .await
+            .iter()
+            .any(|r| r.raw.uri == audit.uri())
+    );
+}
@@ -0,0 +1,122 @@
+//! Integration tests for the sample_table tool.
+
+mod common;
+
+use db_mcp_server::tools::{OutputFormat, SampleTableInput, SampleToolHandler, SamplingMethod};
+use serde_json::json;
+use tempfile::NamedTempFile;
+
+const CONN_ID: &str = "test_db";
+
+async fn setup() -> (SampleToolHandler, NamedTempFile) {
+    let (manager, temp_file) = common::setup(
+        CONN_ID,
+        r#"
+        CREATE TABLE events (id INTEGER PRIMARY KEY, kind TEXT NOT NULL, value INTEGER);
+        WITH RECURSIVE n(i) AS (SELECT 1 UNION ALL SELECT i + 1 FROM n WHERE i < 1000)
+        INSERT INTO events (kind, value)
+        SELECT CASE WHEN i % 100 = 0 THEN 'rare' WHEN i % 2 = 0 THEN 'even' ELSE 'odd' END, i
+        FROM n;
+        "#,
+    )
+    .await;
+
+    (SampleToolHandler::new(manager, 30), temp_file)
+}
+
+fn input() -> SampleTableInput {
+    SampleTableInput {
+        connection_id: CONN_ID.to_string(),
+        table_name: "events".to_string(),
+        database: None,
+        sample_size: Some(5),
+        filter: None,
+        stratify_by: None,
+        max_strata: None,
+        timeout_secs: None,
+        format: OutputFormat::Json,
+        decode_binary: true,
+    }
+}
+
+#[tokio::test]
+async fn test_rowid_range_sample() {
+    let (handler, _db) = setup().await;
+
+    let output = handler.sample_table(input()).await.unwrap();
+    assert_eq!(output.method, SamplingMethod::KeyRange);
+    assert_eq!(output.result.row_count, 5);
+    assert!(output.strata.is_none());
+}
+
+#[tokio::test]
+async fn test_filter_applies() {
+    let (handler, _db) = setup().await;
+
+    let mut request = input();
+    request.filter = Some("kind = 'rare'".to_string());
+    request.sample_size = Some(20);
+    let output = handler.sample_table(request).await.unwrap();
+
+    // Only 10 rare rows exist, so the key-range probe falls short and a full scan is used
+    assert_eq!(output.method, SamplingMethod::Random);
+    assert_eq!(output.result.row_count, 10);
+    assert!(
+        output
+            .result
+            .rows
+            .iter()
+            .all(|row| row["kind"] == json!("rare"))
+    );
+}
+
+#[tokio::test]
+async fn test_rejects_injected_filter() {
+    let (handler, _db) = setup().await;
+
+    let mut request = input();
+    request.filter = Some("1 = 1); DELETE FROM events; --".to_string());
+    assert!(handler.sample_table(request).await.is_err());
+}
+
+#[tokio::test]
+async fn test_stratified_sample() {
+    let (handler, _db) = setup().await;
+
+    let mut request = input();
+    request.stratify_by = Some("kind".to_string());
+    request.sample_size = Some(3);
+    let output = handler.sample_table(request).await.unwrap();
+
+    assert_eq!(output.method, SamplingMethod::Stratified);
+    assert_eq!(output.strata, Some(3));
+    assert_eq!(output.result.row_count, 9);
+    let rare = output
+        .result
+        .rows
+        .iter()
+        .filter(|row| row["kind"] == json!("rare"))
+        .count();
+    assert_eq!(rare, 3);
+    // Helper columns are not exposed
+    assert!(
+        output
+            .result
+            .rows
+            .iter()
+            .all(|row| row.keys().all(|k| !k.starts_with("_sample")))
+    );
+}
+
+#[tokio::test]
+async fn test_markdown_format() {
+    let (handler, _db) = setup().await;
+
+    let mut request = input();
+    request.format = OutputFormat::Markdown;
+    let output = handler.sample_table(request).await.unwrap();
+
+    let formatted = output.result.formatted.unwrap();
+    assert!(formatted.starts_with("| id | kind | value |"));
+    assert!(output.result.rows.is_empty());
+}
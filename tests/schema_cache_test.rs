@@ -0,0 +1,193 @@
+//! Integration tests for the schema metadata cache.
+
+use db_mcp_server::config::PoolOptions;
+use db_mcp_server::db::{ConnectionManager, TransactionRegistry};
+use db_mcp_server::models::ConnectionConfig;
+use db_mcp_server::tools::transaction::{
+    BeginTransactionInput, CommitInput, TransactionToolHandler,
+};
+use db_mcp_server::tools::{
+    DescribeTableInput, ExecuteInput, RefreshSchemaInput, SchemaToolHandler, WriteToolHandler,
+};
+use std::sync::Arc;
+use std::time::Duration;
+use tempfile::NamedTempFile;
+
+const CONN_ID: &str = "test_db";
+
+async fn setup(ttl: Duration) -> (Arc<ConnectionManager>, NamedTempFile) {
+    setup_with_pool(ttl, PoolOptions::default()).await
+}
+
+async fn setup_with_pool(
+    ttl: Duration,
+    pool_options: PoolOptions,
+) -> (Arc<ConnectionManager>, NamedTempFile) {
+    let temp_file = NamedTempFile::new().expect("Failed to create temp file");
+    let url = format!("sqlite:{}?mode=rwc", temp_file.path().display());
+
+    let manager = Arc::new(ConnectionManager::new().with_schema_cache_ttl(ttl));
+    let config = ConnectionConfig::new(CONN_ID, &url, true, false, None, pool_options)
+        .expect("Failed to create config");
+    manager.connect(config).await.expect("Failed to connect");
+    (manager, temp_file)
+}
+
+async fn execute(manager: &Arc<ConnectionManager>, sql: &str) {
+    WriteToolHandler::new(manager.clone(), Arc::new(TransactionRegistry::new()))
+        .execute(ExecuteInput {
+            connection_id: CONN_ID.to_string(),
+            sql: sql.to_string(),
+            params: vec![],
+            timeout_secs: None,
+            transaction_id: None,
+            skip_sql_check: false,
+            database: None,
+        })
+        .await
+        .expect("Failed to execute");
+}
+
+async fn column_names(handler: &SchemaToolHandler) -> Vec<String> {
+    handler
+        .describe_table(DescribeTableInput {
+            connection_id: CONN_ID.to_string(),
+            table_name: "users".to_string(),
+            database: None,
+        })
+        .await
+        .unwrap()
+        .columns
+        .into_iter()
+        .map(|c| c.name)
+        .collect()
+}
+
+#[tokio::test]
+async fn test_describe_table_served_from_cache() {
+    let (manager, _db) = setup(Duration::from_secs(60)).await;
+    execute(&manager, "CREATE TABLE users (id INTEGER PRIMARY KEY)").await;
+    let handler = SchemaToolHandler::new(manager.clone());
+
+    column_names(&handler).await;
+    column_names(&handler).await;
+
+    let stats = manager.schema_cache().stats();
+    assert_eq!((stats.hits, stats.misses, stats.entries), (1, 1, 1));
+}
+
+#[tokio::test]
+async fn test_ddl_invalidates_cache() {
+    let (manager, _db) = setup(Duration::from_secs(60)).await;
+    execute(&manager, "CREATE TABLE users (id INTEGER PRIMARY KEY)").await;
+    let handler = SchemaToolHandler::new(manager.clone());
+    assert_eq!(column_names(&handler).await, vec!["id"]);
+
+    // DML keeps the cached entries
+    execute(&manager, "INSERT INTO users (id) VALUES (1)").await;
+    assert_eq!(manager.schema_cache().stats().invalidations, 0);
+
+    execute(&manager, "ALTER TABLE users ADD COLUMN email TEXT").await;
+    assert_eq!(column_names(&handler).await, vec!["id", "email"]);
+    assert!(manager.schema_cache().stats().invalidations > 0);
+}
+
+#[tokio::test]
+async fn test_failed_ddl_script_invalidates_cache() {
+    let (manager, _db) = setup(Duration::from_secs(60)).await;
+    execute(&manager, "CREATE TABLE users (id INTEGER PRIMARY KEY)").await;
+    let handler = SchemaToolHandler::new(manager.clone());
+    assert_eq!(column_names(&handler).await, vec!["id"]);
+
+    // The first statement is applied before the second fails
+    let result = WriteToolHandler::new(manager.clone(), Arc::new(TransactionRegistry::new()))
+        .execute(ExecuteInput {
+            connection_id: CONN_ID.to_string(),
+            sql: "ALTER TABLE users ADD COLUMN email TEXT; ALTER TABLE missing ADD COLUMN x TEXT"
+                .to_string(),
+            params: vec![],
+            timeout_secs: None,
+            transaction_id: None,
+            skip_sql_check: true,
+            database: None,
+        })
+        .await;
+    assert!(result.is_err());
+    assert_eq!(column_names(&handler).await, vec!["id", "email"]);
+}
+
+#[tokio::test]
+async fn test_ddl_in_transaction_invalidates_cache_on_commit() {
+    // A second connection reads the schema while the transaction is open
+    let pool_options = PoolOptions {
+        max_connections: Some(2),
+        ..PoolOptions::default()
+    };
+    let (manager, _db) = setup_with_pool(Duration::from_secs(60), pool_options).await;
+    execute(&manager, "CREATE TABLE users (id INTEGER PRIMARY KEY)").await;
+    let handler = SchemaToolHandler::new(manager.clone());
+    let registry = Arc::new(TransactionRegistry::new());
+    let transactions = TransactionToolHandler::new(manager.clone(), registry.clone());
+    let begun = transactions
+        .begin_transaction(BeginTransactionInput {
+            connection_id: CONN_ID.to_string(),
+            timeout_secs: None,
+            database: None,
+        })
+        .await
+        .unwrap();
+
+    WriteToolHandler::new(manager.clone(), registry)
+        .execute(ExecuteInput {
+            connection_id: CONN_ID.to_string(),
+            sql: "ALTER TABLE users ADD COLUMN email TEXT".to_string(),
+            params: vec![],
+            timeout_secs: None,
+            transaction_id: Some(begun.transaction_id.clone()),
+            skip_sql_check: false,
+            database: None,
+        })
+        .await
+        .unwrap();
+    // Other connections still see the old schema, which is cached again
+    assert_eq!(column_names(&handler).await, vec!["id"]);
+
+    transactions
+        .commit(CommitInput {
+            connection_id: CONN_ID.to_string(),
+            transaction_id: begun.transaction_id,
+        })
+        .await
+        .unwrap();
+    assert_eq!(column_names(&handler).await, vec!["id", "email"]);
+}
+
+#[tokio::test]
+async fn test_refresh_schema_clears_entries() {
+    let (manager, _db) = setup(Duration::from_secs(60)).await;
+    execute(&manager, "CREATE TABLE users (id INTEGER PRIMARY KEY)").await;
+    let handler = SchemaToolHandler::new(manager.clone());
+    column_names(&handler).await;
+
+    let output = handler
+        .refresh_schema(RefreshSchemaInput {
+            connection_id: CONN_ID.to_string(),
+            database: None,
+        })
+        .unwrap();
+    assert_eq!(output.cleared, 1);
+    assert_eq!(output.cache.entries, 0);
+}
+
+#[tokio::test]
+async fn test_zero_ttl_disables_cache() {
+    let (manager, _db) = setup(Duration::ZERO).await;
+    execute(&manager, "CREATE TABLE users (id INTEGER PRIMARY KEY)").await;
+    let handler = SchemaToolHandler::new(manager.clone());
+
+    column_names(&handler).await;
+    column_names(&handler).await;
+
+    let stats = manager.schema_cache().stats();
+    assert_eq!((stats.hits, stats.misses, stats.entries), (0, 2, 0));
+}
@@ -0,0 +1,103 @@
+//! Integration tests for the diff_schema tool.
+
+mod common;
+
+use db_mcp_server::db::ConnectionManager;
+use db_mcp_server::tools::schema_diff::SchemaTarget;
+use db_mcp_server::tools::{DiffSchemaInput, SchemaDiffToolHandler};
+use std::sync::Arc;
+use tempfile::NamedTempFile;
+
+async fn setup(source_ddl: &str, target_ddl: &str) -> (Arc<ConnectionManager>, [NamedTempFile; 2]) {
+    let manager = Arc::new(ConnectionManager::new());
+    let source = common::connect_sqlite(&manager, "source", true, source_ddl).await;
+    let target = common::connect_sqlite(&manager, "target", true, target_ddl).await;
+    (manager, [source, target])
+}
+
+fn input(include_ddl: bool) -> DiffSchemaInput {
+    DiffSchemaInput {
+        source: SchemaTarget {
+            connection_id: "source".to_string(),
+            database: None,
+        },
+        target: SchemaTarget {
+            connection_id: "target".to_string(),
+            database: None,
+        },
+        tables: None,
+        include_ddl,
+    }
+}
+
+const USERS: &str =
+    "CREATE TABLE users (id INTEGER PRIMARY KEY, email TEXT NOT NULL UNIQUE, name TEXT);";
+
+#[tokio::test]
+async fn test_identical_schemas() {
+    let (manager, _files) = setup(USERS, USERS).await;
+    let handler = SchemaDiffToolHandler::new(manager);
+
+    let output = handler.diff_schema(input(true)).await.unwrap();
+    assert!(output.identical);
+    assert_eq!(output.source.table_count, 1);
+    assert_eq!(output.reconcile_sql, Some(vec![]));
+}
+
+#[tokio::test]
+async fn test_reports_table_and_column_differences() {
+    let source = format!(
+        "{USERS}\nCREATE TABLE orders (id INTEGER PRIMARY KEY, user_id INTEGER REFERENCES users(id));\n\
+         CREATE INDEX idx_users_name ON users(name);"
+    );
+    let target = "CREATE TABLE users (id INTEGER PRIMARY KEY, email TEXT NOT NULL UNIQUE, nickname TEXT);\n\
+                  CREATE TABLE legacy (id INTEGER);";
+    let (manager, _files) = setup(&source, target).await;
+    let handler = SchemaDiffToolHandler::new(manager);
+
+    let output = handler.diff_schema(input(false)).await.unwrap();
+    assert!(!output.identical);
+    assert_eq!(output.removed_tables, vec!["orders"]);
+    assert_eq!(output.added_tables, vec!["legacy"]);
+    assert!(output.reconcile_sql.is_none());
+
+    let users = &output.changed_tables[0];
+    assert_eq!(users.table, "users");
+    assert_eq!(users.removed_columns, vec!["name"]);
+    assert_eq!(users.added_columns, vec!["nickname"]);
+    assert_eq!(users.removed_indexes.len(), 1);
+}
+
+#[tokio::test]
+async fn test_reconcile_ddl_applies_cleanly() {
+    let source = format!("{USERS}\nCREATE INDEX idx_users_name ON users(name);");
+    let target = "CREATE TABLE users (id INTEGER PRIMARY KEY, email TEXT NOT NULL UNIQUE);\n\
+                  CREATE TABLE legacy (id INTEGER);";
+    let (manager, _files) = setup(&source, target).await;
+    let handler = SchemaDiffToolHandler::new(manager.clone());
+
+    let output = handler.diff_schema(input(true)).await.unwrap();
+    let statements = output.reconcile_sql.unwrap();
+    assert!(!statements.is_empty());
+
+    let pool = common::sqlite_pool(&manager, "target").await;
+    for sql in &statements {
+        sqlx::raw_sql(sql).execute(&pool).await.unwrap();
+    }
+
+    let output = handler.diff_schema(input(true)).await.unwrap();
+    assert!(output.identical, "{:?}", output.changed_tables);
+}
+
+#[tokio::test]
+async fn test_table_filter() {
+    let source = format!("{USERS}\nCREATE TABLE orders (id INTEGER PRIMARY KEY);");
+    let (manager, _files) = setup(&source, USERS).await;
+    let handler = SchemaDiffToolHandler::new(manager);
+
+    let mut filtered = input(false);
+    filtered.tables = Some(vec!["USERS".to_string()]);
+    let output = handler.diff_schema(filtered).await.unwrap();
+    assert!(output.identical);
+    assert_eq!(output.source.table_count, 1);
+}
@@ -0,0 +1,155 @@
+//! Integration tests for view, routine, trigger and sequence introspection.
+
+mod common;
+
+use db_mcp_server::tools::{
+    DescribeRoutineInput, DescribeViewInput, ListRoutinesInput, ListSequencesInput,
+    ListTriggersInput, SchemaToolHandler,
+};
+use tempfile::NamedTempFile;
+
+const CONN_ID: &str = "test_db";
+
+const SCHEMA: &str = r#"
+CREATE TABLE users (id INTEGER PRIMARY KEY AUTOINCREMENT, email TEXT NOT NULL, active INTEGER DEFAULT 1);
+CREATE TABLE audit (user_id INTEGER, action TEXT);
+CREATE TABLE notes (id INTEGER PRIMARY KEY, body TEXT);
+CREATE VIEW active_users AS SELECT id, email FROM users WHERE active = 1;
+CREATE TRIGGER users_audit AFTER UPDATE OF email ON users
+BEGIN
+    INSERT INTO audit VALUES (new.id, 'email_changed');
+END;
+CREATE TRIGGER notes_guard BEFORE DELETE ON notes
+BEGIN
+    SELECT RAISE(ABORT, 'notes are append-only');
+END;
+INSERT INTO users (email) VALUES ('a@example.com'), ('b@example.com');
+"#;
+
+async fn setup() -> (SchemaToolHandler, NamedTempFile) {
+    let (manager, temp_file) = common::setup(CONN_ID, SCHEMA).await;
+
+    (SchemaToolHandler::new(manager), temp_file)
+}
+
+#[tokio::test]
+async fn test_describe_view() {
+    let (handler, _db) = setup().await;
+
+    let output = handler
+        .describe_view(DescribeViewInput {
+            connection_id: CONN_ID.to_string(),
+            view_name: "active_users".to_string(),
+            database: None,
+        })
+        .await
+        .unwrap();
+
+    assert!(!output.materialized);
+    assert!(
+        output
+            .definition
+            .unwrap()
+            .contains("SELECT id, email FROM users")
+    );
+    let columns: Vec<&str> = output.columns.iter().map(|c| c.name.as_str()).collect();
+    assert_eq!(columns, vec!["id", "email"]);
+}
+
+#[tokio::test]
+async fn test_describe_view_not_found() {
+    let (handler, _db) = setup().await;
+
+    let err = handler
+        .describe_view(DescribeViewInput {
+            connection_id: CONN_ID.to_string(),
+            view_name: "users".to_string(),
+            database: None,
+        })
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("not found"));
+}
+
+#[tokio::test]
+async fn test_list_triggers() {
+    let (handler, _db) = setup().await;
+
+    let output = handler
+        .list_triggers(ListTriggersInput {
+            connection_id: CONN_ID.to_string(),
+            table_name: None,
+            database: None,
+        })
+        .await
+        .unwrap();
+    assert_eq!(output.count, 2);
+
+    let guard = &output.triggers[0];
+    assert_eq!(guard.name, "notes_guard");
+    assert_eq!(guard.timing, "BEFORE");
+    assert_eq!(guard.event, "DELETE");
+
+    let audit = &output.triggers[1];
+    assert_eq!(audit.table_name, "users");
+    assert_eq!(audit.timing, "AFTER");
+    assert_eq!(audit.event, "UPDATE");
+    assert_eq!(
+        audit.body.as_deref(),
+        Some("INSERT INTO audit VALUES (new.id, 'email_changed');")
+    );
+
+    let filtered = handler
+        .list_triggers(ListTriggersInput {
+            connection_id: CONN_ID.to_string(),
+            table_name: Some("users".to_string()),
+            database: None,
+        })
+        .await
+        .unwrap();
+    assert_eq!(filtered.count, 1);
+}
+
+#[tokio::test]
+async fn test_list_sequences_reports_autoincrement_counters() {
+    let (handler, _db) = setup().await;
+
+    let output = handler
+        .list_sequences(ListSequencesInput {
+            connection_id: CONN_ID.to_string(),
+            database: None,
+        })
+        .await
+        .unwrap();
+
+    // Only AUTOINCREMENT tables get a counter
+    assert_eq!(output.count, 1);
+    let seq = &output.sequences[0];
+    assert_eq!(seq.name, "users");
+    assert_eq!(seq.column_name.as_deref(), Some("id"));
+    assert_eq!(seq.current_value, Some(2));
+}
+
+#[tokio::test]
+async fn test_routines_not_supported_on_sqlite() {
+    let (handler, _db) = setup().await;
+
+    let err = handler
+        .list_routines(ListRoutinesInput {
+            connection_id: CONN_ID.to_string(),
+            database: None,
+        })
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("SQLite does not support"));
+
+    let err = handler
+        .describe_routine(DescribeRoutineInput {
+            connection_id: CONN_ID.to_string(),
+            routine_name: "anything".to_string(),
+            database: None,
+        })
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("SQLite does not support"));
+}
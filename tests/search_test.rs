@@ -0,0 +1,83 @@
+//! Integration tests for the search_schema tool.
+
+mod common;
+
+use db_mcp_server::tools::{SearchSchemaInput, SearchToolHandler};
+use tempfile::NamedTempFile;
+
+const CONN_ID: &str = "test_db";
+
+const SCHEMA: &str = r#"
+CREATE TABLE customers (id INTEGER PRIMARY KEY, email_address TEXT, created_at TEXT);
+CREATE TABLE customer_orders (id INTEGER PRIMARY KEY, customer_id INTEGER, total REAL);
+CREATE TABLE invoices (id INTEGER PRIMARY KEY, order_id INTEGER, paid INTEGER);
+CREATE VIEW recent_orders AS SELECT * FROM customer_orders;
+"#;
+
+async fn setup() -> (SearchToolHandler, NamedTempFile) {
+    let (manager, temp_file) = common::setup(CONN_ID, SCHEMA).await;
+
+    (SearchToolHandler::new(manager), temp_file)
+}
+
+fn input(query: &str) -> SearchSchemaInput {
+    SearchSchemaInput {
+        connection_id: CONN_ID.to_string(),
+        query: query.to_string(),
+        database: None,
+        limit: None,
+        include_columns: true,
+    }
+}
+
+#[tokio::test]
+async fn test_search_tables_views_and_columns() {
+    let (handler, _db) = setup().await;
+
+    let output = handler.search_schema(input("orders")).await.unwrap();
+    assert_eq!(output.tables_searched, 4);
+    let tables: Vec<&str> = output
+        .hits
+        .iter()
+        .filter(|h| h.column.is_none())
+        .map(|h| h.table.as_str())
+        .collect();
+    assert!(tables.contains(&"customer_orders"));
+    assert!(tables.contains(&"recent_orders"));
+    assert!(
+        output
+            .hits
+            .iter()
+            .any(|h| h.kind == "view" && h.table == "recent_orders")
+    );
+    // Singular column token matches the plural query
+    assert!(
+        output
+            .hits
+            .iter()
+            .any(|h| h.table == "invoices" && h.column.as_deref() == Some("order_id"))
+    );
+}
+
+#[tokio::test]
+async fn test_search_fuzzy_and_limit() {
+    let (handler, _db) = setup().await;
+
+    let output = handler.search_schema(input("emial")).await.unwrap();
+    assert_eq!(output.hits[0].column.as_deref(), Some("email_address"));
+    assert_eq!(output.hits[0].table, "customers");
+
+    let mut limited = input("id");
+    limited.limit = Some(2);
+    let output = handler.search_schema(limited).await.unwrap();
+    assert_eq!(output.hits.len(), 2);
+    assert!(output.total_matches > 2);
+}
+
+#[tokio::test]
+async fn test_search_rejects_empty_query() {
+    let (handler, _db) = setup().await;
+
+    let err = handler.search_schema(input("  ")).await.unwrap_err();
+    assert!(err.to_string().contains("at least one letter"));
+}
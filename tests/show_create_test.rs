@@ -0,0 +1,97 @@
+//! Integration tests for the show_create tool.
+
+mod common;
+
+use db_mcp_server::models::DatabaseType;
+use db_mcp_server::tools::{SchemaToolHandler, ShowCreateInput};
+use tempfile::NamedTempFile;
+
+const CONN_ID: &str = "test_db";
+
+const SCHEMA: &str = r#"
+CREATE TABLE users (id INTEGER PRIMARY KEY AUTOINCREMENT, email TEXT NOT NULL, active BOOLEAN DEFAULT 1);
+CREATE UNIQUE INDEX idx_users_email ON users(email);
+CREATE TABLE orders (
+    id INTEGER PRIMARY KEY,
+    user_id INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    total REAL
+);
+"#;
+
+async fn setup() -> (SchemaToolHandler, NamedTempFile) {
+    let (manager, temp_file) = common::setup(CONN_ID, SCHEMA).await;
+
+    (SchemaToolHandler::new(manager), temp_file)
+}
+
+fn input(table: &str, dialect: Option<DatabaseType>) -> ShowCreateInput {
+    ShowCreateInput {
+        connection_id: CONN_ID.to_string(),
+        table_name: table.to_string(),
+        database: None,
+        dialect,
+    }
+}
+
+#[tokio::test]
+async fn test_native_sqlite_ddl() {
+    let (handler, _db) = setup().await;
+
+    let output = handler.show_create(input("users", None)).await.unwrap();
+    assert!(output.native);
+    assert_eq!(output.dialect, DatabaseType::SQLite);
+    assert_eq!(output.statements.len(), 2);
+    assert!(output.statements[0].starts_with("CREATE TABLE users"));
+    assert!(output.statements[1].contains("idx_users_email"));
+    assert!(output.ddl.ends_with(';'));
+}
+
+#[tokio::test]
+async fn test_render_as_postgres() {
+    let (handler, _db) = setup().await;
+
+    let output = handler
+        .show_create(input("orders", Some(DatabaseType::PostgreSQL)))
+        .await
+        .unwrap();
+    assert!(!output.native);
+    assert_eq!(output.dialect, DatabaseType::PostgreSQL);
+    let create = &output.statements[0];
+    assert!(create.starts_with("CREATE TABLE \"orders\""));
+    assert!(create.contains("\"id\" INTEGER NOT NULL GENERATED BY DEFAULT AS IDENTITY"));
+    assert!(create.contains("\"user_id\" INTEGER NOT NULL"));
+    assert!(create.contains("PRIMARY KEY (\"id\")"));
+    assert!(
+        create
+            .contains("FOREIGN KEY (\"user_id\") REFERENCES \"users\" (\"id\") ON DELETE CASCADE")
+    );
+}
+
+#[tokio::test]
+async fn test_render_as_mysql_includes_indexes() {
+    let (handler, _db) = setup().await;
+
+    let output = handler
+        .show_create(input("users", Some(DatabaseType::MySQL)))
+        .await
+        .unwrap();
+    assert!(output.statements[0].starts_with("CREATE TABLE `users`"));
+    assert!(output.statements[0].contains("AUTO_INCREMENT"));
+    assert!(
+        output
+            .statements
+            .iter()
+            .any(|s| s.starts_with("CREATE UNIQUE INDEX `idx_users_email`"))
+    );
+}
+
+#[tokio::test]
+async fn test_unknown_table() {
+    let (handler, _db) = setup().await;
+
+    let err = handler
+        .show_create(input("missing", None))
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("not found"));
+}